@@ -102,7 +102,7 @@ pub async fn log_stock_adjustment(
     product_id: i64,
     user_id: i64,
     adj_type: &str,
-    quantity: i64,
+    quantity: f64,
     reason: &str,
     notes: Option<&str>,
 ) {
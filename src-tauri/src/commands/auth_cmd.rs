@@ -158,3 +158,30 @@ pub async fn check_session(
         role: session.role,
     })
 }
+
+/// Verifikasi kredensial admin untuk persetujuan di mesin kasir (mis.
+/// override harga). Tidak membuat session; mengembalikan user penyetuju.
+pub async fn verify_admin_approval(
+    db: &sqlx::SqlitePool,
+    username: &str,
+    password: &str,
+) -> Result<DbUser, String> {
+    let user = sqlx::query_as::<_, DbUser>(
+        "SELECT * FROM users WHERE username = ? AND is_active = 1",
+    )
+    .bind(username.trim())
+    .fetch_optional(db)
+    .await
+    .map_err(|e| e.to_string())?
+    .ok_or("Penyetuju tidak ditemukan atau akun tidak aktif")?;
+
+    if user.role != "ADMIN" {
+        return Err("Persetujuan harus oleh admin".into());
+    }
+    let valid =
+        bcrypt::verify(password, &user.password_hash).map_err(|_| "Gagal verifikasi password")?;
+    if !valid {
+        return Err("Password penyetuju salah".into());
+    }
+    Ok(user)
+}
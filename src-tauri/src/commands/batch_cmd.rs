@@ -0,0 +1,264 @@
+use crate::commands::location_cmd::{location_available, location_or_default, move_location_stock};
+use crate::models::product::{ProductBatch, StockBatchPayload};
+use crate::models::quantity::{round_quantity, Quantity};
+use crate::AppState;
+use sqlx::SqliteConnection;
+
+const BATCH_SELECT: &str = "
+    SELECT b.id, b.product_id, p.name as product_name, b.batch_number, b.expiry_date,
+           b.quantity, b.initial_quantity, b.cost_price, b.received_at,
+           CAST(julianday(b.expiry_date) - julianday(date('now', 'localtime')) AS INTEGER) as days_to_expiry
+    FROM product_batches b
+    JOIN products p ON p.id = b.product_id
+";
+
+/// Catat batch baru untuk stok masuk. Stok produk diperbarui oleh pemanggil.
+pub async fn receive_batch(
+    conn: &mut SqliteConnection,
+    product_id: i64,
+    quantity: f64,
+    batch: &StockBatchPayload,
+) -> Result<i64, String> {
+    let expiry_date = batch
+        .expiry_date
+        .as_deref()
+        .map(str::trim)
+        .filter(|d| !d.is_empty())
+        .map(|d| {
+            chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d")
+                .map(|date| date.format("%Y-%m-%d").to_string())
+                .map_err(|_| format!("Tanggal kedaluwarsa '{}' tidak valid", d))
+        })
+        .transpose()?;
+    if batch.cost_price.is_some_and(|c| !c.is_finite() || c < 0.0) {
+        return Err("Harga modal batch tidak valid".into());
+    }
+
+    let result = sqlx::query(
+        "INSERT INTO product_batches (product_id, batch_number, expiry_date, quantity, initial_quantity, cost_price)
+         VALUES (?, ?, ?, ?, ?, COALESCE(?, (SELECT cost_price FROM products WHERE id = ?)))",
+    )
+    .bind(product_id)
+    .bind(batch.batch_number.as_deref().map(str::trim).filter(|b| !b.is_empty()))
+    .bind(&expiry_date)
+    .bind(round_quantity(quantity))
+    .bind(round_quantity(quantity))
+    .bind(batch.cost_price)
+    .bind(product_id)
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(result.last_insert_rowid())
+}
+
+/// Potong batch FEFO (kedaluwarsa paling dekat dulu, batch tanpa tanggal
+/// terakhir). Sisa yang melebihi total batch dianggap stok tanpa batch.
+///
+/// Batasan: batch belum punya lokasi (tidak ikut transfer stok), jadi FEFO
+/// dihitung dari semua batch produk — penjualan di toko bisa memotong batch
+/// yang fisiknya ada di gudang. Stok per lokasi tetap akurat.
+pub async fn consume_batches(
+    conn: &mut SqliteConnection,
+    product_id: i64,
+    quantity: f64,
+) -> Result<Vec<(i64, f64)>, String> {
+    let batches: Vec<(i64, Quantity)> = sqlx::query_as(
+        "SELECT id, quantity FROM product_batches
+         WHERE product_id = ? AND quantity > 0
+         ORDER BY expiry_date IS NULL, expiry_date ASC, id ASC",
+    )
+    .bind(product_id)
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let mut remaining = round_quantity(quantity);
+    let mut taken = Vec::new();
+    for (batch_id, Quantity(available)) in batches {
+        if remaining <= 0.0 {
+            break;
+        }
+        let take = round_quantity(available.min(remaining));
+        sqlx::query("UPDATE product_batches SET quantity = ROUND(quantity - ?, 3) WHERE id = ?")
+            .bind(take)
+            .bind(batch_id)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| e.to_string())?;
+        taken.push((batch_id, take));
+        remaining = round_quantity(remaining - take);
+    }
+
+    Ok(taken)
+}
+
+/// Potong batch untuk satu item transaksi dan catat pemakaiannya (untuk void).
+pub async fn consume_batches_for_item(
+    conn: &mut SqliteConnection,
+    transaction_item_id: i64,
+    product_id: i64,
+    quantity: f64,
+) -> Result<(), String> {
+    for (batch_id, taken) in consume_batches(conn, product_id, quantity).await? {
+        sqlx::query(
+            "INSERT INTO transaction_item_batches (transaction_item_id, batch_id, quantity) VALUES (?, ?, ?)",
+        )
+        .bind(transaction_item_id)
+        .bind(batch_id)
+        .bind(taken)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Kembalikan qty batch yang dipotong oleh satu transaksi (void).
+pub async fn restore_transaction_batches(
+    conn: &mut SqliteConnection,
+    transaction_id: &str,
+) -> Result<(), String> {
+    sqlx::query(
+        "UPDATE product_batches
+         SET quantity = ROUND(quantity + (
+             SELECT SUM(tib.quantity) FROM transaction_item_batches tib
+             JOIN transaction_items ti ON ti.id = tib.transaction_item_id
+             WHERE tib.batch_id = product_batches.id AND ti.transaction_id = ?
+         ), 3)
+         WHERE id IN (
+             SELECT tib.batch_id FROM transaction_item_batches tib
+             JOIN transaction_items ti ON ti.id = tib.transaction_item_id
+             WHERE ti.transaction_id = ?
+         )",
+    )
+    .bind(transaction_id)
+    .bind(transaction_id)
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Batch produk yang masih bersisa, urut FEFO
+#[tauri::command]
+pub async fn get_product_batches(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    product_id: i64,
+) -> Result<Vec<ProductBatch>, String> {
+    crate::auth::guard::validate_session(&state, &session_token)?;
+
+    let query = format!(
+        "{} WHERE b.product_id = ? AND b.quantity > 0
+         ORDER BY b.expiry_date IS NULL, b.expiry_date ASC, b.id ASC",
+        BATCH_SELECT
+    );
+    sqlx::query_as::<_, ProductBatch>(&query)
+        .bind(product_id)
+        .fetch_all(&state.db)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Batch yang sudah / akan kedaluwarsa dalam `days` hari (default: setting
+/// app.expiry_warning_days).
+#[tauri::command]
+pub async fn get_expiring_batches(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    days: Option<i64>,
+) -> Result<Vec<ProductBatch>, String> {
+    crate::auth::guard::validate_session(&state, &session_token)?;
+
+    let days = match days {
+        Some(days) => days,
+        None => {
+            crate::commands::settings_cmd::load_settings(&state.db)
+                .await?
+                .expiry_warning_days
+        }
+    };
+
+    let query = format!(
+        "{} WHERE b.quantity > 0 AND p.is_active = 1 AND b.expiry_date IS NOT NULL
+           AND b.expiry_date <= date('now', 'localtime', '+' || ? || ' days')
+         ORDER BY b.expiry_date ASC, p.name ASC",
+        BATCH_SELECT
+    );
+    sqlx::query_as::<_, ProductBatch>(&query)
+        .bind(days.max(0))
+        .fetch_all(&state.db)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Buang sisa batch (mis. kedaluwarsa) — stok produk berkurang (Admin only).
+#[tauri::command]
+pub async fn dispose_batch(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    batch_id: i64,
+    notes: Option<String>,
+    location_id: Option<i64>,
+) -> Result<(), String> {
+    let session = crate::auth::guard::validate_admin(&state, &session_token)?;
+
+    let mut tx = state.db.begin().await.map_err(|e| e.to_string())?;
+    let (product_id, Quantity(quantity), batch_number): (i64, Quantity, Option<String>) =
+        sqlx::query_as("SELECT product_id, quantity, batch_number FROM product_batches WHERE id = ?")
+            .bind(batch_id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?
+            .ok_or("Batch tidak ditemukan")?;
+    if quantity <= 0.0 {
+        return Err("Batch sudah habis".into());
+    }
+    // Batch tidak terikat lokasi: stok yang dibuang dipotong dari lokasi terpilih
+    let location = location_or_default(&mut tx, location_id).await?;
+    if location_available(&mut tx, product_id, location).await? < quantity {
+        return Err("Stok di lokasi ini lebih kecil dari sisa batch".into());
+    }
+
+    sqlx::query("UPDATE product_batches SET quantity = 0 WHERE id = ?")
+        .bind(batch_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    sqlx::query(
+        "UPDATE products SET stock = MAX(ROUND(stock - ?, 3), 0), updated_at = CURRENT_TIMESTAMP WHERE id = ?",
+    )
+    .bind(quantity)
+    .bind(product_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+    move_location_stock(&mut tx, product_id, location, -quantity).await?;
+
+    let label = batch_number.unwrap_or_else(|| format!("#{}", batch_id));
+    let notes = notes.as_deref().map(str::trim).filter(|n| !n.is_empty());
+    crate::commands::activity_cmd::log_stock_adjustment(
+        &state.db,
+        Some(&mut tx),
+        product_id,
+        session.user_id,
+        "OUT",
+        quantity,
+        "EXPIRED",
+        Some(&match notes {
+            Some(notes) => format!("Buang batch {}: {}", label, notes),
+            None => format!("Buang batch {}", label),
+        }),
+    ).await;
+    crate::commands::activity_cmd::log_activity(
+        &state.db,
+        Some(&mut tx),
+        Some(session.user_id),
+        "DISPOSE_BATCH",
+        &format!("Membuang batch {} produk ID {} ({})", label, product_id, quantity),
+        None,
+    ).await;
+
+    tx.commit().await.map_err(|e| e.to_string())
+}
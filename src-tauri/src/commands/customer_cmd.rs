@@ -0,0 +1,253 @@
+use crate::models::customer::{Customer, CustomerPayload};
+use crate::models::pricing::{PriceLevel, PriceLevelPayload};
+use crate::AppState;
+
+const CUSTOMER_SELECT: &str = "SELECT c.*, l.name as price_level_name
+     FROM customers c
+     LEFT JOIN price_levels l ON c.price_level_id = l.id";
+
+/// Daftar pelanggan (cari nama / no. HP)
+#[tauri::command]
+pub async fn get_customers(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    search: Option<String>,
+    show_inactive: Option<bool>,
+) -> Result<Vec<Customer>, String> {
+    crate::auth::guard::validate_session(&state, &session_token)?;
+
+    let mut query = format!("{} WHERE 1=1", CUSTOMER_SELECT);
+    if !show_inactive.unwrap_or(false) {
+        query.push_str(" AND c.is_active = 1");
+    }
+    let search = search.map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+    if search.is_some() {
+        query.push_str(" AND (c.name LIKE ? OR c.phone LIKE ?)");
+    }
+    query.push_str(" ORDER BY c.name ASC");
+
+    let mut q = sqlx::query_as::<_, Customer>(&query);
+    if let Some(s) = &search {
+        let pattern = format!("%{}%", s);
+        q = q.bind(pattern.clone()).bind(pattern);
+    }
+
+    q.fetch_all(&state.db).await.map_err(|e| e.to_string())
+}
+
+/// Tambah pelanggan. Kasir boleh menambah pelanggan eceran; menetapkan
+/// level harga hanya untuk admin.
+#[tauri::command]
+pub async fn create_customer(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    payload: CustomerPayload,
+) -> Result<Customer, String> {
+    let session = crate::auth::guard::validate_session(&state, &session_token)?;
+    if payload.price_level_id.is_some() {
+        crate::auth::guard::validate_admin(&state, &session_token)?;
+    }
+
+    let name = payload.name.trim();
+    if name.is_empty() {
+        return Err("Nama pelanggan tidak boleh kosong".into());
+    }
+
+    let result = sqlx::query(
+        "INSERT INTO customers (name, phone, address, price_level_id) VALUES (?, ?, ?, ?)",
+    )
+    .bind(name)
+    .bind(payload.phone.as_deref().map(str::trim).filter(|p| !p.is_empty()))
+    .bind(payload.address.as_deref().map(str::trim).filter(|a| !a.is_empty()))
+    .bind(payload.price_level_id)
+    .execute(&state.db)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let id = result.last_insert_rowid();
+
+    crate::commands::activity_cmd::log_activity(
+        &state.db,
+        None,
+        Some(session.user_id),
+        "CREATE_CUSTOMER",
+        &format!("Menambah pelanggan {}", name),
+        None,
+    )
+    .await;
+
+    sqlx::query_as::<_, Customer>(&format!("{} WHERE c.id = ?", CUSTOMER_SELECT))
+        .bind(id)
+        .fetch_one(&state.db)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Update data & level harga pelanggan (Admin only)
+#[tauri::command]
+pub async fn update_customer(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    id: i64,
+    payload: CustomerPayload,
+) -> Result<Customer, String> {
+    let session = crate::auth::guard::validate_admin(&state, &session_token)?;
+
+    let name = payload.name.trim();
+    if name.is_empty() {
+        return Err("Nama pelanggan tidak boleh kosong".into());
+    }
+
+    sqlx::query("UPDATE customers SET name = ?, phone = ?, address = ?, price_level_id = ? WHERE id = ?")
+        .bind(name)
+        .bind(payload.phone.as_deref().map(str::trim).filter(|p| !p.is_empty()))
+        .bind(payload.address.as_deref().map(str::trim).filter(|a| !a.is_empty()))
+        .bind(payload.price_level_id)
+        .bind(id)
+        .execute(&state.db)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    crate::commands::activity_cmd::log_activity(
+        &state.db,
+        None,
+        Some(session.user_id),
+        "UPDATE_CUSTOMER",
+        &format!("Mengubah pelanggan {}", name),
+        None,
+    )
+    .await;
+
+    sqlx::query_as::<_, Customer>(&format!("{} WHERE c.id = ?", CUSTOMER_SELECT))
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Pelanggan tidak ditemukan".to_string())
+}
+
+/// Toggle status aktif/nonaktif pelanggan
+#[tauri::command]
+pub async fn toggle_customer(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    id: i64,
+) -> Result<bool, String> {
+    crate::auth::guard::validate_admin(&state, &session_token)?;
+
+    let current: (bool,) = sqlx::query_as("SELECT is_active FROM customers WHERE id = ?")
+        .bind(id)
+        .fetch_one(&state.db)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let new_status = !current.0;
+
+    sqlx::query("UPDATE customers SET is_active = ? WHERE id = ?")
+        .bind(new_status)
+        .bind(id)
+        .execute(&state.db)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(new_status)
+}
+
+/// Daftar level harga
+#[tauri::command]
+pub async fn get_price_levels(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+) -> Result<Vec<PriceLevel>, String> {
+    crate::auth::guard::validate_session(&state, &session_token)?;
+
+    sqlx::query_as::<_, PriceLevel>("SELECT * FROM price_levels ORDER BY name ASC")
+        .fetch_all(&state.db)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+fn validate_price_level(payload: &PriceLevelPayload) -> Result<(), String> {
+    if payload.name.trim().is_empty() {
+        return Err("Nama level harga tidak boleh kosong".into());
+    }
+    if !(0.0..=100.0).contains(&payload.discount_percent) {
+        return Err("Potongan level harus 0–100%".into());
+    }
+    Ok(())
+}
+
+/// Buat level harga baru (Admin only)
+#[tauri::command]
+pub async fn create_price_level(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    payload: PriceLevelPayload,
+) -> Result<PriceLevel, String> {
+    crate::auth::guard::validate_admin(&state, &session_token)?;
+    validate_price_level(&payload)?;
+
+    let result = sqlx::query("INSERT INTO price_levels (name, discount_percent) VALUES (?, ?)")
+        .bind(payload.name.trim())
+        .bind(payload.discount_percent)
+        .execute(&state.db)
+        .await
+        .map_err(|e| {
+            if e.to_string().contains("UNIQUE") {
+                format!("Level harga '{}' sudah ada", payload.name.trim())
+            } else {
+                e.to_string()
+            }
+        })?;
+
+    sqlx::query_as::<_, PriceLevel>("SELECT * FROM price_levels WHERE id = ?")
+        .bind(result.last_insert_rowid())
+        .fetch_one(&state.db)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Update level harga (Admin only)
+#[tauri::command]
+pub async fn update_price_level(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    id: i64,
+    payload: PriceLevelPayload,
+) -> Result<PriceLevel, String> {
+    crate::auth::guard::validate_admin(&state, &session_token)?;
+    validate_price_level(&payload)?;
+
+    sqlx::query("UPDATE price_levels SET name = ?, discount_percent = ? WHERE id = ?")
+        .bind(payload.name.trim())
+        .bind(payload.discount_percent)
+        .bind(id)
+        .execute(&state.db)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    sqlx::query_as::<_, PriceLevel>("SELECT * FROM price_levels WHERE id = ?")
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Level harga tidak ditemukan".to_string())
+}
+
+/// Hapus level harga; pelanggan di level ini kembali ke harga eceran
+#[tauri::command]
+pub async fn delete_price_level(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    id: i64,
+) -> Result<(), String> {
+    crate::auth::guard::validate_admin(&state, &session_token)?;
+
+    sqlx::query("DELETE FROM price_levels WHERE id = ?")
+        .bind(id)
+        .execute(&state.db)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
@@ -0,0 +1,289 @@
+use crate::models::kitchen::{PrinterProfile, PrinterProfilePayload};
+use crate::models::quantity::Quantity;
+use crate::printer::receipt::paper_columns;
+use crate::printer::ticket::{render_ticket, KitchenTicket, TicketItem};
+use crate::AppState;
+
+/// Satu item transaksi beserta printer tujuannya
+#[derive(sqlx::FromRow)]
+struct TicketRow {
+    profile_id: i64,
+    station: String,
+    printer_port: String,
+    paper_width: String,
+    product_name: String,
+    #[sqlx(try_from = "Quantity")]
+    quantity: f64,
+    notes: Option<String>,
+}
+
+// ============================================================================
+// PRINTER PROFILES
+// ============================================================================
+
+/// Daftar printer profile (dapur, bar, ...)
+#[tauri::command]
+pub async fn get_printer_profiles(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+) -> Result<Vec<PrinterProfile>, String> {
+    crate::auth::guard::validate_session(&state, &session_token)?;
+
+    sqlx::query_as::<_, PrinterProfile>("SELECT * FROM printer_profiles ORDER BY name ASC")
+        .fetch_all(&state.db)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Tambah printer profile (Admin only)
+#[tauri::command]
+pub async fn create_printer_profile(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    payload: PrinterProfilePayload,
+) -> Result<PrinterProfile, String> {
+    crate::auth::guard::validate_admin(&state, &session_token)?;
+    validate_profile(&payload)?;
+
+    let result = sqlx::query(
+        "INSERT INTO printer_profiles (name, printer_port, paper_width, is_active) VALUES (?, ?, ?, ?)",
+    )
+    .bind(payload.name.trim())
+    .bind(payload.printer_port.trim())
+    .bind(&payload.paper_width)
+    .bind(payload.is_active)
+    .execute(&state.db)
+    .await
+    .map_err(map_profile_error)?;
+
+    fetch_profile(&state.db, result.last_insert_rowid()).await
+}
+
+/// Ubah printer profile (Admin only)
+#[tauri::command]
+pub async fn update_printer_profile(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    id: i64,
+    payload: PrinterProfilePayload,
+) -> Result<PrinterProfile, String> {
+    crate::auth::guard::validate_admin(&state, &session_token)?;
+    validate_profile(&payload)?;
+
+    let result = sqlx::query(
+        "UPDATE printer_profiles SET name = ?, printer_port = ?, paper_width = ?, is_active = ? WHERE id = ?",
+    )
+    .bind(payload.name.trim())
+    .bind(payload.printer_port.trim())
+    .bind(&payload.paper_width)
+    .bind(payload.is_active)
+    .bind(id)
+    .execute(&state.db)
+    .await
+    .map_err(map_profile_error)?;
+
+    if result.rows_affected() == 0 {
+        return Err("Printer profile tidak ditemukan".into());
+    }
+    fetch_profile(&state.db, id).await
+}
+
+/// Hapus printer profile (Admin only). Kategori yang memakainya tidak lagi
+/// mencetak tiket.
+#[tauri::command]
+pub async fn delete_printer_profile(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    id: i64,
+) -> Result<(), String> {
+    crate::auth::guard::validate_admin(&state, &session_token)?;
+
+    // Mapping kategori ikut terlepas lewat ON DELETE SET NULL
+    let result = sqlx::query("DELETE FROM printer_profiles WHERE id = ?")
+        .bind(id)
+        .execute(&state.db)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if result.rows_affected() == 0 {
+        return Err("Printer profile tidak ditemukan".into());
+    }
+    Ok(())
+}
+
+/// Atur printer tujuan tiket untuk satu kategori (`None` = tidak dicetak).
+/// Admin only.
+#[tauri::command]
+pub async fn set_category_printer(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    category_id: i64,
+    printer_profile_id: Option<i64>,
+) -> Result<(), String> {
+    crate::auth::guard::validate_admin(&state, &session_token)?;
+
+    if let Some(profile_id) = printer_profile_id {
+        fetch_profile(&state.db, profile_id).await?;
+    }
+
+    let result = sqlx::query("UPDATE categories SET printer_profile_id = ? WHERE id = ?")
+        .bind(printer_profile_id)
+        .bind(category_id)
+        .execute(&state.db)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if result.rows_affected() == 0 {
+        return Err("Kategori tidak ditemukan".into());
+    }
+    Ok(())
+}
+
+fn validate_profile(payload: &PrinterProfilePayload) -> Result<(), String> {
+    if payload.name.trim().is_empty() {
+        return Err("Nama printer tidak boleh kosong".into());
+    }
+    if payload.printer_port.trim().is_empty() {
+        return Err("Port printer tidak boleh kosong".into());
+    }
+    if payload.paper_width != "58mm" && payload.paper_width != "80mm" {
+        return Err("Lebar kertas tidak valid".into());
+    }
+    Ok(())
+}
+
+fn map_profile_error(e: sqlx::Error) -> String {
+    match e {
+        sqlx::Error::Database(err) if err.is_unique_violation() => {
+            "Nama printer sudah ada".into()
+        }
+        e => e.to_string(),
+    }
+}
+
+async fn fetch_profile(db: &sqlx::SqlitePool, id: i64) -> Result<PrinterProfile, String> {
+    sqlx::query_as::<_, PrinterProfile>("SELECT * FROM printer_profiles WHERE id = ?")
+        .bind(id)
+        .fetch_optional(db)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Printer profile tidak ditemukan".into())
+}
+
+// ============================================================================
+// TIKET PESANAN
+// ============================================================================
+
+/// Kirim ulang tiket dapur/bar untuk satu transaksi. Mengembalikan id job.
+#[tauri::command]
+pub async fn print_kitchen_tickets(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    transaction_id: String,
+) -> Result<Vec<i64>, String> {
+    let session = crate::auth::guard::validate_session(&state, &session_token)?;
+
+    let jobs = enqueue_kitchen_tickets(&state.db, &transaction_id, Some(session.user_id)).await?;
+    if jobs.is_empty() {
+        return Err("Tidak ada item yang dipetakan ke printer dapur/bar".into());
+    }
+    Ok(jobs)
+}
+
+/// Cetak tiket otomatis setelah transaksi (jika diaktifkan di settings).
+/// Kegagalan hanya dicatat di log — transaksi tetap sah.
+pub async fn auto_print_kitchen_tickets(db: &sqlx::SqlitePool, transaction_id: &str, user_id: i64) {
+    let enabled: Option<(String,)> =
+        sqlx::query_as("SELECT value FROM settings WHERE key = 'kitchen.auto_print'")
+            .fetch_optional(db)
+            .await
+            .unwrap_or(None);
+    if enabled.map(|v| v.0 != "1").unwrap_or(true) {
+        return;
+    }
+
+    if let Err(e) = enqueue_kitchen_tickets(db, transaction_id, Some(user_id)).await {
+        eprintln!("[KITCHEN] Gagal membuat tiket {}: {}", transaction_id, e);
+    }
+}
+
+/// Buat satu tiket per printer profile berisi hanya item dari kategori yang
+/// dipetakan ke printer tersebut, lalu masukkan ke antrian cetak.
+pub async fn enqueue_kitchen_tickets(
+    db: &sqlx::SqlitePool,
+    transaction_id: &str,
+    created_by: Option<i64>,
+) -> Result<Vec<i64>, String> {
+    let header: (Option<String>, Option<String>, Option<String>) = sqlx::query_as(
+        "SELECT t.timestamp, u.name, t.notes
+         FROM transactions t
+         LEFT JOIN users u ON t.cashier_id = u.id
+         WHERE t.id = ?",
+    )
+    .bind(transaction_id)
+    .fetch_optional(db)
+    .await
+    .map_err(|e| e.to_string())?
+    .ok_or("Transaksi tidak ditemukan")?;
+
+    let rows = sqlx::query_as::<_, TicketRow>(
+        "SELECT pp.id AS profile_id, pp.name AS station, pp.printer_port, pp.paper_width,
+                p.name
+                    || CASE WHEN ti.unit_conversion != 1 THEN ' (' || ti.unit || ')' ELSE '' END AS product_name,
+                ti.quantity, ti.notes
+         FROM transaction_items ti
+         JOIN products p ON ti.product_id = p.id
+         JOIN categories c ON p.category_id = c.id
+         JOIN printer_profiles pp ON c.printer_profile_id = pp.id
+         WHERE ti.transaction_id = ? AND pp.is_active = 1
+         ORDER BY pp.id ASC, ti.id ASC",
+    )
+    .bind(transaction_id)
+    .fetch_all(db)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    // Kelompokkan per printer (rows sudah terurut per profile)
+    let mut tickets: Vec<(i64, String, String, KitchenTicket)> = Vec::new();
+    for row in rows {
+        if tickets.last().map(|t| t.0) != Some(row.profile_id) {
+            tickets.push((
+                row.profile_id,
+                row.printer_port,
+                row.paper_width,
+                KitchenTicket {
+                    station: row.station,
+                    transaction_id: transaction_id.to_string(),
+                    cashier_name: header.1.clone().unwrap_or_default(),
+                    timestamp: header.0.clone().unwrap_or_default(),
+                    order_notes: header.2.clone(),
+                    items: Vec::new(),
+                },
+            ));
+        }
+        if let Some(last) = tickets.last_mut() {
+            last.3.items.push(TicketItem {
+                name: row.product_name,
+                quantity: row.quantity,
+                notes: row.notes,
+            });
+        }
+    }
+
+    let mut job_ids = Vec::with_capacity(tickets.len());
+    for (_, port, paper_width, ticket) in tickets {
+        let payload = render_ticket(&ticket, paper_columns(&paper_width));
+        let job_id = crate::commands::print_job_cmd::enqueue_print_job(
+            db,
+            "KITCHEN",
+            Some(transaction_id),
+            &port,
+            &payload,
+            created_by,
+        )
+        .await?;
+        job_ids.push(job_id);
+    }
+
+    Ok(job_ids)
+}
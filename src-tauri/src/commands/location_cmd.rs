@@ -0,0 +1,583 @@
+use crate::models::location::{
+    Location, LocationPayload, LocationProductStock, LocationStock, StockTransfer,
+    StockTransferDetail, StockTransferItem, StockTransferPayload,
+};
+use crate::models::quantity::{round_quantity, validate_quantity, Quantity};
+use crate::AppState;
+use sqlx::SqliteConnection;
+
+/// Stok lokasi utama = stok total - stok lokasi lain - qty dalam perjalanan.
+/// Dipakai dengan alias tabel produk `p`.
+const DEFAULT_STOCK: &str = "ROUND(p.stock
+        - (SELECT COALESCE(SUM(ls.quantity), 0) FROM product_location_stock ls WHERE ls.product_id = p.id)
+        - (SELECT COALESCE(SUM(ti.quantity), 0) FROM stock_transfer_items ti
+           JOIN stock_transfers t ON t.id = ti.transfer_id
+           WHERE t.status = 'IN_TRANSIT' AND ti.product_id = p.id), 3)";
+
+const TRANSFER_SELECT: &str = "SELECT t.id, t.transfer_number, t.from_location_id, fl.name as from_location_name,
+            t.to_location_id, tl.name as to_location_name, t.status, t.notes,
+            cu.name as created_by_name, t.created_at, xu.name as closed_by_name, t.closed_at,
+            (SELECT COUNT(*) FROM stock_transfer_items i WHERE i.transfer_id = t.id) as item_count
+     FROM stock_transfers t
+     JOIN locations fl ON fl.id = t.from_location_id
+     JOIN locations tl ON tl.id = t.to_location_id
+     LEFT JOIN users cu ON cu.id = t.created_by
+     LEFT JOIN users xu ON xu.id = t.closed_by";
+
+/// Validasi lokasi aktif. `None` = lokasi utama (stoknya tidak disimpan per baris).
+pub async fn resolve_location(
+    conn: &mut SqliteConnection,
+    location_id: i64,
+) -> Result<Option<i64>, String> {
+    let (name, is_default, is_active): (String, bool, bool) =
+        sqlx::query_as("SELECT name, is_default, is_active FROM locations WHERE id = ?")
+            .bind(location_id)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| format!("Lokasi id {} tidak ditemukan", location_id))?;
+    if !is_active {
+        return Err(format!("Lokasi '{}' nonaktif", name));
+    }
+    Ok((!is_default).then_some(location_id))
+}
+
+/// Lokasi pilihan pengguna; kosong = lokasi utama.
+pub async fn location_or_default(
+    conn: &mut SqliteConnection,
+    location_id: Option<i64>,
+) -> Result<Option<i64>, String> {
+    match location_id {
+        Some(location_id) => resolve_location(conn, location_id).await,
+        None => Ok(None),
+    }
+}
+
+/// Ekspresi SQL stok produk `p` di satu lokasi (`None` = lokasi utama).
+pub fn location_stock_sql(location: Option<i64>) -> String {
+    match location {
+        None => DEFAULT_STOCK.to_string(),
+        Some(location_id) => format!(
+            "COALESCE((SELECT ls.quantity FROM product_location_stock ls
+                       WHERE ls.product_id = p.id AND ls.location_id = {}), 0)",
+            location_id
+        ),
+    }
+}
+
+/// Lokasi stok kasir ini (setting app.location_id).
+pub async fn till_location(conn: &mut SqliteConnection) -> Result<Option<i64>, String> {
+    let value: Option<(String,)> =
+        sqlx::query_as("SELECT value FROM settings WHERE key = 'app.location_id'")
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| e.to_string())?;
+    location_or_default(conn, value.and_then(|(v,)| v.parse::<i64>().ok())).await
+}
+
+/// Stok tersedia satu produk di lokasi (`None` = lokasi utama).
+pub async fn location_available(
+    conn: &mut SqliteConnection,
+    product_id: i64,
+    location: Option<i64>,
+) -> Result<f64, String> {
+    let (Quantity(available),): (Quantity,) = sqlx::query_as(&format!(
+        "SELECT {} FROM products p WHERE p.id = ?",
+        location_stock_sql(location)
+    ))
+    .bind(product_id)
+    .fetch_one(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+    Ok(available)
+}
+
+/// Ubah stok produk di lokasi non-utama. Lokasi utama (`None`) mengikuti
+/// products.stock sehingga tidak perlu dicatat.
+pub async fn move_location_stock(
+    conn: &mut SqliteConnection,
+    product_id: i64,
+    location: Option<i64>,
+    delta: f64,
+) -> Result<(), String> {
+    let Some(location_id) = location else {
+        return Ok(());
+    };
+    sqlx::query(
+        "INSERT INTO product_location_stock (product_id, location_id, quantity) VALUES (?, ?, ?)
+         ON CONFLICT(product_id, location_id) DO UPDATE SET quantity = ROUND(quantity + excluded.quantity, 3)",
+    )
+    .bind(product_id)
+    .bind(location_id)
+    .bind(round_quantity(delta))
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+fn validate_location_payload(payload: &LocationPayload) -> Result<&str, String> {
+    let name = payload.name.trim();
+    if name.is_empty() {
+        return Err("Nama lokasi tidak boleh kosong".into());
+    }
+    if !matches!(payload.location_type.as_str(), "STORE" | "WAREHOUSE") {
+        return Err(format!("Tipe lokasi '{}' tidak valid", payload.location_type));
+    }
+    Ok(name)
+}
+
+/// Daftar lokasi stok
+#[tauri::command]
+pub async fn get_locations(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    show_inactive: Option<bool>,
+) -> Result<Vec<Location>, String> {
+    crate::auth::guard::validate_session(&state, &session_token)?;
+
+    let query = if show_inactive.unwrap_or(false) {
+        "SELECT * FROM locations ORDER BY is_default DESC, name ASC"
+    } else {
+        "SELECT * FROM locations WHERE is_active = 1 ORDER BY is_default DESC, name ASC"
+    };
+    sqlx::query_as::<_, Location>(query)
+        .fetch_all(&state.db)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Tambah lokasi (Admin only)
+#[tauri::command]
+pub async fn create_location(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    payload: LocationPayload,
+) -> Result<Location, String> {
+    let session = crate::auth::guard::validate_admin(&state, &session_token)?;
+    let name = validate_location_payload(&payload)?;
+
+    let id = sqlx::query("INSERT INTO locations (name, location_type) VALUES (?, ?)")
+        .bind(name)
+        .bind(&payload.location_type)
+        .execute(&state.db)
+        .await
+        .map_err(|e| {
+            if e.to_string().contains("UNIQUE") {
+                format!("Lokasi '{}' sudah ada", name)
+            } else {
+                e.to_string()
+            }
+        })?
+        .last_insert_rowid();
+
+    crate::commands::activity_cmd::log_activity(
+        &state.db,
+        None,
+        Some(session.user_id),
+        "CREATE_LOCATION",
+        &format!("Menambah lokasi {}", name),
+        None,
+    )
+    .await;
+
+    sqlx::query_as::<_, Location>("SELECT * FROM locations WHERE id = ?")
+        .bind(id)
+        .fetch_one(&state.db)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Ubah nama / tipe lokasi (Admin only)
+#[tauri::command]
+pub async fn update_location(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    id: i64,
+    payload: LocationPayload,
+) -> Result<Location, String> {
+    crate::auth::guard::validate_admin(&state, &session_token)?;
+    let name = validate_location_payload(&payload)?;
+
+    sqlx::query("UPDATE locations SET name = ?, location_type = ? WHERE id = ?")
+        .bind(name)
+        .bind(&payload.location_type)
+        .bind(id)
+        .execute(&state.db)
+        .await
+        .map_err(|e| {
+            if e.to_string().contains("UNIQUE") {
+                format!("Lokasi '{}' sudah ada", name)
+            } else {
+                e.to_string()
+            }
+        })?;
+
+    sqlx::query_as::<_, Location>("SELECT * FROM locations WHERE id = ?")
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Lokasi tidak ditemukan".to_string())
+}
+
+/// Aktif/nonaktifkan lokasi. Lokasi utama, lokasi yang masih berstok, atau
+/// yang punya transfer dalam perjalanan tidak bisa dinonaktifkan.
+#[tauri::command]
+pub async fn toggle_location(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    id: i64,
+) -> Result<bool, String> {
+    crate::auth::guard::validate_admin(&state, &session_token)?;
+
+    let (is_default, is_active): (bool, bool) =
+        sqlx::query_as("SELECT is_default, is_active FROM locations WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&state.db)
+            .await
+            .map_err(|e| e.to_string())?
+            .ok_or("Lokasi tidak ditemukan")?;
+
+    if is_active {
+        if is_default {
+            return Err("Lokasi utama tidak bisa dinonaktifkan".into());
+        }
+        let (stocked, in_transit): (i64, i64) = sqlx::query_as(
+            "SELECT
+                (SELECT COUNT(*) FROM product_location_stock WHERE location_id = ? AND quantity > 0),
+                (SELECT COUNT(*) FROM stock_transfers
+                 WHERE status = 'IN_TRANSIT' AND (from_location_id = ? OR to_location_id = ?))",
+        )
+        .bind(id)
+        .bind(id)
+        .bind(id)
+        .fetch_one(&state.db)
+        .await
+        .map_err(|e| e.to_string())?;
+        if stocked > 0 || in_transit > 0 {
+            return Err("Lokasi masih memiliki stok atau transfer dalam perjalanan".into());
+        }
+    }
+
+    sqlx::query("UPDATE locations SET is_active = ? WHERE id = ?")
+        .bind(!is_active)
+        .bind(id)
+        .execute(&state.db)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(!is_active)
+}
+
+/// Stok satu produk di setiap lokasi aktif, beserta qty dalam perjalanan
+#[tauri::command]
+pub async fn get_product_location_stock(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    product_id: i64,
+) -> Result<Vec<LocationStock>, String> {
+    crate::auth::guard::validate_session(&state, &session_token)?;
+
+    let query = format!(
+        "SELECT l.id as location_id, l.name as location_name, l.is_default,
+                CASE WHEN l.is_default = 1 THEN {} ELSE COALESCE(ls.quantity, 0) END as quantity,
+                (SELECT COALESCE(SUM(ti.quantity), 0) FROM stock_transfer_items ti
+                 JOIN stock_transfers t ON t.id = ti.transfer_id
+                 WHERE t.status = 'IN_TRANSIT' AND t.to_location_id = l.id AND ti.product_id = p.id) as incoming
+         FROM locations l
+         CROSS JOIN products p
+         LEFT JOIN product_location_stock ls ON ls.location_id = l.id AND ls.product_id = p.id
+         WHERE p.id = ? AND l.is_active = 1
+         ORDER BY l.is_default DESC, l.name ASC",
+        DEFAULT_STOCK
+    );
+    sqlx::query_as::<_, LocationStock>(&query)
+        .bind(product_id)
+        .fetch_all(&state.db)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Produk berstok di satu lokasi
+#[tauri::command]
+pub async fn get_location_stock(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    location_id: i64,
+) -> Result<Vec<LocationProductStock>, String> {
+    crate::auth::guard::validate_session(&state, &session_token)?;
+
+    let mut conn = state.db.acquire().await.map_err(|e| e.to_string())?;
+    let location = resolve_location(&mut conn, location_id).await?;
+
+    let query = match location {
+        None => format!(
+            "SELECT * FROM (
+                 SELECT p.id as product_id, p.name, p.sku, p.unit, p.is_weighed, {} as quantity
+                 FROM products p
+                 WHERE p.is_active = 1 AND p.is_kit = 0 AND p.is_recipe = 0
+             ) WHERE quantity > 0
+             ORDER BY name ASC",
+            DEFAULT_STOCK
+        ),
+        Some(_) => "SELECT p.id as product_id, p.name, p.sku, p.unit, p.is_weighed, ls.quantity
+             FROM product_location_stock ls
+             JOIN products p ON p.id = ls.product_id
+             WHERE ls.location_id = ? AND ls.quantity > 0
+             ORDER BY p.name ASC"
+            .to_string(),
+    };
+
+    let mut q = sqlx::query_as::<_, LocationProductStock>(&query);
+    if let Some(location_id) = location {
+        q = q.bind(location_id);
+    }
+    q.fetch_all(&mut *conn).await.map_err(|e| e.to_string())
+}
+
+async fn load_transfer(
+    conn: &mut SqliteConnection,
+    id: i64,
+) -> Result<StockTransferDetail, String> {
+    let transfer = sqlx::query_as::<_, StockTransfer>(&format!("{} WHERE t.id = ?", TRANSFER_SELECT))
+        .bind(id)
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or("Transfer tidak ditemukan")?;
+
+    let items = sqlx::query_as::<_, StockTransferItem>(
+        "SELECT i.id, i.transfer_id, i.product_id, p.name as product_name, p.unit, i.quantity
+         FROM stock_transfer_items i
+         JOIN products p ON p.id = i.product_id
+         WHERE i.transfer_id = ?
+         ORDER BY i.id ASC",
+    )
+    .bind(id)
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(StockTransferDetail { transfer, items })
+}
+
+/// Daftar transfer stok, bisa difilter status
+#[tauri::command]
+pub async fn get_stock_transfers(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    status: Option<String>,
+) -> Result<Vec<StockTransfer>, String> {
+    crate::auth::guard::validate_admin(&state, &session_token)?;
+
+    let mut query = format!("{} WHERE 1=1", TRANSFER_SELECT);
+    if status.is_some() {
+        query.push_str(" AND t.status = ?");
+    }
+    query.push_str(" ORDER BY t.created_at DESC, t.id DESC LIMIT 200");
+
+    let mut q = sqlx::query_as::<_, StockTransfer>(&query);
+    if let Some(status) = &status {
+        q = q.bind(status);
+    }
+    q.fetch_all(&state.db).await.map_err(|e| e.to_string())
+}
+
+/// Detail satu transfer
+#[tauri::command]
+pub async fn get_stock_transfer(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    id: i64,
+) -> Result<StockTransferDetail, String> {
+    crate::auth::guard::validate_admin(&state, &session_token)?;
+
+    let mut conn = state.db.acquire().await.map_err(|e| e.to_string())?;
+    load_transfer(&mut conn, id).await
+}
+
+/// Kirim stok ke lokasi lain (Admin only): stok lokasi asal langsung
+/// berkurang dan barang berstatus dalam perjalanan sampai diterima.
+#[tauri::command]
+pub async fn create_stock_transfer(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    payload: StockTransferPayload,
+) -> Result<StockTransferDetail, String> {
+    let session = crate::auth::guard::validate_admin(&state, &session_token)?;
+
+    if payload.from_location_id == payload.to_location_id {
+        return Err("Lokasi asal dan tujuan tidak boleh sama".into());
+    }
+    if payload.items.is_empty() {
+        return Err("Transfer harus berisi minimal satu barang".into());
+    }
+
+    let mut tx = state.db.begin().await.map_err(|e| e.to_string())?;
+    let from = resolve_location(&mut tx, payload.from_location_id).await?;
+    resolve_location(&mut tx, payload.to_location_id).await?;
+
+    // Nomor berikutnya dari urutan terbesar hari ini (bukan COUNT, supaya
+    // tidak bentrok jika ada transfer yang dihapus)
+    let prefix = format!("TRF/{}/", chrono::Local::now().format("%Y%m%d"));
+    let (last,): (i64,) = sqlx::query_as(
+        "SELECT COALESCE(MAX(CAST(SUBSTR(transfer_number, ?) AS INTEGER)), 0)
+         FROM stock_transfers WHERE transfer_number LIKE ?",
+    )
+    .bind(prefix.len() as i64 + 1)
+    .bind(format!("{}%", prefix))
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+    let number = format!("{}{:03}", prefix, last + 1);
+
+    let transfer_id = sqlx::query(
+        "INSERT INTO stock_transfers (transfer_number, from_location_id, to_location_id, notes, created_by)
+         VALUES (?, ?, ?, ?, ?)",
+    )
+    .bind(&number)
+    .bind(payload.from_location_id)
+    .bind(payload.to_location_id)
+    .bind(payload.notes.as_deref().map(str::trim).filter(|n| !n.is_empty()))
+    .bind(session.user_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?
+    .last_insert_rowid();
+
+    let mut seen = std::collections::HashSet::new();
+    for item in &payload.items {
+        if !seen.insert(item.product_id) {
+            return Err(format!("Produk id {} diisi lebih dari sekali", item.product_id));
+        }
+        let (name, is_weighed, composite): (String, bool, bool) =
+            sqlx::query_as("SELECT name, is_weighed, is_kit OR is_recipe FROM products WHERE id = ?")
+                .bind(item.product_id)
+                .fetch_optional(&mut *tx)
+                .await
+                .map_err(|e| e.to_string())?
+                .ok_or_else(|| format!("Produk id {} tidak ditemukan", item.product_id))?;
+        if composite {
+            return Err(format!("'{}' adalah paket/resep, transfer komponennya", name));
+        }
+        validate_quantity(item.quantity, is_weighed).map_err(|e| format!("{}: {}", name, e))?;
+        let quantity = round_quantity(item.quantity);
+
+        // Cek sebelum baris transfer dimasukkan (lokasi utama menghitung qty dalam perjalanan)
+        let available = location_available(&mut tx, item.product_id, from).await?;
+        if available < quantity {
+            return Err(format!("Stok '{}' di lokasi asal tidak cukup (tersedia {})", name, available));
+        }
+
+        sqlx::query("INSERT INTO stock_transfer_items (transfer_id, product_id, quantity) VALUES (?, ?, ?)")
+            .bind(transfer_id)
+            .bind(item.product_id)
+            .bind(quantity)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?;
+        move_location_stock(&mut tx, item.product_id, from, -quantity).await?;
+    }
+
+    crate::commands::activity_cmd::log_activity(
+        &state.db,
+        Some(&mut tx),
+        Some(session.user_id),
+        "CREATE_STOCK_TRANSFER",
+        &format!("Mengirim transfer {} ({} barang)", number, payload.items.len()),
+        None,
+    )
+    .await;
+
+    let detail = load_transfer(&mut tx, transfer_id).await?;
+    tx.commit().await.map_err(|e| e.to_string())?;
+    Ok(detail)
+}
+
+/// Selesaikan transfer dalam perjalanan: diterima di lokasi tujuan, atau
+/// dibatalkan dan stok kembali ke lokasi asal.
+async fn close_transfer(
+    state: &AppState,
+    session_token: &str,
+    id: i64,
+    receive: bool,
+) -> Result<StockTransferDetail, String> {
+    let session = crate::auth::guard::validate_admin(state, session_token)?;
+
+    let mut tx = state.db.begin().await.map_err(|e| e.to_string())?;
+    let current = load_transfer(&mut tx, id).await?;
+    if current.transfer.status != "IN_TRANSIT" {
+        return Err(format!(
+            "Transfer {} sudah {}",
+            current.transfer.transfer_number, current.transfer.status
+        ));
+    }
+
+    // Lokasi utama tidak dicatat per baris, jadi cukup cek bukan lokasi utama
+    let target_id = if receive {
+        current.transfer.to_location_id
+    } else {
+        current.transfer.from_location_id
+    };
+    let (is_default,): (bool,) = sqlx::query_as("SELECT is_default FROM locations WHERE id = ?")
+        .bind(target_id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    let target = (!is_default).then_some(target_id);
+
+    for item in &current.items {
+        move_location_stock(&mut tx, item.product_id, target, item.quantity).await?;
+    }
+
+    sqlx::query(
+        "UPDATE stock_transfers SET status = ?, closed_by = ?, closed_at = CURRENT_TIMESTAMP WHERE id = ?",
+    )
+    .bind(if receive { "RECEIVED" } else { "CANCELLED" })
+    .bind(session.user_id)
+    .bind(id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    crate::commands::activity_cmd::log_activity(
+        &state.db,
+        Some(&mut tx),
+        Some(session.user_id),
+        if receive { "RECEIVE_STOCK_TRANSFER" } else { "CANCEL_STOCK_TRANSFER" },
+        &format!(
+            "{} transfer {} ({} → {})",
+            if receive { "Menerima" } else { "Membatalkan" },
+            current.transfer.transfer_number,
+            current.transfer.from_location_name,
+            current.transfer.to_location_name
+        ),
+        None,
+    )
+    .await;
+
+    let detail = load_transfer(&mut tx, id).await?;
+    tx.commit().await.map_err(|e| e.to_string())?;
+    Ok(detail)
+}
+
+/// Terima transfer di lokasi tujuan (Admin only)
+#[tauri::command]
+pub async fn receive_stock_transfer(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    id: i64,
+) -> Result<StockTransferDetail, String> {
+    close_transfer(&state, &session_token, id, true).await
+}
+
+/// Batalkan transfer dalam perjalanan; stok kembali ke lokasi asal (Admin only)
+#[tauri::command]
+pub async fn cancel_stock_transfer(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    id: i64,
+) -> Result<StockTransferDetail, String> {
+    close_transfer(&state, &session_token, id, false).await
+}
@@ -1,6 +1,8 @@
 pub mod auth_cmd;
 pub mod discount_cmd;
+pub mod kitchen_cmd;
 pub mod product_cmd;
+pub mod scale_cmd;
 pub mod report_cmd;
 pub mod settings_cmd;
 pub mod transaction_cmd;
@@ -8,3 +10,11 @@ pub mod user_cmd;
 pub mod activity_cmd;
 pub mod payment_cmd;
 pub mod system_cmd;
+pub mod print_job_cmd;
+pub mod customer_cmd;
+pub mod pricing_cmd;
+pub mod recipe_cmd;
+pub mod batch_cmd;
+pub mod purchase_cmd;
+pub mod opname_cmd;
+pub mod location_cmd;
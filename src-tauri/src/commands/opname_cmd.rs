@@ -0,0 +1,430 @@
+use crate::models::opname::{
+    OpnameCount, OpnameCountPayload, OpnameItem, OpnameSession, OpnameSessionDetail,
+};
+use crate::commands::location_cmd::{location_or_default, location_stock_sql, move_location_stock};
+use crate::models::quantity::{round_quantity, validate_counted_quantity};
+use crate::AppState;
+use sqlx::SqliteConnection;
+
+const SESSION_SELECT: &str = "SELECT s.id, s.name, s.status, s.notes, s.created_by,
+            cu.name as created_by_name, s.created_at, xu.name as closed_by_name, s.closed_at,
+            s.location_id, COALESCE(l.name, (SELECT name FROM locations WHERE is_default = 1)) as location_name,
+            (SELECT COUNT(*) FROM stock_opname_items i WHERE i.session_id = s.id) as item_count,
+            (SELECT COUNT(DISTINCT c.product_id) FROM stock_opname_counts c WHERE c.session_id = s.id) as counted_count
+     FROM stock_opname_sessions s
+     LEFT JOIN locations l ON l.id = s.location_id
+     LEFT JOIN users cu ON cu.id = s.created_by
+     LEFT JOIN users xu ON xu.id = s.closed_by";
+
+const COUNT_SELECT: &str = "SELECT c.id, c.session_id, c.product_id, p.name as product_name, p.unit,
+            c.quantity, c.counted_by, u.name as counted_by_name, c.counted_at
+     FROM stock_opname_counts c
+     JOIN products p ON p.id = c.product_id
+     LEFT JOIN users u ON u.id = c.counted_by";
+
+async fn load_session(conn: &mut SqliteConnection, id: i64) -> Result<OpnameSession, String> {
+    sqlx::query_as::<_, OpnameSession>(&format!("{} WHERE s.id = ?", SESSION_SELECT))
+        .bind(id)
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Sesi stock opname tidak ditemukan".to_string())
+}
+
+async fn load_items(conn: &mut SqliteConnection, session_id: i64) -> Result<Vec<OpnameItem>, String> {
+    sqlx::query_as::<_, OpnameItem>(
+        "SELECT i.product_id, p.name as product_name, p.sku, p.barcode, p.unit, i.system_stock,
+                c.counted, ROUND(c.counted - i.system_stock, 3) as variance, i.cost_price,
+                ROUND((c.counted - i.system_stock) * i.cost_price) as variance_value, c.counted_by
+         FROM stock_opname_items i
+         JOIN products p ON p.id = i.product_id
+         LEFT JOIN (
+             SELECT sc.product_id, ROUND(SUM(sc.quantity), 3) as counted,
+                    GROUP_CONCAT(DISTINCT u.name) as counted_by
+             FROM stock_opname_counts sc
+             LEFT JOIN users u ON u.id = sc.counted_by
+             WHERE sc.session_id = ?
+             GROUP BY sc.product_id
+         ) c ON c.product_id = i.product_id
+         WHERE i.session_id = ?
+         ORDER BY c.counted IS NULL, ABS(COALESCE((c.counted - i.system_stock) * i.cost_price, 0)) DESC, p.name ASC",
+    )
+    .bind(session_id)
+    .bind(session_id)
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Pastikan sesi masih OPEN.
+async fn ensure_open(conn: &mut SqliteConnection, session_id: i64) -> Result<OpnameSession, String> {
+    let session = load_session(conn, session_id).await?;
+    if session.status != "OPEN" {
+        return Err(format!("Sesi '{}' sudah ditutup ({})", session.name, session.status));
+    }
+    Ok(session)
+}
+
+/// Daftar sesi stock opname (terbaru dulu)
+#[tauri::command]
+pub async fn get_opname_sessions(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    status: Option<String>,
+) -> Result<Vec<OpnameSession>, String> {
+    crate::auth::guard::validate_session(&state, &session_token)?;
+
+    let mut query = format!("{} WHERE 1=1", SESSION_SELECT);
+    if status.is_some() {
+        query.push_str(" AND s.status = ?");
+    }
+    query.push_str(" ORDER BY s.created_at DESC, s.id DESC LIMIT 100");
+
+    let mut q = sqlx::query_as::<_, OpnameSession>(&query);
+    if let Some(status) = &status {
+        q = q.bind(status);
+    }
+    q.fetch_all(&state.db).await.map_err(|e| e.to_string())
+}
+
+/// Detail sesi dengan selisih per produk (Admin only — kasir menghitung
+/// tanpa melihat stok sistem).
+#[tauri::command]
+pub async fn get_opname_session(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    id: i64,
+) -> Result<OpnameSessionDetail, String> {
+    crate::auth::guard::validate_admin(&state, &session_token)?;
+
+    let mut conn = state.db.acquire().await.map_err(|e| e.to_string())?;
+    let session = load_session(&mut conn, id).await?;
+    let items = load_items(&mut conn, id).await?;
+    let total_variance_value = items.iter().filter_map(|i| i.variance_value).sum();
+
+    Ok(OpnameSessionDetail {
+        session,
+        items,
+        total_variance_value,
+    })
+}
+
+/// Buka sesi stock opname baru: snapshot stok sistem semua produk fisik
+/// (bukan paket/resep, bukan induk varian) di satu lokasi (kosong = lokasi
+/// utama). Hanya satu sesi OPEN sekaligus.
+#[tauri::command]
+pub async fn create_opname_session(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    name: String,
+    notes: Option<String>,
+    location_id: Option<i64>,
+) -> Result<OpnameSession, String> {
+    let session = crate::auth::guard::validate_admin(&state, &session_token)?;
+
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Nama sesi tidak boleh kosong".into());
+    }
+
+    let mut tx = state.db.begin().await.map_err(|e| e.to_string())?;
+    let open: Option<(String,)> =
+        sqlx::query_as("SELECT name FROM stock_opname_sessions WHERE status = 'OPEN' LIMIT 1")
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?;
+    if let Some((open_name,)) = open {
+        return Err(format!("Sesi '{}' masih berjalan, selesaikan dulu", open_name));
+    }
+
+    let location = location_or_default(&mut tx, location_id).await?;
+
+    let session_id = sqlx::query(
+        "INSERT INTO stock_opname_sessions (name, notes, created_by, location_id) VALUES (?, ?, ?, ?)",
+    )
+    .bind(name)
+    .bind(notes.as_deref().map(str::trim).filter(|n| !n.is_empty()))
+    .bind(session.user_id)
+    .bind(location)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?
+    .last_insert_rowid();
+
+    let snapshot = sqlx::query(&format!(
+        "INSERT INTO stock_opname_items (session_id, product_id, system_stock, cost_price)
+         SELECT ?, p.id, {}, p.cost_price
+         FROM products p
+         WHERE p.is_active = 1 AND p.is_kit = 0 AND p.is_recipe = 0
+           AND NOT EXISTS (SELECT 1 FROM products v WHERE v.parent_id = p.id AND v.is_active = 1)",
+        location_stock_sql(location)
+    ))
+    .bind(session_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?
+    .rows_affected();
+
+    crate::commands::activity_cmd::log_activity(
+        &state.db,
+        Some(&mut tx),
+        Some(session.user_id),
+        "CREATE_STOCK_OPNAME",
+        &format!("Membuka stock opname '{}' ({} produk)", name, snapshot),
+        None,
+    )
+    .await;
+
+    let created = load_session(&mut tx, session_id).await?;
+    tx.commit().await.map_err(|e| e.to_string())?;
+    Ok(created)
+}
+
+/// Catat hasil hitung (scan barcode / pilih produk). Entri dari beberapa
+/// kasir dijumlahkan per produk.
+#[tauri::command]
+pub async fn submit_opname_count(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    payload: OpnameCountPayload,
+) -> Result<OpnameCount, String> {
+    let session = crate::auth::guard::validate_session(&state, &session_token)?;
+
+    let mut conn = state.db.acquire().await.map_err(|e| e.to_string())?;
+    ensure_open(&mut conn, payload.session_id).await?;
+
+    // Barcode utama / SKU → 1 unit; barcode kemasan → isi kemasan
+    let (product_id, pack_quantity) = match (&payload.barcode, payload.product_id) {
+        (Some(barcode), _) if !barcode.trim().is_empty() => {
+            let barcode = barcode.trim();
+            let found: Option<(i64, i64)> = sqlx::query_as(
+                "SELECT id, 1 FROM products WHERE (barcode = ? OR sku = ?) AND is_active = 1
+                 UNION ALL
+                 SELECT b.product_id, b.pack_quantity FROM product_barcodes b
+                 JOIN products p ON p.id = b.product_id
+                 WHERE b.barcode = ? AND p.is_active = 1
+                 LIMIT 1",
+            )
+            .bind(barcode)
+            .bind(barcode)
+            .bind(barcode)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| e.to_string())?;
+            found.ok_or_else(|| format!("Barcode {} tidak terdaftar", barcode))?
+        }
+        (_, Some(product_id)) => (product_id, 1),
+        _ => return Err("Scan barcode atau pilih produk".into()),
+    };
+
+    let item: Option<(String, bool)> = sqlx::query_as(
+        "SELECT p.name, p.is_weighed FROM stock_opname_items i
+         JOIN products p ON p.id = i.product_id
+         WHERE i.session_id = ? AND i.product_id = ?",
+    )
+    .bind(payload.session_id)
+    .bind(product_id)
+    .fetch_optional(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+    let (name, is_weighed) =
+        item.ok_or("Produk tidak termasuk sesi ini (paket/resep, varian induk, atau produk baru)")?;
+
+    let quantity = payload.quantity.unwrap_or(1.0) * pack_quantity as f64;
+    validate_counted_quantity(quantity, is_weighed).map_err(|e| format!("{}: {}", name, e))?;
+
+    let count_id = sqlx::query(
+        "INSERT INTO stock_opname_counts (session_id, product_id, quantity, counted_by) VALUES (?, ?, ?, ?)",
+    )
+    .bind(payload.session_id)
+    .bind(product_id)
+    .bind(round_quantity(quantity))
+    .bind(session.user_id)
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?
+    .last_insert_rowid();
+
+    sqlx::query_as::<_, OpnameCount>(&format!("{} WHERE c.id = ?", COUNT_SELECT))
+        .bind(count_id)
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Entri hitung terbaru sebuah sesi. Kasir hanya melihat entri miliknya.
+#[tauri::command]
+pub async fn get_opname_counts(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    session_id: i64,
+) -> Result<Vec<OpnameCount>, String> {
+    let session = crate::auth::guard::validate_session(&state, &session_token)?;
+
+    let mut query = format!("{} WHERE c.session_id = ?", COUNT_SELECT);
+    if session.role != "ADMIN" {
+        query.push_str(" AND c.counted_by = ?");
+    }
+    query.push_str(" ORDER BY c.id DESC LIMIT 200");
+
+    let mut q = sqlx::query_as::<_, OpnameCount>(&query).bind(session_id);
+    if session.role != "ADMIN" {
+        q = q.bind(session.user_id);
+    }
+    q.fetch_all(&state.db).await.map_err(|e| e.to_string())
+}
+
+/// Hapus entri hitung yang salah (milik sendiri, atau Admin) selama sesi OPEN.
+#[tauri::command]
+pub async fn delete_opname_count(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    count_id: i64,
+) -> Result<(), String> {
+    let session = crate::auth::guard::validate_session(&state, &session_token)?;
+
+    let mut conn = state.db.acquire().await.map_err(|e| e.to_string())?;
+    let (session_id, counted_by): (i64, Option<i64>) =
+        sqlx::query_as("SELECT session_id, counted_by FROM stock_opname_counts WHERE id = ?")
+            .bind(count_id)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| e.to_string())?
+            .ok_or("Entri hitung tidak ditemukan")?;
+    ensure_open(&mut conn, session_id).await?;
+    if session.role != "ADMIN" && counted_by != Some(session.user_id) {
+        return Err("Hanya bisa menghapus entri hitung milik sendiri".into());
+    }
+
+    sqlx::query("DELETE FROM stock_opname_counts WHERE id = ?")
+        .bind(count_id)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Setujui sesi (Admin only): selisih fisik vs snapshot diposting sebagai
+/// stock_adjustments alasan OPNAME dalam satu DB transaction. Selisih
+/// ditambahkan ke stok saat ini sehingga penjualan selama hitung tetap benar.
+/// Produk yang tidak dihitung dilewati, kecuali `uncounted_as_zero`.
+#[tauri::command]
+pub async fn approve_opname_session(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    session_id: i64,
+    uncounted_as_zero: bool,
+) -> Result<OpnameSessionDetail, String> {
+    let session = crate::auth::guard::validate_admin(&state, &session_token)?;
+
+    let mut tx = state.db.begin().await.map_err(|e| e.to_string())?;
+    let opname = ensure_open(&mut tx, session_id).await?;
+    let items = load_items(&mut tx, session_id).await?;
+
+    let mut adjusted = 0;
+    let mut posted_value = 0.0;
+    for item in &items {
+        let counted = match item.counted {
+            Some(counted) => counted,
+            None if uncounted_as_zero => 0.0,
+            None => continue,
+        };
+        let variance = round_quantity(counted - item.system_stock);
+        if variance == 0.0 {
+            continue;
+        }
+
+        sqlx::query(
+            "UPDATE products SET stock = ROUND(stock + ?, 3), updated_at = CURRENT_TIMESTAMP WHERE id = ?",
+        )
+        .bind(variance)
+        .bind(item.product_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+        move_location_stock(&mut tx, item.product_id, opname.location_id, variance).await?;
+        if variance < 0.0 {
+            crate::commands::batch_cmd::consume_batches(&mut tx, item.product_id, -variance).await?;
+        }
+        crate::commands::activity_cmd::log_stock_adjustment(
+            &state.db,
+            Some(&mut tx),
+            item.product_id,
+            session.user_id,
+            if variance > 0.0 { "IN" } else { "OUT" },
+            variance.abs(),
+            "OPNAME",
+            Some(&format!(
+                "Opname '{}': sistem {} → fisik {}",
+                opname.name, item.system_stock, counted
+            )),
+        )
+        .await;
+        adjusted += 1;
+        posted_value += (variance * item.cost_price).round();
+    }
+
+    sqlx::query(
+        "UPDATE stock_opname_sessions SET status = 'APPROVED', closed_by = ?, closed_at = CURRENT_TIMESTAMP WHERE id = ?",
+    )
+    .bind(session.user_id)
+    .bind(session_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    crate::commands::activity_cmd::log_activity(
+        &state.db,
+        Some(&mut tx),
+        Some(session.user_id),
+        "APPROVE_STOCK_OPNAME",
+        &format!(
+            "Menyetujui stock opname '{}': {} produk disesuaikan, selisih nilai {}",
+            opname.name, adjusted, posted_value
+        ),
+        None,
+    )
+    .await;
+
+    let session_row = load_session(&mut tx, session_id).await?;
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    Ok(OpnameSessionDetail {
+        session: session_row,
+        items,
+        total_variance_value: posted_value,
+    })
+}
+
+/// Batalkan sesi tanpa mengubah stok (Admin only)
+#[tauri::command]
+pub async fn cancel_opname_session(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    session_id: i64,
+) -> Result<(), String> {
+    let session = crate::auth::guard::validate_admin(&state, &session_token)?;
+
+    let mut conn = state.db.acquire().await.map_err(|e| e.to_string())?;
+    let opname = ensure_open(&mut conn, session_id).await?;
+    sqlx::query(
+        "UPDATE stock_opname_sessions SET status = 'CANCELLED', closed_by = ?, closed_at = CURRENT_TIMESTAMP WHERE id = ?",
+    )
+    .bind(session.user_id)
+    .bind(session_id)
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+    drop(conn);
+
+    crate::commands::activity_cmd::log_activity(
+        &state.db,
+        None,
+        Some(session.user_id),
+        "CANCEL_STOCK_OPNAME",
+        &format!("Membatalkan stock opname '{}'", opname.name),
+        None,
+    )
+    .await;
+    Ok(())
+}
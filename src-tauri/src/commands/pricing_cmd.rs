@@ -0,0 +1,735 @@
+use crate::models::pricing::{
+    LevelPrice, PriceHistoryEntry, PriceQuoteItem, PriceSchedule, PriceSchedulePayload, PriceTier,
+    ProductPricing,
+};
+use crate::AppState;
+use sqlx::{SqliteConnection, SqlitePool};
+use std::collections::HashMap;
+use std::time::Duration;
+use tauri::Manager;
+
+/// Interval worker memeriksa jadwal harga yang sudah jatuh tempo
+const SCHEDULE_POLL_SECS: u64 = 60;
+
+/// Level harga pelanggan untuk transaksi. Tanpa pelanggan / pelanggan
+/// eceran → None.
+pub async fn customer_price_level(
+    conn: &mut SqliteConnection,
+    customer_id: Option<i64>,
+) -> Result<Option<i64>, String> {
+    let Some(customer_id) = customer_id else {
+        return Ok(None);
+    };
+    let row: (Option<i64>,) =
+        sqlx::query_as("SELECT price_level_id FROM customers WHERE id = ? AND is_active = 1")
+            .bind(customer_id)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| e.to_string())?
+            .ok_or("Pelanggan tidak ditemukan atau nonaktif")?;
+    Ok(row.0)
+}
+
+/// Harga per satuan jual untuk satu item.
+///
+/// Harga dasar = harga khusus level (atau harga eceran dikurangi potongan
+/// level); satuan jual lain memakai harga satuannya. Jika total pembelian
+/// produk (satuan dasar) mencapai tier grosir, dipakai harga termurah.
+pub async fn resolve_price(
+    conn: &mut SqliteConnection,
+    product_id: i64,
+    unit_id: Option<i64>,
+    total_base_quantity: f64,
+    price_level_id: Option<i64>,
+) -> Result<f64, String> {
+    let (retail,): (f64,) = sqlx::query_as("SELECT price FROM products WHERE id = ?")
+        .bind(product_id)
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or(format!("Produk id {} tidak ditemukan", product_id))?;
+
+    let mut level_factor = 1.0;
+    let mut base_price = retail;
+    if let Some(level_id) = price_level_id {
+        let percent: Option<(f64,)> =
+            sqlx::query_as("SELECT discount_percent FROM price_levels WHERE id = ?")
+                .bind(level_id)
+                .fetch_optional(&mut *conn)
+                .await
+                .map_err(|e| e.to_string())?;
+        level_factor = 1.0 - percent.map(|p| p.0).unwrap_or(0.0) / 100.0;
+
+        let special: Option<(f64,)> = sqlx::query_as(
+            "SELECT price FROM product_level_prices WHERE product_id = ? AND price_level_id = ?",
+        )
+        .bind(product_id)
+        .bind(level_id)
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(|e| e.to_string())?;
+        base_price = special.map(|s| s.0).unwrap_or(retail * level_factor);
+    }
+
+    let (mut price, conversion) = match unit_id {
+        None => (base_price, 1.0),
+        Some(unit_id) => {
+            let (unit_price, conversion): (f64, f64) = sqlx::query_as(
+                "SELECT price, conversion FROM product_units WHERE id = ? AND product_id = ?",
+            )
+            .bind(unit_id)
+            .bind(product_id)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| format!("Satuan tidak valid untuk produk id {}", product_id))?;
+            if unit_price > 0.0 {
+                (unit_price * level_factor, conversion)
+            } else {
+                (base_price * conversion, conversion)
+            }
+        }
+    };
+
+    let tier: Option<(f64,)> = sqlx::query_as(
+        "SELECT price FROM product_price_tiers
+         WHERE product_id = ? AND min_quantity <= ?
+         ORDER BY min_quantity DESC LIMIT 1",
+    )
+    .bind(product_id)
+    .bind(total_base_quantity)
+    .fetch_optional(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+    if let Some((tier_price,)) = tier {
+        price = price.min(tier_price * conversion);
+    }
+
+    Ok(price.round())
+}
+
+/// Hitung harga semua item keranjang. Tier grosir dihitung dari total qty
+/// (satuan dasar) per produk, walau produk muncul di beberapa baris.
+pub async fn price_items(
+    conn: &mut SqliteConnection,
+    items: &[PriceQuoteItem],
+    price_level_id: Option<i64>,
+) -> Result<Vec<f64>, String> {
+    let mut totals: HashMap<i64, f64> = HashMap::new();
+    for item in items {
+        let conversion = match item.unit_id {
+            None => 1.0,
+            Some(unit_id) => {
+                let (conversion,): (f64,) = sqlx::query_as(
+                    "SELECT conversion FROM product_units WHERE id = ? AND product_id = ?",
+                )
+                .bind(unit_id)
+                .bind(item.product_id)
+                .fetch_optional(&mut *conn)
+                .await
+                .map_err(|e| e.to_string())?
+                .ok_or_else(|| format!("Satuan tidak valid untuk produk id {}", item.product_id))?;
+                conversion
+            }
+        };
+        *totals.entry(item.product_id).or_default() += item.quantity * conversion;
+    }
+
+    let mut prices = Vec::with_capacity(items.len());
+    for item in items {
+        // Label harga (produk non-timbang): harga per pcs tertanam di barcode
+        if let Some(barcode) = &item.scanned_barcode {
+            let (product, _, label_price) =
+                crate::commands::product_cmd::resolve_scale_label(conn, barcode)
+                    .await?
+                    .ok_or_else(|| format!("Label {} tidak dikenali", barcode))?;
+            if product.id != item.product_id || item.unit_id.is_some() {
+                return Err(format!("Label {} tidak cocok dengan produk id {}", barcode, item.product_id));
+            }
+            if !product.is_weighed {
+                prices.push(label_price);
+                continue;
+            }
+        }
+        prices.push(
+            resolve_price(
+                conn,
+                item.product_id,
+                item.unit_id,
+                totals[&item.product_id],
+                price_level_id,
+            )
+            .await?,
+        );
+    }
+    Ok(prices)
+}
+
+/// Harga server untuk isi keranjang (ditampilkan di POS sebelum bayar)
+#[tauri::command]
+pub async fn quote_prices(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    customer_id: Option<i64>,
+    items: Vec<PriceQuoteItem>,
+) -> Result<Vec<f64>, String> {
+    crate::auth::guard::validate_session(&state, &session_token)?;
+
+    let mut conn = state.db.acquire().await.map_err(|e| e.to_string())?;
+    let price_level_id = customer_price_level(&mut conn, customer_id).await?;
+    price_items(&mut conn, &items, price_level_id).await
+}
+
+/// Aturan harga satu produk: tier grosir + harga per level
+#[tauri::command]
+pub async fn get_product_pricing(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    product_id: i64,
+) -> Result<ProductPricing, String> {
+    crate::auth::guard::validate_session(&state, &session_token)?;
+
+    let tiers = sqlx::query_as::<_, PriceTier>(
+        "SELECT min_quantity, price FROM product_price_tiers WHERE product_id = ? ORDER BY min_quantity ASC",
+    )
+    .bind(product_id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let level_prices = sqlx::query_as::<_, LevelPrice>(
+        "SELECT price_level_id, price FROM product_level_prices WHERE product_id = ?",
+    )
+    .bind(product_id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(ProductPricing { tiers, level_prices })
+}
+
+/// Simpan (ganti seluruh) aturan harga produk (Admin only)
+#[tauri::command]
+pub async fn save_product_pricing(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    product_id: i64,
+    pricing: ProductPricing,
+) -> Result<ProductPricing, String> {
+    let session = crate::auth::guard::validate_admin(&state, &session_token)?;
+
+    let mut seen = std::collections::HashSet::new();
+    for t in &pricing.tiers {
+        if !(t.min_quantity > 0.0 && t.min_quantity.is_finite()) || t.price < 0.0 {
+            return Err(format!("Tier grosir min. {} tidak valid", t.min_quantity));
+        }
+        if !seen.insert(t.min_quantity.to_bits()) {
+            return Err(format!("Tier grosir min. {} diisi lebih dari sekali", t.min_quantity));
+        }
+    }
+    if pricing.level_prices.iter().any(|l| l.price < 0.0) {
+        return Err("Harga level tidak boleh negatif".into());
+    }
+
+    let mut tx = state.db.begin().await.map_err(|e| e.to_string())?;
+    sqlx::query("DELETE FROM product_price_tiers WHERE product_id = ?")
+        .bind(product_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    sqlx::query("DELETE FROM product_level_prices WHERE product_id = ?")
+        .bind(product_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    for t in &pricing.tiers {
+        sqlx::query("INSERT INTO product_price_tiers (product_id, min_quantity, price) VALUES (?, ?, ?)")
+            .bind(product_id)
+            .bind(t.min_quantity)
+            .bind(t.price)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+    for l in &pricing.level_prices {
+        sqlx::query(
+            "INSERT OR REPLACE INTO product_level_prices (product_id, price_level_id, price) VALUES (?, ?, ?)",
+        )
+        .bind(product_id)
+        .bind(l.price_level_id)
+        .bind(l.price)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    }
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    crate::commands::activity_cmd::log_activity(
+        &state.db,
+        None,
+        Some(session.user_id),
+        "UPDATE_PRODUCT_PRICING",
+        &format!(
+            "Memperbarui {} tier grosir & {} harga level produk ID {}",
+            pricing.tiers.len(),
+            pricing.level_prices.len(),
+            product_id
+        ),
+        None,
+    )
+    .await;
+
+    get_product_pricing(state, session_token, product_id).await
+}
+
+/// Catat perubahan harga jual/modal ke product_price_history (jika berubah)
+pub async fn record_price_change(
+    conn: &mut SqliteConnection,
+    product_id: i64,
+    old: (f64, f64),
+    new: (f64, f64),
+    source: &str,
+    schedule_id: Option<i64>,
+    changed_by: Option<i64>,
+) -> Result<(), String> {
+    if old == new {
+        return Ok(());
+    }
+    sqlx::query(
+        "INSERT INTO product_price_history
+            (product_id, old_price, new_price, old_cost_price, new_cost_price, source, schedule_id, changed_by)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(product_id)
+    .bind(old.0)
+    .bind(new.0)
+    .bind(old.1)
+    .bind(new.1)
+    .bind(source)
+    .bind(schedule_id)
+    .bind(changed_by)
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Riwayat harga satu produk (terbaru dulu)
+#[tauri::command]
+pub async fn get_product_price_history(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    product_id: i64,
+) -> Result<Vec<PriceHistoryEntry>, String> {
+    crate::auth::guard::validate_admin(&state, &session_token)?;
+
+    sqlx::query_as::<_, PriceHistoryEntry>(
+        "SELECT h.id, h.product_id, h.old_price, h.new_price, h.old_cost_price, h.new_cost_price,
+                h.source, h.target, h.schedule_id, u.name as changed_by_name, h.changed_at
+         FROM product_price_history h
+         LEFT JOIN users u ON h.changed_by = u.id
+         WHERE h.product_id = ?
+         ORDER BY h.changed_at DESC, h.id DESC",
+    )
+    .bind(product_id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| e.to_string())
+}
+
+const SCHEDULE_SELECT: &str = "SELECT s.id, s.product_id, p.name as product_name, s.category_id,
+            c.name as category_name, s.new_price, s.percent_change, s.effective_at, s.status,
+            s.notes, s.applied_at, s.applied_count, s.created_at
+     FROM price_schedules s
+     LEFT JOIN products p ON s.product_id = p.id
+     LEFT JOIN categories c ON s.category_id = c.id";
+
+/// Daftar jadwal perubahan harga
+#[tauri::command]
+pub async fn get_price_schedules(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    status: Option<String>,
+) -> Result<Vec<PriceSchedule>, String> {
+    crate::auth::guard::validate_admin(&state, &session_token)?;
+
+    let query = if status.is_some() {
+        format!("{} WHERE s.status = ? ORDER BY s.effective_at DESC", SCHEDULE_SELECT)
+    } else {
+        format!("{} ORDER BY s.effective_at DESC", SCHEDULE_SELECT)
+    };
+    let mut q = sqlx::query_as::<_, PriceSchedule>(&query);
+    if let Some(status) = &status {
+        q = q.bind(status);
+    }
+    q.fetch_all(&state.db).await.map_err(|e| e.to_string())
+}
+
+/// Jadwalkan perubahan harga (Admin only)
+#[tauri::command]
+pub async fn create_price_schedule(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    payload: PriceSchedulePayload,
+) -> Result<PriceSchedule, String> {
+    let session = crate::auth::guard::validate_admin(&state, &session_token)?;
+
+    match (payload.product_id, payload.category_id) {
+        (Some(_), None) => {}
+        (None, Some(_)) if payload.new_price.is_none() => {}
+        (None, Some(_)) => return Err("Jadwal per kategori hanya bisa persentase".into()),
+        _ => return Err("Pilih satu produk atau satu kategori".into()),
+    }
+    match (payload.new_price, payload.percent_change) {
+        (Some(p), None) if p >= 0.0 && p.is_finite() => {}
+        (None, Some(pct)) if pct > -100.0 && pct.is_finite() && pct != 0.0 => {}
+        _ => return Err("Isi harga baru atau persentase perubahan yang valid".into()),
+    }
+
+    let effective_at = chrono::DateTime::parse_from_rfc3339(&payload.effective_at)
+        .map_err(|_| "Waktu berlaku tidak valid")?
+        .with_timezone(&chrono::Utc);
+    if effective_at < chrono::Utc::now() - chrono::Duration::minutes(1) {
+        return Err("Waktu berlaku harus di masa depan".into());
+    }
+
+    let result = sqlx::query(
+        "INSERT INTO price_schedules (product_id, category_id, new_price, percent_change, effective_at, notes, created_by)
+         VALUES (?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(payload.product_id)
+    .bind(payload.category_id)
+    .bind(payload.new_price)
+    .bind(payload.percent_change)
+    .bind(effective_at.format("%Y-%m-%d %H:%M:%S").to_string())
+    .bind(payload.notes.as_deref().map(str::trim).filter(|n| !n.is_empty()))
+    .bind(session.user_id)
+    .execute(&state.db)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let id = result.last_insert_rowid();
+
+    crate::commands::activity_cmd::log_activity(
+        &state.db,
+        None,
+        Some(session.user_id),
+        "CREATE_PRICE_SCHEDULE",
+        &format!("Menjadwalkan perubahan harga #{} berlaku {}", id, effective_at),
+        None,
+    )
+    .await;
+
+    sqlx::query_as::<_, PriceSchedule>(&format!("{} WHERE s.id = ?", SCHEDULE_SELECT))
+        .bind(id)
+        .fetch_one(&state.db)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Batalkan jadwal yang belum berlaku (Admin only)
+#[tauri::command]
+pub async fn cancel_price_schedule(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    id: i64,
+) -> Result<(), String> {
+    let session = crate::auth::guard::validate_admin(&state, &session_token)?;
+
+    let result =
+        sqlx::query("UPDATE price_schedules SET status = 'CANCELLED' WHERE id = ? AND status = 'PENDING'")
+            .bind(id)
+            .execute(&state.db)
+            .await
+            .map_err(|e| e.to_string())?;
+    if result.rows_affected() == 0 {
+        return Err("Jadwal tidak ditemukan atau sudah diterapkan".into());
+    }
+
+    crate::commands::activity_cmd::log_activity(
+        &state.db,
+        None,
+        Some(session.user_id),
+        "CANCEL_PRICE_SCHEDULE",
+        &format!("Membatalkan jadwal harga #{}", id),
+        None,
+    )
+    .await;
+
+    Ok(())
+}
+
+#[derive(sqlx::FromRow)]
+struct DueSchedule {
+    id: i64,
+    product_id: Option<i64>,
+    category_id: Option<i64>,
+    new_price: Option<f64>,
+    percent_change: Option<f64>,
+    created_by: Option<i64>,
+}
+
+/// Skalakan harga satuan jual (yang punya harga sendiri) dan tier grosir
+/// satu produk, dicatat per baris di riwayat harga.
+async fn scale_unit_and_tier_prices(
+    conn: &mut SqliteConnection,
+    product_id: i64,
+    factor: f64,
+    schedule: &DueSchedule,
+) -> Result<(), String> {
+    // Satuan dengan harga 0 mengikuti harga dasar x konversi, tidak perlu diubah
+    let units: Vec<(i64, String, f64)> =
+        sqlx::query_as("SELECT id, name, price FROM product_units WHERE product_id = ? AND price > 0")
+            .bind(product_id)
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(|e| e.to_string())?;
+    for (id, name, old_price) in units {
+        let new_price = (old_price * factor).round().max(0.0);
+        sqlx::query("UPDATE product_units SET price = ? WHERE id = ?")
+            .bind(new_price)
+            .bind(id)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| e.to_string())?;
+        record_target_price_change(conn, product_id, &format!("Satuan {}", name), old_price, new_price, schedule)
+            .await?;
+    }
+
+    let tiers: Vec<(i64, f64, f64)> =
+        sqlx::query_as("SELECT id, min_quantity, price FROM product_price_tiers WHERE product_id = ?")
+            .bind(product_id)
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(|e| e.to_string())?;
+    for (id, min_quantity, old_price) in tiers {
+        let new_price = (old_price * factor).round().max(0.0);
+        sqlx::query("UPDATE product_price_tiers SET price = ? WHERE id = ?")
+            .bind(new_price)
+            .bind(id)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| e.to_string())?;
+        record_target_price_change(
+            conn,
+            product_id,
+            &format!("Grosir ≥ {}", min_quantity),
+            old_price,
+            new_price,
+            schedule,
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+/// Riwayat harga satuan jual / tier dari jadwal (HPP tidak berubah)
+async fn record_target_price_change(
+    conn: &mut SqliteConnection,
+    product_id: i64,
+    target: &str,
+    old_price: f64,
+    new_price: f64,
+    schedule: &DueSchedule,
+) -> Result<(), String> {
+    if old_price == new_price {
+        return Ok(());
+    }
+    sqlx::query(
+        "INSERT INTO product_price_history
+            (product_id, old_price, new_price, old_cost_price, new_cost_price, source, target, schedule_id, changed_by)
+         SELECT id, ?, ?, cost_price, cost_price, 'SCHEDULE', ?, ?, ? FROM products WHERE id = ?",
+    )
+    .bind(old_price)
+    .bind(new_price)
+    .bind(target)
+    .bind(schedule.id)
+    .bind(schedule.created_by)
+    .bind(product_id)
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Terapkan semua jadwal harga yang sudah jatuh tempo. Satu jadwal = satu
+/// DB transaction, sehingga kategori besar tidak berubah setengah-setengah.
+pub async fn apply_due_price_schedules(db: &SqlitePool) -> Result<usize, String> {
+    let due = sqlx::query_as::<_, DueSchedule>(
+        "SELECT id, product_id, category_id, new_price, percent_change, created_by
+         FROM price_schedules
+         WHERE status = 'PENDING' AND effective_at <= datetime('now')
+         ORDER BY effective_at ASC, id ASC",
+    )
+    .fetch_all(db)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    for schedule in &due {
+        apply_price_schedule(db, schedule).await?;
+    }
+    Ok(due.len())
+}
+
+async fn apply_price_schedule(db: &SqlitePool, schedule: &DueSchedule) -> Result<(), String> {
+    let mut tx = db.begin().await.map_err(|e| e.to_string())?;
+
+    let targets: Vec<(i64, f64, f64)> = match (schedule.product_id, schedule.category_id) {
+        (Some(product_id), _) => {
+            sqlx::query_as("SELECT id, price, cost_price FROM products WHERE id = ?")
+                .bind(product_id)
+                .fetch_all(&mut *tx)
+                .await
+        }
+        (None, Some(category_id)) => {
+            sqlx::query_as(
+                "SELECT id, price, cost_price FROM products WHERE category_id = ? AND is_active = 1",
+            )
+            .bind(category_id)
+            .fetch_all(&mut *tx)
+            .await
+        }
+        (None, None) => Ok(Vec::new()),
+    }
+    .map_err(|e| e.to_string())?;
+
+    let mut applied = 0;
+    for (product_id, price, cost_price) in targets {
+        // Satuan jual & tier grosir ikut naik/turun dengan faktor yang sama;
+        // harga baru tetap → faktor = rasio harga baru / lama (tidak ada
+        // faktor jika harga lama 0, satuan lain dibiarkan)
+        let (new_price, factor) = match (schedule.new_price, schedule.percent_change) {
+            (Some(p), _) => (p, (price > 0.0).then(|| p / price)),
+            (None, Some(pct)) => {
+                let factor = 1.0 + pct / 100.0;
+                ((price * factor).round().max(0.0), Some(factor))
+            }
+            (None, None) => continue,
+        };
+        if new_price == price {
+            continue;
+        }
+
+        sqlx::query("UPDATE products SET price = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?")
+            .bind(new_price)
+            .bind(product_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?;
+        record_price_change(
+            &mut tx,
+            product_id,
+            (price, cost_price),
+            (new_price, cost_price),
+            "SCHEDULE",
+            Some(schedule.id),
+            schedule.created_by,
+        )
+        .await?;
+        if let Some(factor) = factor {
+            scale_unit_and_tier_prices(&mut tx, product_id, factor, schedule).await?;
+        }
+        applied += 1;
+    }
+
+    sqlx::query(
+        "UPDATE price_schedules SET status = 'APPLIED', applied_at = CURRENT_TIMESTAMP, applied_count = ?
+         WHERE id = ? AND status = 'PENDING'",
+    )
+    .bind(applied)
+    .bind(schedule.id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    crate::commands::activity_cmd::log_activity(
+        db,
+        Some(&mut tx),
+        schedule.created_by,
+        "APPLY_PRICE_SCHEDULE",
+        &format!("Jadwal harga #{} diterapkan ke {} produk", schedule.id, applied),
+        None,
+    )
+    .await;
+
+    tx.commit().await.map_err(|e| e.to_string())
+}
+
+/// Jalankan worker jadwal harga di background. Dipanggil sekali saat setup,
+/// setelah `AppState` di-manage.
+pub fn start_price_schedule_worker(app: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let state = app.state::<AppState>();
+            if let Err(e) = apply_due_price_schedules(&state.db).await {
+                eprintln!("[PRICE SCHEDULE] Worker error: {}", e);
+            }
+            tokio::time::sleep(Duration::from_secs(SCHEDULE_POLL_SECS)).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    #[tokio::test]
+    async fn schedule_scales_unit_and_tier_prices() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        crate::database::migrations::run_migrations(&pool).await.unwrap();
+        sqlx::query(
+            "INSERT INTO products (id, name, price, cost_price) VALUES (1, 'Teh', 10000, 7000);
+             INSERT INTO product_units (product_id, name, conversion, price) VALUES (1, 'box', 10, 95000);
+             INSERT INTO product_units (product_id, name, conversion, price) VALUES (1, 'pack', 5, 0);
+             INSERT INTO product_price_tiers (product_id, min_quantity, price) VALUES (1, 12, 9000);
+             INSERT INTO price_schedules (product_id, percent_change, effective_at)
+                 VALUES (1, 10, datetime('now', '-1 minute'));",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        assert_eq!(apply_due_price_schedules(&pool).await.unwrap(), 1);
+
+        let price = |sql: &'static str| {
+            let pool = pool.clone();
+            async move { sqlx::query_as::<_, (f64,)>(sql).fetch_one(&pool).await.unwrap().0 }
+        };
+        assert_eq!(price("SELECT price FROM products WHERE id = 1").await, 11000.0);
+        assert_eq!(price("SELECT price FROM product_units WHERE name = 'box'").await, 104500.0);
+        // Harga 0 = ikut harga dasar x konversi, tetap 0
+        assert_eq!(price("SELECT price FROM product_units WHERE name = 'pack'").await, 0.0);
+        assert_eq!(price("SELECT price FROM product_price_tiers").await, 9900.0);
+
+        let history: Vec<(Option<String>, f64, f64)> = sqlx::query_as(
+            "SELECT target, old_price, new_price FROM product_price_history ORDER BY id",
+        )
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        assert_eq!(
+            history,
+            vec![
+                (None, 10000.0, 11000.0),
+                (Some("Satuan box".into()), 95000.0, 104500.0),
+                (Some("Grosir ≥ 12".into()), 9000.0, 9900.0),
+            ]
+        );
+
+        // Harga baru tetap: satuan ikut rasio harga baru / lama
+        sqlx::query(
+            "INSERT INTO price_schedules (product_id, new_price, effective_at)
+             VALUES (1, 22000, datetime('now', '-1 minute'))",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        apply_due_price_schedules(&pool).await.unwrap();
+        assert_eq!(price("SELECT price FROM product_units WHERE name = 'box'").await, 209000.0);
+    }
+}
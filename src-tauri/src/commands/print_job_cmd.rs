@@ -0,0 +1,381 @@
+use crate::models::print_job::{PaginatedPrintJobs, PrintJob, PrintJobFailedEvent};
+use crate::printer::queue::{
+    is_exhausted, retry_delay_secs, DEFAULT_MAX_ATTEMPTS, EVENT_JOB_FAILED, JOB_DONE, JOB_FAILED,
+    JOB_PRINTING, JOB_QUEUED,
+};
+use crate::AppState;
+use std::sync::OnceLock;
+use std::time::Duration;
+use tauri::{Emitter, Manager};
+use tokio::sync::Notify;
+
+/// Interval worker memeriksa job yang jadwal retry-nya sudah tiba
+const WORKER_POLL_SECS: u64 = 2;
+
+static QUEUE_WAKE: OnceLock<Notify> = OnceLock::new();
+
+/// Sinyal untuk membangunkan worker saat ada job baru / job diantrikan ulang
+fn queue_wake() -> &'static Notify {
+    QUEUE_WAKE.get_or_init(Notify::new)
+}
+
+#[derive(sqlx::FromRow)]
+struct QueuedJob {
+    id: i64,
+    job_type: String,
+    reference_id: Option<String>,
+    printer_port: String,
+    payload: Vec<u8>,
+    attempts: i64,
+    max_attempts: i64,
+}
+
+// ============================================================================
+// ENQUEUE & WORKER
+// ============================================================================
+
+/// Simpan data cetak ke antrian lalu bangunkan worker. Mengembalikan id job.
+pub async fn enqueue_print_job(
+    db: &sqlx::SqlitePool,
+    job_type: &str,
+    reference_id: Option<&str>,
+    printer_port: &str,
+    payload: &[u8],
+    created_by: Option<i64>,
+) -> Result<i64, String> {
+    let result = sqlx::query(
+        "INSERT INTO print_jobs (job_type, reference_id, printer_port, payload, max_attempts, created_by)
+         VALUES (?, ?, ?, ?, ?, ?)",
+    )
+    .bind(job_type)
+    .bind(reference_id)
+    .bind(printer_port)
+    .bind(payload)
+    .bind(DEFAULT_MAX_ATTEMPTS)
+    .bind(created_by)
+    .execute(db)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    queue_wake().notify_one();
+    Ok(result.last_insert_rowid())
+}
+
+/// Jalankan worker antrian cetak di background. Dipanggil sekali saat setup,
+/// setelah `AppState` di-manage.
+pub fn start_print_worker(app: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let state = app.state::<AppState>();
+            match process_next_job(&app, &state.db).await {
+                // Masih mungkin ada job lain yang siap — lanjut tanpa menunggu
+                Ok(true) => continue,
+                Ok(false) => {}
+                Err(e) => eprintln!("[PRINT QUEUE] Worker error: {}", e),
+            }
+            let _ = tokio::time::timeout(
+                Duration::from_secs(WORKER_POLL_SECS),
+                queue_wake().notified(),
+            )
+            .await;
+        }
+    });
+}
+
+/// Proses satu job yang siap cetak. `Ok(false)` jika antrian kosong.
+async fn process_next_job(app: &tauri::AppHandle, db: &sqlx::SqlitePool) -> Result<bool, String> {
+    let job = sqlx::query_as::<_, QueuedJob>(
+        "SELECT id, job_type, reference_id, printer_port, payload, attempts, max_attempts
+         FROM print_jobs
+         WHERE status = ? AND (next_attempt_at IS NULL OR next_attempt_at <= datetime('now'))
+         ORDER BY id ASC
+         LIMIT 1",
+    )
+    .bind(JOB_QUEUED)
+    .fetch_optional(db)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let Some(job) = job else {
+        return Ok(false);
+    };
+
+    // Port diambil dari settings terbaru supaya job gagal ikut terkirim ke
+    // printer pengganti setelah kasir mengubah konfigurasi
+    let port = configured_port(db, &job.job_type)
+        .await?
+        .unwrap_or(job.printer_port);
+
+    let claimed = sqlx::query(
+        "UPDATE print_jobs SET status = ?, printer_port = ?, updated_at = CURRENT_TIMESTAMP
+         WHERE id = ? AND status = ?",
+    )
+    .bind(JOB_PRINTING)
+    .bind(&port)
+    .bind(job.id)
+    .bind(JOB_QUEUED)
+    .execute(db)
+    .await
+    .map_err(|e| e.to_string())?;
+    if claimed.rows_affected() == 0 {
+        return Ok(true);
+    }
+
+    let attempts = job.attempts + 1;
+    // Status DLE EOT hanya dimengerti printer struk ESC/POS
+    let check_status = job.job_type == "RECEIPT";
+    match crate::commands::settings_cmd::deliver_to_printer(&port, &job.payload, check_status).await {
+        Ok(()) => {
+            sqlx::query(
+                "UPDATE print_jobs
+                 SET status = ?, attempts = ?, last_error = NULL,
+                     printed_at = CURRENT_TIMESTAMP, updated_at = CURRENT_TIMESTAMP
+                 WHERE id = ?",
+            )
+            .bind(JOB_DONE)
+            .bind(attempts)
+            .bind(job.id)
+            .execute(db)
+            .await
+            .map_err(|e| e.to_string())?;
+        }
+        Err(error) => {
+            eprintln!(
+                "[PRINT QUEUE] Job {} gagal (percobaan {}/{}): {}",
+                job.id, attempts, job.max_attempts, error
+            );
+
+            if is_exhausted(attempts, job.max_attempts) {
+                sqlx::query(
+                    "UPDATE print_jobs
+                     SET status = ?, attempts = ?, last_error = ?, updated_at = CURRENT_TIMESTAMP
+                     WHERE id = ?",
+                )
+                .bind(JOB_FAILED)
+                .bind(attempts)
+                .bind(&error)
+                .bind(job.id)
+                .execute(db)
+                .await
+                .map_err(|e| e.to_string())?;
+
+                let event = PrintJobFailedEvent {
+                    job_id: job.id,
+                    job_type: job.job_type,
+                    reference_id: job.reference_id,
+                    attempts,
+                    error,
+                };
+                if let Err(e) = app.emit(EVENT_JOB_FAILED, event) {
+                    eprintln!("[PRINT QUEUE] Gagal mengirim event: {}", e);
+                }
+            } else {
+                sqlx::query(
+                    "UPDATE print_jobs
+                     SET status = ?, attempts = ?, last_error = ?,
+                         next_attempt_at = datetime('now', ?), updated_at = CURRENT_TIMESTAMP
+                     WHERE id = ?",
+                )
+                .bind(JOB_QUEUED)
+                .bind(attempts)
+                .bind(&error)
+                .bind(format!("+{} seconds", retry_delay_secs(attempts)))
+                .bind(job.id)
+                .execute(db)
+                .await
+                .map_err(|e| e.to_string())?;
+            }
+        }
+    }
+
+    Ok(true)
+}
+
+/// Port printer aktif untuk jenis job: struk → `app.printer_port`,
+/// label → `label.printer_port`. `None` = pakai port yang tersimpan di job
+/// (mis. label ESC/POS yang dikirim ke printer struk).
+async fn configured_port(db: &sqlx::SqlitePool, job_type: &str) -> Result<Option<String>, String> {
+    let keys: &[&str] = match job_type {
+        "RECEIPT" => &["app.printer_port"],
+        "LABEL" => &["label.printer_port"],
+        _ => &[],
+    };
+    for key in keys {
+        let value: Option<(String,)> = sqlx::query_as("SELECT value FROM settings WHERE key = ?")
+            .bind(key)
+            .fetch_optional(db)
+            .await
+            .map_err(|e| e.to_string())?;
+        if let Some((port,)) = value.filter(|v| !v.0.is_empty()) {
+            return Ok(Some(port));
+        }
+    }
+    Ok(None)
+}
+
+// ============================================================================
+// COMMANDS
+// ============================================================================
+
+/// Riwayat job cetak, terbaru dulu (opsional filter status)
+#[tauri::command]
+pub async fn get_print_jobs(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    status: Option<String>,
+    page: i64,
+) -> Result<PaginatedPrintJobs, String> {
+    crate::auth::guard::validate_session(&state, &session_token)?;
+
+    let status = status.filter(|s| !s.is_empty());
+    let per_page = 20;
+    let offset = (page.max(1) - 1) * per_page;
+
+    let total: (i64,) =
+        sqlx::query_as("SELECT COUNT(*) FROM print_jobs WHERE (? IS NULL OR status = ?)")
+            .bind(&status)
+            .bind(&status)
+            .fetch_one(&state.db)
+            .await
+            .map_err(|e| e.to_string())?;
+
+    let data = sqlx::query_as::<_, PrintJob>(
+        "SELECT id, job_type, reference_id, printer_port, status, attempts, max_attempts,
+                last_error, next_attempt_at, created_by, created_at, updated_at, printed_at
+         FROM print_jobs
+         WHERE (? IS NULL OR status = ?)
+         ORDER BY id DESC
+         LIMIT ? OFFSET ?",
+    )
+    .bind(&status)
+    .bind(&status)
+    .bind(per_page)
+    .bind(offset)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(PaginatedPrintJobs {
+        data,
+        total: total.0,
+        page,
+        per_page,
+    })
+}
+
+/// Antrikan ulang satu job yang gagal (setelah printer diperbaiki)
+#[tauri::command]
+pub async fn retry_print_job(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    job_id: i64,
+) -> Result<(), String> {
+    crate::auth::guard::validate_session(&state, &session_token)?;
+
+    let result = sqlx::query(
+        "UPDATE print_jobs
+         SET status = ?, attempts = 0, next_attempt_at = CURRENT_TIMESTAMP,
+             updated_at = CURRENT_TIMESTAMP
+         WHERE id = ? AND status IN (?, ?)",
+    )
+    .bind(JOB_QUEUED)
+    .bind(job_id)
+    .bind(JOB_FAILED)
+    .bind(JOB_QUEUED)
+    .execute(&state.db)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    if result.rows_affected() == 0 {
+        return Err("Job cetak tidak ditemukan atau sudah selesai".into());
+    }
+
+    queue_wake().notify_one();
+    Ok(())
+}
+
+/// Lanjutkan antrian: semua job FAILED diantrikan ulang. Mengembalikan jumlah job.
+#[tauri::command]
+pub async fn resume_print_queue(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+) -> Result<u64, String> {
+    crate::auth::guard::validate_session(&state, &session_token)?;
+
+    let result = sqlx::query(
+        "UPDATE print_jobs
+         SET status = ?, attempts = 0, next_attempt_at = CURRENT_TIMESTAMP,
+             updated_at = CURRENT_TIMESTAMP
+         WHERE status = ?",
+    )
+    .bind(JOB_QUEUED)
+    .bind(JOB_FAILED)
+    .execute(&state.db)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    queue_wake().notify_one();
+    Ok(result.rows_affected())
+}
+
+/// Cetak ulang job dari riwayat — payload yang sama dimasukkan sebagai job
+/// baru. Struk dirender ulang sebagai SALINAN agar tercatat di audit.
+#[tauri::command]
+pub async fn reprint_print_job(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    job_id: i64,
+) -> Result<i64, String> {
+    let session = crate::auth::guard::validate_session(&state, &session_token)?;
+
+    let job: (String, Option<String>, String, Vec<u8>) = sqlx::query_as(
+        "SELECT job_type, reference_id, printer_port, payload FROM print_jobs WHERE id = ?",
+    )
+    .bind(job_id)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|e| e.to_string())?
+    .ok_or("Job cetak tidak ditemukan")?;
+
+    if job.0 == "RECEIPT" {
+        let transaction_id = job.1.as_deref().ok_or("Job struk tanpa id transaksi")?;
+        return crate::commands::settings_cmd::enqueue_receipt_reprint(
+            &state.db,
+            transaction_id,
+            session.user_id,
+        )
+        .await;
+    }
+
+    enqueue_print_job(
+        &state.db,
+        &job.0,
+        job.1.as_deref(),
+        &job.2,
+        &job.3,
+        Some(session.user_id),
+    )
+    .await
+}
+
+/// Hapus job dari antrian (mis. struk gagal yang tidak perlu dicetak lagi). Admin only
+#[tauri::command]
+pub async fn delete_print_job(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    job_id: i64,
+) -> Result<(), String> {
+    crate::auth::guard::validate_admin(&state, &session_token)?;
+
+    let result = sqlx::query("DELETE FROM print_jobs WHERE id = ? AND status != ?")
+        .bind(job_id)
+        .bind(JOB_PRINTING)
+        .execute(&state.db)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if result.rows_affected() == 0 {
+        return Err("Job cetak tidak ditemukan atau sedang dicetak".into());
+    }
+    Ok(())
+}
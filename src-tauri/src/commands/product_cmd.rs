@@ -1,8 +1,14 @@
 use crate::models::product::{
-    Category, CategoryWithCount, CreateProductPayload, Product, ProductWithCategory,
+    Category, CategoryWithCount, CreateProductPayload, Product, ProductBarcode,
+    CreateVariantPayload, ProductBarcodePayload, ProductComponent, ProductComponentPayload, ProductUnit, StockBatchPayload, ProductUnitPayload, ProductWithCategory, ScannedProduct,
     UpdateProductPayload,
 };
+use crate::printer::barcode::{parse_embedded_barcode, EmbeddedValue};
+use crate::commands::location_cmd::{location_available, location_or_default, move_location_stock};
+use crate::models::quantity::{round_quantity, validate_quantity, Quantity};
 use crate::AppState;
+use sqlx::SqliteConnection;
+use std::collections::BTreeMap;
 use tauri::Manager;
 
 /// Ambil daftar kategori + jumlah produk
@@ -14,7 +20,7 @@ pub async fn get_categories(
     crate::auth::guard::validate_session(&state, &session_token)?;
 
     let query = "
-        SELECT c.id, c.name, COUNT(p.id) as product_count
+        SELECT c.id, c.name, COUNT(p.id) as product_count, c.printer_profile_id
         FROM categories c
         LEFT JOIN products p ON c.id = p.category_id
         GROUP BY c.id
@@ -71,6 +77,7 @@ pub async fn get_products(
     search: Option<String>,
     category_id: Option<i64>,
     show_inactive: Option<bool>,
+    parents_only: Option<bool>,
 ) -> Result<Vec<ProductWithCategory>, String> {
     crate::auth::guard::validate_session(&state, &session_token)?;
 
@@ -84,7 +91,8 @@ pub async fn get_products(
     };
 
     let base_query = "
-        SELECT p.*, c.name as category_name
+        SELECT p.*, c.name as category_name,
+               (SELECT COUNT(*) FROM products v WHERE v.parent_id = p.id AND v.is_active = 1) as variant_count
         FROM products p
         LEFT JOIN categories c ON p.category_id = c.id
         WHERE 1=1
@@ -100,20 +108,30 @@ pub async fn get_products(
         query.push_str(&format!(" AND p.category_id = {}", id));
     }
 
-    if let Some(mut term) = search {
-        term = format!("%{}%", term.to_lowercase());
-        query.push_str(&format!(
-            " AND (LOWER(p.name) LIKE '{}' OR LOWER(p.sku) LIKE '{}' OR p.barcode LIKE '{}')",
-            term, term, term
-        ));
+    // POS: varian tidak ditampilkan terpisah, dipilih lewat produk induk
+    if parents_only.unwrap_or(false) {
+        query.push_str(" AND p.parent_id IS NULL");
+    }
+
+    let term = search.map(|t| format!("%{}%", t.to_lowercase()));
+    if term.is_some() {
+        query.push_str(
+            " AND (LOWER(p.name) LIKE ? OR LOWER(p.sku) LIKE ? OR p.barcode LIKE ? \
+             OR p.id IN (SELECT product_id FROM product_barcodes WHERE barcode LIKE ?))",
+        );
     }
 
     query.push_str(" ORDER BY p.name ASC");
 
-    let products = sqlx::query_as::<_, ProductWithCategory>(&query)
+    let mut q = sqlx::query_as::<_, ProductWithCategory>(&query);
+    if let Some(term) = &term {
+        q = q.bind(term).bind(term).bind(term).bind(term);
+    }
+    let mut products = q
         .fetch_all(&state.db)
         .await
         .map_err(|e| e.to_string())?;
+    fill_kit_stock(&state.db, &mut products).await?;
 
     Ok(products)
 }
@@ -124,7 +142,7 @@ pub async fn get_product_by_barcode(
     state: tauri::State<'_, AppState>,
     session_token: String,
     barcode: String,
-) -> Result<ProductWithCategory, String> {
+) -> Result<ScannedProduct, String> {
     crate::auth::guard::validate_session(&state, &session_token)?;
 
     let query = "
@@ -138,10 +156,114 @@ pub async fn get_product_by_barcode(
         .bind(&barcode)
         .fetch_optional(&state.db)
         .await
-        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())?;
+
+    if let Some(mut product) = product {
+        fill_kit_stock(&state.db, std::slice::from_mut(&mut product)).await?;
+        return Ok(ScannedProduct {
+            product,
+            scanned_quantity: None,
+            scanned_price: None,
+        });
+    }
+
+    // Barcode tambahan / kemasan → 1 scan = pack_quantity unit
+    let alternate: Option<(i64, i64)> = sqlx::query_as(
+        "SELECT b.product_id, b.pack_quantity
+         FROM product_barcodes b
+         JOIN products p ON p.id = b.product_id
+         WHERE b.barcode = ? AND p.is_active = 1
+         LIMIT 1",
+    )
+    .bind(&barcode)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    if let Some((product_id, pack_quantity)) = alternate {
+        let mut product = sqlx::query_as::<_, ProductWithCategory>(
+            "SELECT p.*, c.name as category_name
+             FROM products p
+             LEFT JOIN categories c ON p.category_id = c.id
+             WHERE p.id = ?",
+        )
+        .bind(product_id)
+        .fetch_one(&state.db)
+        .await
+        .map_err(|e| e.to_string())?;
+        fill_kit_stock(&state.db, std::slice::from_mut(&mut product)).await?;
+
+        return Ok(ScannedProduct {
+            product,
+            scanned_quantity: (pack_quantity > 1).then_some(pack_quantity as f64),
+            scanned_price: None,
+        });
+    }
+
+    // Bukan barcode produk → coba sebagai label timbangan (prefix 20–29)
+    let mut conn = state.db.acquire().await.map_err(|e| e.to_string())?;
+    let (product, quantity, price) = resolve_scale_label(&mut conn, &barcode)
+        .await?
         .ok_or("Produk tidak ditemukan atau tidak aktif")?;
 
-    Ok(product)
+    Ok(ScannedProduct {
+        product,
+        scanned_quantity: Some(quantity),
+        scanned_price: Some(price),
+    })
+}
+
+/// Baca label timbangan: produk, jumlah, dan harga per satuan dasar.
+/// `None` jika fitur nonaktif atau barcode bukan label timbangan. Dipakai
+/// juga saat transaksi supaya harga label dihitung ulang di server.
+pub(crate) async fn resolve_scale_label(
+    conn: &mut SqliteConnection,
+    barcode: &str,
+) -> Result<Option<(ProductWithCategory, f64, f64)>, String> {
+    let settings = crate::commands::settings_cmd::load_scale_barcode_settings(conn).await?;
+    if !settings.enabled {
+        return Ok(None);
+    }
+    let Some(embedded) = parse_embedded_barcode(barcode, &settings.rules)? else {
+        return Ok(None);
+    };
+
+    // PLU dicocokkan ke SKU atau barcode produk, dengan/tanpa nol di depan
+    let plu_short = embedded.plu.trim_start_matches('0');
+    let product = sqlx::query_as::<_, ProductWithCategory>(
+        "SELECT p.*, c.name as category_name
+         FROM products p
+         LEFT JOIN categories c ON p.category_id = c.id
+         WHERE (p.sku IN (?, ?) OR p.barcode IN (?, ?)) AND p.is_active = 1
+         ORDER BY p.id LIMIT 1",
+    )
+    .bind(&embedded.plu)
+    .bind(plu_short)
+    .bind(&embedded.plu)
+    .bind(plu_short)
+    .fetch_optional(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?
+    .ok_or_else(|| format!("PLU {} tidak terdaftar", embedded.plu))?;
+
+    let (quantity, price) = match embedded.value {
+        EmbeddedValue::Weight(kg) => {
+            if !product.is_weighed {
+                return Err(format!("{} bukan produk timbang", product.name));
+            }
+            (round_quantity(kg), product.price)
+        }
+        // Produk timbang: berat = total / harga per kg; selain itu 1 pcs seharga label
+        EmbeddedValue::Price(total) if product.is_weighed && product.price > 0.0 => {
+            (round_quantity(total / product.price), product.price)
+        }
+        EmbeddedValue::Price(total) => (1.0, total),
+    };
+    if quantity <= 0.0 {
+        return Err("Berat/harga di barcode tidak valid".into());
+    }
+
+    Ok(Some((product, quantity, price)))
 }
 
 /// Buat produk baru (Admin only)
@@ -161,12 +283,15 @@ pub async fn create_product(
         return Err("Harga tidak valid".into());
     }
 
-    if payload.stock < 0 {
+    if payload.stock < 0.0 || (!payload.is_weighed && payload.stock.fract() != 0.0) {
         return Err("Stok tiak valid".into());
     }
 
+    let mut tx = state.db.begin().await.map_err(|e| e.to_string())?;
+    check_product_barcodes(&mut tx, &payload).await?;
+
     let result = sqlx::query(
-        "INSERT INTO products (name, sku, barcode, category_id, price, cost_price, stock, image_path) VALUES (?, ?, ?, ?, ?, ?, ?, ?)"
+        "INSERT INTO products (name, sku, barcode, category_id, price, cost_price, stock, image_path, is_weighed, unit) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
     )
     .bind(&payload.name)
     .bind(&payload.sku)
@@ -174,15 +299,19 @@ pub async fn create_product(
     .bind(payload.category_id)
     .bind(payload.price)
     .bind(payload.cost_price)
-    .bind(payload.stock)
+    .bind(round_quantity(payload.stock))
     .bind(&payload.image_path)
-    .execute(&state.db)
+    .bind(payload.is_weighed)
+    .bind(base_unit(payload.unit.as_deref(), payload.is_weighed))
+    .execute(&mut *tx)
     .await;
 
     match result {
         Ok(res) => {
             let id = res.last_insert_rowid();
-            
+            insert_product_barcodes(&mut tx, id, &payload.extra_barcodes).await?;
+            tx.commit().await.map_err(|e| e.to_string())?;
+
             // Auto-generate barcode jika tidak diisi user
             if payload.barcode.as_ref().map_or(true, |b| b.trim().is_empty()) {
                 let mut barcode;
@@ -213,7 +342,7 @@ pub async fn create_product(
             }
 
             // Log Stock Adjustment (Initial)
-            if payload.stock > 0 {
+            if payload.stock > 0.0 {
                 let session = crate::auth::guard::validate_admin(&state, &session_token)?;
                 crate::commands::activity_cmd::log_stock_adjustment(
                     &state.db,
@@ -221,7 +350,7 @@ pub async fn create_product(
                     id,
                     session.user_id,
                     "IN",
-                    payload.stock,
+                    round_quantity(payload.stock),
                     "RESTOCK",
                     Some("Stok awal saat pembuatan produk"),
                 ).await;
@@ -270,8 +399,36 @@ pub async fn update_product(
         return Err("Harga tidak valid".into());
     }
 
+    if let Some(barcode) = payload.barcode.as_deref().filter(|b| !b.trim().is_empty()) {
+        let mut conn = state.db.acquire().await.map_err(|e| e.to_string())?;
+        ensure_barcode_available(&mut conn, barcode, Some(id)).await?;
+    }
+
+    let (old_price, old_cost, was_active): (f64, f64, bool) =
+        sqlx::query_as("SELECT price, cost_price, is_active FROM products WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&state.db)
+            .await
+            .map_err(|e| e.to_string())?
+            .ok_or("Produk tidak ditemukan")?;
+    let old_prices = (old_price, old_cost);
+
+    // Selama nonaktif, barcode tambahannya bisa dipakai produk lain
+    if payload.is_active && !was_active {
+        let extra: Vec<(String,)> = sqlx::query_as("SELECT barcode FROM product_barcodes WHERE product_id = ?")
+            .bind(id)
+            .fetch_all(&state.db)
+            .await
+            .map_err(|e| e.to_string())?;
+        let mut conn = state.db.acquire().await.map_err(|e| e.to_string())?;
+        for (barcode,) in &extra {
+            ensure_barcode_available(&mut conn, barcode, Some(id)).await?;
+        }
+    }
+
+    let mut tx = state.db.begin().await.map_err(|e| e.to_string())?;
     let result = sqlx::query(
-        "UPDATE products SET name = ?, sku = ?, barcode = ?, category_id = ?, price = ?, cost_price = ?, is_active = ?, image_path = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?"
+        "UPDATE products SET name = ?, sku = ?, barcode = ?, category_id = ?, price = ?, cost_price = ?, is_active = ?, image_path = ?, is_weighed = ?, unit = COALESCE(?, unit), updated_at = CURRENT_TIMESTAMP WHERE id = ?"
     )
     .bind(&payload.name)
     .bind(&payload.sku)
@@ -281,13 +438,29 @@ pub async fn update_product(
     .bind(payload.cost_price)
     .bind(payload.is_active)
     .bind(&payload.image_path)
+    .bind(payload.is_weighed)
+    .bind(payload.unit.as_deref().map(str::trim).filter(|u| !u.is_empty()))
     .bind(id)
-    .execute(&state.db)
+    .execute(&mut *tx)
     .await;
 
     match result {
         Ok(_) => {
             let session = crate::auth::guard::validate_admin(&state, &session_token)?;
+            crate::commands::pricing_cmd::record_price_change(
+                &mut tx,
+                id,
+                old_prices,
+                (payload.price, payload.cost_price),
+                "MANUAL",
+                None,
+                Some(session.user_id),
+            )
+            .await?;
+            tx.commit().await.map_err(|e| e.to_string())?;
+
+            sync_variants_with_parent(&state.db, id).await?;
+
             crate::commands::activity_cmd::log_activity(
                 &state.db,
                 None,
@@ -311,53 +484,82 @@ pub async fn update_product(
     }
 }
 
-/// Sesuaikan stok tambah/kurang (Admin only)
+/// Sesuaikan stok tambah/kurang di satu lokasi (Admin only, kosong = lokasi utama)
 #[tauri::command]
 pub async fn adjust_stock(
     state: tauri::State<'_, AppState>,
     session_token: String,
     product_id: i64,
-    delta: i64,
-) -> Result<i64, String> {
+    delta: f64,
+    unit_id: Option<i64>,
+    batch: Option<StockBatchPayload>,
+    location_id: Option<i64>,
+) -> Result<f64, String> {
     crate::auth::guard::validate_admin(&state, &session_token)?;
 
-    if delta == 0 {
+    if delta == 0.0 || !delta.is_finite() {
         return Err("Nilai penyesuaian harus selain 0".into());
     }
 
     // Ambil stok sekarang
-    let current_stock: (i64,) = sqlx::query_as("SELECT stock FROM products WHERE id = ?")
-        .bind(product_id)
-        .fetch_optional(&state.db)
-        .await
-        .map_err(|e| e.to_string())?
-        .ok_or("Produk tidak ditemukan")?;
+    let current: (Quantity, bool, String, bool) =
+        sqlx::query_as("SELECT stock, is_weighed, unit, is_kit OR is_recipe FROM products WHERE id = ?")
+            .bind(product_id)
+            .fetch_optional(&state.db)
+            .await
+            .map_err(|e| e.to_string())?
+            .ok_or("Produk tidak ditemukan")?;
+    if current.3 {
+        return Err("Stok paket/resep mengikuti komponennya — sesuaikan stok komponen".into());
+    }
 
-    let new_stock = current_stock.0 + delta;
+    validate_quantity(delta.abs(), current.1)?;
+    // Delta dalam satuan terpilih (mis. 2 box) → satuan dasar
+    let mut conn = state.db.acquire().await.map_err(|e| e.to_string())?;
+    let (_, conversion) = resolve_unit(&mut conn, product_id, unit_id, &current.2).await?;
+    let delta = round_quantity(delta * conversion);
+    let new_stock = round_quantity(current.0 .0 + delta);
 
-    if new_stock < 0 {
+    if new_stock < 0.0 {
         return Err("Stok akhir tidak boleh negatif".into());
     }
+    let location = location_or_default(&mut conn, location_id).await?;
+    if delta < 0.0 && location_available(&mut conn, product_id, location).await? < -delta {
+        return Err("Stok di lokasi ini tidak cukup untuk dikurangi".into());
+    }
+    drop(conn);
 
+    let mut tx = state.db.begin().await.map_err(|e| e.to_string())?;
     sqlx::query("UPDATE products SET stock = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?")
         .bind(new_stock)
         .bind(product_id)
-        .execute(&state.db)
+        .execute(&mut *tx)
         .await
         .map_err(|e| e.to_string())?;
+    move_location_stock(&mut tx, product_id, location, delta).await?;
+
+    // Stok masuk dengan data batch → batch baru; stok keluar → potong batch FEFO
+    if delta > 0.0 {
+        if let Some(batch) = &batch {
+            crate::commands::batch_cmd::receive_batch(&mut tx, product_id, delta, batch).await?;
+        }
+    } else {
+        crate::commands::batch_cmd::consume_batches(&mut tx, product_id, delta.abs()).await?;
+    }
 
     // Log Stock Adjustment
     let session = crate::auth::guard::validate_admin(&state, &session_token)?;
     crate::commands::activity_cmd::log_stock_adjustment(
         &state.db,
-        None,
+        Some(&mut tx),
         product_id,
         session.user_id,
-        if delta > 0 { "IN" } else { "OUT" },
+        if delta > 0.0 { "IN" } else { "OUT" },
         delta.abs(),
         "ADJUSTMENT",
         None,
     ).await;
+    tx.commit().await.map_err(|e| e.to_string())?;
 
     // Log Activity
     crate::commands::activity_cmd::log_activity(
@@ -597,6 +799,15 @@ pub async fn generate_barcode(
     Ok(barcode)
 }
 
+/// Satuan dasar produk baru: isian user, atau "kg"/"pcs" sesuai jenis produk.
+fn base_unit(unit: Option<&str>, is_weighed: bool) -> String {
+    match unit.map(str::trim).filter(|u| !u.is_empty()) {
+        Some(u) => u.to_string(),
+        None if is_weighed => "kg".into(),
+        None => "pcs".into(),
+    }
+}
+
 /// Generate 2 digit acak (00-99)
 fn rand_digits() -> u32 {
     use std::time::{SystemTime, UNIX_EPOCH};
@@ -647,16 +858,31 @@ pub async fn get_low_stock_products(
         SELECT p.*, c.name as category_name
         FROM products p
         LEFT JOIN categories c ON p.category_id = c.id
-        WHERE p.stock <= ? AND p.is_active = 1
+        WHERE p.stock <= ? AND p.is_active = 1 AND p.is_kit = 0 AND p.is_recipe = 0
+          AND NOT EXISTS (SELECT 1 FROM products v WHERE v.parent_id = p.id AND v.is_active = 1)
         ORDER BY p.stock ASC
     ";
 
-    let products = sqlx::query_as::<_, ProductWithCategory>(query)
+    let mut products = sqlx::query_as::<_, ProductWithCategory>(query)
         .bind(threshold_val)
         .fetch_all(&state.db)
         .await
         .map_err(|e| e.to_string())?;
 
+    // Paket: stok mengikuti ketersediaan komponen
+    let mut kits = sqlx::query_as::<_, ProductWithCategory>(
+        "SELECT p.*, c.name as category_name
+         FROM products p
+         LEFT JOIN categories c ON p.category_id = c.id
+         WHERE p.is_kit = 1 AND p.is_active = 1",
+    )
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| e.to_string())?;
+    fill_kit_stock(&state.db, &mut kits).await?;
+    products.extend(kits.into_iter().filter(|k| k.stock <= threshold_val as f64));
+    products.sort_by(|a, b| a.stock.total_cmp(&b.stock));
+
     Ok(products)
 }
 
@@ -685,9 +911,17 @@ pub async fn bulk_import_products(
             errors.push(format!("Baris {}: Harga tidak valid", row_num));
             continue;
         }
+        if p.stock < 0.0 || (!p.is_weighed && p.stock.fract() != 0.0) {
+            errors.push(format!("Baris {}: Stok tidak valid", row_num));
+            continue;
+        }
+        if let Err(e) = check_product_barcodes(&mut tx, p).await {
+            errors.push(format!("Baris {}: {}", row_num, e));
+            continue;
+        }
 
         let result = sqlx::query(
-            "INSERT INTO products (name, sku, barcode, category_id, price, cost_price, stock, image_path) VALUES (?, ?, ?, ?, ?, ?, ?, ?)"
+            "INSERT INTO products (name, sku, barcode, category_id, price, cost_price, stock, image_path, is_weighed, unit) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
         )
         .bind(&p.name)
         .bind(&p.sku)
@@ -695,14 +929,17 @@ pub async fn bulk_import_products(
         .bind(p.category_id)
         .bind(p.price)
         .bind(p.cost_price)
-        .bind(p.stock)
+        .bind(round_quantity(p.stock))
         .bind(&p.image_path)
+        .bind(p.is_weighed)
+        .bind(base_unit(p.unit.as_deref(), p.is_weighed))
         .execute(&mut *tx)
         .await;
 
         match result {
             Ok(res) => {
                 let id = res.last_insert_rowid();
+                insert_product_barcodes(&mut tx, id, &p.extra_barcodes).await?;
                 // Auto-generate barcode jika tidak diisi
                 if p.barcode.as_ref().map_or(true, |b| b.trim().is_empty()) {
                     let base = format!("200{:07}{:02}", id % 10_000_000, rand_digits());
@@ -745,6 +982,616 @@ pub async fn bulk_import_products(
     })
 }
 
+/// Daftar barcode tambahan produk
+#[tauri::command]
+pub async fn get_product_barcodes(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    product_id: i64,
+) -> Result<Vec<ProductBarcode>, String> {
+    crate::auth::guard::validate_session(&state, &session_token)?;
+
+    sqlx::query_as::<_, ProductBarcode>(
+        "SELECT * FROM product_barcodes WHERE product_id = ? ORDER BY pack_quantity ASC, id ASC",
+    )
+    .bind(product_id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Tambah barcode tambahan / kemasan ke produk (Admin only)
+#[tauri::command]
+pub async fn add_product_barcode(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    product_id: i64,
+    payload: ProductBarcodePayload,
+) -> Result<ProductBarcode, String> {
+    let session = crate::auth::guard::validate_admin(&state, &session_token)?;
+
+    let mut tx = state.db.begin().await.map_err(|e| e.to_string())?;
+    insert_product_barcodes(&mut tx, product_id, std::slice::from_ref(&payload)).await?;
+    let created = sqlx::query_as::<_, ProductBarcode>(
+        "SELECT * FROM product_barcodes WHERE id = last_insert_rowid()",
+    )
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    crate::commands::activity_cmd::log_activity(
+        &state.db,
+        None,
+        Some(session.user_id),
+        "ADD_PRODUCT_BARCODE",
+        &format!(
+            "Menambah barcode {} (isi {}) ke produk ID {}",
+            created.barcode, created.pack_quantity, product_id
+        ),
+        None,
+    ).await;
+
+    Ok(created)
+}
+
+/// Hapus barcode tambahan (Admin only)
+#[tauri::command]
+pub async fn delete_product_barcode(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    id: i64,
+) -> Result<(), String> {
+    let session = crate::auth::guard::validate_admin(&state, &session_token)?;
+
+    let removed: Option<(i64, String)> =
+        sqlx::query_as("DELETE FROM product_barcodes WHERE id = ? RETURNING product_id, barcode")
+            .bind(id)
+            .fetch_optional(&state.db)
+            .await
+            .map_err(|e| e.to_string())?;
+    let (product_id, barcode) = removed.ok_or("Barcode tidak ditemukan")?;
+
+    crate::commands::activity_cmd::log_activity(
+        &state.db,
+        None,
+        Some(session.user_id),
+        "DELETE_PRODUCT_BARCODE",
+        &format!("Menghapus barcode {} dari produk ID {}", barcode, product_id),
+        None,
+    ).await;
+
+    Ok(())
+}
+
+/// Pastikan barcode belum dipakai produk aktif lain — baik sebagai barcode
+/// utama maupun barcode tambahan. `own_product_id` dikecualikan dari cek
+/// barcode utama (update produk dengan barcode yang sama).
+async fn ensure_barcode_available(
+    conn: &mut SqliteConnection,
+    barcode: &str,
+    own_product_id: Option<i64>,
+) -> Result<(), String> {
+    let owner: Option<(String,)> = sqlx::query_as(
+        "SELECT name FROM products WHERE barcode = ? AND is_active = 1 AND id != ?
+         UNION ALL
+         SELECT p.name FROM product_barcodes b
+         JOIN products p ON p.id = b.product_id
+         WHERE b.barcode = ? AND p.is_active = 1
+         LIMIT 1",
+    )
+    .bind(barcode)
+    .bind(own_product_id.unwrap_or(0))
+    .bind(barcode)
+    .fetch_optional(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    match owner {
+        Some((name,)) => Err(format!("Barcode {} sudah digunakan oleh {}", barcode, name)),
+        None => Ok(()),
+    }
+}
+
+/// Cek barcode utama + barcode tambahan di payload produk baru, termasuk
+/// duplikat di dalam payload itu sendiri.
+async fn check_product_barcodes(
+    conn: &mut SqliteConnection,
+    payload: &CreateProductPayload,
+) -> Result<(), String> {
+    for b in &payload.extra_barcodes {
+        if b.barcode.trim().is_empty() || b.pack_quantity < 1 {
+            return Err("Barcode tambahan kosong atau isi kemasan < 1".into());
+        }
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    let main = payload.barcode.as_deref().map(str::trim).filter(|b| !b.is_empty());
+    for barcode in main
+        .into_iter()
+        .chain(payload.extra_barcodes.iter().map(|b| b.barcode.trim()))
+    {
+        if !seen.insert(barcode) {
+            return Err(format!("Barcode {} diisi lebih dari sekali", barcode));
+        }
+        ensure_barcode_available(conn, barcode, None).await?;
+    }
+    Ok(())
+}
+
+/// Validasi & simpan barcode tambahan untuk satu produk.
+async fn insert_product_barcodes(
+    conn: &mut SqliteConnection,
+    product_id: i64,
+    barcodes: &[ProductBarcodePayload],
+) -> Result<(), String> {
+    for b in barcodes {
+        let barcode = b.barcode.trim();
+        if barcode.is_empty() {
+            return Err("Barcode tidak boleh kosong".into());
+        }
+        if b.pack_quantity < 1 {
+            return Err(format!("Isi kemasan barcode {} minimal 1", barcode));
+        }
+        ensure_barcode_available(conn, barcode, None).await?;
+
+        let own: Option<(i64,)> =
+            sqlx::query_as("SELECT id FROM products WHERE id = ? AND barcode = ?")
+                .bind(product_id)
+                .bind(barcode)
+                .fetch_optional(&mut *conn)
+                .await
+                .map_err(|e| e.to_string())?;
+        if own.is_some() {
+            return Err(format!("Barcode {} sudah menjadi barcode utama produk ini", barcode));
+        }
+
+        sqlx::query(
+            "INSERT INTO product_barcodes (product_id, barcode, pack_quantity, label) VALUES (?, ?, ?, ?)",
+        )
+        .bind(product_id)
+        .bind(barcode)
+        .bind(b.pack_quantity)
+        .bind(b.label.as_deref().map(str::trim).filter(|l| !l.is_empty()))
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Daftar satuan jual produk
+#[tauri::command]
+pub async fn get_product_units(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    product_id: i64,
+) -> Result<Vec<ProductUnit>, String> {
+    crate::auth::guard::validate_session(&state, &session_token)?;
+
+    sqlx::query_as::<_, ProductUnit>(
+        "SELECT * FROM product_units WHERE product_id = ? ORDER BY conversion ASC",
+    )
+    .bind(product_id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Simpan ulang seluruh satuan jual produk (Admin only). Item transaksi lama
+/// menyimpan nama & konversi sendiri sehingga tidak terpengaruh.
+#[tauri::command]
+pub async fn save_product_units(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    product_id: i64,
+    units: Vec<ProductUnitPayload>,
+) -> Result<Vec<ProductUnit>, String> {
+    let session = crate::auth::guard::validate_admin(&state, &session_token)?;
+
+    let base_unit: (String,) = sqlx::query_as("SELECT unit FROM products WHERE id = ?")
+        .bind(product_id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or("Produk tidak ditemukan")?;
+
+    let mut seen = std::collections::HashSet::new();
+    for u in &units {
+        let name = u.name.trim().to_lowercase();
+        if name.is_empty() || name == base_unit.0.to_lowercase() {
+            return Err(format!("Nama satuan '{}' tidak valid", u.name));
+        }
+        if !seen.insert(name) {
+            return Err(format!("Satuan '{}' diisi lebih dari sekali", u.name));
+        }
+        if !(u.conversion > 0.0 && u.conversion.is_finite()) || u.price < 0.0 {
+            return Err(format!("Konversi/harga satuan '{}' tidak valid", u.name));
+        }
+    }
+
+    let mut tx = state.db.begin().await.map_err(|e| e.to_string())?;
+    sqlx::query("DELETE FROM product_units WHERE product_id = ?")
+        .bind(product_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    for u in &units {
+        sqlx::query(
+            "INSERT INTO product_units (product_id, name, conversion, price) VALUES (?, ?, ?, ?)",
+        )
+        .bind(product_id)
+        .bind(u.name.trim())
+        .bind(u.conversion)
+        .bind(u.price)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    }
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    crate::commands::activity_cmd::log_activity(
+        &state.db,
+        None,
+        Some(session.user_id),
+        "UPDATE_PRODUCT_UNITS",
+        &format!("Memperbarui {} satuan jual produk ID {}", units.len(), product_id),
+        None,
+    ).await;
+
+    get_product_units(state, session_token, product_id).await
+}
+
+/// Nama satuan & faktor konversi ke satuan dasar. `unit_id = None` → satuan dasar.
+pub async fn resolve_unit(
+    conn: &mut SqliteConnection,
+    product_id: i64,
+    unit_id: Option<i64>,
+    base_unit: &str,
+) -> Result<(String, f64), String> {
+    let Some(unit_id) = unit_id else {
+        return Ok((base_unit.to_string(), 1.0));
+    };
+    sqlx::query_as::<_, (String, f64)>(
+        "SELECT name, conversion FROM product_units WHERE id = ? AND product_id = ?",
+    )
+    .bind(unit_id)
+    .bind(product_id)
+    .fetch_optional(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?
+    .ok_or_else(|| format!("Satuan tidak valid untuk produk id {}", product_id))
+}
+
+/// Daftar varian satu produk induk
+#[tauri::command]
+pub async fn get_product_variants(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    parent_id: i64,
+) -> Result<Vec<ProductWithCategory>, String> {
+    crate::auth::guard::validate_session(&state, &session_token)?;
+
+    sqlx::query_as::<_, ProductWithCategory>(
+        "SELECT p.*, c.name as category_name
+         FROM products p
+         LEFT JOIN categories c ON p.category_id = c.id
+         WHERE p.parent_id = ? AND p.is_active = 1
+         ORDER BY p.name ASC",
+    )
+    .bind(parent_id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Tambah varian ke produk induk (Admin only). Nama varian dibentuk dari nama
+/// induk + nilai atribut, mis. "Kaos Polos (L, Merah)".
+#[tauri::command]
+pub async fn create_product_variant(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    parent_id: i64,
+    payload: CreateVariantPayload,
+) -> Result<Product, String> {
+    let session = crate::auth::guard::validate_admin(&state, &session_token)?;
+
+    let parent = sqlx::query_as::<_, Product>("SELECT * FROM products WHERE id = ? AND is_active = 1")
+        .bind(parent_id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or("Produk induk tidak ditemukan")?;
+    if parent.parent_id.is_some() {
+        return Err("Varian tidak bisa memiliki varian lagi".into());
+    }
+
+    // Stok induk tidak terjual lagi setelah punya varian, jadi harus dikosongkan dulu
+    let (variant_count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM products WHERE parent_id = ?")
+        .bind(parent_id)
+        .fetch_one(&state.db)
+        .await
+        .map_err(|e| e.to_string())?;
+    if variant_count == 0 && parent.stock != 0.0 {
+        return Err(format!(
+            "Produk induk masih memiliki stok {} {} — pindahkan atau sesuaikan stok ke 0 sebelum membuat varian pertama",
+            parent.stock, parent.unit
+        ));
+    }
+
+    let attributes: BTreeMap<String, String> = payload
+        .attributes
+        .iter()
+        .map(|(k, v)| (k.trim().to_string(), v.trim().to_string()))
+        .filter(|(k, v)| !k.is_empty() && !v.is_empty())
+        .collect();
+    if attributes.is_empty() {
+        return Err("Isi minimal satu atribut varian (mis. Ukuran = L)".into());
+    }
+    let price = payload.price.unwrap_or(parent.price);
+    if price < 0.0 || payload.stock < 0.0 || (!parent.is_weighed && payload.stock.fract() != 0.0) {
+        return Err("Harga atau stok tidak valid".into());
+    }
+
+    let attributes_json = serde_json::to_string(&attributes).map_err(|e| e.to_string())?;
+    let name = variant_name(&parent.name, &attributes);
+    let barcode = payload.barcode.as_deref().map(str::trim).filter(|b| !b.is_empty());
+
+    let mut tx = state.db.begin().await.map_err(|e| e.to_string())?;
+    if let Some(barcode) = barcode {
+        ensure_barcode_available(&mut tx, barcode, None).await?;
+    }
+
+    let result = sqlx::query(
+        "INSERT INTO products (name, sku, barcode, category_id, price, cost_price, stock, image_path, is_weighed, unit, parent_id, variant_attributes)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&name)
+    .bind(payload.sku.as_deref().map(str::trim).filter(|s| !s.is_empty()))
+    .bind(barcode)
+    .bind(parent.category_id)
+    .bind(price)
+    .bind(payload.cost_price.unwrap_or(parent.cost_price))
+    .bind(round_quantity(payload.stock))
+    .bind(&parent.image_path)
+    .bind(parent.is_weighed)
+    .bind(&parent.unit)
+    .bind(parent_id)
+    .bind(&attributes_json)
+    .execute(&mut *tx)
+    .await;
+
+    let id = match result {
+        Ok(res) => res.last_insert_rowid(),
+        Err(sqlx::Error::Database(err)) if err.is_unique_violation() => {
+            return Err("Varian, SKU, atau barcode sudah ada".into());
+        }
+        Err(e) => return Err(e.to_string()),
+    };
+
+    if payload.stock > 0.0 {
+        crate::commands::activity_cmd::log_stock_adjustment(
+            &state.db,
+            Some(&mut tx),
+            id,
+            session.user_id,
+            "IN",
+            round_quantity(payload.stock),
+            "RESTOCK",
+            Some("Stok awal saat pembuatan varian"),
+        ).await;
+    }
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    crate::commands::activity_cmd::log_activity(
+        &state.db,
+        None,
+        Some(session.user_id),
+        "CREATE_PRODUCT_VARIANT",
+        &format!("Membuat varian {} untuk produk ID {}", name, parent_id),
+        None,
+    ).await;
+
+    sqlx::query_as::<_, Product>("SELECT * FROM products WHERE id = ?")
+        .bind(id)
+        .fetch_one(&state.db)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// "Kaos Polos" + {Ukuran: L, Warna: Merah} → "Kaos Polos (L, Merah)"
+fn variant_name(parent_name: &str, attributes: &BTreeMap<String, String>) -> String {
+    let values: Vec<&str> = attributes.values().map(String::as_str).collect();
+    format!("{} ({})", parent_name.trim(), values.join(", "))
+}
+
+/// Setelah produk induk diubah: samakan kategori/satuan/jenis timbang varian
+/// dan bentuk ulang nama varian dari nama induk yang baru.
+async fn sync_variants_with_parent(db: &sqlx::SqlitePool, parent_id: i64) -> Result<(), String> {
+    let variants: Vec<(i64, Option<String>)> =
+        sqlx::query_as("SELECT id, variant_attributes FROM products WHERE parent_id = ?")
+            .bind(parent_id)
+            .fetch_all(db)
+            .await
+            .map_err(|e| e.to_string())?;
+    if variants.is_empty() {
+        return Ok(());
+    }
+
+    let parent = sqlx::query_as::<_, Product>("SELECT * FROM products WHERE id = ?")
+        .bind(parent_id)
+        .fetch_one(db)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut tx = db.begin().await.map_err(|e| e.to_string())?;
+    for (id, attributes) in variants {
+        let attributes: BTreeMap<String, String> = attributes
+            .as_deref()
+            .and_then(|a| serde_json::from_str(a).ok())
+            .unwrap_or_default();
+        sqlx::query(
+            "UPDATE products SET name = ?, category_id = ?, unit = ?, is_weighed = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?",
+        )
+        .bind(variant_name(&parent.name, &attributes))
+        .bind(parent.category_id)
+        .bind(&parent.unit)
+        .bind(parent.is_weighed)
+        .bind(id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    }
+    tx.commit().await.map_err(|e| e.to_string())
+}
+
+/// Stok paket / menu resep = jumlah utuh yang bisa dibuat dari stok komponen.
+pub async fn kit_availability(conn: &mut SqliteConnection, kit_id: i64) -> Result<f64, String> {
+    // Kolom INTEGER: stok / qty bulat tersimpan sebagai integer, jadi decode via Quantity
+    let components: Vec<(Quantity, Quantity)> = sqlx::query_as(
+        "SELECT pc.quantity * pc.unit_conversion, p.stock
+         FROM product_components pc
+         JOIN products p ON p.id = pc.component_id
+         WHERE pc.product_id = ?",
+    )
+    .bind(kit_id)
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(components
+        .iter()
+        .map(|(Quantity(quantity), Quantity(stock))| round_quantity(stock / quantity).floor().max(0.0))
+        .reduce(f64::min)
+        .unwrap_or(0.0))
+}
+
+/// Isi `stock` produk paket / resep dengan ketersediaan dari komponen.
+pub async fn fill_kit_stock(db: &sqlx::SqlitePool, products: &mut [ProductWithCategory]) -> Result<(), String> {
+    let mut conn = db.acquire().await.map_err(|e| e.to_string())?;
+    for product in products.iter_mut().filter(|p| p.is_kit || p.is_recipe) {
+        product.stock = kit_availability(&mut conn, product.id).await?;
+    }
+    Ok(())
+}
+
+/// Daftar komponen satu paket
+#[tauri::command]
+pub async fn get_kit_components(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    product_id: i64,
+) -> Result<Vec<ProductComponent>, String> {
+    crate::auth::guard::validate_session(&state, &session_token)?;
+
+    sqlx::query_as::<_, ProductComponent>(
+        "SELECT pc.component_id, p.name, COALESCE(pc.unit, p.unit) as unit, pc.quantity,
+                (SELECT pu.id FROM product_units pu
+                 WHERE pu.product_id = pc.component_id AND pu.name = pc.unit) as unit_id,
+                pc.unit_conversion, p.unit as base_unit, p.stock
+         FROM product_components pc
+         JOIN products p ON p.id = pc.component_id
+         WHERE pc.product_id = ?
+         ORDER BY p.name ASC",
+    )
+    .bind(product_id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Simpan ulang seluruh komponen paket (Admin only). Daftar kosong = produk
+/// kembali menjadi produk biasa dengan stok sendiri.
+#[tauri::command]
+pub async fn save_kit_components(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    product_id: i64,
+    components: Vec<ProductComponentPayload>,
+) -> Result<Vec<ProductComponent>, String> {
+    let session = crate::auth::guard::validate_admin(&state, &session_token)?;
+
+    let (variant_count, is_recipe): (i64, bool) = sqlx::query_as(
+        "SELECT (SELECT COUNT(*) FROM products v WHERE v.parent_id = p.id AND v.is_active = 1), p.is_recipe
+         FROM products p WHERE p.id = ?",
+    )
+    .bind(product_id)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|e| e.to_string())?
+    .ok_or("Produk tidak ditemukan")?;
+    if variant_count > 0 && !components.is_empty() {
+        return Err("Produk induk varian tidak bisa dijadikan paket".into());
+    }
+    if is_recipe {
+        return Err("Produk ini memakai resep — kosongkan resep terlebih dahulu".into());
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    for c in &components {
+        if c.component_id == product_id {
+            return Err("Paket tidak bisa berisi dirinya sendiri".into());
+        }
+        if !seen.insert(c.component_id) {
+            return Err(format!("Komponen id {} diisi lebih dari sekali", c.component_id));
+        }
+        let component: Option<(String, bool, bool, i64)> = sqlx::query_as(
+            "SELECT p.name, p.is_weighed, p.is_kit OR p.is_recipe,
+                    (SELECT COUNT(*) FROM products v WHERE v.parent_id = p.id AND v.is_active = 1)
+             FROM products p WHERE p.id = ? AND p.is_active = 1",
+        )
+        .bind(c.component_id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|e| e.to_string())?;
+        let (name, is_weighed, is_kit, variants) =
+            component.ok_or_else(|| format!("Komponen id {} tidak ditemukan", c.component_id))?;
+        if is_kit {
+            return Err(format!("'{}' adalah paket/resep, tidak bisa menjadi komponen", name));
+        }
+        if variants > 0 {
+            return Err(format!("'{}' memiliki varian — pilih variannya sebagai komponen", name));
+        }
+        validate_quantity(c.quantity, is_weighed).map_err(|e| format!("{}: {}", name, e))?;
+    }
+
+    let mut tx = state.db.begin().await.map_err(|e| e.to_string())?;
+    sqlx::query("DELETE FROM product_components WHERE product_id = ?")
+        .bind(product_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    for c in &components {
+        sqlx::query(
+            "INSERT INTO product_components (product_id, component_id, quantity) VALUES (?, ?, ?)",
+        )
+        .bind(product_id)
+        .bind(c.component_id)
+        .bind(round_quantity(c.quantity))
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    }
+    sqlx::query("UPDATE products SET is_kit = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?")
+        .bind(!components.is_empty())
+        .bind(product_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    crate::commands::activity_cmd::log_activity(
+        &state.db,
+        None,
+        Some(session.user_id),
+        "UPDATE_KIT_COMPONENTS",
+        &format!("Memperbarui {} komponen paket produk ID {}", components.len(), product_id),
+        None,
+    ).await;
+
+    get_kit_components(state, session_token, product_id).await
+}
+
 /// Hasil bulk import.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct BulkImportResult {
@@ -752,3 +1599,42 @@ pub struct BulkImportResult {
     pub error_count: i64,
     pub errors: Vec<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    #[tokio::test]
+    async fn kit_availability_decodes_integer_stock() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::query(
+            "CREATE TABLE products (id INTEGER PRIMARY KEY, stock INTEGER NOT NULL DEFAULT 0);
+             CREATE TABLE product_components (
+                 product_id INTEGER, component_id INTEGER,
+                 quantity REAL NOT NULL, unit_conversion REAL NOT NULL DEFAULT 1
+             );
+             INSERT INTO products (id, stock) VALUES (1, 0), (2, 7.5), (3, 10);
+             INSERT INTO product_components VALUES (1, 2, 2, 1), (1, 3, 3, 1);
+             UPDATE products SET stock = ROUND(stock - 0.5, 3) WHERE id = 2;",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let mut conn = pool.acquire().await.unwrap();
+        // stok 7 (integer) / 2 = 3, stok 10 / 3 = 3
+        assert_eq!(kit_availability(&mut conn, 1).await.unwrap(), 3.0);
+
+        sqlx::query("UPDATE product_components SET quantity = 1.5 WHERE component_id = 3")
+            .execute(&mut *conn)
+            .await
+            .unwrap();
+        assert_eq!(kit_availability(&mut conn, 1).await.unwrap(), 3.0);
+        assert_eq!(kit_availability(&mut conn, 99).await.unwrap(), 0.0);
+    }
+}
@@ -0,0 +1,855 @@
+use crate::commands::batch_cmd::receive_batch;
+use crate::commands::location_cmd::{location_or_default, move_location_stock};
+use crate::models::product::StockBatchPayload;
+use crate::models::purchase::{
+    CostHistoryEntry, GoodsReceipt, GoodsReceiptDetail, GoodsReceiptItem, GoodsReceiptPayload,
+    PurchaseOrder, PurchaseOrderDetail, PurchaseOrderItem, PurchaseOrderPayload, Supplier,
+    SupplierPayload,
+};
+use crate::models::quantity::{round_quantity, validate_quantity, Quantity};
+use crate::printer::purchase_order::{po_number, purchase_order_pdf};
+use crate::printer::raster::load_logo;
+use crate::AppState;
+use sqlx::SqliteConnection;
+use tauri::Manager;
+
+const PO_SELECT: &str = "SELECT po.*, s.name as supplier_name, u.name as created_by_name,
+            (SELECT COUNT(*) FROM purchase_order_items i WHERE i.purchase_order_id = po.id) as item_count
+     FROM purchase_orders po
+     JOIN suppliers s ON s.id = po.supplier_id
+     LEFT JOIN users u ON u.id = po.created_by";
+
+fn trimmed(value: &Option<String>) -> Option<&str> {
+    value.as_deref().map(str::trim).filter(|v| !v.is_empty())
+}
+
+/// Daftar supplier
+#[tauri::command]
+pub async fn get_suppliers(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    show_inactive: Option<bool>,
+) -> Result<Vec<Supplier>, String> {
+    crate::auth::guard::validate_admin(&state, &session_token)?;
+
+    let query = if show_inactive.unwrap_or(false) {
+        "SELECT * FROM suppliers ORDER BY name ASC"
+    } else {
+        "SELECT * FROM suppliers WHERE is_active = 1 ORDER BY name ASC"
+    };
+    sqlx::query_as::<_, Supplier>(query)
+        .fetch_all(&state.db)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Tambah supplier (Admin only)
+#[tauri::command]
+pub async fn create_supplier(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    payload: SupplierPayload,
+) -> Result<Supplier, String> {
+    let session = crate::auth::guard::validate_admin(&state, &session_token)?;
+
+    let name = payload.name.trim();
+    if name.is_empty() {
+        return Err("Nama supplier tidak boleh kosong".into());
+    }
+
+    let id = sqlx::query(
+        "INSERT INTO suppliers (name, contact_person, phone, email, address, notes) VALUES (?, ?, ?, ?, ?, ?)",
+    )
+    .bind(name)
+    .bind(trimmed(&payload.contact_person))
+    .bind(trimmed(&payload.phone))
+    .bind(trimmed(&payload.email))
+    .bind(trimmed(&payload.address))
+    .bind(trimmed(&payload.notes))
+    .execute(&state.db)
+    .await
+    .map_err(|e| e.to_string())?
+    .last_insert_rowid();
+
+    crate::commands::activity_cmd::log_activity(
+        &state.db,
+        None,
+        Some(session.user_id),
+        "CREATE_SUPPLIER",
+        &format!("Menambah supplier {}", name),
+        None,
+    )
+    .await;
+
+    sqlx::query_as::<_, Supplier>("SELECT * FROM suppliers WHERE id = ?")
+        .bind(id)
+        .fetch_one(&state.db)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Update data supplier (Admin only)
+#[tauri::command]
+pub async fn update_supplier(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    id: i64,
+    payload: SupplierPayload,
+) -> Result<Supplier, String> {
+    let session = crate::auth::guard::validate_admin(&state, &session_token)?;
+
+    let name = payload.name.trim();
+    if name.is_empty() {
+        return Err("Nama supplier tidak boleh kosong".into());
+    }
+
+    sqlx::query(
+        "UPDATE suppliers SET name = ?, contact_person = ?, phone = ?, email = ?, address = ?, notes = ? WHERE id = ?",
+    )
+    .bind(name)
+    .bind(trimmed(&payload.contact_person))
+    .bind(trimmed(&payload.phone))
+    .bind(trimmed(&payload.email))
+    .bind(trimmed(&payload.address))
+    .bind(trimmed(&payload.notes))
+    .bind(id)
+    .execute(&state.db)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    crate::commands::activity_cmd::log_activity(
+        &state.db,
+        None,
+        Some(session.user_id),
+        "UPDATE_SUPPLIER",
+        &format!("Mengubah supplier {}", name),
+        None,
+    )
+    .await;
+
+    sqlx::query_as::<_, Supplier>("SELECT * FROM suppliers WHERE id = ?")
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Supplier tidak ditemukan".to_string())
+}
+
+/// Toggle status aktif/nonaktif supplier
+#[tauri::command]
+pub async fn toggle_supplier(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    id: i64,
+) -> Result<bool, String> {
+    crate::auth::guard::validate_admin(&state, &session_token)?;
+
+    let current: (bool,) = sqlx::query_as("SELECT is_active FROM suppliers WHERE id = ?")
+        .bind(id)
+        .fetch_one(&state.db)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    sqlx::query("UPDATE suppliers SET is_active = ? WHERE id = ?")
+        .bind(!current.0)
+        .bind(id)
+        .execute(&state.db)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(!current.0)
+}
+
+/// Ambil PO lengkap dengan supplier & item.
+pub async fn load_purchase_order(
+    conn: &mut SqliteConnection,
+    id: i64,
+) -> Result<PurchaseOrderDetail, String> {
+    let order = sqlx::query_as::<_, PurchaseOrder>(&format!("{} WHERE po.id = ?", PO_SELECT))
+        .bind(id)
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or("Purchase order tidak ditemukan")?;
+
+    let supplier = sqlx::query_as::<_, Supplier>("SELECT * FROM suppliers WHERE id = ?")
+        .bind(order.supplier_id)
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let items = sqlx::query_as::<_, PurchaseOrderItem>(
+        "SELECT i.*, p.name as product_name, p.sku, p.unit
+         FROM purchase_order_items i
+         JOIN products p ON p.id = i.product_id
+         WHERE i.purchase_order_id = ?
+         ORDER BY i.id ASC",
+    )
+    .bind(id)
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(PurchaseOrderDetail { order, supplier, items })
+}
+
+/// Daftar PO, bisa difilter status / supplier
+#[tauri::command]
+pub async fn get_purchase_orders(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    status: Option<String>,
+    supplier_id: Option<i64>,
+) -> Result<Vec<PurchaseOrder>, String> {
+    crate::auth::guard::validate_admin(&state, &session_token)?;
+
+    let mut query = format!("{} WHERE 1=1", PO_SELECT);
+    if status.is_some() {
+        query.push_str(" AND po.status = ?");
+    }
+    if supplier_id.is_some() {
+        query.push_str(" AND po.supplier_id = ?");
+    }
+    query.push_str(" ORDER BY po.created_at DESC, po.id DESC LIMIT 200");
+
+    let mut q = sqlx::query_as::<_, PurchaseOrder>(&query);
+    if let Some(status) = &status {
+        q = q.bind(status);
+    }
+    if let Some(supplier_id) = supplier_id {
+        q = q.bind(supplier_id);
+    }
+    q.fetch_all(&state.db).await.map_err(|e| e.to_string())
+}
+
+/// Detail satu PO
+#[tauri::command]
+pub async fn get_purchase_order(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    id: i64,
+) -> Result<PurchaseOrderDetail, String> {
+    crate::auth::guard::validate_admin(&state, &session_token)?;
+
+    let mut conn = state.db.acquire().await.map_err(|e| e.to_string())?;
+    load_purchase_order(&mut conn, id).await
+}
+
+/// Validasi & simpan item PO (mengganti item lama). Mengembalikan total.
+async fn save_po_items(
+    conn: &mut SqliteConnection,
+    purchase_order_id: i64,
+    payload: &PurchaseOrderPayload,
+) -> Result<f64, String> {
+    if payload.items.is_empty() {
+        return Err("PO harus berisi minimal satu barang".into());
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    let mut total = 0.0;
+    sqlx::query("DELETE FROM purchase_order_items WHERE purchase_order_id = ?")
+        .bind(purchase_order_id)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    for item in &payload.items {
+        if !seen.insert(item.product_id) {
+            return Err(format!("Produk id {} diisi lebih dari sekali", item.product_id));
+        }
+        let product: Option<(String, bool, bool, i64)> = sqlx::query_as(
+            "SELECT p.name, p.is_weighed, p.is_kit OR p.is_recipe,
+                    (SELECT COUNT(*) FROM products v WHERE v.parent_id = p.id AND v.is_active = 1)
+             FROM products p WHERE p.id = ? AND p.is_active = 1",
+        )
+        .bind(item.product_id)
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(|e| e.to_string())?;
+        let (name, is_weighed, composite, variants) =
+            product.ok_or_else(|| format!("Produk id {} tidak ditemukan", item.product_id))?;
+        if composite || variants > 0 {
+            return Err(format!("'{}' tidak bisa dipesan langsung — pesan komponen/variannya", name));
+        }
+        validate_quantity(item.quantity, is_weighed).map_err(|e| format!("{}: {}", name, e))?;
+        if !item.unit_cost.is_finite() || item.unit_cost < 0.0 {
+            return Err(format!("Harga '{}' tidak valid", name));
+        }
+
+        let quantity = round_quantity(item.quantity);
+        let subtotal = (quantity * item.unit_cost).round();
+        sqlx::query(
+            "INSERT INTO purchase_order_items (purchase_order_id, product_id, quantity, unit_cost, subtotal)
+             VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(purchase_order_id)
+        .bind(item.product_id)
+        .bind(quantity)
+        .bind(item.unit_cost)
+        .bind(subtotal)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| e.to_string())?;
+        total += subtotal;
+    }
+
+    Ok(total)
+}
+
+fn validate_expected_date(value: &Option<String>) -> Result<Option<String>, String> {
+    trimmed(value)
+        .map(|d| {
+            chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d")
+                .map(|date| date.format("%Y-%m-%d").to_string())
+                .map_err(|_| format!("Tanggal '{}' tidak valid", d))
+        })
+        .transpose()
+}
+
+/// Buat PO baru berstatus DRAFT (Admin only)
+#[tauri::command]
+pub async fn create_purchase_order(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    payload: PurchaseOrderPayload,
+) -> Result<PurchaseOrderDetail, String> {
+    let session = crate::auth::guard::validate_admin(&state, &session_token)?;
+    let expected_date = validate_expected_date(&payload.expected_date)?;
+
+    let mut tx = state.db.begin().await.map_err(|e| e.to_string())?;
+    let supplier: Option<(bool,)> = sqlx::query_as("SELECT is_active FROM suppliers WHERE id = ?")
+        .bind(payload.supplier_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    if !supplier.is_some_and(|s| s.0) {
+        return Err("Supplier tidak ditemukan atau nonaktif".into());
+    }
+
+    // Nomor urut per hari (tanggal lokal)
+    let today = chrono::Local::now().format("%Y%m%d").to_string();
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM purchase_orders WHERE po_number LIKE ?")
+        .bind(format!("PO/{}/%", today))
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    let number = po_number(&today, count + 1);
+
+    let id = sqlx::query(
+        "INSERT INTO purchase_orders (po_number, supplier_id, expected_date, notes, created_by)
+         VALUES (?, ?, ?, ?, ?)",
+    )
+    .bind(&number)
+    .bind(payload.supplier_id)
+    .bind(&expected_date)
+    .bind(trimmed(&payload.notes))
+    .bind(session.user_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?
+    .last_insert_rowid();
+
+    let total = save_po_items(&mut tx, id, &payload).await?;
+    sqlx::query("UPDATE purchase_orders SET total_amount = ? WHERE id = ?")
+        .bind(total)
+        .bind(id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    crate::commands::activity_cmd::log_activity(
+        &state.db,
+        Some(&mut tx),
+        Some(session.user_id),
+        "CREATE_PURCHASE_ORDER",
+        &format!("Membuat PO {} ({} barang)", number, payload.items.len()),
+        None,
+    )
+    .await;
+
+    let detail = load_purchase_order(&mut tx, id).await?;
+    tx.commit().await.map_err(|e| e.to_string())?;
+    Ok(detail)
+}
+
+/// Ubah PO yang masih DRAFT (Admin only)
+#[tauri::command]
+pub async fn update_purchase_order(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    id: i64,
+    payload: PurchaseOrderPayload,
+) -> Result<PurchaseOrderDetail, String> {
+    let session = crate::auth::guard::validate_admin(&state, &session_token)?;
+    let expected_date = validate_expected_date(&payload.expected_date)?;
+
+    let mut tx = state.db.begin().await.map_err(|e| e.to_string())?;
+    let current = load_purchase_order(&mut tx, id).await?;
+    if current.order.status != "DRAFT" {
+        return Err("Hanya PO berstatus DRAFT yang bisa diubah".into());
+    }
+
+    let total = save_po_items(&mut tx, id, &payload).await?;
+    sqlx::query(
+        "UPDATE purchase_orders SET supplier_id = ?, expected_date = ?, notes = ?, total_amount = ? WHERE id = ?",
+    )
+    .bind(payload.supplier_id)
+    .bind(&expected_date)
+    .bind(trimmed(&payload.notes))
+    .bind(total)
+    .bind(id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    crate::commands::activity_cmd::log_activity(
+        &state.db,
+        Some(&mut tx),
+        Some(session.user_id),
+        "UPDATE_PURCHASE_ORDER",
+        &format!("Mengubah PO {}", current.order.po_number),
+        None,
+    )
+    .await;
+
+    let detail = load_purchase_order(&mut tx, id).await?;
+    tx.commit().await.map_err(|e| e.to_string())?;
+    Ok(detail)
+}
+
+/// Ubah status PO: SENT (dari DRAFT) atau CANCELLED (selama belum diterima
+/// penuh). Status penerimaan diatur oleh penerimaan barang.
+#[tauri::command]
+pub async fn set_purchase_order_status(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    id: i64,
+    status: String,
+) -> Result<PurchaseOrderDetail, String> {
+    let session = crate::auth::guard::validate_admin(&state, &session_token)?;
+
+    let mut tx = state.db.begin().await.map_err(|e| e.to_string())?;
+    let current = load_purchase_order(&mut tx, id).await?;
+    let from = current.order.status.as_str();
+
+    let sql = match (from, status.as_str()) {
+        ("DRAFT", "SENT") => "UPDATE purchase_orders SET status = 'SENT', sent_at = CURRENT_TIMESTAMP WHERE id = ?",
+        ("DRAFT" | "SENT" | "PARTIALLY_RECEIVED", "CANCELLED") => {
+            "UPDATE purchase_orders SET status = 'CANCELLED', closed_at = CURRENT_TIMESTAMP WHERE id = ?"
+        }
+        _ => return Err(format!("Status PO tidak bisa diubah dari {} ke {}", from, status)),
+    };
+    sqlx::query(sql)
+        .bind(id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    crate::commands::activity_cmd::log_activity(
+        &state.db,
+        Some(&mut tx),
+        Some(session.user_id),
+        "UPDATE_PURCHASE_ORDER_STATUS",
+        &format!("PO {}: {} → {}", current.order.po_number, from, status),
+        None,
+    )
+    .await;
+
+    let detail = load_purchase_order(&mut tx, id).await?;
+    tx.commit().await.map_err(|e| e.to_string())?;
+    Ok(detail)
+}
+
+/// Export dokumen PO (PDF A4) ke folder Documents. Mengembalikan path file.
+#[tauri::command]
+pub async fn export_purchase_order_pdf(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    id: i64,
+) -> Result<String, String> {
+    crate::auth::guard::validate_admin(&state, &session_token)?;
+
+    let settings = crate::commands::settings_cmd::load_settings(&state.db).await?;
+    let mut conn = state.db.acquire().await.map_err(|e| e.to_string())?;
+    let po = load_purchase_order(&mut conn, id).await?;
+    drop(conn);
+
+    let logo = if settings.company.logo_path.is_empty() {
+        None
+    } else {
+        load_logo(
+            &settings.company.logo_path,
+            crate::commands::settings_cmd::INVOICE_LOGO_DOTS,
+        )
+        .ok()
+    };
+    let pdf = purchase_order_pdf(&settings.company, &po, logo.as_ref());
+
+    let docs_dir = app_handle
+        .path()
+        .document_dir()
+        .map_err(|e| format!("Gagal akses folder Documents: {}", e))?;
+    let po_dir = docs_dir.join("POS-Kasir-PO");
+    std::fs::create_dir_all(&po_dir).map_err(|e| format!("Gagal buat folder: {}", e))?;
+
+    let filename = format!("{}.pdf", po.order.po_number.replace('/', "_"));
+    let file_path = po_dir.join(&filename);
+    std::fs::write(&file_path, &pdf).map_err(|e| format!("Gagal simpan file: {}", e))?;
+
+    Ok(file_path.to_string_lossy().to_string())
+}
+
+const GR_SELECT: &str = "SELECT gr.*, po.po_number, s.name as supplier_name, u.name as received_by_name,
+            COALESCE(l.name, (SELECT name FROM locations WHERE is_default = 1)) as location_name,
+            (SELECT COUNT(*) FROM goods_receipt_items i WHERE i.goods_receipt_id = gr.id) as item_count
+     FROM goods_receipts gr
+     LEFT JOIN locations l ON l.id = gr.location_id
+     LEFT JOIN purchase_orders po ON po.id = gr.purchase_order_id
+     LEFT JOIN suppliers s ON s.id = gr.supplier_id
+     LEFT JOIN users u ON u.id = gr.received_by";
+
+/// HPP rata-rata bergerak setelah menerima `quantity` dengan modal `unit_cost`.
+/// Stok minus (terjual sebelum barang dicatat masuk) dianggap nol.
+pub fn moving_average_cost(stock_before: f64, cost_before: f64, quantity: f64, unit_cost: f64) -> f64 {
+    let stock_before = stock_before.max(0.0);
+    let total_qty = stock_before + quantity;
+    if total_qty <= 0.0 {
+        return cost_before;
+    }
+    let cost = (stock_before * cost_before + quantity * unit_cost) / total_qty;
+    (cost * 100.0).round() / 100.0
+}
+
+async fn load_goods_receipt(
+    conn: &mut SqliteConnection,
+    id: i64,
+) -> Result<GoodsReceiptDetail, String> {
+    let receipt = sqlx::query_as::<_, GoodsReceipt>(&format!("{} WHERE gr.id = ?", GR_SELECT))
+        .bind(id)
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or("Penerimaan barang tidak ditemukan")?;
+
+    let items = sqlx::query_as::<_, GoodsReceiptItem>(
+        "SELECT i.id, i.goods_receipt_id, i.product_id, p.name as product_name, p.unit,
+                i.quantity, i.unit_cost, i.subtotal, i.batch_id, b.batch_number, b.expiry_date
+         FROM goods_receipt_items i
+         JOIN products p ON p.id = i.product_id
+         LEFT JOIN product_batches b ON b.id = i.batch_id
+         WHERE i.goods_receipt_id = ?
+         ORDER BY i.id ASC",
+    )
+    .bind(id)
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(GoodsReceiptDetail { receipt, items })
+}
+
+/// Daftar penerimaan barang, bisa difilter per PO
+#[tauri::command]
+pub async fn get_goods_receipts(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    purchase_order_id: Option<i64>,
+) -> Result<Vec<GoodsReceipt>, String> {
+    crate::auth::guard::validate_admin(&state, &session_token)?;
+
+    let mut query = format!("{} WHERE 1=1", GR_SELECT);
+    if purchase_order_id.is_some() {
+        query.push_str(" AND gr.purchase_order_id = ?");
+    }
+    query.push_str(" ORDER BY gr.received_at DESC, gr.id DESC LIMIT 200");
+
+    let mut q = sqlx::query_as::<_, GoodsReceipt>(&query);
+    if let Some(purchase_order_id) = purchase_order_id {
+        q = q.bind(purchase_order_id);
+    }
+    q.fetch_all(&state.db).await.map_err(|e| e.to_string())
+}
+
+/// Detail satu penerimaan barang
+#[tauri::command]
+pub async fn get_goods_receipt(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    id: i64,
+) -> Result<GoodsReceiptDetail, String> {
+    crate::auth::guard::validate_admin(&state, &session_token)?;
+
+    let mut conn = state.db.acquire().await.map_err(|e| e.to_string())?;
+    load_goods_receipt(&mut conn, id).await
+}
+
+/// Catat penerimaan barang (Admin only): stok bertambah, HPP dihitung ulang
+/// dengan rata-rata bergerak, batch dibuat jika no. batch / ED diisi, dan
+/// qty diterima PO diperbarui jika terkait PO.
+#[tauri::command]
+pub async fn create_goods_receipt(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    payload: GoodsReceiptPayload,
+) -> Result<GoodsReceiptDetail, String> {
+    let session = crate::auth::guard::validate_admin(&state, &session_token)?;
+
+    if payload.items.is_empty() {
+        return Err("Penerimaan harus berisi minimal satu barang".into());
+    }
+
+    let mut tx = state.db.begin().await.map_err(|e| e.to_string())?;
+    let location = location_or_default(&mut tx, payload.location_id).await?;
+
+    // PO terkait: supplier & barang mengikuti PO
+    let po = match payload.purchase_order_id {
+        Some(po_id) => {
+            let po = load_purchase_order(&mut tx, po_id).await?;
+            if !matches!(po.order.status.as_str(), "SENT" | "PARTIALLY_RECEIVED") {
+                return Err(format!(
+                    "PO {} berstatus {} — hanya PO terkirim yang bisa diterima",
+                    po.order.po_number, po.order.status
+                ));
+            }
+            Some(po)
+        }
+        None => None,
+    };
+    let supplier_id = match &po {
+        Some(po) => Some(po.order.supplier_id),
+        None => {
+            if let Some(supplier_id) = payload.supplier_id {
+                let (exists,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM suppliers WHERE id = ?")
+                    .bind(supplier_id)
+                    .fetch_one(&mut *tx)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                if exists == 0 {
+                    return Err("Supplier tidak ditemukan".into());
+                }
+            }
+            payload.supplier_id
+        }
+    };
+
+    let today = chrono::Local::now().format("%Y%m%d").to_string();
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM goods_receipts WHERE receipt_number LIKE ?")
+        .bind(format!("GR/{}/%", today))
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    let number = format!("GR/{}/{:03}", today, count + 1);
+
+    let receipt_id = sqlx::query(
+        "INSERT INTO goods_receipts (receipt_number, purchase_order_id, supplier_id, invoice_number, notes, received_by, location_id)
+         VALUES (?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&number)
+    .bind(payload.purchase_order_id)
+    .bind(supplier_id)
+    .bind(trimmed(&payload.invoice_number))
+    .bind(trimmed(&payload.notes))
+    .bind(session.user_id)
+    .bind(location)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?
+    .last_insert_rowid();
+
+    let mut seen = std::collections::HashSet::new();
+    let mut total = 0.0;
+    for item in &payload.items {
+        if !seen.insert(item.product_id) {
+            return Err(format!("Produk id {} diisi lebih dari sekali", item.product_id));
+        }
+        let product: Option<(String, bool, bool, i64, Quantity, f64)> = sqlx::query_as(
+            "SELECT p.name, p.is_weighed, p.is_kit OR p.is_recipe,
+                    (SELECT COUNT(*) FROM products v WHERE v.parent_id = p.id AND v.is_active = 1),
+                    p.stock, p.cost_price
+             FROM products p WHERE p.id = ?",
+        )
+        .bind(item.product_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+        let (name, is_weighed, composite, variants, Quantity(stock), cost_before) =
+            product.ok_or_else(|| format!("Produk id {} tidak ditemukan", item.product_id))?;
+        if composite || variants > 0 {
+            return Err(format!("'{}' tidak bisa diterima langsung — terima komponen/variannya", name));
+        }
+        validate_quantity(item.quantity, is_weighed).map_err(|e| format!("{}: {}", name, e))?;
+        if !item.unit_cost.is_finite() || item.unit_cost < 0.0 {
+            return Err(format!("Harga modal '{}' tidak valid", name));
+        }
+        let quantity = round_quantity(item.quantity);
+
+        // Validasi terhadap sisa qty PO
+        let po_item = match &po {
+            Some(po) => {
+                let po_item = po
+                    .items
+                    .iter()
+                    .find(|i| i.product_id == item.product_id)
+                    .ok_or_else(|| format!("'{}' tidak ada di PO {}", name, po.order.po_number))?;
+                let remaining = round_quantity(po_item.quantity - po_item.received_quantity);
+                if quantity > remaining {
+                    return Err(format!(
+                        "Qty '{}' melebihi sisa PO ({} {})",
+                        name, remaining, po_item.unit
+                    ));
+                }
+                Some(po_item.id)
+            }
+            None => None,
+        };
+
+        let cost_after = moving_average_cost(stock, cost_before, quantity, item.unit_cost);
+        sqlx::query(
+            "UPDATE products SET stock = ROUND(stock + ?, 3), cost_price = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?",
+        )
+        .bind(quantity)
+        .bind(cost_after)
+        .bind(item.product_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+        move_location_stock(&mut tx, item.product_id, location, quantity).await?;
+
+        let batch_id = if trimmed(&item.batch_number).is_some() || trimmed(&item.expiry_date).is_some() {
+            let batch = StockBatchPayload {
+                batch_number: item.batch_number.clone(),
+                expiry_date: item.expiry_date.clone(),
+                cost_price: Some(item.unit_cost),
+            };
+            Some(receive_batch(&mut tx, item.product_id, quantity, &batch).await?)
+        } else {
+            None
+        };
+
+        let subtotal = (quantity * item.unit_cost).round();
+        sqlx::query(
+            "INSERT INTO goods_receipt_items (goods_receipt_id, product_id, purchase_order_item_id, quantity, unit_cost, subtotal, batch_id)
+             VALUES (?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(receipt_id)
+        .bind(item.product_id)
+        .bind(po_item)
+        .bind(quantity)
+        .bind(item.unit_cost)
+        .bind(subtotal)
+        .bind(batch_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+        total += subtotal;
+
+        sqlx::query(
+            "INSERT INTO product_cost_history
+                (product_id, goods_receipt_id, stock_before, cost_before, quantity, unit_cost, cost_after, created_by)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(item.product_id)
+        .bind(receipt_id)
+        .bind(stock)
+        .bind(cost_before)
+        .bind(quantity)
+        .bind(item.unit_cost)
+        .bind(cost_after)
+        .bind(session.user_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+
+        if let Some(po_item_id) = po_item {
+            sqlx::query(
+                "UPDATE purchase_order_items SET received_quantity = ROUND(received_quantity + ?, 3) WHERE id = ?",
+            )
+            .bind(quantity)
+            .bind(po_item_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?;
+        }
+
+        crate::commands::activity_cmd::log_stock_adjustment(
+            &state.db,
+            Some(&mut tx),
+            item.product_id,
+            session.user_id,
+            "IN",
+            quantity,
+            "PURCHASE",
+            Some(&format!("Penerimaan {}", number)),
+        )
+        .await;
+    }
+
+    sqlx::query("UPDATE goods_receipts SET total_amount = ? WHERE id = ?")
+        .bind(total)
+        .bind(receipt_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if let Some(po) = &po {
+        let (open_items,): (i64,) = sqlx::query_as(
+            "SELECT COUNT(*) FROM purchase_order_items WHERE purchase_order_id = ? AND received_quantity < quantity",
+        )
+        .bind(po.order.id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+        let sql = if open_items == 0 {
+            "UPDATE purchase_orders SET status = 'RECEIVED', closed_at = CURRENT_TIMESTAMP WHERE id = ?"
+        } else {
+            "UPDATE purchase_orders SET status = 'PARTIALLY_RECEIVED' WHERE id = ?"
+        };
+        sqlx::query(sql)
+            .bind(po.order.id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+
+    crate::commands::activity_cmd::log_activity(
+        &state.db,
+        Some(&mut tx),
+        Some(session.user_id),
+        "CREATE_GOODS_RECEIPT",
+        &match &po {
+            Some(po) => format!("Penerimaan {} untuk PO {} ({} barang)", number, po.order.po_number, payload.items.len()),
+            None => format!("Penerimaan {} ({} barang)", number, payload.items.len()),
+        },
+        None,
+    )
+    .await;
+
+    let detail = load_goods_receipt(&mut tx, receipt_id).await?;
+    tx.commit().await.map_err(|e| e.to_string())?;
+    Ok(detail)
+}
+
+/// Riwayat pergerakan HPP satu produk (terbaru dulu)
+#[tauri::command]
+pub async fn get_product_cost_history(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    product_id: i64,
+) -> Result<Vec<CostHistoryEntry>, String> {
+    crate::auth::guard::validate_admin(&state, &session_token)?;
+
+    sqlx::query_as::<_, CostHistoryEntry>(
+        "SELECT h.id, h.product_id, h.goods_receipt_id, gr.receipt_number, h.stock_before, h.cost_before,
+                h.quantity, h.unit_cost, h.cost_after, u.name as created_by_name, h.created_at
+         FROM product_cost_history h
+         LEFT JOIN goods_receipts gr ON gr.id = h.goods_receipt_id
+         LEFT JOIN users u ON u.id = h.created_by
+         WHERE h.product_id = ?
+         ORDER BY h.created_at DESC, h.id DESC",
+    )
+    .bind(product_id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| e.to_string())
+}
@@ -0,0 +1,246 @@
+use crate::commands::product_cmd::{get_kit_components, resolve_unit};
+use crate::models::product::{
+    IngredientUsage, ProductComponent, ProductComponentPayload, StockCountPayload,
+};
+use crate::models::quantity::{round_quantity, Quantity};
+use crate::AppState;
+
+/// Resep menu: daftar bahan beserta takaran per porsi
+#[tauri::command]
+pub async fn get_recipe(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    product_id: i64,
+) -> Result<Vec<ProductComponent>, String> {
+    get_kit_components(state, session_token, product_id).await
+}
+
+/// Simpan ulang resep menu (Admin only). Takaran boleh desimal dan memakai
+/// satuan jual bahan (mis. ml, g); konversi ke satuan dasar disimpan saat ini
+/// juga. Daftar kosong = produk kembali memakai stok sendiri.
+#[tauri::command]
+pub async fn save_recipe(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    product_id: i64,
+    ingredients: Vec<ProductComponentPayload>,
+) -> Result<Vec<ProductComponent>, String> {
+    let session = crate::auth::guard::validate_admin(&state, &session_token)?;
+
+    let (variant_count, is_kit): (i64, bool) = sqlx::query_as(
+        "SELECT (SELECT COUNT(*) FROM products v WHERE v.parent_id = p.id AND v.is_active = 1), p.is_kit
+         FROM products p WHERE p.id = ?",
+    )
+    .bind(product_id)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|e| e.to_string())?
+    .ok_or("Produk tidak ditemukan")?;
+    if variant_count > 0 && !ingredients.is_empty() {
+        return Err("Produk induk varian tidak bisa memakai resep — atur resep per varian".into());
+    }
+    if is_kit {
+        return Err("Produk ini adalah paket — kosongkan isi paket terlebih dahulu".into());
+    }
+
+    let mut tx = state.db.begin().await.map_err(|e| e.to_string())?;
+    let mut seen = std::collections::HashSet::new();
+    let mut rows = Vec::with_capacity(ingredients.len());
+    for i in &ingredients {
+        if i.component_id == product_id {
+            return Err("Resep tidak bisa berisi produk itu sendiri".into());
+        }
+        if !seen.insert(i.component_id) {
+            return Err(format!("Bahan id {} diisi lebih dari sekali", i.component_id));
+        }
+        let ingredient: Option<(String, String, bool, i64)> = sqlx::query_as(
+            "SELECT p.name, p.unit, p.is_kit OR p.is_recipe,
+                    (SELECT COUNT(*) FROM products v WHERE v.parent_id = p.id AND v.is_active = 1)
+             FROM products p WHERE p.id = ? AND p.is_active = 1",
+        )
+        .bind(i.component_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+        let (name, base_unit, composite, variants) =
+            ingredient.ok_or_else(|| format!("Bahan id {} tidak ditemukan", i.component_id))?;
+        if composite {
+            return Err(format!("'{}' adalah paket/resep, tidak bisa menjadi bahan", name));
+        }
+        if variants > 0 {
+            return Err(format!("'{}' memiliki varian — pilih variannya sebagai bahan", name));
+        }
+        if !i.quantity.is_finite() || round_quantity(i.quantity) <= 0.0 {
+            return Err(format!("Takaran '{}' harus lebih dari 0", name));
+        }
+        let (unit, conversion) =
+            resolve_unit(&mut tx, i.component_id, i.unit_id, &base_unit).await?;
+        rows.push((i.component_id, round_quantity(i.quantity), unit, conversion));
+    }
+
+    sqlx::query("DELETE FROM product_components WHERE product_id = ?")
+        .bind(product_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    for (component_id, quantity, unit, conversion) in &rows {
+        sqlx::query(
+            "INSERT INTO product_components (product_id, component_id, quantity, unit, unit_conversion)
+             VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(product_id)
+        .bind(component_id)
+        .bind(quantity)
+        .bind(unit)
+        .bind(conversion)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    }
+    sqlx::query("UPDATE products SET is_recipe = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?")
+        .bind(!rows.is_empty())
+        .bind(product_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    crate::commands::activity_cmd::log_activity(
+        &state.db,
+        None,
+        Some(session.user_id),
+        "UPDATE_RECIPE",
+        &format!("Memperbarui resep ({} bahan) produk ID {}", rows.len(), product_id),
+        None,
+    ).await;
+
+    get_kit_components(state, session_token, product_id).await
+}
+
+/// Catat hasil hitung fisik stok (Admin only). Stok disamakan dengan hasil
+/// hitung; selisihnya dicatat sebagai penyesuaian "COUNT".
+#[tauri::command]
+pub async fn record_stock_counts(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    counts: Vec<StockCountPayload>,
+) -> Result<(), String> {
+    let session = crate::auth::guard::validate_admin(&state, &session_token)?;
+
+    let mut tx = state.db.begin().await.map_err(|e| e.to_string())?;
+    for count in &counts {
+        if !count.counted.is_finite() || count.counted < 0.0 {
+            return Err(format!("Hasil hitung produk id {} tidak valid", count.product_id));
+        }
+        let (Quantity(stock), composite): (Quantity, bool) =
+            sqlx::query_as("SELECT stock, is_kit OR is_recipe FROM products WHERE id = ?")
+                .bind(count.product_id)
+                .fetch_optional(&mut *tx)
+                .await
+                .map_err(|e| e.to_string())?
+                .ok_or_else(|| format!("Produk id {} tidak ditemukan", count.product_id))?;
+        if composite {
+            return Err(format!("Produk id {} adalah paket/resep, hitung bahannya", count.product_id));
+        }
+
+        let counted = round_quantity(count.counted);
+        let diff = round_quantity(counted - stock);
+        sqlx::query("UPDATE products SET stock = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?")
+            .bind(counted)
+            .bind(count.product_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?;
+        if diff < 0.0 {
+            crate::commands::batch_cmd::consume_batches(&mut tx, count.product_id, -diff).await?;
+        }
+        // Selisih 0 tetap dicatat sebagai penanda waktu hitung terakhir
+        crate::commands::activity_cmd::log_stock_adjustment(
+            &state.db,
+            Some(&mut tx),
+            count.product_id,
+            session.user_id,
+            if diff > 0.0 { "IN" } else { "OUT" },
+            diff.abs(),
+            "COUNT",
+            Some(&format!("Hitung stok: sistem {} → fisik {}", stock, counted)),
+        ).await;
+    }
+
+    crate::commands::activity_cmd::log_activity(
+        &state.db,
+        Some(&mut tx),
+        Some(session.user_id),
+        "STOCK_COUNT",
+        &format!("Mencatat hitung stok {} produk", counts.len()),
+        None,
+    ).await;
+
+    tx.commit().await.map_err(|e| e.to_string())
+}
+
+/// Laporan pemakaian bahan resep: teoretis vs aktual (Admin only).
+/// Teoretis = bahan terpotong oleh penjualan; aktual = teoretis + selisih
+/// hitung stok pada periode yang sama.
+#[tauri::command]
+pub async fn get_ingredient_usage_report(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    start_date: String,
+    end_date: String,
+) -> Result<Vec<IngredientUsage>, String> {
+    crate::auth::guard::validate_admin(&state, &session_token)?;
+
+    let query = r#"
+        WITH sold AS (
+            SELECT tic.product_id, tic.quantity
+            FROM transaction_item_components tic
+            JOIN transaction_items ti ON ti.id = tic.transaction_item_id
+            JOIN transactions t ON t.id = ti.transaction_id
+            WHERE date(t.timestamp) BETWEEN ? AND ? AND t.status = 'COMPLETED'
+            UNION ALL
+            SELECT ti.product_id, ti.quantity * ti.unit_conversion
+            FROM transaction_items ti
+            JOIN transactions t ON t.id = ti.transaction_id
+            WHERE date(t.timestamp) BETWEEN ? AND ? AND t.status = 'COMPLETED'
+              AND NOT EXISTS (SELECT 1 FROM transaction_item_components tic WHERE tic.transaction_item_id = ti.id)
+        ),
+        counted AS (
+            SELECT product_id,
+                   SUM(CASE WHEN type = 'OUT' THEN quantity ELSE -quantity END) as variance
+            FROM stock_adjustments
+            WHERE reason IN ('COUNT', 'OPNAME') AND date(created_at) BETWEEN ? AND ?
+            GROUP BY product_id
+        )
+        SELECT p.id as product_id, p.name, p.unit,
+               ROUND(COALESCE((SELECT SUM(s.quantity) FROM sold s WHERE s.product_id = p.id), 0.0), 3) as theoretical,
+               ROUND(COALESCE(c.variance, 0.0), 3) as count_variance,
+               0.0 as actual,
+               ROUND(COALESCE(c.variance, 0.0) * p.cost_price) as variance_value,
+               CAST(p.stock AS REAL) as stock,
+               (SELECT MAX(sa.created_at) FROM stock_adjustments sa
+                WHERE sa.product_id = p.id AND sa.reason IN ('COUNT', 'OPNAME')) as last_counted_at
+        FROM products p
+        LEFT JOIN counted c ON c.product_id = p.id
+        WHERE p.id IN (SELECT pc.component_id FROM product_components pc
+                       JOIN products r ON r.id = pc.product_id
+                       WHERE r.is_recipe = 1)
+        ORDER BY p.name ASC
+    "#;
+
+    let mut rows = sqlx::query_as::<_, IngredientUsage>(query)
+        .bind(&start_date)
+        .bind(&end_date)
+        .bind(&start_date)
+        .bind(&end_date)
+        .bind(&start_date)
+        .bind(&end_date)
+        .fetch_all(&state.db)
+        .await
+        .map_err(|e| e.to_string())?;
+    for row in &mut rows {
+        row.actual = round_quantity(row.theoretical + row.count_variance);
+    }
+
+    Ok(rows)
+}
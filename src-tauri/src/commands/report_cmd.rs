@@ -1,4 +1,7 @@
-use crate::models::settings::{ChartPoint, DailyReport, FinancialSummary, ProductStat, ProfitReport, ShiftSummary};
+use crate::models::quantity::Quantity;
+use crate::models::settings::{
+    CategoryStat, ChartPoint, DailyReport, FinancialSummary, ProductStat, ProfitReport, ShiftSummary,
+};
 use crate::AppState;
 
 /// Ambil ringkasan keuangan untuk periode tertentu (Admin Only)
@@ -103,13 +106,13 @@ pub async fn get_daily_report(
             .map_err(|e| e.to_string())?;
 
     let items_query = r#"
-        SELECT COALESCE(SUM(ti.quantity), 0) as total_items
+        SELECT COALESCE(SUM(ti.quantity * ti.unit_conversion), 0) as total_items
         FROM transaction_items ti
         JOIN transactions t ON ti.transaction_id = t.id
         WHERE date(t.timestamp) = ? AND t.status != 'VOID'
     "#;
 
-    let (items_count,): (i64,) = sqlx::query_as(items_query)
+    let (items_count,): (Quantity,) = sqlx::query_as(items_query)
         .bind(&date)
         .fetch_one(&state.db)
         .await
@@ -126,7 +129,7 @@ pub async fn get_daily_report(
         total_revenue: revenue,
         transaction_count: trx_count,
         average_transaction: avg_trx,
-        total_items_sold: items_count,
+        total_items_sold: items_count.0,
         cash_total: cash,
         debit_total: debit,
         qris_total: qris,
@@ -178,15 +181,16 @@ pub async fn get_top_products(
 
     let query = r#"
         SELECT
-            p.id as product_id,
-            p.name as name,
-            SUM(ti.quantity) as total_sold,
+            COALESCE(pp.id, p.id) as product_id,
+            COALESCE(pp.name, p.name) as name,
+            SUM(ti.quantity * ti.unit_conversion) as total_sold,
             ROUND(SUM(ti.subtotal)) as total_revenue
         FROM transaction_items ti
         JOIN transactions t ON ti.transaction_id = t.id
         JOIN products p ON ti.product_id = p.id
+        LEFT JOIN products pp ON p.parent_id = pp.id -- varian digabung ke induk
         WHERE date(t.timestamp) BETWEEN ? AND ? AND t.status != 'VOID'
-        GROUP BY p.id
+        GROUP BY COALESCE(pp.id, p.id)
         ORDER BY total_sold DESC
         LIMIT ?
     "#;
@@ -202,6 +206,40 @@ pub async fn get_top_products(
     Ok(stats)
 }
 
+/// Penjualan per kategori dalam rentang tanggal (Admin only)
+#[tauri::command]
+pub async fn get_category_sales(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    start_date: String,
+    end_date: String,
+) -> Result<Vec<CategoryStat>, String> {
+    crate::auth::guard::validate_admin(&state, &session_token)?;
+
+    let query = r#"
+        SELECT
+            c.id as category_id,
+            COALESCE(c.name, 'Tanpa Kategori') as name,
+            SUM(ti.quantity * ti.unit_conversion) as total_sold,
+            ROUND(SUM(ti.subtotal)) as total_revenue
+        FROM transaction_items ti
+        JOIN transactions t ON ti.transaction_id = t.id
+        JOIN products p ON ti.product_id = p.id
+        LEFT JOIN products pp ON p.parent_id = pp.id
+        LEFT JOIN categories c ON c.id = COALESCE(pp.category_id, p.category_id)
+        WHERE date(t.timestamp) BETWEEN ? AND ? AND t.status != 'VOID'
+        GROUP BY c.id
+        ORDER BY total_revenue DESC
+    "#;
+
+    sqlx::query_as::<_, CategoryStat>(query)
+        .bind(&start_date)
+        .bind(&end_date)
+        .fetch_all(&state.db)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 /// Ambil ringkasan shift harian kasir saat ini
 #[tauri::command]
 pub async fn get_shift_summary(
@@ -248,14 +286,13 @@ pub async fn get_profit_report(
 ) -> Result<ProfitReport, String> {
     crate::auth::guard::validate_admin(&state, &session_token)?;
 
-    // Hitung total cost (HPP) dari transaksi completed
+    // Hitung total cost (HPP saat terjual) dari transaksi completed
     let query = r#"
         SELECT
-            ROUND(COALESCE(SUM(ti.quantity * p.cost_price), 0.0)) as total_cost,
+            ROUND(COALESCE(SUM(ti.quantity * ti.cost_at_time), 0.0)) as total_cost,
             ROUND(COALESCE(SUM(ti.subtotal), 0.0)) as total_revenue
         FROM transaction_items ti
         JOIN transactions t ON ti.transaction_id = t.id
-        JOIN products p ON ti.product_id = p.id
         WHERE date(t.timestamp) BETWEEN ? AND ? AND t.status = 'COMPLETED'
     "#;
 
@@ -0,0 +1,126 @@
+use crate::models::settings::ScaleSettings;
+use crate::printer::scale::{
+    parse_scale_line, ScaleProtocol, ScaleReading, ScaleWeight, StabilityTracker,
+};
+use crate::AppState;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Batas waktu menunggu berat stabil
+const READ_TIMEOUT: Duration = Duration::from_secs(3);
+/// Jumlah pembacaan stabil berturut-turut yang sama sebelum berat dianggap final
+const CONTINUOUS_STABLE_READS: usize = 3;
+const POLLED_STABLE_READS: usize = 2;
+
+/// Tare aktif (kg) — berlaku sampai diubah/di-reset kasir
+static SCALE_TARE: Mutex<f64> = Mutex::new(0.0);
+
+fn current_tare() -> f64 {
+    SCALE_TARE.lock().map(|t| *t).unwrap_or(0.0)
+}
+
+/// Baca berat dari timbangan. Menunggu berat stabil sampai batas waktu;
+/// jika tidak stabil, pembacaan terakhir dikembalikan dengan `stable = false`.
+#[tauri::command]
+pub async fn read_scale_weight(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+) -> Result<ScaleWeight, String> {
+    crate::auth::guard::validate_session(&state, &session_token)?;
+
+    let settings = scale_settings(&state.db).await?;
+    let reading = tauri::async_runtime::spawn_blocking(move || read_stable(&settings))
+        .await
+        .map_err(|e| e.to_string())??;
+
+    Ok(ScaleWeight::new(reading, current_tare()))
+}
+
+/// Atur tare. `tare_kg = None` → pakai berat stabil saat ini (wadah di atas
+/// timbangan); `Some(0)` → reset tare.
+#[tauri::command]
+pub async fn set_scale_tare(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    tare_kg: Option<f64>,
+) -> Result<ScaleWeight, String> {
+    crate::auth::guard::validate_session(&state, &session_token)?;
+
+    let settings = scale_settings(&state.db).await?;
+    let reading = tauri::async_runtime::spawn_blocking(move || read_stable(&settings))
+        .await
+        .map_err(|e| e.to_string())??;
+
+    let tare = match tare_kg {
+        Some(t) if t < 0.0 || !t.is_finite() => return Err("Nilai tare tidak valid".into()),
+        Some(t) => t,
+        None if !reading.stable => {
+            return Err("Berat belum stabil, tunggu sebentar lalu coba lagi".into())
+        }
+        None => reading.weight_kg.max(0.0),
+    };
+
+    *SCALE_TARE.lock().map_err(|e| e.to_string())? = tare;
+    Ok(ScaleWeight::new(reading, tare))
+}
+
+async fn scale_settings(db: &sqlx::SqlitePool) -> Result<ScaleSettings, String> {
+    let settings = crate::commands::settings_cmd::load_settings(db).await?.scale;
+    if !settings.enabled || settings.port.is_empty() {
+        return Err("Timbangan belum dikonfigurasi. Silakan atur di Settings → Hardware.".into());
+    }
+    Ok(settings)
+}
+
+/// Baca baris data dari port serial sampai berat stabil atau timeout.
+fn read_stable(settings: &ScaleSettings) -> Result<ScaleReading, String> {
+    use std::io::{BufRead, BufReader, Write};
+
+    let protocol = ScaleProtocol::parse(&settings.protocol);
+    let required = match protocol {
+        ScaleProtocol::Continuous => CONTINUOUS_STABLE_READS,
+        ScaleProtocol::Polled => POLLED_STABLE_READS,
+    };
+
+    let device_path = settings.port.trim_start_matches("serial:");
+    let port = serialport::new(device_path, settings.baud_rate)
+        .timeout(Duration::from_millis(500))
+        .open()
+        .map_err(|e| format!("Gagal membuka timbangan {}: {}", device_path, e))?;
+    let mut writer = port
+        .try_clone()
+        .map_err(|e| format!("Gagal membuka timbangan {}: {}", device_path, e))?;
+    let _ = port.clear(serialport::ClearBuffer::Input);
+    let mut reader = BufReader::new(port);
+
+    let deadline = Instant::now() + READ_TIMEOUT;
+    let mut tracker = StabilityTracker::default();
+    let mut last: Option<ScaleReading> = None;
+    let mut line = String::new();
+
+    while Instant::now() < deadline {
+        if protocol == ScaleProtocol::Polled {
+            writer
+                .write_all(format!("{}\r", settings.poll_command).as_bytes())
+                .map_err(|e| format!("Gagal kirim perintah ke timbangan: {}", e))?;
+        }
+
+        line.clear();
+        match reader.read_line(&mut line) {
+            Ok(0) => continue,
+            Ok(_) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::TimedOut => continue,
+            Err(e) => return Err(format!("Gagal membaca timbangan: {}", e)),
+        }
+
+        if let Some(reading) = parse_scale_line(&line)? {
+            last = Some(reading);
+            if tracker.push(reading, required) {
+                return Ok(reading);
+            }
+        }
+    }
+
+    last.map(|r| ScaleReading { stable: false, ..r })
+        .ok_or_else(|| "Timbangan tidak mengirim data. Periksa kabel, port, dan baud rate.".into())
+}
@@ -1,19 +1,28 @@
-use crate::models::settings::{AppSettings, CompanyProfile, ReceiptSettings, TaxSettings};
+use crate::models::settings::{
+    AppSettings, CompanyProfile, DisplaySettings, DrawerSettings, KitchenSettings, LabelSettings,
+    ReceiptSettings, ScaleBarcodeSettings, ScaleSettings, TaxSettings,
+};
+use crate::models::quantity::Quantity;
+use crate::models::transaction::TransactionWithCashier;
+use crate::printer::display::{display_frame, screen_lines, DisplayProtocol, DisplayScreen};
+use crate::printer::document::{receipt_pdf, tax_invoice_pdf, InvoiceCustomer};
+use crate::printer::label::{
+    escpos_labels, label_sheet_pdf, tspl_labels, zpl_labels, BarcodeLabelItem, LabelLanguage,
+    LabelSize,
+};
+use crate::printer::escpos::{
+    EscPosBuilder, ESC_POS_BOLD_OFF, ESC_POS_BOLD_ON, ESC_POS_CENTER, ESC_POS_CUT,
+    ESC_POS_DOUBLE_WIDTH, ESC_POS_INIT, ESC_POS_NORMAL,
+};
+use crate::printer::raster::{load_logo, paper_dots};
+use crate::printer::receipt::{ReceiptData, ReceiptItem, ReceiptTemplate};
+use crate::printer::status::{PrinterStatus, DLE_EOT_OFFLINE, DLE_EOT_PAPER, DLE_EOT_PRINTER};
 use crate::AppState;
 use std::collections::HashMap;
 use tauri::Manager;
 
-// ============================================================================
-// CONSTANTS - ESC/POS Commands
-// ============================================================================
-const ESC_POS_INIT: &[u8] = b"\x1B\x40";           // ESC @ - Initialize
-const ESC_POS_CENTER: &[u8] = b"\x1B\x61\x01";     // ESC a 1 - Center alignment
-const ESC_POS_LEFT: &[u8] = b"\x1B\x61\x00";       // ESC a 0 - Left alignment
-const ESC_POS_BOLD_ON: &[u8] = b"\x1B\x45\x01";    // ESC E 1 - Bold ON
-const ESC_POS_BOLD_OFF: &[u8] = b"\x1B\x45\x00";   // ESC E 0 - Bold OFF
-const ESC_POS_DOUBLE_WIDTH: &[u8] = b"\x1D\x21\x11"; // GS ! 0x11 - Double width+height
-const ESC_POS_NORMAL: &[u8] = b"\x1D\x21\x00";     // GS ! 0x00 - Normal size
-const ESC_POS_CUT: &[u8] = b"\x1D\x56\x41";        // GS V A - Full cut
+/// Lebar maksimal logo (dot) di kop invoice / PO A4
+pub(crate) const INVOICE_LOGO_DOTS: u32 = 400;
 
 // ============================================================================
 // HELPER FUNCTIONS
@@ -48,21 +57,6 @@ fn create_temp_file(extension: &str) -> std::path::PathBuf {
     ))
 }
 
-/// Format number with thousand separator (optimized)
-fn format_number(n: i64) -> String {
-    let s = n.abs().to_string();
-    let len = s.len();
-    let mut result = String::with_capacity(len + len / 3);
-    for (i, c) in s.chars().enumerate() {
-        if i > 0 && (len - i) % 3 == 0 {
-            result.push('.');
-        }
-        result.push(c);
-    }
-    if n < 0 { result.insert(0, '-'); }
-    result
-}
-
 /// RAII wrapper for temporary file cleanup
 struct TempFile(std::path::PathBuf);
 impl TempFile {
@@ -79,84 +73,6 @@ impl Drop for TempFile {
     }
 }
 
-/// Generate HTML for receipt (single source of truth)
-fn generate_receipt_html(html_content: &str, transaction_id: &str, include_instructions: bool) -> String {
-    let print_instructions = if include_instructions {
-        r#"<div class="print-instructions" id="printInstructions">
-        <h3>📄 Export ke PDF</h3>
-        <ol>
-            <li>Klik tombol "Print" di bawah</li>
-            <li>Pilih "Save as PDF" atau "Microsoft Print to PDF"</li>
-            <li>Pilih lokasi penyimpanan</li>
-            <li>Klik "Save"</li>
-        </ol>
-        <button class="btn" onclick="window.print()">🖨️ Print / Save as PDF</button>
-        <button class="btn" onclick="document.getElementById('printInstructions').style.display='none'" style="background:#6c757d;margin-left:5px;">Sembunyikan Panduan</button>
-    </div>
-    <script>
-        setTimeout(() => {
-            const instructions = document.getElementById('printInstructions');
-            if (instructions) { instructions.style.opacity = '0.5'; }
-        }, 5000);
-    </script>"#
-    } else {
-        ""
-    };
-
-    format!(r#"<!DOCTYPE html>
-<html>
-<head>
-    <meta charset="UTF-8">
-    <meta name="viewport" content="width=device-width, initial-scale=1.0">
-    <title>Struk Penjualan - {transaction_id}</title>
-    <style>
-        * {{ margin: 0; padding: 0; box-sizing: border-box; }}
-        @page {{ size: auto; margin: 0; }}
-        body {{
-            font-family: 'Courier New', Courier, monospace;
-            font-size: 12px;
-            line-height: 1.4;
-            color: #000;
-            background: #fff;
-            width: 58mm;
-            min-height: 100vh;
-            padding: 3mm;
-        }}
-        .receipt-container {{ width: 100%; max-width: 58mm; margin: 0 auto; }}
-        .header {{ text-align: center; margin-bottom: 10px; border-bottom: 1px dashed #000; padding-bottom: 10px; }}
-        .store-name {{ font-size: 16px; font-weight: bold; margin-bottom: 5px; }}
-        .store-info {{ font-size: 10px; margin-bottom: 3px; }}
-        .divider {{ border-bottom: 1px dashed #000; margin: 8px 0; }}
-        .divider-double {{ border-bottom: 2px solid #000; margin: 8px 0; }}
-        .items-table {{ width: 100%; border-collapse: collapse; margin: 8px 0; }}
-        .items-table th {{ text-align: left; border-bottom: 1px solid #000; padding: 4px 0; font-size: 11px; }}
-        .items-table td {{ padding: 4px 0; vertical-align: top; }}
-        .item-name {{ width: 55%; }}
-        .item-qty {{ width: 15%; text-align: center; }}
-        .item-price {{ width: 30%; text-align: right; }}
-        .totals {{ margin-top: 10px; }}
-        .totals-row {{ display: flex; justify-content: space-between; margin: 3px 0; }}
-        .totals-row.total {{ font-weight: bold; font-size: 14px; border-top: 1px solid #000; padding-top: 5px; margin-top: 5px; }}
-        .footer {{ text-align: center; margin-top: 15px; font-size: 10px; }}
-        .barcode {{ text-align: center; margin: 10px 0; font-family: 'Libre Barcode 39 Text', cursive; font-size: 18px; }}
-        @media print {{ body {{ width: 58mm; padding: 0; }} .receipt-container {{ width: 100%; }} }}
-        .print-instructions {{ position: fixed; top: 10px; right: 10px; background: #f0f0f0; padding: 15px; border-radius: 5px; font-family: Arial, sans-serif; font-size: 12px; max-width: 300px; box-shadow: 0 2px 10px rgba(0,0,0,0.2); }}
-        .print-instructions h3 {{ margin-bottom: 10px; color: #333; }}
-        .print-instructions ol {{ margin-left: 20px; }}
-        .print-instructions li {{ margin: 5px 0; }}
-        .print-instructions .btn {{ display: inline-block; background: #007bff; color: white; padding: 8px 16px; border-radius: 4px; text-decoration: none; margin-top: 10px; cursor: pointer; }}
-        .print-instructions .btn:hover {{ background: #0056b3; }}
-    </style>
-</head>
-<body>
-    {print_instructions}
-    <div class="receipt-container">
-        {html_content}
-    </div>
-</body>
-</html>"#, transaction_id = transaction_id, html_content = html_content, print_instructions = print_instructions)
-}
-
 // ============================================================================
 // MAIN COMMANDS
 // ============================================================================
@@ -169,8 +85,35 @@ pub async fn get_settings(
 ) -> Result<AppSettings, String> {
     crate::auth::guard::validate_session(&state, &session_token)?;
 
+    load_settings(&state.db).await
+}
+
+fn scale_barcode_settings(map: &HashMap<String, String>) -> ScaleBarcodeSettings {
+    ScaleBarcodeSettings {
+        enabled: map.get("scale_barcode.enabled").unwrap_or(&"0".to_string()) == "1",
+        rules: map
+            .get("scale_barcode.rules")
+            .and_then(|v| serde_json::from_str(v).ok())
+            .unwrap_or_else(|| ScaleBarcodeSettings::default().rules),
+    }
+}
+
+/// Aturan label timbangan saja, untuk dipakai di dalam koneksi/transaksi DB
+pub(crate) async fn load_scale_barcode_settings(
+    conn: &mut sqlx::SqliteConnection,
+) -> Result<ScaleBarcodeSettings, String> {
+    let rows: Vec<(String, String)> =
+        sqlx::query_as("SELECT key, value FROM settings WHERE key LIKE 'scale_barcode.%'")
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(|e| e.to_string())?;
+    Ok(scale_barcode_settings(&rows.into_iter().collect()))
+}
+
+/// Baca tabel settings dan bentuk struct AppSettings (dipakai juga oleh printer)
+pub async fn load_settings(db: &sqlx::SqlitePool) -> Result<AppSettings, String> {
     let rows: Vec<(String, String)> = sqlx::query_as("SELECT key, value FROM settings")
-        .fetch_all(&state.db)
+        .fetch_all(db)
         .await
         .map_err(|e| e.to_string())?;
 
@@ -211,6 +154,14 @@ pub async fn get_settings(
             .unwrap_or(&"1".to_string())
             .parse()
             .unwrap_or(1),
+        qr_mode: map
+            .get("receipt.qr_mode")
+            .cloned()
+            .unwrap_or_else(|| "NONE".into()),
+        qr_link_template: map
+            .get("receipt.qr_link_template")
+            .cloned()
+            .unwrap_or_else(|| "{id}".into()),
     };
 
     let tax = TaxSettings {
@@ -227,15 +178,104 @@ pub async fn get_settings(
         is_included: map.get("tax.is_included").unwrap_or(&"0".to_string()) == "1",
     };
 
+    let defaults = DrawerSettings::default();
+    let drawer = DrawerSettings {
+        enabled: map.get("drawer.enabled").unwrap_or(&"0".to_string()) == "1",
+        open_on_cash: map.get("drawer.open_on_cash").unwrap_or(&"1".to_string()) == "1",
+        pin: map
+            .get("drawer.pin")
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(defaults.pin),
+        pulse_on_ms: map
+            .get("drawer.pulse_on_ms")
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(defaults.pulse_on_ms),
+        pulse_off_ms: map
+            .get("drawer.pulse_off_ms")
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(defaults.pulse_off_ms),
+    };
+
+    let label_defaults = LabelSettings::default();
+    let label = LabelSettings {
+        printer_port: map.get("label.printer_port").cloned().unwrap_or_default(),
+        language: map
+            .get("label.language")
+            .cloned()
+            .unwrap_or(label_defaults.language),
+        size: map.get("label.size").cloned().unwrap_or(label_defaults.size),
+        dpi: map
+            .get("label.dpi")
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(label_defaults.dpi),
+        gap_mm: map
+            .get("label.gap_mm")
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(label_defaults.gap_mm),
+    };
+
+    let kitchen = KitchenSettings {
+        auto_print: map.get("kitchen.auto_print").unwrap_or(&"0".to_string()) == "1",
+    };
+
+    let display_defaults = DisplaySettings::default();
+    let display = DisplaySettings {
+        enabled: map.get("display.enabled").unwrap_or(&"0".to_string()) == "1",
+        port: map.get("display.port").cloned().unwrap_or_default(),
+        baud_rate: map
+            .get("display.baud_rate")
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(display_defaults.baud_rate),
+        protocol: map
+            .get("display.protocol")
+            .cloned()
+            .unwrap_or(display_defaults.protocol),
+        idle_message: map
+            .get("display.idle_message")
+            .cloned()
+            .unwrap_or(display_defaults.idle_message),
+    };
+
+    let scale_defaults = ScaleSettings::default();
+    let scale = ScaleSettings {
+        enabled: map.get("scale.enabled").unwrap_or(&"0".to_string()) == "1",
+        port: map.get("scale.port").cloned().unwrap_or_default(),
+        baud_rate: map
+            .get("scale.baud_rate")
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(scale_defaults.baud_rate),
+        protocol: map
+            .get("scale.protocol")
+            .cloned()
+            .unwrap_or(scale_defaults.protocol),
+        poll_command: map
+            .get("scale.poll_command")
+            .cloned()
+            .unwrap_or(scale_defaults.poll_command),
+    };
+
+    let scale_barcode = scale_barcode_settings(&map);
+
     let app = AppSettings {
         company,
         receipt,
         tax,
+        drawer,
+        label,
+        kitchen,
+        display,
+        scale,
+        scale_barcode,
         low_stock_threshold: map
             .get("app.low_stock_threshold")
             .unwrap_or(&"5".to_string())
             .parse()
             .unwrap_or(5),
+        expiry_warning_days: map
+            .get("app.expiry_warning_days")
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(30),
+        location_id: map.get("app.location_id").and_then(|v| v.parse().ok()),
         printer_port: map.get("app.printer_port").cloned().unwrap_or_default(),
         timezone: map
             .get("app.timezone")
@@ -255,6 +295,18 @@ pub async fn save_settings(
 ) -> Result<(), String> {
     crate::auth::guard::validate_admin(&state, &session_token)?;
 
+    for rule in &payload.scale_barcode.rules {
+        let value_len = 12usize
+            .saturating_sub(rule.prefix.len() + rule.plu_length + usize::from(rule.value_check_digit));
+        if rule.prefix.is_empty()
+            || !rule.prefix.bytes().all(|b| b.is_ascii_digit())
+            || rule.plu_length == 0
+            || value_len == 0
+        {
+            return Err(format!("Aturan barcode timbangan prefix '{}' tidak valid", rule.prefix));
+        }
+    }
+
     let kvs = vec![
         // Company
         ("company.store_name", payload.company.store_name),
@@ -273,13 +325,52 @@ pub async fn save_settings(
         ("receipt.show_discount_detail", bool_to_db(payload.receipt.show_discount_detail).to_string()),
         ("receipt.paper_width", payload.receipt.paper_width),
         ("receipt.copies", payload.receipt.copies.to_string()),
+        ("receipt.qr_mode", payload.receipt.qr_mode),
+        ("receipt.qr_link_template", payload.receipt.qr_link_template),
         // Tax
         ("tax.is_enabled", bool_to_db(payload.tax.is_enabled).to_string()),
         ("tax.rate", payload.tax.rate.to_string()),
         ("tax.label", payload.tax.label),
         ("tax.is_included", bool_to_db(payload.tax.is_included).to_string()),
+        // Cash drawer
+        ("drawer.enabled", bool_to_db(payload.drawer.enabled).to_string()),
+        ("drawer.open_on_cash", bool_to_db(payload.drawer.open_on_cash).to_string()),
+        ("drawer.pin", if payload.drawer.pin == 5 { "5" } else { "2" }.to_string()),
+        ("drawer.pulse_on_ms", payload.drawer.pulse_on_ms.to_string()),
+        ("drawer.pulse_off_ms", payload.drawer.pulse_off_ms.to_string()),
+        // Label printer
+        ("label.printer_port", payload.label.printer_port),
+        ("label.language", payload.label.language),
+        ("label.size", payload.label.size),
+        ("label.dpi", payload.label.dpi.to_string()),
+        ("label.gap_mm", payload.label.gap_mm.to_string()),
+        // Tiket dapur/bar
+        ("kitchen.auto_print", bool_to_db(payload.kitchen.auto_print).to_string()),
+        // Pole display
+        ("display.enabled", bool_to_db(payload.display.enabled).to_string()),
+        ("display.port", payload.display.port),
+        ("display.baud_rate", payload.display.baud_rate.to_string()),
+        ("display.protocol", payload.display.protocol),
+        ("display.idle_message", payload.display.idle_message),
+        // Timbangan
+        ("scale.enabled", bool_to_db(payload.scale.enabled).to_string()),
+        ("scale.port", payload.scale.port),
+        ("scale.baud_rate", payload.scale.baud_rate.to_string()),
+        ("scale.protocol", payload.scale.protocol),
+        ("scale.poll_command", payload.scale.poll_command),
+        // Barcode timbangan
+        ("scale_barcode.enabled", bool_to_db(payload.scale_barcode.enabled).to_string()),
+        (
+            "scale_barcode.rules",
+            serde_json::to_string(&payload.scale_barcode.rules).map_err(|e| e.to_string())?,
+        ),
         // App
         ("app.low_stock_threshold", payload.low_stock_threshold.to_string()),
+        ("app.expiry_warning_days", payload.expiry_warning_days.max(0).to_string()),
+        (
+            "app.location_id",
+            payload.location_id.map(|id| id.to_string()).unwrap_or_default(),
+        ),
         ("app.printer_port", payload.printer_port),
         ("app.timezone", payload.timezone),
     ];
@@ -605,32 +696,81 @@ pub async fn list_serial_ports(
 pub async fn print_receipt_windows(
     state: tauri::State<'_, AppState>,
     session_token: String,
-    _html_content: String,
-    _printer_name: Option<String>,
+    transaction_id: String,
+    html_content: String,
+    printer_name: Option<String>,
 ) -> Result<(), String> {
-    crate::auth::guard::validate_session(&state, &session_token)?;
+    let session = crate::auth::guard::validate_session(&state, &session_token)?;
 
     #[cfg(target_os = "windows")]
     {
-        print_via_windows_html(&_html_content, _printer_name.as_deref()).await
+        let settings = load_settings(&state.db).await?;
+        let mut data = load_receipt_data(&state.db, &transaction_id, &settings).await?;
+        data.reprint_number = mark_receipt_printed(&state.db, &transaction_id, false).await?;
+        let html_content = match data.reprint_number {
+            Some(n) => format!(
+                "<div style=\"text-align:center;font-weight:900;font-size:16px\">SALINAN/COPY<br/>Cetak ulang ke-{}</div>{}",
+                n, html_content
+            ),
+            None => html_content,
+        };
+        print_via_windows_html(&html_content, printer_name.as_deref()).await?;
+        log_receipt_reprint(&state.db, session.user_id, &data, "WINDOWS", None).await;
+        Ok(())
     }
 
     #[cfg(not(target_os = "windows"))]
     {
+        let _ = (session, transaction_id, html_content, printer_name);
         Err("Windows printing hanya tersedia di Windows. Untuk printer biasa, gunakan browser print (Ctrl+P).".into())
     }
 }
 
-/// Export receipt to PDF file
+/// Export struk/invoice transaksi ke file PDF (dibuat langsung di Rust).
+/// `layout`: "RECEIPT" (lebar kertas thermal, default) atau "INVOICE" (A4
+/// dengan NPWP toko). Mengembalikan path file.
 #[tauri::command]
 pub async fn export_receipt_pdf(
     app_handle: tauri::AppHandle,
     state: tauri::State<'_, AppState>,
     session_token: String,
-    html_content: String,
     transaction_id: String,
+    layout: Option<String>,
+    customer: Option<InvoiceCustomer>,
 ) -> Result<String, String> {
-    crate::auth::guard::validate_session(&state, &session_token)?;
+    let session = crate::auth::guard::validate_session(&state, &session_token)?;
+
+    let settings = load_settings(&state.db).await?;
+    let mut data = load_receipt_data(&state.db, &transaction_id, &settings).await?;
+    let is_invoice = layout.as_deref() == Some("INVOICE");
+    // Faktur pajak dokumen terpisah; PDF struk ikut aturan asli/SALINAN
+    if !is_invoice {
+        data.reprint_number = mark_receipt_printed(&state.db, &transaction_id, false).await?;
+    }
+
+    let pdf = if is_invoice {
+        let logo = if settings.company.logo_path.is_empty() {
+            None
+        } else {
+            load_logo(&settings.company.logo_path, INVOICE_LOGO_DOTS).ok()
+        };
+        tax_invoice_pdf(
+            &settings.company,
+            &settings.tax,
+            &data,
+            customer.as_ref(),
+            logo.as_ref(),
+        )
+    } else {
+        let logo = receipt_logo(&settings);
+        let template = ReceiptTemplate {
+            company: &settings.company,
+            receipt: &settings.receipt,
+            tax: &settings.tax,
+            logo: logo.as_ref(),
+        };
+        receipt_pdf(&template, &data)
+    };
 
     // Get documents directory
     let docs_dir = app_handle
@@ -644,142 +784,273 @@ pub async fn export_receipt_pdf(
 
     let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
     let tx_prefix = transaction_id.split('-').next().unwrap_or("TX");
-
-    // Generate HTML with instructions (for interactive use)
-    let filename = format!("Struk_{}_{}.pdf.html", tx_prefix, timestamp);
+    let filename = format!(
+        "{}_{}_{}.pdf",
+        if is_invoice { "Invoice" } else { "Struk" },
+        tx_prefix,
+        timestamp
+    );
     let file_path = receipts_dir.join(&filename);
-    let full_html = generate_receipt_html(&html_content, &transaction_id, true);
-    std::fs::write(&file_path, &full_html)
+    std::fs::write(&file_path, &pdf)
         .map_err(|e| format!("Gagal simpan file: {}", e))?;
+    log_receipt_reprint(&state.db, session.user_id, &data, "PDF", None).await;
 
-    // Generate plain HTML (for batch printing)
-    let plain_filename = format!("Struk_{}_{}_plain.html", tx_prefix, timestamp);
-    let plain_path = receipts_dir.join(&plain_filename);
-    let plain_html = generate_receipt_html(&html_content, &transaction_id, false);
-    std::fs::write(&plain_path, &plain_html)
-        .map_err(|e| format!("Gagal simpan file plain: {}", e))?;
-
-    Ok(format!("File tersimpan di:\n\n1. {}\n   (dengan panduan print)\n\n2. {}\n   (tanpa panduan, siap print)", 
-        file_path.to_string_lossy(), 
-        plain_path.to_string_lossy()))
+    Ok(file_path.to_string_lossy().to_string())
 }
 
-/// Print receipt via ESC/POS (thermal printer)
+/// Print receipt via ESC/POS (thermal printer). Struk masuk antrian cetak
+/// sehingga tetap tercetak setelah printer kembali online; mengembalikan id job.
 #[tauri::command]
 pub async fn print_receipt(
     state: tauri::State<'_, AppState>,
     session_token: String,
     transaction_id: String,
-) -> Result<(), String> {
-    crate::auth::guard::validate_session(&state, &session_token)?;
-    
-    // Ambil printer port dari settings
-    let port = get_printer_port(&state).await?;
-    if port.is_empty() {
-        return Err("Printer belum dikonfigurasi. Silakan atur di Settings → Hardware.".into());
-    }
-
-    // Ambil data transaksi
-    let tx: Option<(String, f64, f64, f64, String, String)> = sqlx::query_as(
-        "SELECT id, total_amount, discount_amount, tax_amount, payment_method, timestamp FROM transactions WHERE id = ?"
-    )
-    .bind(&transaction_id)
-    .fetch_optional(&state.db)
-    .await
-    .map_err(|e| e.to_string())?;
-
-    let tx = tx.ok_or("Transaksi tidak ditemukan")?;
-
-    // Ambil item transaksi
-    let items: Vec<(String, i64, f64, f64)> = sqlx::query_as(
-        "SELECT p.name, ti.quantity, ti.price_at_time, ti.subtotal FROM transaction_items ti JOIN products p ON ti.product_id = p.id WHERE ti.transaction_id = ?"
-    )
-    .bind(&transaction_id)
-    .fetch_all(&state.db)
-    .await
-    .map_err(|e| e.to_string())?;
+) -> Result<i64, String> {
+    let session = crate::auth::guard::validate_session(&state, &session_token)?;
+    enqueue_receipt(&state.db, &transaction_id, session.user_id, false).await
+}
 
-    // Ambil settings toko
-    let store_name = get_setting(&state, "company.store_name").await.unwrap_or("TOKO".to_string());
-    let address = get_setting(&state, "company.address").await.unwrap_or_default();
-    let footer = get_setting(&state, "receipt.footer_text").await.unwrap_or("Terima Kasih!".to_string());
+/// Cetak ulang struk transaksi lama dengan banner "SALINAN/COPY" dan nomor
+/// cetak ulang. Transaksi VOID tetap bisa dicetak dengan tanda VOID.
+/// Setiap cetak ulang dicatat di activity log; mengembalikan id job.
+#[tauri::command]
+pub async fn reprint_receipt(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    transaction_id: String,
+) -> Result<i64, String> {
+    let session = crate::auth::guard::validate_session(&state, &session_token)?;
+    enqueue_receipt_reprint(&state.db, &transaction_id, session.user_id).await
+}
 
-    // Build ESC/POS receipt
-    let mut esc: Vec<u8> = Vec::with_capacity(1024);
+/// Antrikan struk bertanda SALINAN, naikkan `reprint_count`, dan catat
+/// REPRINT_RECEIPT. Dipakai semua jalur cetak ulang struk.
+pub async fn enqueue_receipt_reprint(
+    db: &sqlx::SqlitePool,
+    transaction_id: &str,
+    user_id: i64,
+) -> Result<i64, String> {
+    enqueue_receipt(db, transaction_id, user_id, true).await
+}
 
-    // Init printer
-    esc.extend_from_slice(ESC_POS_INIT);
+/// Antrikan struk ESC/POS. Struk asli hanya sekali; selain itu (atau jika
+/// `copy`) dicetak sebagai SALINAN lewat `mark_receipt_printed`.
+async fn enqueue_receipt(
+    db: &sqlx::SqlitePool,
+    transaction_id: &str,
+    user_id: i64,
+    copy: bool,
+) -> Result<i64, String> {
+    let settings = load_settings(db).await?;
+    if settings.printer_port.is_empty() {
+        return Err("Printer belum dikonfigurasi. Silakan atur di Settings → Hardware.".into());
+    }
 
-    // Center align
-    esc.extend_from_slice(ESC_POS_CENTER);
+    let mut data = load_receipt_data(db, transaction_id, &settings).await?;
+    data.reprint_number = mark_receipt_printed(db, transaction_id, copy).await?;
 
-    // Bold + Double height for store name
-    esc.extend_from_slice(ESC_POS_BOLD_ON);
-    esc.extend_from_slice(ESC_POS_DOUBLE_WIDTH);
-    esc.extend_from_slice(store_name.as_bytes());
-    esc.push(b'\n');
+    let logo = receipt_logo(&settings);
+    let template = ReceiptTemplate {
+        company: &settings.company,
+        receipt: &settings.receipt,
+        tax: &settings.tax,
+        logo: logo.as_ref(),
+    };
+    let esc = template.render_escpos(&data);
+
+    let job_id = crate::commands::print_job_cmd::enqueue_print_job(
+        db,
+        "RECEIPT",
+        Some(transaction_id),
+        &settings.printer_port,
+        &esc,
+        Some(user_id),
+    )
+    .await?;
 
-    // Reset size
-    esc.extend_from_slice(ESC_POS_NORMAL);
-    esc.extend_from_slice(ESC_POS_BOLD_OFF);
+    log_receipt_reprint(db, user_id, &data, "PRINTER", Some(job_id)).await;
+    Ok(job_id)
+}
 
-    if !address.is_empty() {
-        esc.extend_from_slice(address.as_bytes());
-        esc.push(b'\n');
+/// Tandai struk transaksi sudah dicetak. Cetakan pertama (lewat jalur apa
+/// pun) adalah struk asli → `None`; berikutnya, atau jika `copy`, menaikkan
+/// `reprint_count` dan mengembalikan nomornya. Status disimpan di transaksi,
+/// bukan di print_jobs, supaya tidak ter-reset saat job dihapus.
+async fn mark_receipt_printed(
+    db: &sqlx::SqlitePool,
+    transaction_id: &str,
+    copy: bool,
+) -> Result<Option<i64>, String> {
+    if !copy {
+        let claimed = sqlx::query(
+            "UPDATE transactions SET receipt_printed_at = CURRENT_TIMESTAMP
+             WHERE id = ? AND receipt_printed_at IS NULL",
+        )
+        .bind(transaction_id)
+        .execute(db)
+        .await
+        .map_err(|e| e.to_string())?;
+        if claimed.rows_affected() > 0 {
+            return Ok(None);
+        }
     }
 
-    // Separator
-    esc.extend_from_slice(b"================================\n");
-
-    // Left align for items
-    esc.extend_from_slice(ESC_POS_LEFT);
-
-    // Transaction info
-    esc.extend_from_slice(format!("No: {}\n", &tx.0[..8.min(tx.0.len())]).as_bytes());
-    esc.extend_from_slice(format!("Tgl: {}\n", tx.5).as_bytes());
-    esc.extend_from_slice(b"--------------------------------\n");
-
-    // Items
-    for (name, qty, price, subtotal) in &items {
-        let short_name: String = name.chars().take(20).collect();
-        esc.extend_from_slice(format!("{} x{}\n", short_name, qty).as_bytes());
-        esc.extend_from_slice(format!("  @{:>10} = {:>10}\n",
-            format_number(*price as i64),
-            format_number(*subtotal as i64),
-        ).as_bytes());
-    }
+    let (reprint_number,): (i64,) = sqlx::query_as(
+        "UPDATE transactions
+         SET reprint_count = reprint_count + 1,
+             receipt_printed_at = COALESCE(receipt_printed_at, CURRENT_TIMESTAMP)
+         WHERE id = ? RETURNING reprint_count",
+    )
+    .bind(transaction_id)
+    .fetch_optional(db)
+    .await
+    .map_err(|e| e.to_string())?
+    .ok_or("Transaksi tidak ditemukan")?;
+    Ok(Some(reprint_number))
+}
 
-    esc.extend_from_slice(b"--------------------------------\n");
+/// Catat REPRINT_RECEIPT untuk struk bertanda SALINAN. `channel`: PRINTER,
+/// PDF, atau WINDOWS.
+async fn log_receipt_reprint(
+    db: &sqlx::SqlitePool,
+    user_id: i64,
+    data: &ReceiptData,
+    channel: &str,
+    print_job_id: Option<i64>,
+) {
+    let Some(reprint_number) = data.reprint_number else {
+        return;
+    };
+    let metadata = serde_json::json!({
+        "transaction_id": data.transaction_id,
+        "reprint_number": reprint_number,
+        "is_void": data.is_void,
+        "total_amount": data.total_amount,
+        "channel": channel,
+        "print_job_id": print_job_id,
+    })
+    .to_string();
+    crate::commands::activity_cmd::log_activity(
+        db,
+        None,
+        Some(user_id),
+        "REPRINT_RECEIPT",
+        &format!(
+            "Cetak ulang struk {} (ke-{}){}",
+            data.transaction_id.chars().take(8).collect::<String>().to_uppercase(),
+            reprint_number,
+            if data.is_void { " — transaksi VOID" } else { "" }
+        ),
+        Some(&metadata),
+    ).await;
+}
 
-    // Totals
-    if tx.2 > 0.0 {
-        esc.extend_from_slice(format!("Diskon:     {:>10}\n", format_number(tx.2 as i64)).as_bytes());
+/// Konversi logo toko ke raster jika `receipt.show_logo` aktif.
+/// Logo yang gagal dibaca tidak menggagalkan cetak struk.
+fn receipt_logo(settings: &AppSettings) -> Option<crate::printer::raster::RasterImage> {
+    if !settings.receipt.show_logo || settings.company.logo_path.is_empty() {
+        return None;
     }
-    if tx.3 > 0.0 {
-        esc.extend_from_slice(format!("Pajak:      {:>10}\n", format_number(tx.3 as i64)).as_bytes());
+    match load_logo(&settings.company.logo_path, paper_dots(&settings.receipt.paper_width)) {
+        Ok(raster) => Some(raster),
+        Err(e) => {
+            eprintln!("[PRINTER] Logo dilewati: {}", e);
+            None
+        }
     }
-    esc.extend_from_slice(b"\x1B\x45\x01"); // Bold
-    esc.extend_from_slice(format!("TOTAL:      {:>10}\n", format_number(tx.1 as i64)).as_bytes());
-    esc.extend_from_slice(b"\x1B\x45\x00"); // Bold off
-    esc.extend_from_slice(format!("Bayar ({:>4}): {:>10}\n", tx.4, format_number(tx.1 as i64)).as_bytes());
-
-    esc.extend_from_slice(b"================================\n");
-
-    // Footer (center)
-    esc.extend_from_slice(ESC_POS_CENTER);
-    esc.extend_from_slice(footer.as_bytes());
-    esc.push(b'\n');
-    esc.push(b'\n');
+}
 
-    // Cut paper (thermal)
-    esc.extend_from_slice(ESC_POS_CUT);
-    esc.extend_from_slice(b"\x03"); // Partial cut
-    esc.extend_from_slice(b"\n\n\n");
+/// Tentukan isi QR struk sesuai `receipt.qr_mode`
+async fn receipt_qr_payload(
+    db: &sqlx::SqlitePool,
+    transaction_id: &str,
+    qris_reference: Option<&str>,
+    settings: &AppSettings,
+) -> Result<Option<String>, String> {
+    match settings.receipt.qr_mode.as_str() {
+        "TRANSACTION" => {
+            let template = if settings.receipt.qr_link_template.trim().is_empty() {
+                "{id}"
+            } else {
+                settings.receipt.qr_link_template.trim()
+            };
+            Ok(Some(template.replace("{id}", transaction_id)))
+        }
+        "QRIS" => {
+            let qr: Option<(Option<String>,)> = sqlx::query_as(
+                "SELECT qr_string FROM qris_payments
+                 WHERE transaction_id = ? OR order_id = ?
+                 ORDER BY id DESC LIMIT 1"
+            )
+            .bind(transaction_id)
+            .bind(qris_reference.unwrap_or_default())
+            .fetch_optional(db)
+            .await
+            .map_err(|e| e.to_string())?;
+            Ok(qr.and_then(|r| r.0))
+        }
+        _ => Ok(None),
+    }
+}
 
-    // Kirim ke printer
-    send_to_printer(&port, &esc).await?;
+/// Ambil transaksi + item dalam bentuk data struk
+async fn load_receipt_data(
+    db: &sqlx::SqlitePool,
+    transaction_id: &str,
+    settings: &AppSettings,
+) -> Result<ReceiptData, String> {
+    let tx = sqlx::query_as::<_, TransactionWithCashier>(
+        "SELECT t.*, COALESCE(u.name, '-') as cashier_name
+         FROM transactions t
+         LEFT JOIN users u ON t.cashier_id = u.id
+         WHERE t.id = ?",
+    )
+    .bind(transaction_id)
+    .fetch_optional(db)
+    .await
+    .map_err(|e| e.to_string())?
+    .ok_or("Transaksi tidak ditemukan")?;
+
+    let items: Vec<(String, Quantity, f64, f64, f64)> = sqlx::query_as(
+        "SELECT p.name || CASE WHEN ti.unit_conversion != 1 THEN ' (' || ti.unit || ')' ELSE '' END,
+                ti.quantity, ti.price_at_time, ti.discount_amount, ti.subtotal
+         FROM transaction_items ti
+         JOIN products p ON ti.product_id = p.id
+         WHERE ti.transaction_id = ?
+         ORDER BY ti.id ASC"
+    )
+    .bind(transaction_id)
+    .fetch_all(db)
+    .await
+    .map_err(|e| e.to_string())?;
 
-    Ok(())
+    let qr_payload =
+        receipt_qr_payload(db, transaction_id, tx.qris_reference.as_deref(), settings).await?;
+
+    Ok(ReceiptData {
+        transaction_id: tx.id,
+        timestamp: tx.timestamp.unwrap_or_default(),
+        cashier_name: Some(tx.cashier_name),
+        items: items
+            .into_iter()
+            .map(|(name, quantity, price, discount_amount, subtotal)| ReceiptItem {
+                name,
+                quantity: quantity.0,
+                price,
+                discount_amount,
+                subtotal,
+            })
+            .collect(),
+        discount_amount: tx.discount_amount,
+        tax_amount: tx.tax_amount,
+        total_amount: tx.total_amount,
+        payment_method: tx.payment_method,
+        amount_paid: tx.amount_paid,
+        change_given: tx.change_given,
+        qr_payload,
+        reprint_number: None,
+        is_void: tx.status == "VOID",
+    })
 }
 
 /// Test printer koneksi
@@ -819,16 +1090,23 @@ pub async fn test_print(
     Ok(())
 }
 
-/// Cetak label barcode via printer thermal (ESC/POS native barcode)
+/// Cetak label barcode. Memakai printer label (TSPL/ZPL) jika dikonfigurasi
+/// di Settings → Hardware, selain itu printer struk (ESC/POS native barcode).
 #[tauri::command]
 pub async fn print_barcode_labels(
     state: tauri::State<'_, AppState>,
     session_token: String,
     labels: Vec<BarcodeLabelItem>,
 ) -> Result<(), String> {
-    crate::auth::guard::validate_admin(&state, &session_token)?;
+    let session = crate::auth::guard::validate_admin(&state, &session_token)?;
 
-    let port = get_printer_port(&state).await?;
+    let settings = load_settings(&state.db).await?;
+    let label = &settings.label;
+    let port = if label.printer_port.is_empty() {
+        settings.printer_port.clone()
+    } else {
+        label.printer_port.clone()
+    };
     if port.is_empty() {
         return Err("Printer belum dikonfigurasi. Silakan atur di Settings → Hardware.".into());
     }
@@ -837,71 +1115,216 @@ pub async fn print_barcode_labels(
         return Err("Tidak ada label untuk dicetak.".into());
     }
 
-    let mut esc: Vec<u8> = Vec::with_capacity(labels.len() * 200);
+    // Printer struk tidak mengerti TSPL/ZPL
+    let language = if label.printer_port.is_empty() {
+        LabelLanguage::EscPos
+    } else {
+        LabelLanguage::parse(&label.language)
+    };
+    let size = LabelSize::parse(&label.size);
+    let data = match language {
+        LabelLanguage::Tspl => tspl_labels(&labels, size, label.dpi, label.gap_mm),
+        LabelLanguage::Zpl => zpl_labels(&labels, size, label.dpi),
+        LabelLanguage::EscPos => escpos_labels(&labels),
+    };
 
-    // Init printer
-    esc.extend_from_slice(ESC_POS_INIT);
+    crate::commands::print_job_cmd::enqueue_print_job(
+        &state.db,
+        "LABEL",
+        None,
+        &port,
+        &data,
+        Some(session.user_id),
+    )
+    .await?;
 
-    for label in &labels {
-        for _ in 0..label.qty {
-            // Center align
-            esc.extend_from_slice(ESC_POS_CENTER);
-
-            // Product name (bold)
-            esc.extend_from_slice(ESC_POS_BOLD_ON);
-            let name: String = label.name.chars().take(24).collect();
-            esc.extend_from_slice(name.as_bytes());
-            esc.push(b'\n');
-            esc.extend_from_slice(ESC_POS_BOLD_OFF);
-
-            // Price
-            let price_str = format!("Rp {}", format_number(label.price as i64));
-            esc.extend_from_slice(price_str.as_bytes());
-            esc.push(b'\n');
-
-            // Barcode settings
-            esc.extend_from_slice(b"\x1D\x48\x02"); // GS H 2 — HRI below barcode
-            esc.extend_from_slice(b"\x1D\x68\x50"); // GS h 80 — Barcode height 80 dots
-            esc.extend_from_slice(b"\x1D\x77\x02"); // GS w 2 — Barcode width multiplier
-
-            // Print barcode
-            let barcode = &label.barcode;
-            if barcode.len() == 13 {
-                // EAN-13
-                esc.extend_from_slice(b"\x1D\x6B\x43"); // GS k C (CODE 67 = EAN13)
-                esc.push(barcode.len() as u8);
-                esc.extend_from_slice(barcode.as_bytes());
-            } else {
-                // Code128 for arbitrary length
-                esc.extend_from_slice(b"\x1D\x6B\x49"); // GS k I (CODE 73 = CODE128)
-                esc.push(barcode.len() as u8);
-                esc.extend_from_slice(barcode.as_bytes());
-            }
-            esc.push(b'\n');
+    eprintln!("[PRINTER] Barcode labels queued: {} items ({:?})", labels.len(), language);
+    Ok(())
+}
 
-            // Separator between labels
-            esc.extend_from_slice(b"--------------------------------\n");
-        }
+/// Export label barcode ke PDF lembar stiker A4 (untuk printer kantor).
+/// Ukuran stiker mengikuti `label.size`. Mengembalikan path file.
+#[tauri::command]
+pub async fn export_label_sheet_pdf(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    labels: Vec<BarcodeLabelItem>,
+) -> Result<String, String> {
+    crate::auth::guard::validate_admin(&state, &session_token)?;
+
+    if labels.is_empty() {
+        return Err("Tidak ada label untuk dicetak.".into());
     }
 
-    // Feed and cut
-    esc.extend_from_slice(b"\n\n");
-    esc.extend_from_slice(ESC_POS_CUT);
-    esc.extend_from_slice(b"\x03"); // Partial cut
-    esc.extend_from_slice(b"\n\n\n");
+    let settings = load_settings(&state.db).await?;
+    let pdf = label_sheet_pdf(&labels, LabelSize::parse(&settings.label.size));
 
-    send_to_printer(&port, &esc).await?;
+    let docs_dir = app_handle
+        .path()
+        .document_dir()
+        .map_err(|e| format!("Gagal akses folder Documents: {}", e))?;
+    let labels_dir = docs_dir.join("POS-Kasir-Labels");
+    std::fs::create_dir_all(&labels_dir)
+        .map_err(|e| format!("Gagal buat folder: {}", e))?;
+
+    let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
+    let file_path = labels_dir.join(format!("Label_{}.pdf", timestamp));
+    std::fs::write(&file_path, &pdf)
+        .map_err(|e| format!("Gagal simpan file: {}", e))?;
+
+    Ok(file_path.to_string_lossy().to_string())
+}
+
+/// Buka laci kas tanpa transaksi ("no sale"). Dicatat di activity log.
+#[tauri::command]
+pub async fn open_cash_drawer(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    reason: Option<String>,
+) -> Result<(), String> {
+    let session = crate::auth::guard::validate_session(&state, &session_token)?;
+
+    let settings = load_settings(&state.db).await?;
+    if !settings.drawer.enabled {
+        return Err("Laci kas belum diaktifkan. Silakan atur di Settings → Hardware.".into());
+    }
+    if settings.printer_port.is_empty() {
+        return Err("Printer belum dikonfigurasi. Silakan atur di Settings → Hardware.".into());
+    }
+
+    kick_drawer(&settings).await?;
+
+    let reason = reason.map(|r| r.trim().to_string()).filter(|r| !r.is_empty());
+    let metadata = serde_json::json!({ "reason": reason }).to_string();
+    crate::commands::activity_cmd::log_activity(
+        &state.db,
+        None,
+        Some(session.user_id),
+        "NO_SALE",
+        &format!(
+            "Membuka laci kas tanpa transaksi{}",
+            reason.as_ref().map(|r| format!(": {}", r)).unwrap_or_default()
+        ),
+        Some(&metadata),
+    ).await;
 
-    eprintln!("[PRINTER] Barcode labels printed: {} items", labels.len());
     Ok(())
 }
 
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
-pub struct BarcodeLabelItem {
-    pub name: String,
-    pub price: f64,
-    pub barcode: String,
-    pub qty: u32,
+/// Buka laci kas setelah transaksi tunai (jika diaktifkan di settings).
+/// Kegagalan hanya dicatat di log — transaksi tetap sah.
+pub async fn kick_drawer_after_sale(db: &sqlx::SqlitePool) {
+    let settings = match load_settings(db).await {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("[DRAWER] Gagal baca settings: {}", e);
+            return;
+        }
+    };
+    if !settings.drawer.enabled || !settings.drawer.open_on_cash || settings.printer_port.is_empty() {
+        return;
+    }
+    if let Err(e) = kick_drawer(&settings).await {
+        eprintln!("[DRAWER] Gagal membuka laci kas: {}", e);
+    }
+}
+
+/// Kirim pulse `ESC p` ke printer. Status kertas tidak dicek — laci tetap
+/// harus bisa dibuka walau kertas habis.
+async fn kick_drawer(settings: &AppSettings) -> Result<(), String> {
+    let mut esc = EscPosBuilder::new();
+    esc.drawer_kick(
+        settings.drawer.pin,
+        settings.drawer.pulse_on_ms,
+        settings.drawer.pulse_off_ms,
+    );
+    deliver_to_printer(&settings.printer_port, &esc.build(), false).await
+}
+
+/// Cek status printer (kertas, cover, online) — hanya untuk printer serial/network
+#[tauri::command]
+pub async fn get_printer_status(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+) -> Result<PrinterStatus, String> {
+    crate::auth::guard::validate_session(&state, &session_token)?;
+
+    let port = get_printer_port(&state).await?;
+    if port.is_empty() {
+        return Err("Printer belum dikonfigurasi. Silakan atur di Settings → Hardware.".into());
+    }
+
+    query_printer_status(&port).await
+}
+
+/// Tampilkan layar di pole display pelanggan (item terakhir, total, kembalian,
+/// atau pesan idle). Diabaikan jika display tidak diaktifkan.
+#[tauri::command]
+pub async fn update_customer_display(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    screen: DisplayScreen,
+) -> Result<(), String> {
+    crate::auth::guard::validate_session(&state, &session_token)?;
+
+    let settings = load_settings(&state.db).await?;
+    if !settings.display.enabled || settings.display.port.is_empty() {
+        return Ok(());
+    }
+    write_customer_display(&settings.display, &screen)
+}
+
+/// Uji pole display dengan pesan idle — tetap dikirim walau belum diaktifkan
+#[tauri::command]
+pub async fn test_customer_display(
+    state: tauri::State<'_, AppState>,
+    session_token: String,
+    display: DisplaySettings,
+) -> Result<(), String> {
+    crate::auth::guard::validate_session(&state, &session_token)?;
+
+    if display.port.is_empty() {
+        return Err("Port display belum diisi".into());
+    }
+    write_customer_display(&display, &DisplayScreen::Idle)
+}
+
+/// Tampilkan kembalian setelah transaksi. Kegagalan hanya dicatat di log.
+pub async fn show_change_on_display(db: &sqlx::SqlitePool, paid: f64, change: f64) {
+    let settings = match load_settings(db).await {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("[DISPLAY] Gagal baca settings: {}", e);
+            return;
+        }
+    };
+    if !settings.display.enabled || settings.display.port.is_empty() {
+        return;
+    }
+    if let Err(e) = write_customer_display(&settings.display, &DisplayScreen::Change { paid, change }) {
+        eprintln!("[DISPLAY] Gagal menampilkan kembalian: {}", e);
+    }
+}
+
+/// Kirim satu layar ke display lewat serial dengan baud rate dari settings
+/// (display tidak memakai deteksi baud rate seperti printer).
+fn write_customer_display(display: &DisplaySettings, screen: &DisplayScreen) -> Result<(), String> {
+    use std::io::Write;
+    use std::time::Duration;
+
+    let (line1, line2) = screen_lines(screen, &display.idle_message);
+    let data = display_frame(DisplayProtocol::parse(&display.protocol), &line1, &line2);
+
+    let device_path = display.port.trim_start_matches("serial:");
+    let mut port = serialport::new(device_path, display.baud_rate)
+        .timeout(Duration::from_secs(2))
+        .open()
+        .map_err(|e| format!("Gagal membuka display {}: {}", device_path, e))?;
+    port.write_all(&data)
+        .map_err(|e| format!("Gagal kirim data ke display: {}", e))?;
+    port.flush()
+        .map_err(|e| format!("Gagal flush data display: {}", e))
 }
 
 // ──────── Helper Functions ────────
@@ -1087,18 +1510,14 @@ async fn get_printer_port(state: &tauri::State<'_, AppState>) -> Result<String,
     Ok(result.map(|r| r.0).unwrap_or_default())
 }
 
-async fn get_setting(state: &tauri::State<'_, AppState>, key: &str) -> Option<String> {
-    let result: Option<(String,)> = sqlx::query_as(
-        "SELECT value FROM settings WHERE key = ?"
-    )
-    .bind(key)
-    .fetch_optional(&state.db)
-    .await
-    .ok()?;
-    result.map(|r| r.0)
+async fn send_to_printer(port: &str, data: &[u8]) -> Result<(), String> {
+    deliver_to_printer(port, data, true).await
 }
 
-async fn send_to_printer(port: &str, data: &[u8]) -> Result<(), String> {
+/// Kirim data ke printer. Jika `check_status` aktif dan transport bisa dibaca
+/// (serial/network), status `DLE EOT` dicek dulu sehingga kertas habis,
+/// cover terbuka, atau offline dilaporkan sebelum mencetak.
+pub(crate) async fn deliver_to_printer(port: &str, data: &[u8], check_status: bool) -> Result<(), String> {
     eprintln!("[PRINTER] Sending {} bytes to port: {}", data.len(), port);
     
     // Route 1: CUPS printer (Linux/macOS)
@@ -1129,17 +1548,14 @@ async fn send_to_printer(port: &str, data: &[u8]) -> Result<(), String> {
     
     // Route 3: Network printer (TCP/IP)
     if port.starts_with("network:") {
-        let addr = port.trim_start_matches("network:").trim();
-        if !addr.contains(':') {
-            return Err(format!("Format alamat network salah: '{}'. Gunakan IP:PORT (contoh: 192.168.1.100:9100)", addr));
-        }
-        return send_via_network(addr, data).await;
+        let addr = network_addr(port)?;
+        return send_via_network(addr, data, check_status).await;
     }
     
     // Route 4: Serial port via serialport crate (cross-platform)
     if port.starts_with("serial:") {
         let device_path = port.trim_start_matches("serial:");
-        return send_via_serial(device_path, data);
+        return send_via_serial(device_path, data, check_status);
     }
     
     // Route 5: Direct USB printer class device (Linux /dev/usb/lp*)
@@ -1149,37 +1565,136 @@ async fn send_to_printer(port: &str, data: &[u8]) -> Result<(), String> {
     
     // Route 6: Legacy — try to detect the best method
     // If it looks like an IP:PORT, try network
-    if port.contains(':') && !port.starts_with('/') && !port.starts_with('\\') {
-        return send_via_network(port, data).await;
+    if is_legacy_network(port) {
+        return send_via_network(port, data, check_status).await;
     }
     
     // If it looks like a serial port, try serialport crate
-    if port.starts_with("/dev/tty") || port.starts_with("/dev/cu.") || port.starts_with("COM") {
-        return send_via_serial(port, data);
+    if is_legacy_serial(port) {
+        return send_via_serial(port, data, check_status);
     }
     
     // Fallback: direct file write
     send_via_device_file(port, data).await
 }
 
-/// Send data via TCP network connection
-async fn send_via_network(addr: &str, data: &[u8]) -> Result<(), String> {
-    use tokio::io::AsyncWriteExt;
-    
+/// Ambil alamat IP:PORT dari port `network:...`
+fn network_addr(port: &str) -> Result<&str, String> {
+    let addr = port.trim_start_matches("network:").trim();
+    if !addr.contains(':') {
+        return Err(format!("Format alamat network salah: '{}'. Gunakan IP:PORT (contoh: 192.168.1.100:9100)", addr));
+    }
+    Ok(addr)
+}
+
+fn is_legacy_network(port: &str) -> bool {
+    port.contains(':') && !port.starts_with('/') && !port.starts_with('\\')
+}
+
+fn is_legacy_serial(port: &str) -> bool {
+    port.starts_with("/dev/tty") || port.starts_with("/dev/cu.") || port.starts_with("COM")
+}
+
+/// Query status printer real-time. Transport tanpa jalur baca (CUPS, spooler,
+/// device file) mengembalikan status `unknown`.
+async fn query_printer_status(port: &str) -> Result<PrinterStatus, String> {
+    // Prefix spooler harus dicek sebelum heuristik legacy: `cups:Nama` dan
+    // `winprint:Nama` mengandung ':' sehingga akan dianggap alamat IP:PORT
+    if port.starts_with("cups:") || port.starts_with("winprint:") || port.starts_with("/dev/usb/lp") {
+        return Ok(PrinterStatus::unknown());
+    }
+
+    if port.starts_with("network:") || (!port.starts_with("serial:") && is_legacy_network(port)) {
+        let addr = network_addr(port)?;
+        let mut stream = connect_network(addr).await?;
+        return Ok(query_status_network(&mut stream).await);
+    }
+
+    if port.starts_with("serial:") || is_legacy_serial(port) {
+        let device_path = port.trim_start_matches("serial:");
+        let mut serial = open_serial(device_path)?;
+        return Ok(query_status_serial(serial.as_mut()));
+    }
+
+    Ok(PrinterStatus::unknown())
+}
+
+/// Baca satu byte balasan status dari stream TCP (timeout singkat —
+/// printer yang tidak mendukung `DLE EOT` tidak akan menjawab).
+async fn query_status_network(stream: &mut tokio::net::TcpStream) -> PrinterStatus {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let mut replies = [0u8; 3];
+    for (i, cmd) in [DLE_EOT_PRINTER, DLE_EOT_OFFLINE, DLE_EOT_PAPER].iter().enumerate() {
+        if stream.write_all(cmd).await.is_err() {
+            return PrinterStatus::unknown();
+        }
+        let mut byte = [0u8; 1];
+        match tokio::time::timeout(std::time::Duration::from_millis(500), stream.read_exact(&mut byte)).await {
+            Ok(Ok(_)) => replies[i] = byte[0],
+            _ => return PrinterStatus::unknown(),
+        }
+    }
+    PrinterStatus::from_bytes(replies[0], replies[1], replies[2])
+}
+
+/// Versi serial dari `query_status_network`.
+fn query_status_serial(port: &mut dyn serialport::SerialPort) -> PrinterStatus {
+    use std::time::Duration;
+
+    let previous_timeout = port.timeout();
+    let _ = port.set_timeout(Duration::from_millis(500));
+    let _ = port.clear(serialport::ClearBuffer::Input);
+
+    let mut replies = [0u8; 3];
+    let mut ok = true;
+    for (i, cmd) in [DLE_EOT_PRINTER, DLE_EOT_OFFLINE, DLE_EOT_PAPER].iter().enumerate() {
+        let mut byte = [0u8; 1];
+        if port.write_all(cmd).is_err() || port.read_exact(&mut byte).is_err() {
+            ok = false;
+            break;
+        }
+        replies[i] = byte[0];
+    }
+
+    let _ = port.set_timeout(previous_timeout);
+    if ok {
+        PrinterStatus::from_bytes(replies[0], replies[1], replies[2])
+    } else {
+        PrinterStatus::unknown()
+    }
+}
+
+async fn connect_network(addr: &str) -> Result<tokio::net::TcpStream, String> {
     eprintln!("[PRINTER NET] Connecting to {}...", addr);
     
-    let stream = tokio::net::TcpStream::connect(addr)
-        .await
-        .map_err(|e| format!(
+    let connect = tokio::net::TcpStream::connect(addr);
+    match tokio::time::timeout(std::time::Duration::from_secs(5), connect).await {
+        Ok(Ok(stream)) => Ok(stream),
+        Ok(Err(e)) => Err(format!(
             "Gagal koneksi ke printer network {}!\n\n\
              Pastikan:\n\
              1. IP dan Port benar\n\
              2. Printer menyala dan terhubung ke jaringan\n\
              3. Port tidak diblokir firewall\n\n\
              Error: {}", addr, e
-        ))?;
+        )),
+        Err(_) => Err(format!("Timeout koneksi ke printer network {}. Pastikan printer menyala.", addr)),
+    }
+}
+
+/// Send data via TCP network connection
+async fn send_via_network(addr: &str, data: &[u8], check_status: bool) -> Result<(), String> {
+    use tokio::io::AsyncWriteExt;
+    
+    let mut stream = connect_network(addr).await?;
+
+    if check_status {
+        if let Some(problem) = query_status_network(&mut stream).await.blocking_problem() {
+            return Err(problem);
+        }
+    }
     
-    let mut stream = stream;
     stream.write_all(data).await
         .map_err(|e| format!("Gagal kirim data ke printer: {}", e))?;
     stream.flush().await
@@ -1190,9 +1705,39 @@ async fn send_via_network(addr: &str, data: &[u8]) -> Result<(), String> {
 }
 
 /// Send data via serial port using serialport crate (proper baud rate, cross-platform)
-fn send_via_serial(device_path: &str, data: &[u8]) -> Result<(), String> {
+fn send_via_serial(device_path: &str, data: &[u8], check_status: bool) -> Result<(), String> {
     use std::io::Write;
     use std::time::Duration;
+
+    let mut port = open_serial(device_path)?;
+
+    if check_status {
+        if let Some(problem) = query_status_serial(port.as_mut()).blocking_problem() {
+            return Err(problem);
+        }
+    }
+
+    // Send data in chunks to avoid buffer overflow
+    let chunk_size = 1024;
+    for chunk in data.chunks(chunk_size) {
+        port.write_all(chunk).map_err(|e| {
+            format!("Gagal kirim data serial ke {}: {}", device_path, e)
+        })?;
+        // Small delay between chunks for slow printers
+        std::thread::sleep(Duration::from_millis(10));
+    }
+
+    port.flush().map_err(|e| {
+        format!("Gagal flush data serial: {}", e)
+    })?;
+
+    eprintln!("[PRINTER SERIAL] Sent {} bytes successfully", data.len());
+    Ok(())
+}
+
+/// Buka port serial, mencoba baud rate umum printer thermal secara berurutan
+fn open_serial(device_path: &str) -> Result<Box<dyn serialport::SerialPort>, String> {
+    use std::time::Duration;
     
     eprintln!("[PRINTER SERIAL] Opening {} with serialport crate...", device_path);
     
@@ -1208,25 +1753,9 @@ fn send_via_serial(device_path: &str, data: &[u8]) -> Result<(), String> {
             .timeout(Duration::from_secs(5))
             .open()
         {
-            Ok(mut port) => {
+            Ok(port) => {
                 eprintln!("[PRINTER SERIAL] Connected at {} baud", baud_rate);
-                
-                // Send data in chunks to avoid buffer overflow
-                let chunk_size = 1024;
-                for chunk in data.chunks(chunk_size) {
-                    port.write_all(chunk).map_err(|e| {
-                        format!("Gagal kirim data serial ke {}: {}", device_path, e)
-                    })?;
-                    // Small delay between chunks for slow printers
-                    std::thread::sleep(Duration::from_millis(10));
-                }
-                
-                port.flush().map_err(|e| {
-                    format!("Gagal flush data serial: {}", e)
-                })?;
-                
-                eprintln!("[PRINTER SERIAL] Sent {} bytes successfully", data.len());
-                return Ok(());
+                return Ok(port);
             }
             Err(e) => {
                 last_error = e.to_string();
@@ -2,6 +2,9 @@ use crate::models::transaction::{
     CreateTransactionPayload, PaginatedTransactions, Transaction, TransactionDetail,
     TransactionItemWithProduct, TransactionWithCashier,
 };
+use crate::models::pricing::PriceQuoteItem;
+use crate::commands::location_cmd::{location_available, move_location_stock, till_location};
+use crate::models::quantity::{round_quantity, validate_quantity, Quantity};
 use crate::AppState;
 use std::collections::HashMap;
 
@@ -39,18 +42,109 @@ pub async fn create_transaction(
         .map(|v| v == "1")
         .unwrap_or(false);
 
-    // ── 2. Hitung subtotal items (dengan diskon per-item) ──
+    // ── 2. Harga dihitung server (level pelanggan + grosir), bukan dari klien ──
+    let server_prices = {
+        let mut conn = state.db.acquire().await.map_err(|e| e.to_string())?;
+        let price_level_id =
+            crate::commands::pricing_cmd::customer_price_level(&mut conn, payload.customer_id)
+                .await?;
+        let quote: Vec<PriceQuoteItem> = payload
+            .items
+            .iter()
+            .map(|i| PriceQuoteItem {
+                product_id: i.product_id,
+                quantity: i.quantity,
+                unit_id: i.unit_id,
+                scanned_barcode: i.scanned_barcode.clone(),
+            })
+            .collect();
+        crate::commands::pricing_cmd::price_items(&mut conn, &quote, price_level_id).await?
+    };
+
+    // Harga manual hanya lewat price_override + persetujuan admin
+    let approver = if payload.items.iter().any(|i| i.price_override.is_some()) {
+        if session.role == "ADMIN" {
+            Some((session.user_id, session.username.clone()))
+        } else {
+            let approval = payload
+                .override_approval
+                .as_ref()
+                .ok_or("Ubah harga membutuhkan persetujuan admin")?;
+            let user = crate::commands::auth_cmd::verify_admin_approval(
+                &state.db,
+                &approval.username,
+                &approval.password,
+            )
+            .await?;
+            Some((user.id, user.username))
+        }
+    } else {
+        None
+    };
+
+    let mut prices = Vec::with_capacity(payload.items.len());
+    for (item, &server_price) in payload.items.iter().zip(&server_prices) {
+        match item.price_override {
+            Some(p) if !(p >= 0.0 && p.is_finite()) => {
+                return Err(format!("Harga manual produk id {} tidak valid", item.product_id));
+            }
+            Some(p) => prices.push(p),
+            None => {
+                if let Some(client_price) = item.price_at_time {
+                    if (client_price - server_price).abs() >= 0.5 {
+                        return Err(format!(
+                            "Harga produk id {} sudah berubah ({} → {}), perbarui keranjang",
+                            item.product_id, client_price, server_price
+                        ));
+                    }
+                }
+                prices.push(server_price);
+            }
+        }
+    }
+
+    // ── 3. Hitung subtotal items (dengan diskon per-item) ──
     let mut items_subtotal: f64 = 0.0;
-    for item in &payload.items {
-        let raw = item.price_at_time * item.quantity as f64;
+    for (item, price) in payload.items.iter().zip(&prices) {
+        let raw = price * item.quantity;
+        if !(item.discount_amount >= 0.0 && item.discount_amount <= raw) {
+            return Err(format!("Diskon produk id {} tidak valid", item.product_id));
+        }
         let after_discount = raw - item.discount_amount;
         items_subtotal += after_discount;
     }
 
-    // ── 3. Kurangi diskon transaksi-level ──
-    let subtotal_after_discount = items_subtotal - payload.discount_amount;
+    // ── 4. Kurangi diskon transaksi-level ──
+    // Diskon terdaftar dihitung ulang dari discount_id; nominal klien diabaikan
+    let discount_amount = match payload.discount_id {
+        Some(discount_id) => {
+            let (kind, value, min_purchase): (String, f64, f64) = sqlx::query_as(
+                "SELECT type, value, min_purchase FROM discounts WHERE id = ? AND is_active = 1",
+            )
+            .bind(discount_id)
+            .fetch_optional(&state.db)
+            .await
+            .map_err(|e| e.to_string())?
+            .ok_or("Diskon tidak ditemukan atau nonaktif")?;
+            if items_subtotal < min_purchase {
+                return Err(format!("Minimum belanja untuk diskon ini adalah {}", min_purchase));
+            }
+            if kind == "PERCENT" {
+                (items_subtotal * value / 100.0).round()
+            } else {
+                value.min(items_subtotal)
+            }
+        }
+        None => {
+            if !(payload.discount_amount >= 0.0 && payload.discount_amount <= items_subtotal) {
+                return Err("Diskon transaksi tidak valid".into());
+            }
+            payload.discount_amount
+        }
+    };
+    let subtotal_after_discount = items_subtotal - discount_amount;
 
-    // ── 4. Hitung pajak ──
+    // ── 5. Hitung pajak ──
     let tax_amount = if tax_enabled && tax_rate > 0.0 {
         if tax_included {
             // Pajak sudah termasuk dalam harga: hitung porsi pajak saja (informasi struk)
@@ -63,7 +157,7 @@ pub async fn create_transaction(
         0.0
     };
 
-    // ── 5. Hitung total ──
+    // ── 6. Hitung total ──
     let total_amount = if tax_included {
         subtotal_after_discount // pajak sudah di dalam harga
     } else {
@@ -84,66 +178,211 @@ pub async fn create_transaction(
     let change_given = (payload.amount_paid - total_amount).round();
     let transaction_id = uuid::Uuid::new_v4().to_string();
 
-    // ── 6. Mulai DB Transaction ──
+    // ── 7. Mulai DB Transaction ──
     let mut tx = state.db.begin().await.map_err(|e| e.to_string())?;
 
+    // Stok dipotong dari lokasi kasir ini (None = lokasi utama)
+    let location = till_location(&mut tx).await?;
+
     sqlx::query(
         "INSERT INTO transactions (
             id, cashier_id, total_amount, discount_id, discount_amount,
-            tax_amount, payment_method, amount_paid, change_given, notes
-        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            tax_amount, payment_method, amount_paid, change_given, notes, customer_id, location_id
+        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
     )
     .bind(&transaction_id)
     .bind(session.user_id)
     .bind(total_amount)
     .bind(payload.discount_id)
-    .bind(payload.discount_amount)
+    .bind(discount_amount)
     .bind(tax_amount)
     .bind(&payload.payment_method)
     .bind(payload.amount_paid)
     .bind(change_given)
     .bind(&payload.notes)
+    .bind(payload.customer_id)
+    .bind(location)
     .execute(&mut *tx)
     .await
     .map_err(|e| e.to_string())?;
 
-    // ── 7. Loop items ──
-    for item in &payload.items {
-        let stock_row: (i64,) = sqlx::query_as("SELECT stock FROM products WHERE id = ?")
-            .bind(item.product_id)
-            .fetch_optional(&mut *tx)
-            .await
-            .map_err(|e| e.to_string())?
-            .ok_or(format!("Produk id {} tidak ditemukan", item.product_id))?;
+    // ── 8. Loop items ──
+    for ((item, &price), &server_price) in payload.items.iter().zip(&prices).zip(&server_prices) {
+        let stock_row: (Quantity, bool, String, i64, f64) = sqlx::query_as(
+            "SELECT stock, is_weighed, unit,
+                    (SELECT COUNT(*) FROM products v WHERE v.parent_id = products.id AND v.is_active = 1),
+                    cost_price
+             FROM products WHERE id = ?",
+        )
+        .bind(item.product_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or(format!("Produk id {} tidak ditemukan", item.product_id))?;
 
-        if stock_row.0 < item.quantity {
+        if stock_row.3 > 0 {
+            return Err(format!(
+                "Produk id {} memiliki varian, pilih salah satu varian",
+                item.product_id
+            ));
+        }
+
+        validate_quantity(item.quantity, stock_row.1)
+            .map_err(|e| format!("{} (produk id {})", e, item.product_id))?;
+        let quantity = round_quantity(item.quantity);
+
+        // Qty dalam satuan jual → stok dikurangi dalam satuan dasar
+        let (unit, conversion) = crate::commands::product_cmd::resolve_unit(
+            &mut tx,
+            item.product_id,
+            item.unit_id,
+            &stock_row.2,
+        )
+        .await?;
+        let base_quantity = round_quantity(quantity * conversion);
+
+        // Paket / menu resep: stok yang dipotong adalah stok komponen / bahannya
+        let components: Vec<(i64, f64, String, f64)> = sqlx::query_as(
+            "SELECT pc.component_id, pc.quantity * pc.unit_conversion, p.name, p.cost_price
+             FROM product_components pc
+             JOIN products p ON p.id = pc.component_id
+             WHERE pc.product_id = ?",
+        )
+        .bind(item.product_id)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+
+        if components.is_empty()
+            && location_available(&mut tx, item.product_id, location).await? < base_quantity
+        {
             return Err(format!(
                 "Stok tidak cukup untuk produk id {}",
                 item.product_id
             ));
         }
+        for (component_id, per_kit, name, _) in &components {
+            if location_available(&mut tx, *component_id, location).await?
+                < round_quantity(per_kit * base_quantity)
+            {
+                return Err(format!(
+                    "Stok komponen '{}' tidak cukup untuk produk id {}",
+                    name, item.product_id
+                ));
+            }
+        }
 
-        let subtotal = (item.price_at_time * item.quantity as f64) - item.discount_amount;
+        let subtotal = (price * quantity) - item.discount_amount;
+        // HPP per satuan jual saat ini (paket/resep: dari HPP komponen)
+        let cost_at_time = if components.is_empty() {
+            stock_row.4 * conversion
+        } else {
+            components.iter().map(|c| c.1 * c.3).sum::<f64>() * conversion
+        };
+        let overridden = item.price_override.is_some() && price != server_price;
 
-        sqlx::query(
-            "INSERT INTO transaction_items (transaction_id, product_id, quantity, price_at_time, subtotal, discount_amount) VALUES (?, ?, ?, ?, ?, ?)"
+        let transaction_item_id = sqlx::query(
+            "INSERT INTO transaction_items (transaction_id, product_id, quantity, price_at_time, subtotal, discount_amount, notes, unit, unit_conversion, original_price, price_override_by, cost_at_time) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
         )
         .bind(&transaction_id)
         .bind(item.product_id)
-        .bind(item.quantity)
-        .bind(item.price_at_time)
+        .bind(quantity)
+        .bind(price)
         .bind(subtotal)
         .bind(item.discount_amount)
+        .bind(item.notes.as_deref().map(str::trim).filter(|n| !n.is_empty()))
+        .bind(&unit)
+        .bind(conversion)
+        .bind(overridden.then_some(server_price))
+        .bind(approver.as_ref().filter(|_| overridden).map(|a| a.0))
+        .bind(cost_at_time)
         .execute(&mut *tx)
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(|e| e.to_string())?
+        .last_insert_rowid();
+
+        if overridden {
+            let (approver_id, approver_name) = approver.as_ref().ok_or("Persetujuan tidak ada")?;
+            crate::commands::activity_cmd::log_activity(
+                &state.db,
+                Some(&mut tx),
+                Some(session.user_id),
+                "PRICE_OVERRIDE",
+                &format!(
+                    "Harga produk id {} diubah {} → {} pada transaksi {}, disetujui {}",
+                    item.product_id, server_price, price, transaction_id, approver_name
+                ),
+                Some(
+                    &serde_json::json!({
+                        "transaction_id": transaction_id,
+                        "product_id": item.product_id,
+                        "original_price": server_price,
+                        "override_price": price,
+                        "approved_by": approver_id,
+                    })
+                    .to_string(),
+                ),
+            )
+            .await;
+        }
+
+        for (component_id, per_kit, _, _) in &components {
+            let used = round_quantity(per_kit * base_quantity);
+            sqlx::query("UPDATE products SET stock = ROUND(stock - ?, 3) WHERE id = ?")
+                .bind(used)
+                .bind(component_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| e.to_string())?;
+            move_location_stock(&mut tx, *component_id, location, -used).await?;
+            sqlx::query(
+                "INSERT INTO transaction_item_components (transaction_item_id, product_id, quantity) VALUES (?, ?, ?)",
+            )
+            .bind(transaction_item_id)
+            .bind(component_id)
+            .bind(used)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?;
+            crate::commands::batch_cmd::consume_batches_for_item(
+                &mut tx,
+                transaction_item_id,
+                *component_id,
+                used,
+            )
+            .await?;
+            crate::commands::activity_cmd::log_stock_adjustment(
+                &state.db,
+                Some(&mut tx),
+                *component_id,
+                session.user_id,
+                "OUT",
+                used,
+                "SALE",
+                Some(&format!(
+                    "Komponen produk id {} pada transaksi {}",
+                    item.product_id, transaction_id
+                )),
+            ).await;
+        }
+        if !components.is_empty() {
+            continue;
+        }
 
-        sqlx::query("UPDATE products SET stock = stock - ? WHERE id = ?")
-            .bind(item.quantity)
+        sqlx::query("UPDATE products SET stock = ROUND(stock - ?, 3) WHERE id = ?")
+            .bind(base_quantity)
             .bind(item.product_id)
             .execute(&mut *tx)
             .await
             .map_err(|e| e.to_string())?;
+        move_location_stock(&mut tx, item.product_id, location, -base_quantity).await?;
+        crate::commands::batch_cmd::consume_batches_for_item(
+            &mut tx,
+            transaction_item_id,
+            item.product_id,
+            base_quantity,
+        )
+        .await?;
 
         // Log Stock Adjustment (SALE)
         crate::commands::activity_cmd::log_stock_adjustment(
@@ -152,7 +391,7 @@ pub async fn create_transaction(
             item.product_id,
             session.user_id,
             "OUT",
-            item.quantity,
+            base_quantity,
             "SALE",
             Some(&format!("Penjualan transaksi {}", transaction_id)),
         ).await;
@@ -170,6 +409,34 @@ pub async fn create_transaction(
 
     tx.commit().await.map_err(|e| e.to_string())?;
 
+    // Buka laci kas untuk transaksi tunai (di background, tidak menahan respons)
+    if payload.payment_method == "CASH" {
+        let db = state.db.clone();
+        tauri::async_runtime::spawn(async move {
+            crate::commands::settings_cmd::kick_drawer_after_sale(&db).await;
+        });
+    }
+
+    // Tampilkan pembayaran & kembalian di pole display pelanggan
+    {
+        let db = state.db.clone();
+        let paid = payload.amount_paid;
+        tauri::async_runtime::spawn(async move {
+            crate::commands::settings_cmd::show_change_on_display(&db, paid, change_given).await;
+        });
+    }
+
+    // Tiket dapur/bar per kategori (masuk antrian cetak)
+    {
+        let db = state.db.clone();
+        let transaction_id = transaction_id.clone();
+        let user_id = session.user_id;
+        tauri::async_runtime::spawn(async move {
+            crate::commands::kitchen_cmd::auto_print_kitchen_tickets(&db, &transaction_id, user_id)
+                .await;
+        });
+    }
+
     let saved = sqlx::query_as::<_, Transaction>("SELECT * FROM transactions WHERE id = ?")
         .bind(&transaction_id)
         .fetch_one(&state.db)
@@ -212,21 +479,43 @@ pub async fn void_transaction(
     .map_err(|e| e.to_string())?;
 
     // 2. Kembalikan stok
-    let items: Vec<(i64, i64)> = sqlx::query_as(
-        "SELECT product_id, quantity FROM transaction_items WHERE transaction_id = ?",
+    // Item paket → kembalikan stok komponen yang dipotong saat penjualan
+    let items: Vec<(i64, f64)> = sqlx::query_as(
+        "SELECT ti.product_id, ti.quantity * ti.unit_conversion
+         FROM transaction_items ti
+         WHERE ti.transaction_id = ?
+           AND NOT EXISTS (SELECT 1 FROM transaction_item_components tic WHERE tic.transaction_item_id = ti.id)
+         UNION ALL
+         SELECT tic.product_id, tic.quantity
+         FROM transaction_item_components tic
+         JOIN transaction_items ti ON ti.id = tic.transaction_item_id
+         WHERE ti.transaction_id = ?",
     )
     .bind(&transaction_id)
+    .bind(&transaction_id)
     .fetch_all(&mut *tx)
     .await
     .map_err(|e| e.to_string())?;
 
+    crate::commands::batch_cmd::restore_transaction_batches(&mut tx, &transaction_id).await?;
+
+    // Stok kembali ke lokasi tempat transaksi terjual (NULL = lokasi utama)
+    let (location,): (Option<i64>,) =
+        sqlx::query_as("SELECT location_id FROM transactions WHERE id = ?")
+            .bind(&transaction_id)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?;
+
     for (product_id, qty) in items {
-        sqlx::query("UPDATE products SET stock = stock + ? WHERE id = ?")
+        let qty = round_quantity(qty);
+        sqlx::query("UPDATE products SET stock = ROUND(stock + ?, 3) WHERE id = ?")
             .bind(qty)
             .bind(product_id)
             .execute(&mut *tx)
             .await
             .map_err(|e| e.to_string())?;
+        move_location_stock(&mut tx, product_id, location, qty).await?;
 
         // Log Stock Adjustment (VOID)
         crate::commands::activity_cmd::log_stock_adjustment(
@@ -186,13 +186,47 @@ pub async fn run_migrations(pool: &SqlitePool) -> Result<(), sqlx::Error> {
         ("receipt.show_discount_detail", "1"),
         ("receipt.paper_width", "80mm"),
         ("receipt.copies", "1"),
+        ("receipt.qr_mode", "NONE"),
+        ("receipt.qr_link_template", "{id}"),
         // Tax
         ("tax.is_enabled", "0"),
         ("tax.rate", "11"),
         ("tax.label", "PPN"),
         ("tax.is_included", "0"),
+        // Cash drawer
+        ("drawer.enabled", "0"),
+        ("drawer.open_on_cash", "1"),
+        ("drawer.pin", "2"),
+        ("drawer.pulse_on_ms", "100"),
+        ("drawer.pulse_off_ms", "100"),
+        // Label printer
+        ("label.printer_port", ""),
+        ("label.language", "ESCPOS"),
+        ("label.size", "50x30"),
+        ("label.dpi", "203"),
+        ("label.gap_mm", "2"),
+        // Tiket dapur/bar
+        ("kitchen.auto_print", "0"),
+        // Pole display pelanggan
+        ("display.enabled", "0"),
+        ("display.port", ""),
+        ("display.baud_rate", "9600"),
+        ("display.protocol", "ESCPOS"),
+        ("display.idle_message", "Selamat Datang"),
+        // Timbangan
+        ("scale.enabled", "0"),
+        ("scale.port", ""),
+        ("scale.baud_rate", "9600"),
+        ("scale.protocol", "CONTINUOUS"),
+        ("scale.poll_command", "W"),
+        // Barcode timbangan (harga/berat tertanam); aturan kosong = pakai default
+        ("scale_barcode.enabled", "0"),
+        ("scale_barcode.rules", ""),
         // App
         ("app.low_stock_threshold", "5"),
+        ("app.expiry_warning_days", "30"),
+        // Lokasi stok kasir ini; kosong = lokasi utama
+        ("app.location_id", ""),
         ("app.printer_port", ""),
         ("app.timezone", "Asia/Jakarta"),
     ];
@@ -298,6 +332,638 @@ pub async fn run_migrations(pool: &SqlitePool) -> Result<(), sqlx::Error> {
     .execute(pool)
     .await?;
 
+    // Jumlah cetak ulang struk (audit reprint)
+    safe_add_column(pool, "transactions", "reprint_count", "INTEGER NOT NULL DEFAULT 0").await;
+    // Waktu struk asli dicetak; cetakan berikutnya selalu SALINAN
+    safe_add_column(pool, "transactions", "receipt_printed_at", "DATETIME").await;
+
+    // ═══════════════════════════════════════
+    // TABLE: print_jobs (antrian cetak persisten)
+    // ═══════════════════════════════════════
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS print_jobs (
+            id              INTEGER  PRIMARY KEY AUTOINCREMENT,
+            job_type        TEXT     NOT NULL, -- 'RECEIPT', 'LABEL', 'KITCHEN'
+            reference_id    TEXT,              -- mis. id transaksi
+            printer_port    TEXT     NOT NULL,
+            payload         BLOB     NOT NULL, -- byte ESC/POS siap kirim
+            status          TEXT     NOT NULL DEFAULT 'QUEUED'
+                            CHECK(status IN ('QUEUED', 'PRINTING', 'DONE', 'FAILED')),
+            attempts        INTEGER  NOT NULL DEFAULT 0,
+            max_attempts    INTEGER  NOT NULL DEFAULT 5,
+            last_error      TEXT,
+            next_attempt_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+            created_by      INTEGER  REFERENCES users(id) ON DELETE SET NULL,
+            created_at      DATETIME DEFAULT CURRENT_TIMESTAMP,
+            updated_at      DATETIME DEFAULT CURRENT_TIMESTAMP,
+            printed_at      DATETIME
+        )",
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_print_jobs_status ON print_jobs(status, next_attempt_at)")
+        .execute(pool)
+        .await?;
+
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_print_jobs_reference ON print_jobs(reference_id)")
+        .execute(pool)
+        .await?;
+
+    // Job yang terputus saat aplikasi ditutup ketika mencetak → antrikan ulang
+    sqlx::query("UPDATE print_jobs SET status = 'QUEUED' WHERE status = 'PRINTING'")
+        .execute(pool)
+        .await?;
+
+    // ═══════════════════════════════════════
+    // TABLE: printer_profiles (printer tiket dapur/bar)
+    // ═══════════════════════════════════════
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS printer_profiles (
+            id           INTEGER PRIMARY KEY AUTOINCREMENT,
+            name         TEXT    NOT NULL UNIQUE, -- 'DAPUR', 'BAR', ...
+            printer_port TEXT    NOT NULL,
+            paper_width  TEXT    NOT NULL DEFAULT '80mm',
+            is_active    INTEGER NOT NULL DEFAULT 1,
+            created_at   DATETIME DEFAULT CURRENT_TIMESTAMP
+        )",
+    )
+    .execute(pool)
+    .await?;
+
+    // Routing tiket: kategori → printer profile (NULL = tidak dicetak)
+    safe_add_column(
+        pool,
+        "categories",
+        "printer_profile_id",
+        "INTEGER REFERENCES printer_profiles(id) ON DELETE SET NULL",
+    )
+    .await;
+
+    // Catatan per item (mis. "tanpa es") untuk tiket dapur/bar
+    safe_add_column(pool, "transaction_items", "notes", "TEXT").await;
+
+    // Produk timbang: stok & qty boleh desimal (kolom INTEGER menyimpan
+    // nilai pecahan sebagai REAL — lihat models::quantity)
+    safe_add_column(pool, "products", "is_weighed", "INTEGER NOT NULL DEFAULT 0").await;
+
+    // ═══════════════════════════════════════
+    // TABLE: product_barcodes (barcode tambahan / barcode kemasan)
+    // ═══════════════════════════════════════
+    // Unik lintas products.barcode + product_barcodes untuk produk aktif
+    // dicek di product_cmd::ensure_barcode_available (tidak bisa via index).
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS product_barcodes (
+            id            INTEGER PRIMARY KEY AUTOINCREMENT,
+            product_id    INTEGER NOT NULL REFERENCES products(id) ON DELETE CASCADE,
+            barcode       TEXT    NOT NULL,
+            pack_quantity INTEGER NOT NULL DEFAULT 1, -- 1 scan = N unit (karton/pak)
+            label         TEXT,                       -- mis. 'Karton', 'Barcode lama'
+            created_at    DATETIME DEFAULT CURRENT_TIMESTAMP
+        )",
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_product_barcodes_barcode ON product_barcodes(barcode)")
+        .execute(pool)
+        .await?;
+
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_product_barcodes_product ON product_barcodes(product_id)")
+        .execute(pool)
+        .await?;
+
+    // ═══════════════════════════════════════
+    // TABLE: product_units (satuan jual + konversi ke satuan dasar)
+    // ═══════════════════════════════════════
+    safe_add_column(pool, "products", "unit", "TEXT NOT NULL DEFAULT 'pcs'").await;
+
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS product_units (
+            id         INTEGER PRIMARY KEY AUTOINCREMENT,
+            product_id INTEGER NOT NULL REFERENCES products(id) ON DELETE CASCADE,
+            name       TEXT    NOT NULL,             -- 'pack', 'box', ...
+            conversion REAL    NOT NULL CHECK (conversion > 0), -- satuan dasar per unit
+            price      REAL    NOT NULL DEFAULT 0,   -- harga jual per unit
+            UNIQUE (product_id, name)
+        )",
+    )
+    .execute(pool)
+    .await?;
+
+    // Satuan pada item transaksi; qty x unit_conversion = qty satuan dasar
+    safe_add_column(pool, "transaction_items", "unit", "TEXT").await;
+    safe_add_column(pool, "transaction_items", "unit_conversion", "REAL NOT NULL DEFAULT 1").await;
+
+    // Varian produk: baris products biasa dengan induk (parent_id) dan atribut
+    // JSON, sehingga stok/SKU/barcode/harga per varian memakai kolom yang sama
+    safe_add_column(
+        pool,
+        "products",
+        "parent_id",
+        "INTEGER REFERENCES products(id) ON DELETE CASCADE",
+    )
+    .await;
+    safe_add_column(pool, "products", "variant_attributes", "TEXT").await;
+
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_products_parent ON products(parent_id)")
+        .execute(pool)
+        .await?;
+
+    // ═══════════════════════════════════════
+    // TABLE: price_levels, customers, harga grosir
+    // ═══════════════════════════════════════
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS price_levels (
+            id               INTEGER PRIMARY KEY AUTOINCREMENT,
+            name             TEXT    NOT NULL UNIQUE,  -- 'Reseller', 'Member', ...
+            discount_percent REAL    NOT NULL DEFAULT 0, -- dipakai jika produk tidak punya harga khusus level
+            created_at       DATETIME DEFAULT CURRENT_TIMESTAMP
+        )",
+    )
+    .execute(pool)
+    .await?;
+
+    for name in ["Reseller", "Member"] {
+        sqlx::query("INSERT OR IGNORE INTO price_levels (name) VALUES (?)")
+            .bind(name)
+            .execute(pool)
+            .await?;
+    }
+
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS customers (
+            id             INTEGER PRIMARY KEY AUTOINCREMENT,
+            name           TEXT    NOT NULL,
+            phone          TEXT,
+            address        TEXT,
+            price_level_id INTEGER REFERENCES price_levels(id) ON DELETE SET NULL, -- NULL = harga eceran
+            is_active      INTEGER NOT NULL DEFAULT 1,
+            created_at     DATETIME DEFAULT CURRENT_TIMESTAMP
+        )",
+    )
+    .execute(pool)
+    .await?;
+
+    safe_add_column(pool, "transactions", "customer_id", "INTEGER REFERENCES customers(id)").await;
+
+    // Harga khusus per level untuk satu produk (satuan dasar)
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS product_level_prices (
+            product_id     INTEGER NOT NULL REFERENCES products(id) ON DELETE CASCADE,
+            price_level_id INTEGER NOT NULL REFERENCES price_levels(id) ON DELETE CASCADE,
+            price          REAL    NOT NULL CHECK (price >= 0),
+            PRIMARY KEY (product_id, price_level_id)
+        )",
+    )
+    .execute(pool)
+    .await?;
+
+    // Harga grosir: beli >= min_quantity (satuan dasar) → harga per satuan dasar
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS product_price_tiers (
+            id           INTEGER PRIMARY KEY AUTOINCREMENT,
+            product_id   INTEGER NOT NULL REFERENCES products(id) ON DELETE CASCADE,
+            min_quantity REAL    NOT NULL CHECK (min_quantity > 0),
+            price        REAL    NOT NULL CHECK (price >= 0),
+            UNIQUE (product_id, min_quantity)
+        )",
+    )
+    .execute(pool)
+    .await?;
+
+    // Override harga kasir: harga server asli + admin penyetuju (NULL = tanpa override)
+    safe_add_column(pool, "transaction_items", "original_price", "REAL").await;
+    safe_add_column(
+        pool,
+        "transaction_items",
+        "price_override_by",
+        "INTEGER REFERENCES users(id)",
+    )
+    .await;
+
+    // ═══════════════════════════════════════
+    // TABLE: product_price_history, price_schedules
+    // ═══════════════════════════════════════
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS product_price_history (
+            id             INTEGER PRIMARY KEY AUTOINCREMENT,
+            product_id     INTEGER NOT NULL REFERENCES products(id) ON DELETE CASCADE,
+            old_price      REAL    NOT NULL,
+            new_price      REAL    NOT NULL,
+            old_cost_price REAL    NOT NULL,
+            new_cost_price REAL    NOT NULL,
+            source         TEXT    NOT NULL CHECK (source IN ('MANUAL', 'SCHEDULE')),
+            target         TEXT,  -- NULL = harga satuan dasar; mis. 'Satuan box', 'Grosir ≥ 10'
+            schedule_id    INTEGER REFERENCES price_schedules(id) ON DELETE SET NULL,
+            changed_by     INTEGER REFERENCES users(id) ON DELETE SET NULL,
+            changed_at     DATETIME DEFAULT CURRENT_TIMESTAMP
+        )",
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        "CREATE INDEX IF NOT EXISTS idx_price_history_product ON product_price_history(product_id, changed_at)",
+    )
+    .execute(pool)
+    .await?;
+
+    // Target satu produk (harga baru atau %) atau satu kategori (%)
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS price_schedules (
+            id             INTEGER PRIMARY KEY AUTOINCREMENT,
+            product_id     INTEGER REFERENCES products(id) ON DELETE CASCADE,
+            category_id    INTEGER REFERENCES categories(id) ON DELETE CASCADE,
+            new_price      REAL CHECK (new_price >= 0),
+            percent_change REAL,
+            effective_at   DATETIME NOT NULL,  -- UTC
+            status         TEXT    NOT NULL DEFAULT 'PENDING'
+                           CHECK (status IN ('PENDING', 'APPLIED', 'CANCELLED')),
+            notes          TEXT,
+            applied_at     DATETIME,
+            applied_count  INTEGER NOT NULL DEFAULT 0,
+            created_by     INTEGER REFERENCES users(id) ON DELETE SET NULL,
+            created_at     DATETIME DEFAULT CURRENT_TIMESTAMP,
+            CHECK ((product_id IS NULL) != (category_id IS NULL)),
+            CHECK ((new_price IS NULL) != (percent_change IS NULL))
+        )",
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        "CREATE INDEX IF NOT EXISTS idx_price_schedules_due ON price_schedules(status, effective_at)",
+    )
+    .execute(pool)
+    .await?;
+
+    // ═══════════════════════════════════════
+    // TABLE: product_components (paket / kit)
+    // ═══════════════════════════════════════
+    safe_add_column(pool, "products", "is_kit", "INTEGER NOT NULL DEFAULT 0").await;
+
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS product_components (
+            id            INTEGER PRIMARY KEY AUTOINCREMENT,
+            product_id    INTEGER NOT NULL REFERENCES products(id) ON DELETE CASCADE,
+            component_id  INTEGER NOT NULL REFERENCES products(id) ON DELETE CASCADE,
+            quantity      REAL    NOT NULL CHECK (quantity > 0),
+            UNIQUE (product_id, component_id)
+        )",
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        "CREATE INDEX IF NOT EXISTS idx_product_components_component ON product_components(component_id)",
+    )
+    .execute(pool)
+    .await?;
+
+    // Resep (menu kafe): takaran bahan boleh desimal & memakai satuan bahan
+    safe_add_column(pool, "products", "is_recipe", "INTEGER NOT NULL DEFAULT 0").await;
+    safe_add_column(pool, "product_components", "unit", "TEXT").await;
+    safe_add_column(pool, "product_components", "unit_conversion", "REAL NOT NULL DEFAULT 1").await;
+
+    // Potongan stok komponen per item kit yang terjual (untuk void)
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS transaction_item_components (
+            id                   INTEGER PRIMARY KEY AUTOINCREMENT,
+            transaction_item_id  INTEGER NOT NULL REFERENCES transaction_items(id) ON DELETE CASCADE,
+            product_id           INTEGER NOT NULL REFERENCES products(id),
+            quantity             REAL    NOT NULL
+        )",
+    )
+    .execute(pool)
+    .await?;
+
+    // ═══════════════════════════════════════
+    // TABLE: product_batches (batch & kedaluwarsa, FEFO)
+    // ═══════════════════════════════════════
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS product_batches (
+            id                INTEGER PRIMARY KEY AUTOINCREMENT,
+            product_id        INTEGER NOT NULL REFERENCES products(id) ON DELETE CASCADE,
+            batch_number      TEXT,
+            expiry_date       DATE,
+            quantity          REAL    NOT NULL CHECK (quantity >= 0),
+            initial_quantity  REAL    NOT NULL,
+            cost_price        REAL    NOT NULL DEFAULT 0,
+            received_at       DATETIME DEFAULT CURRENT_TIMESTAMP
+        )",
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        "CREATE INDEX IF NOT EXISTS idx_product_batches_fefo ON product_batches(product_id, expiry_date)",
+    )
+    .execute(pool)
+    .await?;
+
+    // Batch yang terpotong per item transaksi (untuk void)
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS transaction_item_batches (
+            id                   INTEGER PRIMARY KEY AUTOINCREMENT,
+            transaction_item_id  INTEGER NOT NULL REFERENCES transaction_items(id) ON DELETE CASCADE,
+            batch_id             INTEGER NOT NULL REFERENCES product_batches(id) ON DELETE CASCADE,
+            quantity             REAL    NOT NULL
+        )",
+    )
+    .execute(pool)
+    .await?;
+
+    // ═══════════════════════════════════════
+    // TABLE: suppliers, purchase_orders
+    // ═══════════════════════════════════════
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS suppliers (
+            id              INTEGER PRIMARY KEY AUTOINCREMENT,
+            name            TEXT    NOT NULL,
+            contact_person  TEXT,
+            phone           TEXT,
+            email           TEXT,
+            address         TEXT,
+            notes           TEXT,
+            is_active       INTEGER NOT NULL DEFAULT 1,
+            created_at      DATETIME DEFAULT CURRENT_TIMESTAMP
+        )",
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS purchase_orders (
+            id             INTEGER PRIMARY KEY AUTOINCREMENT,
+            po_number      TEXT    NOT NULL UNIQUE,  -- PO/YYYYMMDD/001
+            supplier_id    INTEGER NOT NULL REFERENCES suppliers(id),
+            status         TEXT    NOT NULL DEFAULT 'DRAFT'
+                           CHECK (status IN ('DRAFT', 'SENT', 'PARTIALLY_RECEIVED', 'RECEIVED', 'CANCELLED')),
+            expected_date  DATE,
+            notes          TEXT,
+            total_amount   REAL    NOT NULL DEFAULT 0,
+            created_by     INTEGER REFERENCES users(id) ON DELETE SET NULL,
+            created_at     DATETIME DEFAULT CURRENT_TIMESTAMP,
+            sent_at        DATETIME,
+            closed_at      DATETIME  -- diterima penuh / dibatalkan
+        )",
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS purchase_order_items (
+            id                 INTEGER PRIMARY KEY AUTOINCREMENT,
+            purchase_order_id  INTEGER NOT NULL REFERENCES purchase_orders(id) ON DELETE CASCADE,
+            product_id         INTEGER NOT NULL REFERENCES products(id),
+            quantity           REAL    NOT NULL CHECK (quantity > 0),  -- satuan dasar
+            received_quantity  REAL    NOT NULL DEFAULT 0,
+            unit_cost          REAL    NOT NULL DEFAULT 0,             -- perkiraan modal per satuan dasar
+            subtotal           REAL    NOT NULL DEFAULT 0
+        )",
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        "CREATE INDEX IF NOT EXISTS idx_purchase_orders_status ON purchase_orders(status, created_at)",
+    )
+    .execute(pool)
+    .await?;
+
+    // ═══════════════════════════════════════
+    // TABLE: goods_receipts, goods_receipt_items, product_cost_history
+    // ═══════════════════════════════════════
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS goods_receipts (
+            id                 INTEGER PRIMARY KEY AUTOINCREMENT,
+            receipt_number     TEXT    NOT NULL UNIQUE,
+            purchase_order_id  INTEGER REFERENCES purchase_orders(id),
+            supplier_id        INTEGER REFERENCES suppliers(id),
+            invoice_number     TEXT,                -- no. faktur / surat jalan supplier
+            notes              TEXT,
+            total_amount       REAL    NOT NULL DEFAULT 0,
+            received_by        INTEGER REFERENCES users(id),
+            received_at        DATETIME DEFAULT CURRENT_TIMESTAMP
+        )",
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS goods_receipt_items (
+            id                      INTEGER PRIMARY KEY AUTOINCREMENT,
+            goods_receipt_id        INTEGER NOT NULL REFERENCES goods_receipts(id) ON DELETE CASCADE,
+            product_id              INTEGER NOT NULL REFERENCES products(id),
+            purchase_order_item_id  INTEGER REFERENCES purchase_order_items(id),
+            quantity                REAL    NOT NULL CHECK (quantity > 0),  -- satuan dasar
+            unit_cost               REAL    NOT NULL,                       -- modal aktual per satuan dasar
+            subtotal                REAL    NOT NULL,
+            batch_id                INTEGER REFERENCES product_batches(id)
+        )",
+    )
+    .execute(pool)
+    .await?;
+
+    // Pergerakan HPP (rata-rata bergerak) per penerimaan barang
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS product_cost_history (
+            id                INTEGER PRIMARY KEY AUTOINCREMENT,
+            product_id        INTEGER NOT NULL REFERENCES products(id) ON DELETE CASCADE,
+            goods_receipt_id  INTEGER REFERENCES goods_receipts(id) ON DELETE SET NULL,
+            stock_before      REAL    NOT NULL,
+            cost_before       REAL    NOT NULL,
+            quantity          REAL    NOT NULL,
+            unit_cost         REAL    NOT NULL,
+            cost_after        REAL    NOT NULL,
+            created_by        INTEGER REFERENCES users(id) ON DELETE SET NULL,
+            created_at        DATETIME DEFAULT CURRENT_TIMESTAMP
+        )",
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        "CREATE INDEX IF NOT EXISTS idx_cost_history_product ON product_cost_history(product_id, created_at)",
+    )
+    .execute(pool)
+    .await?;
+
+    // ═══════════════════════════════════════
+    // COLUMN: transaction_items.cost_at_time (HPP per satuan jual saat terjual)
+    // ═══════════════════════════════════════
+    safe_add_column(pool, "transaction_items", "cost_at_time", "REAL").await;
+
+    // Backfill baris lama (best-effort): komponen paket/resep pakai HPP
+    // sekarang; produk biasa pakai HPP terakhir yang tercatat di riwayat
+    // harga/HPP sebelum waktu transaksi, lalu HPP lama dari perubahan
+    // berikutnya, lalu HPP sekarang.
+    sqlx::query(
+        "UPDATE transaction_items SET cost_at_time = COALESCE(
+            (SELECT SUM(tic.quantity * p.cost_price) / transaction_items.quantity
+             FROM transaction_item_components tic
+             JOIN products p ON p.id = tic.product_id
+             WHERE tic.transaction_item_id = transaction_items.id),
+            transaction_items.unit_conversion * COALESCE(
+                (SELECT h.cost FROM (
+                    SELECT new_cost_price AS cost, changed_at AS at FROM product_price_history
+                    WHERE product_id = transaction_items.product_id
+                    UNION ALL
+                    SELECT cost_after, created_at FROM product_cost_history
+                    WHERE product_id = transaction_items.product_id
+                 ) h
+                 WHERE h.at <= (SELECT timestamp FROM transactions WHERE id = transaction_items.transaction_id)
+                 ORDER BY h.at DESC LIMIT 1),
+                (SELECT h.cost FROM (
+                    SELECT old_cost_price AS cost, changed_at AS at FROM product_price_history
+                    WHERE product_id = transaction_items.product_id
+                    UNION ALL
+                    SELECT cost_before, created_at FROM product_cost_history
+                    WHERE product_id = transaction_items.product_id
+                 ) h
+                 WHERE h.at > (SELECT timestamp FROM transactions WHERE id = transaction_items.transaction_id)
+                 ORDER BY h.at ASC LIMIT 1),
+                (SELECT cost_price FROM products WHERE id = transaction_items.product_id),
+                0
+            )
+        )
+        WHERE cost_at_time IS NULL",
+    )
+    .execute(pool)
+    .await?;
+
+    // ═══════════════════════════════════════
+    // TABLE: stock_opname_sessions, stock_opname_items, stock_opname_counts
+    // ═══════════════════════════════════════
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS stock_opname_sessions (
+            id          INTEGER PRIMARY KEY AUTOINCREMENT,
+            name        TEXT    NOT NULL,
+            status      TEXT    NOT NULL DEFAULT 'OPEN'
+                        CHECK (status IN ('OPEN', 'APPROVED', 'CANCELLED')),
+            notes       TEXT,
+            created_by  INTEGER REFERENCES users(id),
+            created_at  DATETIME DEFAULT CURRENT_TIMESTAMP,
+            closed_by   INTEGER REFERENCES users(id),
+            closed_at   DATETIME
+        )",
+    )
+    .execute(pool)
+    .await?;
+
+    // Snapshot stok sistem & HPP saat sesi dibuka
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS stock_opname_items (
+            session_id    INTEGER NOT NULL REFERENCES stock_opname_sessions(id) ON DELETE CASCADE,
+            product_id    INTEGER NOT NULL REFERENCES products(id) ON DELETE CASCADE,
+            system_stock  REAL    NOT NULL,
+            cost_price    REAL    NOT NULL DEFAULT 0,
+            PRIMARY KEY (session_id, product_id)
+        )",
+    )
+    .execute(pool)
+    .await?;
+
+    // Entri hitung per kasir; hasil hitung produk = jumlah semua entri.
+    // Qty 0 = produk dicari tapi tidak ditemukan secara fisik.
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS stock_opname_counts (
+            id          INTEGER PRIMARY KEY AUTOINCREMENT,
+            session_id  INTEGER NOT NULL REFERENCES stock_opname_sessions(id) ON DELETE CASCADE,
+            product_id  INTEGER NOT NULL REFERENCES products(id) ON DELETE CASCADE,
+            quantity    REAL    NOT NULL CHECK (quantity >= 0),
+            counted_by  INTEGER REFERENCES users(id),
+            counted_at  DATETIME DEFAULT CURRENT_TIMESTAMP
+        )",
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        "CREATE INDEX IF NOT EXISTS idx_opname_counts_session ON stock_opname_counts(session_id, product_id)",
+    )
+    .execute(pool)
+    .await?;
+
+    // ═══════════════════════════════════════
+    // TABLE: locations, product_location_stock, stock_transfers
+    // ═══════════════════════════════════════
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS locations (
+            id             INTEGER PRIMARY KEY AUTOINCREMENT,
+            name           TEXT    NOT NULL UNIQUE,
+            location_type  TEXT    NOT NULL DEFAULT 'STORE' CHECK (location_type IN ('STORE', 'WAREHOUSE')),
+            is_default     INTEGER NOT NULL DEFAULT 0,
+            is_active      INTEGER NOT NULL DEFAULT 1,
+            created_at     DATETIME DEFAULT CURRENT_TIMESTAMP
+        )",
+    )
+    .execute(pool)
+    .await?;
+
+    // Lokasi utama: stoknya = products.stock - lokasi lain - dalam perjalanan
+    sqlx::query(
+        "INSERT INTO locations (name, location_type, is_default)
+         SELECT 'Toko Utama', 'STORE', 1
+         WHERE NOT EXISTS (SELECT 1 FROM locations WHERE is_default = 1)",
+    )
+    .execute(pool)
+    .await?;
+
+    // Stok per lokasi selain lokasi utama
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS product_location_stock (
+            product_id   INTEGER NOT NULL REFERENCES products(id) ON DELETE CASCADE,
+            location_id  INTEGER NOT NULL REFERENCES locations(id),
+            quantity     REAL    NOT NULL DEFAULT 0,
+            PRIMARY KEY (product_id, location_id)
+        )",
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS stock_transfers (
+            id                INTEGER PRIMARY KEY AUTOINCREMENT,
+            transfer_number   TEXT    NOT NULL UNIQUE,
+            from_location_id  INTEGER NOT NULL REFERENCES locations(id),
+            to_location_id    INTEGER NOT NULL REFERENCES locations(id),
+            status            TEXT    NOT NULL DEFAULT 'IN_TRANSIT'
+                              CHECK (status IN ('IN_TRANSIT', 'RECEIVED', 'CANCELLED')),
+            notes             TEXT,
+            created_by        INTEGER REFERENCES users(id),
+            created_at        DATETIME DEFAULT CURRENT_TIMESTAMP,
+            closed_by         INTEGER REFERENCES users(id),
+            closed_at         DATETIME
+        )",
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS stock_transfer_items (
+            id           INTEGER PRIMARY KEY AUTOINCREMENT,
+            transfer_id  INTEGER NOT NULL REFERENCES stock_transfers(id) ON DELETE CASCADE,
+            product_id   INTEGER NOT NULL REFERENCES products(id),
+            quantity     REAL    NOT NULL CHECK (quantity > 0)  -- satuan dasar
+        )",
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        "CREATE INDEX IF NOT EXISTS idx_stock_transfers_status ON stock_transfers(status, created_at)",
+    )
+    .execute(pool)
+    .await?;
+
+    // Lokasi tempat transaksi terjual (NULL = lokasi utama)
+    safe_add_column(pool, "transactions", "location_id", "INTEGER REFERENCES locations(id)").await;
+    // Lokasi penerimaan barang & lokasi yang di-opname (NULL = lokasi utama)
+    safe_add_column(pool, "goods_receipts", "location_id", "INTEGER REFERENCES locations(id)").await;
+    safe_add_column(pool, "stock_opname_sessions", "location_id", "INTEGER REFERENCES locations(id)").await;
+
     Ok(())
 }
 
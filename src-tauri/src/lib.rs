@@ -9,6 +9,7 @@ pub mod rate_limiter;
 pub mod logger;
 pub mod config;
 pub mod validation;
+pub mod printer;
 
 use auth::session::SessionStore;
 use std::sync::Mutex;
@@ -70,6 +71,11 @@ pub fn run() {
                     sessions: Mutex::new(SessionStore::new()),
                 });
             });
+
+            // Worker antrian cetak (retry otomatis saat printer offline)
+            commands::print_job_cmd::start_print_worker(app.handle().clone());
+            // Worker jadwal perubahan harga
+            commands::pricing_cmd::start_price_schedule_worker(app.handle().clone());
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
@@ -100,11 +106,80 @@ pub fn run() {
             commands::product_cmd::generate_barcode,
             commands::product_cmd::get_low_stock_products,
             commands::product_cmd::bulk_import_products,
+            commands::product_cmd::get_product_barcodes,
+            commands::product_cmd::add_product_barcode,
+            commands::product_cmd::delete_product_barcode,
+            commands::product_cmd::get_product_units,
+            commands::product_cmd::save_product_units,
+            commands::product_cmd::get_kit_components,
+            commands::product_cmd::save_kit_components,
+            commands::recipe_cmd::get_recipe,
+            commands::recipe_cmd::save_recipe,
+            commands::recipe_cmd::record_stock_counts,
+            commands::recipe_cmd::get_ingredient_usage_report,
+            commands::batch_cmd::get_product_batches,
+            commands::batch_cmd::get_expiring_batches,
+            commands::batch_cmd::dispose_batch,
+            // Procurement
+            commands::purchase_cmd::get_suppliers,
+            commands::purchase_cmd::create_supplier,
+            commands::purchase_cmd::update_supplier,
+            commands::purchase_cmd::toggle_supplier,
+            commands::purchase_cmd::get_purchase_orders,
+            commands::purchase_cmd::get_purchase_order,
+            commands::purchase_cmd::create_purchase_order,
+            commands::purchase_cmd::update_purchase_order,
+            commands::purchase_cmd::set_purchase_order_status,
+            commands::purchase_cmd::export_purchase_order_pdf,
+            commands::purchase_cmd::get_goods_receipts,
+            commands::purchase_cmd::get_goods_receipt,
+            commands::purchase_cmd::create_goods_receipt,
+            commands::purchase_cmd::get_product_cost_history,
+            // Stock opname
+            commands::opname_cmd::get_opname_sessions,
+            commands::opname_cmd::get_opname_session,
+            commands::opname_cmd::create_opname_session,
+            commands::opname_cmd::submit_opname_count,
+            commands::opname_cmd::get_opname_counts,
+            commands::opname_cmd::delete_opname_count,
+            commands::opname_cmd::approve_opname_session,
+            commands::opname_cmd::cancel_opname_session,
+            // Lokasi & transfer stok
+            commands::location_cmd::get_locations,
+            commands::location_cmd::create_location,
+            commands::location_cmd::update_location,
+            commands::location_cmd::toggle_location,
+            commands::location_cmd::get_product_location_stock,
+            commands::location_cmd::get_location_stock,
+            commands::location_cmd::get_stock_transfers,
+            commands::location_cmd::get_stock_transfer,
+            commands::location_cmd::create_stock_transfer,
+            commands::location_cmd::receive_stock_transfer,
+            commands::location_cmd::cancel_stock_transfer,
+            commands::product_cmd::get_product_variants,
+            commands::product_cmd::create_product_variant,
             // Discounts
             commands::discount_cmd::get_discounts,
             commands::discount_cmd::create_discount,
             commands::discount_cmd::update_discount,
             commands::discount_cmd::toggle_discount,
+            // Pelanggan & level harga
+            commands::customer_cmd::get_customers,
+            commands::customer_cmd::create_customer,
+            commands::customer_cmd::update_customer,
+            commands::customer_cmd::toggle_customer,
+            commands::customer_cmd::get_price_levels,
+            commands::customer_cmd::create_price_level,
+            commands::customer_cmd::update_price_level,
+            commands::customer_cmd::delete_price_level,
+            // Harga grosir & harga server
+            commands::pricing_cmd::get_product_pricing,
+            commands::pricing_cmd::save_product_pricing,
+            commands::pricing_cmd::quote_prices,
+            commands::pricing_cmd::get_product_price_history,
+            commands::pricing_cmd::get_price_schedules,
+            commands::pricing_cmd::create_price_schedule,
+            commands::pricing_cmd::cancel_price_schedule,
             // Transactions
             commands::transaction_cmd::create_transaction,
             commands::transaction_cmd::void_transaction,
@@ -115,6 +190,7 @@ pub fn run() {
             commands::report_cmd::get_financial_summary,
             commands::report_cmd::get_sales_chart,
             commands::report_cmd::get_top_products,
+            commands::report_cmd::get_category_sales,
             commands::report_cmd::get_shift_summary,
             commands::report_cmd::get_profit_report,
             // Activity & Stock Logs
@@ -126,10 +202,32 @@ pub fn run() {
             commands::settings_cmd::save_logo,
             commands::settings_cmd::list_serial_ports,
             commands::settings_cmd::print_receipt,
+            commands::settings_cmd::reprint_receipt,
             commands::settings_cmd::test_print,
             commands::settings_cmd::print_barcode_labels,
+            commands::settings_cmd::export_label_sheet_pdf,
             commands::settings_cmd::print_receipt_windows,
             commands::settings_cmd::export_receipt_pdf,
+            commands::settings_cmd::open_cash_drawer,
+            commands::settings_cmd::get_printer_status,
+            commands::settings_cmd::update_customer_display,
+            commands::settings_cmd::test_customer_display,
+            // Print Queue
+            commands::print_job_cmd::get_print_jobs,
+            commands::print_job_cmd::retry_print_job,
+            commands::print_job_cmd::resume_print_queue,
+            commands::print_job_cmd::reprint_print_job,
+            commands::print_job_cmd::delete_print_job,
+            // Kitchen / Bar Tickets
+            commands::kitchen_cmd::get_printer_profiles,
+            commands::kitchen_cmd::create_printer_profile,
+            commands::kitchen_cmd::update_printer_profile,
+            commands::kitchen_cmd::delete_printer_profile,
+            commands::kitchen_cmd::set_category_printer,
+            commands::kitchen_cmd::print_kitchen_tickets,
+            // Timbangan
+            commands::scale_cmd::read_scale_weight,
+            commands::scale_cmd::set_scale_tare,
             // Payment QRIS
             commands::payment_cmd::generate_qris_payment,
             commands::payment_cmd::check_qris_status,
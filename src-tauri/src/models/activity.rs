@@ -1,3 +1,4 @@
+use super::quantity::Quantity;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
@@ -27,7 +28,8 @@ pub struct StockAdjustment {
     pub product_id: i64,
     pub user_id: i64,
     pub r#type: String, // "IN" | "OUT"
-    pub quantity: i64,
+    #[sqlx(try_from = "Quantity")]
+    pub quantity: f64,
     pub reason: String,
     pub notes: Option<String>,
     pub created_at: Option<String>,
@@ -41,7 +43,8 @@ pub struct StockAdjustmentWithDetail {
     pub user_id: i64,
     pub user_name: String,
     pub r#type: String,
-    pub quantity: i64,
+    #[sqlx(try_from = "Quantity")]
+    pub quantity: f64,
     pub reason: String,
     pub notes: Option<String>,
     pub created_at: Option<String>,
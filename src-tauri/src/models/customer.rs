@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Customer {
+    pub id: i64,
+    pub name: String,
+    pub phone: Option<String>,
+    pub address: Option<String>,
+    pub price_level_id: Option<i64>, // NULL = harga eceran
+    pub price_level_name: Option<String>,
+    pub is_active: bool,
+    pub created_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CustomerPayload {
+    pub name: String,
+    pub phone: Option<String>,
+    pub address: Option<String>,
+    pub price_level_id: Option<i64>,
+}
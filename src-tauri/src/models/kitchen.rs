@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+/// Printer tujuan tiket pesanan (mis. dapur, bar).
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct PrinterProfile {
+    pub id: i64,
+    pub name: String,
+    pub printer_port: String,
+    pub paper_width: String, // "58mm" | "80mm"
+    pub is_active: bool,
+    pub created_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PrinterProfilePayload {
+    pub name: String,
+    pub printer_port: String,
+    pub paper_width: String,
+    pub is_active: bool,
+}
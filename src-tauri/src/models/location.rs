@@ -0,0 +1,94 @@
+use super::quantity::Quantity;
+use serde::{Deserialize, Serialize};
+
+/// Lokasi stok (toko / gudang). Tepat satu lokasi utama (`is_default`).
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Location {
+    pub id: i64,
+    pub name: String,
+    pub location_type: String, // "STORE" | "WAREHOUSE"
+    pub is_default: bool,
+    pub is_active: bool,
+    pub created_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LocationPayload {
+    pub name: String,
+    pub location_type: String,
+}
+
+/// Stok satu produk di satu lokasi.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct LocationStock {
+    pub location_id: i64,
+    pub location_name: String,
+    pub is_default: bool,
+    #[sqlx(try_from = "Quantity")]
+    pub quantity: f64,
+    /// Qty dalam perjalanan menuju lokasi ini
+    #[sqlx(try_from = "Quantity")]
+    pub incoming: f64,
+}
+
+/// Stok produk di satu lokasi (untuk daftar per lokasi).
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct LocationProductStock {
+    pub product_id: i64,
+    pub name: String,
+    pub sku: Option<String>,
+    pub unit: String,
+    pub is_weighed: bool,
+    #[sqlx(try_from = "Quantity")]
+    pub quantity: f64,
+}
+
+/// Transfer stok antar lokasi. Status: IN_TRANSIT → RECEIVED, atau CANCELLED.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct StockTransfer {
+    pub id: i64,
+    pub transfer_number: String,
+    pub from_location_id: i64,
+    pub from_location_name: String,
+    pub to_location_id: i64,
+    pub to_location_name: String,
+    pub status: String,
+    pub notes: Option<String>,
+    pub created_by_name: Option<String>,
+    pub created_at: Option<String>,
+    pub closed_by_name: Option<String>,
+    pub closed_at: Option<String>,
+    pub item_count: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct StockTransferItem {
+    pub id: i64,
+    pub transfer_id: i64,
+    pub product_id: i64,
+    pub product_name: String,
+    pub unit: String,
+    #[sqlx(try_from = "Quantity")]
+    pub quantity: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StockTransferDetail {
+    #[serde(flatten)]
+    pub transfer: StockTransfer,
+    pub items: Vec<StockTransferItem>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct StockTransferItemPayload {
+    pub product_id: i64,
+    pub quantity: f64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct StockTransferPayload {
+    pub from_location_id: i64,
+    pub to_location_id: i64,
+    pub notes: Option<String>,
+    pub items: Vec<StockTransferItemPayload>,
+}
@@ -1,7 +1,15 @@
 pub mod discount;
 pub mod product;
+pub mod quantity;
 pub mod settings;
 pub mod transaction;
 pub mod user;
 pub mod activity;
 pub mod payment;
+pub mod kitchen;
+pub mod print_job;
+pub mod customer;
+pub mod pricing;
+pub mod purchase;
+pub mod opname;
+pub mod location;
@@ -0,0 +1,75 @@
+use super::quantity::Quantity;
+use serde::{Deserialize, Serialize};
+
+/// Sesi stock opname. Status: OPEN → APPROVED, atau CANCELLED.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct OpnameSession {
+    pub id: i64,
+    pub name: String,
+    pub status: String,
+    pub notes: Option<String>,
+    pub created_by: Option<i64>,
+    pub created_by_name: Option<String>,
+    pub created_at: Option<String>,
+    pub closed_by_name: Option<String>,
+    pub closed_at: Option<String>,
+    /// Lokasi yang di-opname (None = lokasi utama)
+    pub location_id: Option<i64>,
+    pub location_name: Option<String>,
+    /// Jumlah produk dalam snapshot
+    pub item_count: i64,
+    /// Jumlah produk yang sudah dihitung
+    pub counted_count: i64,
+}
+
+/// Satu produk dalam sesi: stok sistem saat snapshot vs hasil hitung.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct OpnameItem {
+    pub product_id: i64,
+    pub product_name: String,
+    pub sku: Option<String>,
+    pub barcode: Option<String>,
+    pub unit: String,
+    #[sqlx(try_from = "Quantity")]
+    pub system_stock: f64,
+    /// Total hitungan semua kasir (None = belum dihitung)
+    pub counted: Option<f64>,
+    /// counted - system_stock
+    pub variance: Option<f64>,
+    pub cost_price: f64,
+    pub variance_value: Option<f64>,
+    /// Nama penghitung, dipisah koma
+    pub counted_by: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OpnameSessionDetail {
+    #[serde(flatten)]
+    pub session: OpnameSession,
+    pub items: Vec<OpnameItem>,
+    pub total_variance_value: f64,
+}
+
+/// Satu entri hitung (scan / input) dari kasir.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct OpnameCount {
+    pub id: i64,
+    pub session_id: i64,
+    pub product_id: i64,
+    pub product_name: String,
+    pub unit: String,
+    pub quantity: f64,
+    pub counted_by: Option<i64>,
+    pub counted_by_name: Option<String>,
+    pub counted_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OpnameCountPayload {
+    pub session_id: i64,
+    /// Barcode / SKU hasil scan; atau isi product_id
+    pub barcode: Option<String>,
+    pub product_id: Option<i64>,
+    /// Kosong = 1 (atau isi kemasan untuk barcode kemasan); 0 = barang tidak ditemukan
+    pub quantity: Option<f64>,
+}
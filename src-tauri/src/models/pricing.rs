@@ -0,0 +1,95 @@
+use serde::{Deserialize, Serialize};
+
+/// Level harga pelanggan (Reseller, Member, ...)
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct PriceLevel {
+    pub id: i64,
+    pub name: String,
+    pub discount_percent: f64, // potongan default jika produk tidak punya harga level
+    pub created_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PriceLevelPayload {
+    pub name: String,
+    #[serde(default)]
+    pub discount_percent: f64,
+}
+
+/// Harga grosir: beli >= min_quantity (satuan dasar) → harga per satuan dasar
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct PriceTier {
+    pub min_quantity: f64,
+    pub price: f64,
+}
+
+/// Harga khusus satu produk untuk satu level
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct LevelPrice {
+    pub price_level_id: i64,
+    pub price: f64,
+}
+
+/// Aturan harga satu produk (grosir + harga per level)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProductPricing {
+    pub tiers: Vec<PriceTier>,
+    pub level_prices: Vec<LevelPrice>,
+}
+
+/// Item keranjang yang harganya ingin dihitung server
+#[derive(Debug, Clone, Deserialize)]
+pub struct PriceQuoteItem {
+    pub product_id: i64,
+    pub quantity: f64,
+    #[serde(default)]
+    pub unit_id: Option<i64>,
+    /// Barcode label timbangan yang di-scan; harga label dihitung ulang server
+    #[serde(default)]
+    pub scanned_barcode: Option<String>,
+}
+
+/// Perubahan harga terjadwal (produk atau kategori)
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct PriceSchedule {
+    pub id: i64,
+    pub product_id: Option<i64>,
+    pub product_name: Option<String>,
+    pub category_id: Option<i64>,
+    pub category_name: Option<String>,
+    pub new_price: Option<f64>,
+    pub percent_change: Option<f64>,
+    pub effective_at: String, // UTC "YYYY-MM-DD HH:MM:SS"
+    pub status: String,       // "PENDING" | "APPLIED" | "CANCELLED"
+    pub notes: Option<String>,
+    pub applied_at: Option<String>,
+    pub applied_count: i64,
+    pub created_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PriceSchedulePayload {
+    pub product_id: Option<i64>,
+    pub category_id: Option<i64>,
+    pub new_price: Option<f64>,
+    pub percent_change: Option<f64>,
+    pub effective_at: String, // RFC 3339
+    pub notes: Option<String>,
+}
+
+/// Riwayat harga jual & modal satu produk
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct PriceHistoryEntry {
+    pub id: i64,
+    pub product_id: i64,
+    pub old_price: f64,
+    pub new_price: f64,
+    pub old_cost_price: f64,
+    pub new_cost_price: f64,
+    pub source: String, // "MANUAL" | "SCHEDULE"
+    /// Satuan jual / tier grosir yang berubah; None = harga satuan dasar
+    pub target: Option<String>,
+    pub schedule_id: Option<i64>,
+    pub changed_by_name: Option<String>,
+    pub changed_at: Option<String>,
+}
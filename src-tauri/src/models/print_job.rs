@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+
+/// Job cetak di antrian (tanpa payload biner)
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct PrintJob {
+    pub id: i64,
+    pub job_type: String,       // "RECEIPT" | "LABEL" | "KITCHEN"
+    pub reference_id: Option<String>,
+    pub printer_port: String,
+    pub status: String,         // "QUEUED" | "PRINTING" | "DONE" | "FAILED"
+    pub attempts: i64,
+    pub max_attempts: i64,
+    pub last_error: Option<String>,
+    pub next_attempt_at: Option<String>,
+    pub created_by: Option<i64>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+    pub printed_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaginatedPrintJobs {
+    pub data: Vec<PrintJob>,
+    pub total: i64,
+    pub page: i64,
+    pub per_page: i64,
+}
+
+/// Payload event `print-job-failed`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrintJobFailedEvent {
+    pub job_id: i64,
+    pub job_type: String,
+    pub reference_id: Option<String>,
+    pub attempts: i64,
+    pub error: String,
+}
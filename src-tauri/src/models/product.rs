@@ -1,4 +1,6 @@
+use super::quantity::Quantity;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct Product {
@@ -8,10 +10,29 @@ pub struct Product {
     pub name: String,
     pub price: f64,
     pub cost_price: f64,
-    pub stock: i64,
+    #[sqlx(try_from = "Quantity")]
+    pub stock: f64,
     pub barcode: Option<String>,
     pub image_path: Option<String>,
     pub is_active: bool,
+    /// Dijual per berat (kg) — qty & stok boleh desimal
+    #[sqlx(default)]
+    pub is_weighed: bool,
+    /// Satuan dasar stok, mis. "pcs" / "kg"
+    #[sqlx(default)]
+    pub unit: String,
+    /// Produk induk (untuk varian)
+    #[sqlx(default)]
+    pub parent_id: Option<i64>,
+    /// Atribut varian (JSON), mis. {"Ukuran":"L","Warna":"Merah"}
+    #[sqlx(default)]
+    pub variant_attributes: Option<String>,
+    /// Paket: stok dihitung dari komponen (product_components)
+    #[sqlx(default)]
+    pub is_kit: bool,
+    /// Menu dengan resep: menjual memotong stok bahan
+    #[sqlx(default)]
+    pub is_recipe: bool,
     pub created_at: Option<String>,
     pub updated_at: Option<String>,
 }
@@ -26,10 +47,37 @@ pub struct ProductWithCategory {
     pub name: String,
     pub price: f64,
     pub cost_price: f64,
-    pub stock: i64,
+    #[sqlx(try_from = "Quantity")]
+    pub stock: f64,
     pub barcode: Option<String>,
     pub image_path: Option<String>,
     pub is_active: bool,
+    /// Dijual per berat (kg) — qty & stok boleh desimal
+    #[sqlx(default)]
+    pub is_weighed: bool,
+    #[sqlx(default)]
+    pub unit: String,
+    #[sqlx(default)]
+    pub parent_id: Option<i64>,
+    #[sqlx(default)]
+    pub variant_attributes: Option<String>,
+    /// Jumlah varian aktif (hanya diisi get_products)
+    #[sqlx(default)]
+    pub variant_count: i64,
+    #[sqlx(default)]
+    pub is_kit: bool,
+    #[sqlx(default)]
+    pub is_recipe: bool,
+}
+
+/// Hasil scan barcode. Untuk barcode timbangan (harga/berat tertanam),
+/// `scanned_quantity` & `scanned_price` mengisi jumlah dan harga satuan di keranjang.
+#[derive(Debug, Clone, Serialize)]
+pub struct ScannedProduct {
+    #[serde(flatten)]
+    pub product: ProductWithCategory,
+    pub scanned_quantity: Option<f64>,
+    pub scanned_price: Option<f64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
@@ -44,6 +92,153 @@ pub struct CategoryWithCount {
     pub id: i64,
     pub name: String,
     pub product_count: i64,
+    /// Printer tujuan tiket dapur/bar
+    #[sqlx(default)]
+    pub printer_profile_id: Option<i64>,
+}
+
+/// Barcode tambahan produk (barcode lama supplier / barcode kemasan).
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ProductBarcode {
+    pub id: i64,
+    pub product_id: i64,
+    pub barcode: String,
+    /// Jumlah unit per scan (1 = satuan, 24 = karton isi 24)
+    pub pack_quantity: i64,
+    pub label: Option<String>,
+    pub created_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ProductBarcodePayload {
+    pub barcode: String,
+    #[serde(default = "default_pack_quantity")]
+    pub pack_quantity: i64,
+    pub label: Option<String>,
+}
+
+fn default_pack_quantity() -> i64 {
+    1
+}
+
+/// Satuan jual tambahan (pack, box, ...) dengan faktor konversi ke satuan dasar.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ProductUnit {
+    pub id: i64,
+    pub product_id: i64,
+    pub name: String,
+    /// Jumlah satuan dasar per 1 satuan ini (mis. box = 40 pcs)
+    pub conversion: f64,
+    /// Harga jual per 1 satuan ini
+    pub price: f64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ProductUnitPayload {
+    pub name: String,
+    pub conversion: f64,
+    pub price: f64,
+}
+
+/// Komponen paket (kit) / bahan resep beserta stoknya saat ini.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ProductComponent {
+    pub component_id: i64,
+    pub name: String,
+    /// Satuan takaran (resep bisa memakai satuan jual bahan, mis. "ml")
+    pub unit: String,
+    /// Jumlah komponen dalam `unit` per 1 paket / porsi
+    #[sqlx(try_from = "Quantity")]
+    pub quantity: f64,
+    /// Satuan jual bahan yang dipakai (None = satuan dasar)
+    #[sqlx(default)]
+    pub unit_id: Option<i64>,
+    /// Satuan dasar per 1 `unit`
+    #[sqlx(default)]
+    pub unit_conversion: f64,
+    /// Satuan dasar stok komponen
+    #[sqlx(default)]
+    pub base_unit: String,
+    #[sqlx(try_from = "Quantity")]
+    pub stock: f64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ProductComponentPayload {
+    pub component_id: i64,
+    pub quantity: f64,
+    /// Satuan jual bahan (resep); None = satuan dasar
+    #[serde(default)]
+    pub unit_id: Option<i64>,
+}
+
+/// Batch stok (no. batch, kedaluwarsa, sisa qty, modal). Penjualan memotong
+/// batch yang paling cepat kedaluwarsa lebih dulu (FEFO).
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ProductBatch {
+    pub id: i64,
+    pub product_id: i64,
+    #[sqlx(default)]
+    pub product_name: Option<String>,
+    pub batch_number: Option<String>,
+    /// Tanggal kedaluwarsa (YYYY-MM-DD)
+    pub expiry_date: Option<String>,
+    /// Sisa qty batch (satuan dasar)
+    #[sqlx(try_from = "Quantity")]
+    pub quantity: f64,
+    #[sqlx(try_from = "Quantity")]
+    pub initial_quantity: f64,
+    pub cost_price: f64,
+    pub received_at: Option<String>,
+    /// Sisa hari sampai kedaluwarsa (negatif = sudah lewat)
+    #[sqlx(default)]
+    pub days_to_expiry: Option<i64>,
+}
+
+/// Data batch untuk stok masuk (adjust_stock / penerimaan barang).
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct StockBatchPayload {
+    pub batch_number: Option<String>,
+    pub expiry_date: Option<String>,
+    /// Modal per satuan dasar; kosong = harga modal produk
+    pub cost_price: Option<f64>,
+}
+
+/// Hasil hitung fisik satu bahan/produk.
+#[derive(Debug, Clone, Deserialize)]
+pub struct StockCountPayload {
+    pub product_id: i64,
+    /// Jumlah terhitung dalam satuan dasar
+    pub counted: f64,
+}
+
+/// Pemakaian bahan resep dalam satu periode: teoretis (dari penjualan) vs
+/// aktual (teoretis + selisih hitung stok).
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct IngredientUsage {
+    pub product_id: i64,
+    pub name: String,
+    pub unit: String,
+    pub theoretical: f64,
+    /// Kekurangan dari hitung stok (positif = stok fisik lebih sedikit)
+    pub count_variance: f64,
+    pub actual: f64,
+    /// Nilai selisih = count_variance × harga modal
+    pub variance_value: f64,
+    pub stock: f64,
+    pub last_counted_at: Option<String>,
+}
+
+/// Payload varian baru. Kategori, satuan, dan gambar mengikuti produk induk;
+/// harga/modal kosong = sama dengan induk.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreateVariantPayload {
+    pub attributes: BTreeMap<String, String>,
+    pub sku: Option<String>,
+    pub barcode: Option<String>,
+    pub price: Option<f64>,
+    pub cost_price: Option<f64>,
+    pub stock: f64,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -54,8 +249,16 @@ pub struct CreateProductPayload {
     pub category_id: Option<i64>,
     pub price: f64,
     pub cost_price: f64,
-    pub stock: i64,
+    pub stock: f64,
     pub image_path: Option<String>,
+    #[serde(default)]
+    pub is_weighed: bool,
+    /// Satuan dasar; kosong = "pcs" (atau "kg" untuk produk timbang)
+    #[serde(default)]
+    pub unit: Option<String>,
+    /// Barcode tambahan (dipakai bulk import)
+    #[serde(default)]
+    pub extra_barcodes: Vec<ProductBarcodePayload>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -68,4 +271,8 @@ pub struct UpdateProductPayload {
     pub cost_price: f64,
     pub is_active: bool,
     pub image_path: Option<String>,
+    #[serde(default)]
+    pub is_weighed: bool,
+    #[serde(default)]
+    pub unit: Option<String>,
 }
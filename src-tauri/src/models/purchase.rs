@@ -0,0 +1,174 @@
+use super::quantity::Quantity;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Supplier {
+    pub id: i64,
+    pub name: String,
+    pub contact_person: Option<String>,
+    pub phone: Option<String>,
+    pub email: Option<String>,
+    pub address: Option<String>,
+    pub notes: Option<String>,
+    pub is_active: bool,
+    pub created_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SupplierPayload {
+    pub name: String,
+    pub contact_person: Option<String>,
+    pub phone: Option<String>,
+    pub email: Option<String>,
+    pub address: Option<String>,
+    pub notes: Option<String>,
+}
+
+/// Purchase order (PO). Status: DRAFT → SENT → PARTIALLY_RECEIVED → RECEIVED,
+/// atau CANCELLED.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct PurchaseOrder {
+    pub id: i64,
+    pub po_number: String,
+    pub supplier_id: i64,
+    pub supplier_name: String,
+    pub status: String,
+    pub expected_date: Option<String>,
+    pub notes: Option<String>,
+    pub total_amount: f64,
+    pub created_by: Option<i64>,
+    pub created_by_name: Option<String>,
+    pub created_at: Option<String>,
+    pub sent_at: Option<String>,
+    pub closed_at: Option<String>,
+    /// Jumlah baris item (hanya diisi daftar PO)
+    #[sqlx(default)]
+    pub item_count: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct PurchaseOrderItem {
+    pub id: i64,
+    pub purchase_order_id: i64,
+    pub product_id: i64,
+    pub product_name: String,
+    pub sku: Option<String>,
+    pub unit: String,
+    /// Qty dipesan (satuan dasar)
+    #[sqlx(try_from = "Quantity")]
+    pub quantity: f64,
+    #[sqlx(try_from = "Quantity")]
+    pub received_quantity: f64,
+    /// Perkiraan modal per satuan dasar
+    pub unit_cost: f64,
+    pub subtotal: f64,
+}
+
+/// PO beserta item-itemnya.
+#[derive(Debug, Clone, Serialize)]
+pub struct PurchaseOrderDetail {
+    #[serde(flatten)]
+    pub order: PurchaseOrder,
+    pub supplier: Supplier,
+    pub items: Vec<PurchaseOrderItem>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PurchaseOrderItemPayload {
+    pub product_id: i64,
+    pub quantity: f64,
+    pub unit_cost: f64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PurchaseOrderPayload {
+    pub supplier_id: i64,
+    pub expected_date: Option<String>,
+    pub notes: Option<String>,
+    pub items: Vec<PurchaseOrderItemPayload>,
+}
+
+/// Dokumen penerimaan barang (GR), bisa terkait PO atau berdiri sendiri.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct GoodsReceipt {
+    pub id: i64,
+    pub receipt_number: String,
+    pub purchase_order_id: Option<i64>,
+    pub po_number: Option<String>,
+    pub supplier_id: Option<i64>,
+    pub supplier_name: Option<String>,
+    pub invoice_number: Option<String>,
+    pub notes: Option<String>,
+    pub total_amount: f64,
+    pub received_by: Option<i64>,
+    pub received_by_name: Option<String>,
+    pub received_at: Option<String>,
+    pub location_id: Option<i64>,
+    pub location_name: Option<String>,
+    /// Jumlah baris item (hanya diisi daftar GR)
+    #[sqlx(default)]
+    pub item_count: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct GoodsReceiptItem {
+    pub id: i64,
+    pub goods_receipt_id: i64,
+    pub product_id: i64,
+    pub product_name: String,
+    pub unit: String,
+    #[sqlx(try_from = "Quantity")]
+    pub quantity: f64,
+    pub unit_cost: f64,
+    pub subtotal: f64,
+    pub batch_id: Option<i64>,
+    pub batch_number: Option<String>,
+    pub expiry_date: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GoodsReceiptDetail {
+    #[serde(flatten)]
+    pub receipt: GoodsReceipt,
+    pub items: Vec<GoodsReceiptItem>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GoodsReceiptItemPayload {
+    pub product_id: i64,
+    pub quantity: f64,
+    pub unit_cost: f64,
+    pub batch_number: Option<String>,
+    pub expiry_date: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GoodsReceiptPayload {
+    /// Jika diisi, supplier & barang mengikuti PO dan qty diterima PO diperbarui
+    pub purchase_order_id: Option<i64>,
+    pub supplier_id: Option<i64>,
+    pub invoice_number: Option<String>,
+    pub notes: Option<String>,
+    /// Lokasi penerimaan barang; kosong = lokasi utama
+    #[serde(default)]
+    pub location_id: Option<i64>,
+    pub items: Vec<GoodsReceiptItemPayload>,
+}
+
+/// Satu pergerakan HPP rata-rata bergerak.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct CostHistoryEntry {
+    pub id: i64,
+    pub product_id: i64,
+    pub goods_receipt_id: Option<i64>,
+    pub receipt_number: Option<String>,
+    #[sqlx(try_from = "Quantity")]
+    pub stock_before: f64,
+    pub cost_before: f64,
+    #[sqlx(try_from = "Quantity")]
+    pub quantity: f64,
+    pub unit_cost: f64,
+    pub cost_after: f64,
+    pub created_by_name: Option<String>,
+    pub created_at: Option<String>,
+}
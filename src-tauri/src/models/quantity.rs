@@ -0,0 +1,68 @@
+use sqlx::sqlite::{Sqlite, SqliteArgumentValue, SqliteTypeInfo, SqliteValueRef};
+
+/// Jumlah stok / qty yang boleh desimal (produk timbang).
+///
+/// Kolom `stock` & `quantity` bertipe INTEGER sehingga SQLite menyimpan
+/// 3.0 sebagai integer dan 1.25 sebagai real. Decoder `f64` bawaan sqlx
+/// menolak nilai integer, jadi tipe ini menerima keduanya. Dipakai lewat
+/// `#[sqlx(try_from = "Quantity")]` atau langsung di tuple `query_as`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+pub struct Quantity(pub f64);
+
+impl From<Quantity> for f64 {
+    fn from(q: Quantity) -> Self {
+        q.0
+    }
+}
+
+impl sqlx::Type<Sqlite> for Quantity {
+    fn type_info() -> SqliteTypeInfo {
+        <f64 as sqlx::Type<Sqlite>>::type_info()
+    }
+
+    fn compatible(ty: &SqliteTypeInfo) -> bool {
+        <f64 as sqlx::Type<Sqlite>>::compatible(ty) || <i64 as sqlx::Type<Sqlite>>::compatible(ty)
+    }
+}
+
+impl<'r> sqlx::Decode<'r, Sqlite> for Quantity {
+    fn decode(value: SqliteValueRef<'r>) -> Result<Self, sqlx::error::BoxDynError> {
+        // sqlite3_value_double mengonversi integer tanpa kehilangan presisi
+        <f64 as sqlx::Decode<Sqlite>>::decode(value).map(Quantity)
+    }
+}
+
+impl<'q> sqlx::Encode<'q, Sqlite> for Quantity {
+    fn encode_by_ref(&self, args: &mut Vec<SqliteArgumentValue<'q>>) -> sqlx::encode::IsNull {
+        <f64 as sqlx::Encode<Sqlite>>::encode_by_ref(&self.0, args)
+    }
+}
+
+/// Qty produk non-timbang harus bilangan bulat positif; produk timbang boleh
+/// desimal (maks. 3 angka di belakang koma = gram).
+pub fn validate_quantity(quantity: f64, is_weighed: bool) -> Result<(), String> {
+    if !quantity.is_finite() || quantity <= 0.0 {
+        return Err("Jumlah harus lebih dari 0".into());
+    }
+    if !is_weighed && quantity.fract() != 0.0 {
+        return Err("Jumlah produk ini harus bilangan bulat".into());
+    }
+    Ok(())
+}
+
+/// Hasil hitung fisik (stock opname): boleh 0 (barang memang habis), selain
+/// itu aturannya sama dengan `validate_quantity`.
+pub fn validate_counted_quantity(quantity: f64, is_weighed: bool) -> Result<(), String> {
+    if quantity == 0.0 {
+        return Ok(());
+    }
+    if !quantity.is_finite() || quantity < 0.0 {
+        return Err("Jumlah hitung tidak boleh negatif".into());
+    }
+    validate_quantity(quantity, is_weighed)
+}
+
+/// Bulatkan qty timbang ke gram agar tidak menumpuk error floating point.
+pub fn round_quantity(quantity: f64) -> f64 {
+    (quantity * 1000.0).round() / 1000.0
+}
@@ -1,3 +1,4 @@
+use super::quantity::Quantity;
 use serde::{Deserialize, Serialize};
 
 /// Profil perusahaan/toko.
@@ -23,6 +24,12 @@ pub struct ReceiptSettings {
     pub show_discount_detail: bool,
     pub paper_width: String, // "58mm" | "80mm"
     pub copies: i32,
+    /// Isi QR di struk: "NONE" | "TRANSACTION" | "QRIS"
+    #[serde(default)]
+    pub qr_mode: String,
+    /// Template link QR untuk mode TRANSACTION, `{id}` diganti ID transaksi
+    #[serde(default)]
+    pub qr_link_template: String,
 }
 
 /// Pengaturan pajak.
@@ -34,17 +41,188 @@ pub struct TaxSettings {
     pub is_included: bool,
 }
 
+/// Pengaturan laci kas (dibuka lewat printer struk, `ESC p`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DrawerSettings {
+    pub enabled: bool,
+    /// Buka laci otomatis setelah transaksi tunai
+    pub open_on_cash: bool,
+    pub pin: u8, // 2 | 5
+    pub pulse_on_ms: u16,
+    pub pulse_off_ms: u16,
+}
+
+impl Default for DrawerSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            open_on_cash: true,
+            pin: 2,
+            pulse_on_ms: 100,
+            pulse_off_ms: 100,
+        }
+    }
+}
+
+/// Pengaturan printer label barcode (bisa berbeda dari printer struk).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LabelSettings {
+    /// Kosong = pakai printer struk (ESC/POS)
+    pub printer_port: String,
+    pub language: String, // "ESCPOS" | "TSPL" | "ZPL"
+    pub size: String,     // "33x15" | "50x30" | "LEBARxTINGGI" (mm)
+    pub dpi: u32,         // 203 | 300
+    pub gap_mm: f32,
+}
+
+impl Default for LabelSettings {
+    fn default() -> Self {
+        Self {
+            printer_port: String::new(),
+            language: "ESCPOS".into(),
+            size: "50x30".into(),
+            dpi: 203,
+            gap_mm: 2.0,
+        }
+    }
+}
+
+/// Pengaturan tiket pesanan dapur/bar (printer per kategori).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct KitchenSettings {
+    /// Cetak tiket otomatis setelah transaksi dibuat
+    pub auto_print: bool,
+}
+
+/// Pengaturan pole display pelanggan (VFD/LCD 2x20 via serial).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DisplaySettings {
+    pub enabled: bool,
+    pub port: String,
+    pub baud_rate: u32,
+    pub protocol: String, // "ESCPOS" | "CD5220"
+    /// Pesan saat tidak ada transaksi (maks. 2 baris x 20 karakter)
+    pub idle_message: String,
+}
+
+impl Default for DisplaySettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: String::new(),
+            baud_rate: 9600,
+            protocol: "ESCPOS".into(),
+            idle_message: "Selamat Datang".into(),
+        }
+    }
+}
+
+/// Pengaturan timbangan elektronik (serial) untuk produk timbang.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScaleSettings {
+    pub enabled: bool,
+    pub port: String,
+    pub baud_rate: u32,
+    pub protocol: String,     // "CONTINUOUS" | "POLLED"
+    pub poll_command: String, // dikirim + CR pada mode POLLED, mis. "W"
+}
+
+impl Default for ScaleSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: String::new(),
+            baud_rate: 9600,
+            protocol: "CONTINUOUS".into(),
+            poll_command: "W".into(),
+        }
+    }
+}
+
+/// Aturan barcode in-store (EAN-13 prefix 20–29) dari label timbangan:
+/// `prefix` + PLU + [digit cek harga] + nilai + digit cek EAN.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScaleBarcodeRule {
+    /// Prefix barcode, mis. "20" atau "2"
+    pub prefix: String,
+    /// Jumlah digit PLU (dicocokkan ke SKU / barcode produk)
+    pub plu_length: usize,
+    pub value_type: String, // "WEIGHT" | "PRICE"
+    /// Jumlah desimal nilai, mis. 3 untuk berat dalam gram → kg
+    pub value_decimals: u32,
+    /// Ada satu digit cek harga di antara PLU dan nilai (dilewati)
+    #[serde(default)]
+    pub value_check_digit: bool,
+    /// Tolak barcode dengan digit cek EAN-13 yang salah
+    pub verify_check_digit: bool,
+}
+
+/// Pengaturan parsing barcode timbangan.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScaleBarcodeSettings {
+    pub enabled: bool,
+    pub rules: Vec<ScaleBarcodeRule>,
+}
+
+impl Default for ScaleBarcodeSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            rules: vec![
+                ScaleBarcodeRule {
+                    prefix: "20".into(),
+                    plu_length: 5,
+                    value_type: "WEIGHT".into(),
+                    value_decimals: 3,
+                    value_check_digit: false,
+                    verify_check_digit: true,
+                },
+                ScaleBarcodeRule {
+                    prefix: "21".into(),
+                    plu_length: 5,
+                    value_type: "PRICE".into(),
+                    value_decimals: 0,
+                    value_check_digit: false,
+                    verify_check_digit: true,
+                },
+            ],
+        }
+    }
+}
+
 /// Semua pengaturan aplikasi.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppSettings {
     pub company: CompanyProfile,
     pub receipt: ReceiptSettings,
     pub tax: TaxSettings,
+    #[serde(default)]
+    pub drawer: DrawerSettings,
+    #[serde(default)]
+    pub label: LabelSettings,
+    #[serde(default)]
+    pub kitchen: KitchenSettings,
+    #[serde(default)]
+    pub display: DisplaySettings,
+    #[serde(default)]
+    pub scale: ScaleSettings,
+    #[serde(default)]
+    pub scale_barcode: ScaleBarcodeSettings,
     pub low_stock_threshold: i64,
+    /// Batch dengan kedaluwarsa ≤ N hari lagi masuk peringatan
+    #[serde(default = "default_expiry_warning_days")]
+    pub expiry_warning_days: i64,
+    /// Lokasi stok yang dipotong penjualan di kasir ini (None = lokasi utama)
+    #[serde(default)]
+    pub location_id: Option<i64>,
     pub printer_port: String,
     pub timezone: String,
 }
 
+fn default_expiry_warning_days() -> i64 {
+    30
+}
+
 /// Laporan keuangan lengkap untuk periode tertentu.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FinancialSummary {
@@ -69,7 +247,7 @@ pub struct DailyReport {
     pub total_revenue: f64,
     pub transaction_count: i64,
     pub average_transaction: f64,
-    pub total_items_sold: i64,
+    pub total_items_sold: f64,
     pub cash_total: f64,
     pub debit_total: f64,
     pub qris_total: f64,
@@ -89,7 +267,18 @@ pub struct ChartPoint {
 pub struct ProductStat {
     pub product_id: i64,
     pub name: String,
-    pub total_sold: i64,
+    #[sqlx(try_from = "Quantity")]
+    pub total_sold: f64,
+    pub total_revenue: f64,
+}
+
+/// Penjualan per kategori (varian ikut kategori produk induk).
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct CategoryStat {
+    pub category_id: Option<i64>,
+    pub name: String,
+    #[sqlx(try_from = "Quantity")]
+    pub total_sold: f64,
     pub total_revenue: f64,
 }
 
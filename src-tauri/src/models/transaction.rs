@@ -1,3 +1,4 @@
+use super::quantity::Quantity;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
@@ -18,6 +19,10 @@ pub struct Transaction {
     pub notes: Option<String>,
     pub qris_reference: Option<String>,
     pub payment_status: Option<String>,
+    #[sqlx(default)]
+    pub reprint_count: i64,
+    #[sqlx(default)]
+    pub customer_id: Option<i64>,
 }
 
 /// Transaction dengan nama kasir (JOIN result).
@@ -40,6 +45,8 @@ pub struct TransactionWithCashier {
     pub notes: Option<String>,
     pub qris_reference: Option<String>,
     pub payment_status: Option<String>,
+    #[sqlx(default)]
+    pub reprint_count: i64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
@@ -47,10 +54,17 @@ pub struct TransactionItem {
     pub id: i64,
     pub transaction_id: String,
     pub product_id: i64,
-    pub quantity: i64,
+    #[sqlx(try_from = "Quantity")]
+    pub quantity: f64,
     pub price_at_time: f64,
     pub subtotal: f64,
     pub discount_amount: f64,
+    /// Satuan jual (quantity dalam satuan ini)
+    #[sqlx(default)]
+    pub unit: Option<String>,
+    /// Satuan dasar per 1 satuan jual — stok berkurang quantity x konversi
+    #[sqlx(default)]
+    pub unit_conversion: f64,
 }
 
 /// Item transaksi dengan nama produk (JOIN result).
@@ -60,10 +74,17 @@ pub struct TransactionItemWithProduct {
     pub transaction_id: String,
     pub product_id: i64,
     pub product_name: String,
-    pub quantity: i64,
+    #[sqlx(try_from = "Quantity")]
+    pub quantity: f64,
     pub price_at_time: f64,
     pub subtotal: f64,
     pub discount_amount: f64,
+    /// Satuan jual (quantity dalam satuan ini)
+    #[sqlx(default)]
+    pub unit: Option<String>,
+    /// Satuan dasar per 1 satuan jual — stok berkurang quantity x konversi
+    #[sqlx(default)]
+    pub unit_conversion: f64,
 }
 
 /// Detail lengkap satu transaksi (untuk frontend).
@@ -83,14 +104,42 @@ pub struct CreateTransactionPayload {
     pub payment_method: String,
     pub amount_paid: f64,
     pub notes: Option<String>,
+    /// Pelanggan (menentukan level harga); kosong = umum/eceran
+    #[serde(default)]
+    pub customer_id: Option<i64>,
+    /// Kredensial admin yang menyetujui override harga (tidak perlu jika
+    /// kasir sendiri admin)
+    #[serde(default)]
+    pub override_approval: Option<OverrideApproval>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OverrideApproval {
+    pub username: String,
+    pub password: String,
 }
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct CreateTransactionItem {
     pub product_id: i64,
-    pub quantity: i64,
-    pub price_at_time: f64,
+    pub quantity: f64, // desimal hanya untuk produk timbang (kg)
     pub discount_amount: f64, // diskon per item (0 jika tidak ada)
+    /// Catatan untuk dapur/bar, mis. "tanpa es"
+    #[serde(default)]
+    pub notes: Option<String>,
+    /// Satuan jual (product_units.id); kosong = satuan dasar
+    #[serde(default)]
+    pub unit_id: Option<i64>,
+    /// Harga yang tampil di kasir. Hanya untuk deteksi keranjang basi —
+    /// transaksi ditolak jika berbeda dari harga server.
+    #[serde(default)]
+    pub price_at_time: Option<f64>,
+    /// Harga manual per satuan jual; butuh persetujuan admin dan dicatat
+    #[serde(default)]
+    pub price_override: Option<f64>,
+    /// Barcode label timbangan asal item; harga label bukan harga manual
+    #[serde(default)]
+    pub scanned_barcode: Option<String>,
 }
 
 /// Hasil paginated untuk daftar transaksi.
@@ -0,0 +1,226 @@
+//! Encoder barcode ke pola modul (true = batang hitam) untuk digambar
+//! sendiri, mis. di PDF lembar stiker. Printer thermal/label memakai
+//! perintah barcode bawaan firmware, bukan modul ini. Juga parser barcode
+//! in-store (harga/berat tertanam) dari label timbangan.
+
+use crate::models::settings::ScaleBarcodeRule;
+
+/// Pola L (ganjil) EAN digit 0–9; pola R = komplemen L, pola G = R dibalik.
+const EAN_L: [&str; 10] = [
+    "0001101", "0011001", "0010011", "0111101", "0100011",
+    "0110001", "0101111", "0111011", "0110111", "0001011",
+];
+
+/// Paritas 6 digit kiri (pola L atau G) berdasarkan digit pertama.
+const EAN_PARITY: [&str; 10] = [
+    "LLLLLL", "LLGLGG", "LLGGLG", "LLGGGL", "LGLLGG",
+    "LGGLLG", "LGGGLL", "LGLGLG", "LGLGGL", "LGGLGL",
+];
+
+/// Lebar batang/spasi Code 128 untuk nilai 0–106 (106 = stop).
+const CODE128_PATTERNS: [&str; 107] = [
+    "212222", "222122", "222221", "121223", "121322", "131222", "122213", "122312",
+    "132212", "221213", "221312", "231212", "112232", "122132", "122231", "113222",
+    "123122", "123221", "223211", "221132", "221231", "213212", "223112", "312131",
+    "311222", "321122", "321221", "312212", "322112", "322211", "212123", "212321",
+    "232121", "111323", "131123", "131321", "112313", "132113", "132311", "211313",
+    "231113", "231311", "112133", "112331", "132131", "113123", "113321", "133121",
+    "313121", "211331", "231131", "213113", "213311", "213131", "311123", "311321",
+    "331121", "312113", "312311", "332111", "314111", "221411", "431111", "111224",
+    "111422", "121124", "121421", "141122", "141221", "112214", "112412", "122114",
+    "122411", "142112", "142211", "241211", "221114", "413111", "241112", "134111",
+    "111242", "121142", "121241", "114212", "124112", "124211", "411212", "421112",
+    "421211", "212141", "214121", "412121", "111143", "111341", "131141", "114113",
+    "114311", "411113", "411311", "113141", "114131", "311141", "411131", "211412",
+    "211214", "211232", "2331112",
+];
+
+const CODE128_START_B: usize = 104;
+const CODE128_STOP: usize = 106;
+
+/// Hitung digit cek EAN-13 dari 12 digit pertama.
+pub fn ean13_check_digit(digits: &[u8]) -> u8 {
+    let sum: u32 = digits
+        .iter()
+        .take(12)
+        .enumerate()
+        .map(|(i, d)| *d as u32 * if i % 2 == 0 { 1 } else { 3 })
+        .sum();
+    ((10 - sum % 10) % 10) as u8
+}
+
+/// Apakah kode adalah EAN-13 valid (13 digit dengan digit cek benar).
+pub fn is_valid_ean13(code: &str) -> bool {
+    let digits: Vec<u8> = code.bytes().filter(u8::is_ascii_digit).map(|b| b - b'0').collect();
+    digits.len() == 13 && code.len() == 13 && ean13_check_digit(&digits) == digits[12]
+}
+
+/// Encode EAN-13 (95 modul tanpa quiet zone). `None` jika kode tidak valid.
+pub fn ean13_modules(code: &str) -> Option<Vec<bool>> {
+    if !is_valid_ean13(code) {
+        return None;
+    }
+    let digits: Vec<usize> = code.bytes().map(|b| (b - b'0') as usize).collect();
+    let parity = EAN_PARITY[digits[0]].as_bytes();
+
+    let mut pattern = String::with_capacity(95);
+    pattern.push_str("101");
+    for (i, &d) in digits[1..7].iter().enumerate() {
+        if parity[i] == b'L' {
+            pattern.push_str(EAN_L[d]);
+        } else {
+            // G = pola R dibalik
+            pattern.extend(EAN_L[d].chars().map(invert).rev());
+        }
+    }
+    pattern.push_str("01010");
+    for &d in &digits[7..13] {
+        pattern.extend(EAN_L[d].chars().map(invert));
+    }
+    pattern.push_str("101");
+
+    Some(pattern.chars().map(|c| c == '1').collect())
+}
+
+fn invert(c: char) -> char {
+    if c == '1' { '0' } else { '1' }
+}
+
+/// Encode Code 128 set B (ASCII 32–126). `None` jika ada karakter lain.
+pub fn code128_modules(data: &str) -> Option<Vec<bool>> {
+    if data.is_empty() || !data.bytes().all(|b| (32..=126).contains(&b)) {
+        return None;
+    }
+
+    let mut values = vec![CODE128_START_B];
+    values.extend(data.bytes().map(|b| (b - 32) as usize));
+    let checksum = values
+        .iter()
+        .enumerate()
+        .map(|(i, v)| v * i.max(1))
+        .sum::<usize>()
+        % 103;
+    values.push(checksum);
+    values.push(CODE128_STOP);
+
+    let mut modules = Vec::new();
+    for v in values {
+        for (i, w) in CODE128_PATTERNS[v].bytes().enumerate() {
+            let bar = i % 2 == 0;
+            modules.extend(std::iter::repeat_n(bar, (w - b'0') as usize));
+        }
+    }
+    Some(modules)
+}
+
+/// Encode barcode: EAN-13 jika valid, selain itu Code 128.
+pub fn barcode_modules(code: &str) -> Option<Vec<bool>> {
+    ean13_modules(code).or_else(|| code128_modules(code))
+}
+
+/// Jenis nilai yang tertanam di barcode timbangan.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EmbeddedValue {
+    /// Berat dalam kg
+    Weight(f64),
+    /// Total harga item
+    Price(f64),
+}
+
+/// Hasil parsing barcode timbangan.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmbeddedBarcode {
+    pub plu: String,
+    pub value: EmbeddedValue,
+}
+
+/// Cocokkan barcode ke aturan pertama yang prefix-nya sesuai. `None` jika
+/// bukan barcode in-store; `Err` jika cocok tapi digit cek salah.
+pub fn parse_embedded_barcode(
+    code: &str,
+    rules: &[ScaleBarcodeRule],
+) -> Result<Option<EmbeddedBarcode>, String> {
+    let code = code.trim();
+    if code.len() != 13 || !code.bytes().all(|b| b.is_ascii_digit()) {
+        return Ok(None);
+    }
+
+    for rule in rules {
+        if rule.prefix.is_empty() || !code.starts_with(&rule.prefix) {
+            continue;
+        }
+        let plu_start = rule.prefix.len();
+        let value_start = plu_start + rule.plu_length + usize::from(rule.value_check_digit);
+        // Sisakan minimal 1 digit nilai + 1 digit cek EAN
+        if rule.plu_length == 0 || value_start >= 12 {
+            continue;
+        }
+
+        if rule.verify_check_digit && !is_valid_ean13(code) {
+            return Err(format!("Digit cek barcode {} tidak valid", code));
+        }
+
+        let raw: f64 = code[value_start..12].parse().map_err(|_| "Barcode tidak valid")?;
+        let value = raw / 10f64.powi(rule.value_decimals as i32);
+        let value = if rule.value_type.eq_ignore_ascii_case("PRICE") {
+            EmbeddedValue::Price(value)
+        } else {
+            EmbeddedValue::Weight(value)
+        };
+
+        return Ok(Some(EmbeddedBarcode {
+            plu: code[plu_start..plu_start + rule.plu_length].to_string(),
+            value,
+        }));
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_code128_patterns_width() {
+        for (i, p) in CODE128_PATTERNS.iter().enumerate() {
+            let width: u32 = p.bytes().map(|b| (b - b'0') as u32).sum();
+            let expected = if i == CODE128_STOP { 13 } else { 11 };
+            assert_eq!(width, expected, "pola {} salah", i);
+        }
+    }
+
+    #[test]
+    fn test_ean13() {
+        assert!(is_valid_ean13("8991234567891"));
+        assert!(!is_valid_ean13("8991234567899"));
+        let modules = ean13_modules("8991234567891").unwrap();
+        assert_eq!(modules.len(), 95);
+        assert_eq!(&modules[..3], &[true, false, true]);
+    }
+
+    #[test]
+    fn test_code128_length() {
+        // start + 3 karakter + checksum = 5 simbol x 11 modul, stop 13 modul
+        let modules = code128_modules("ABC").unwrap();
+        assert_eq!(modules.len(), 5 * 11 + 13);
+        assert!(code128_modules("é").is_none());
+    }
+
+    #[test]
+    fn test_parse_embedded_barcode() {
+        let rules = crate::models::settings::ScaleBarcodeSettings::default().rules;
+
+        // 20 | 01234 | 01250 | 8 → PLU 01234, 1,250 kg
+        let parsed = parse_embedded_barcode("2001234012508", &rules).unwrap().unwrap();
+        assert_eq!(parsed.plu, "01234");
+        assert_eq!(parsed.value, EmbeddedValue::Weight(1.25));
+
+        // 21 → total harga Rp15.000
+        let parsed = parse_embedded_barcode("2101234150009", &rules).unwrap().unwrap();
+        assert_eq!(parsed.value, EmbeddedValue::Price(15000.0));
+
+        // Digit cek salah → error; barcode biasa → None
+        assert!(parse_embedded_barcode("2001234012501", &rules).is_err());
+        assert_eq!(parse_embedded_barcode("8991234567890", &rules).unwrap(), None);
+    }
+}
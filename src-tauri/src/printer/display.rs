@@ -0,0 +1,144 @@
+//! Pole display pelanggan (VFD/LCD 2x20) — command set Epson ESC/POS display
+//! dan mode CD5220.
+
+use super::receipt::format_number;
+use serde::Deserialize;
+
+/// Lebar satu baris display.
+pub const DISPLAY_COLUMNS: usize = 20;
+
+/// Bahasa perintah display.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DisplayProtocol {
+    /// Epson ESC/POS customer display (DM-D series & kompatibel)
+    EscPos,
+    /// Mode CD5220 (banyak dipakai display generik)
+    Cd5220,
+}
+
+impl DisplayProtocol {
+    pub fn parse(value: &str) -> Self {
+        match value.to_uppercase().as_str() {
+            "CD5220" => DisplayProtocol::Cd5220,
+            _ => DisplayProtocol::EscPos,
+        }
+    }
+}
+
+/// Isi layar yang diminta frontend / setelah transaksi.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "UPPERCASE")]
+pub enum DisplayScreen {
+    /// Item terakhir yang di-scan + total berjalan
+    Item { name: String, price: f64, total: f64 },
+    Total { total: f64 },
+    Change { paid: f64, change: f64 },
+    Idle,
+}
+
+/// Potong / pad teks tepat selebar display. Karakter non-ASCII diganti '?'
+/// karena font bawaan display hanya ASCII.
+pub fn fit(text: &str) -> String {
+    let mut line: String = text
+        .chars()
+        .map(|c| if c.is_ascii() && !c.is_ascii_control() { c } else { '?' })
+        .take(DISPLAY_COLUMNS)
+        .collect();
+    while line.len() < DISPLAY_COLUMNS {
+        line.push(' ');
+    }
+    line
+}
+
+/// Teks kiri + kanan dalam satu baris; teks kiri dipotong bila tidak muat.
+pub fn split_line(left: &str, right: &str) -> String {
+    let right_len = right.chars().count().min(DISPLAY_COLUMNS);
+    let left: String = left.chars().take(DISPLAY_COLUMNS.saturating_sub(right_len + 1)).collect();
+    let pad = DISPLAY_COLUMNS - left.chars().count() - right_len;
+    fit(&format!("{}{}{}", left, " ".repeat(pad), right))
+}
+
+/// Dua baris teks untuk satu layar.
+pub fn screen_lines(screen: &DisplayScreen, idle_message: &str) -> (String, String) {
+    let rp = |v: f64| format!("Rp{}", format_number(v.round() as i64));
+    match screen {
+        DisplayScreen::Item { name, price, total } => {
+            (split_line(name, &rp(*price)), split_line("TOTAL", &rp(*total)))
+        }
+        DisplayScreen::Total { total } => (fit("TOTAL BAYAR"), split_line("", &rp(*total))),
+        DisplayScreen::Change { paid, change } => {
+            (split_line("BAYAR", &rp(*paid)), split_line("KEMBALI", &rp(*change)))
+        }
+        DisplayScreen::Idle => {
+            let mut lines = super::receipt::wrap_text(idle_message, DISPLAY_COLUMNS).into_iter();
+            let first = lines.next().unwrap_or_default();
+            let second = lines.next().unwrap_or_default();
+            (center(&first), center(&second))
+        }
+    }
+}
+
+fn center(text: &str) -> String {
+    let len = text.chars().count().min(DISPLAY_COLUMNS);
+    fit(&format!("{}{}", " ".repeat((DISPLAY_COLUMNS - len) / 2), text))
+}
+
+/// Susun byte perintah untuk menampilkan dua baris (menimpa isi layar).
+pub fn display_frame(protocol: DisplayProtocol, line1: &str, line2: &str) -> Vec<u8> {
+    let (line1, line2) = (fit(line1), fit(line2));
+    let mut out = Vec::with_capacity(64);
+    match protocol {
+        DisplayProtocol::EscPos => {
+            // ESC @ init, CLR, lalu US $ x y untuk posisi tiap baris
+            out.extend_from_slice(b"\x1B\x40\x0C");
+            out.extend_from_slice(b"\x1F\x24\x01\x01");
+            out.extend_from_slice(line1.as_bytes());
+            out.extend_from_slice(b"\x1F\x24\x01\x02");
+            out.extend_from_slice(line2.as_bytes());
+        }
+        DisplayProtocol::Cd5220 => {
+            // ESC Q A = baris atas, ESC Q B = baris bawah, diakhiri CR
+            out.extend_from_slice(b"\x1B\x40\x0C");
+            out.extend_from_slice(b"\x1B\x51\x41");
+            out.extend_from_slice(line1.as_bytes());
+            out.push(b'\r');
+            out.extend_from_slice(b"\x1B\x51\x42");
+            out.extend_from_slice(line2.as_bytes());
+            out.push(b'\r');
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_screen_lines() {
+        let screen = DisplayScreen::Item {
+            name: "Indomie Goreng Spesial Jumbo".into(),
+            price: 3500.0,
+            total: 125000.0,
+        };
+        let (l1, l2) = screen_lines(&screen, "");
+        assert_eq!(l1, "Indomie Gore Rp3.500");
+        assert_eq!(l2, "TOTAL      Rp125.000");
+        assert_eq!(l2.len(), DISPLAY_COLUMNS);
+
+        let (l1, l2) = screen_lines(&DisplayScreen::Idle, "Selamat Datang di Toko Saya");
+        assert_eq!(l1.trim(), "Selamat Datang di");
+        assert_eq!(l2.trim(), "Toko Saya");
+    }
+
+    #[test]
+    fn test_display_frame() {
+        let frame = display_frame(DisplayProtocol::Cd5220, "A", "B");
+        assert!(frame.starts_with(b"\x1B\x40\x0C\x1B\x51\x41A"));
+        assert_eq!(frame.len(), 3 + 3 + 20 + 1 + 3 + 20 + 1);
+
+        let frame = display_frame(DisplayProtocol::EscPos, "A", "B");
+        assert_eq!(&frame[3..7], b"\x1F\x24\x01\x01");
+        assert_eq!(frame.len(), 3 + 4 + 20 + 4 + 20);
+    }
+}
@@ -0,0 +1,393 @@
+//! Dokumen PDF: struk selebar kertas thermal dan invoice pajak A4.
+
+use super::escpos::Align;
+use super::pdf::{courier_width, PdfDocument, PdfFont, PdfPage, A4_HEIGHT, A4_WIDTH, PT_PER_MM};
+use super::raster::RasterImage;
+use super::receipt::{
+    columns, format_number, format_quantity, payment_label, wrap_text, ReceiptData, ReceiptLine, ReceiptTemplate,
+};
+use crate::models::settings::{CompanyProfile, TaxSettings};
+use serde::Deserialize;
+
+/// Resolusi printer thermal (dot per inci) untuk skala logo raster.
+const PRINTER_DPI: f32 = 203.0;
+
+/// Render struk ke PDF satu halaman dengan lebar kertas thermal.
+/// Isi sama dengan struk ESC/POS (memakai `ReceiptTemplate::lines`);
+/// QR code tidak dicetak karena dibuat oleh firmware printer.
+pub fn receipt_pdf(template: &ReceiptTemplate, data: &ReceiptData) -> Vec<u8> {
+    let paper_mm = if template.receipt.paper_width == "58mm" { 58.0 } else { 80.0 };
+    let width = paper_mm * PT_PER_MM;
+    let margin = 3.0 * PT_PER_MM;
+    let cols = template.columns();
+    // Ukuran font supaya `cols` karakter Courier pas selebar area cetak
+    let size = (width - 2.0 * margin) / (cols as f32 * 0.6);
+    let leading = size * 1.25;
+
+    let lines = template.lines(data);
+    let image_size = |image: &RasterImage| {
+        let w = (image.width as f32 * 72.0 / PRINTER_DPI).min(width - 2.0 * margin);
+        (w, w * image.height as f32 / image.width.max(1) as f32)
+    };
+
+    // Hitung tinggi halaman dulu
+    let mut height = 2.0 * margin;
+    for line in &lines {
+        height += match line {
+            ReceiptLine::Text { text, large, .. } => {
+                let wrap = if *large { cols / 2 } else { cols };
+                let factor = if *large { 2.0 } else { 1.0 };
+                wrap_text(text, wrap).len() as f32 * leading * factor
+            }
+            ReceiptLine::Columns { left, right, .. } => {
+                columns(left, right, cols).len() as f32 * leading
+            }
+            ReceiptLine::Separator(_) | ReceiptLine::Feed => leading,
+            ReceiptLine::Image(image) => image_size(image).1 + leading / 2.0,
+            ReceiptLine::QrCode(_) => 0.0,
+        };
+    }
+
+    let mut page = PdfPage::new(width, height);
+    let mut y = height - margin;
+    for line in &lines {
+        match line {
+            ReceiptLine::Text { text, align, bold, large } => {
+                let (wrap, font_size) = if *large { (cols / 2, size * 2.0) } else { (cols, size) };
+                let font = if *bold { PdfFont::CourierBold } else { PdfFont::Courier };
+                for l in wrap_text(text, wrap) {
+                    y -= leading * font_size / size;
+                    let x = match align {
+                        Align::Left => margin,
+                        Align::Center => (width - courier_width(&l, font_size)) / 2.0,
+                        Align::Right => width - margin - courier_width(&l, font_size),
+                    };
+                    page.text(x, y + leading * 0.2, font_size, font, &l);
+                }
+            }
+            ReceiptLine::Columns { left, right, bold } => {
+                let font = if *bold { PdfFont::CourierBold } else { PdfFont::Courier };
+                for l in columns(left, right, cols) {
+                    y -= leading;
+                    page.text(margin, y + leading * 0.2, size, font, &l);
+                }
+            }
+            ReceiptLine::Separator(ch) => {
+                y -= leading;
+                page.text(margin, y + leading * 0.2, size, PdfFont::Courier, &ch.to_string().repeat(cols));
+            }
+            ReceiptLine::Image(image) => {
+                let (w, h) = image_size(image);
+                y -= h;
+                page.image(image, (width - w) / 2.0, y, w, h);
+                y -= leading / 2.0;
+            }
+            ReceiptLine::QrCode(_) => {}
+            ReceiptLine::Feed => y -= leading,
+        }
+    }
+
+    let mut doc = PdfDocument::new(&format!("Struk {}", short_id(&data.transaction_id)));
+    doc.add_page(page);
+    doc.to_bytes()
+}
+
+/// Identitas pembeli di invoice (opsional).
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct InvoiceCustomer {
+    pub name: String,
+    #[serde(default)]
+    pub tax_number: String,
+    #[serde(default)]
+    pub address: String,
+}
+
+/// Nomor invoice: `INV/YYYYMMDD/XXXXXXXX` dari tanggal & id transaksi.
+pub fn invoice_number(data: &ReceiptData) -> String {
+    let date: String = data.timestamp.chars().filter(|c| c.is_ascii_digit()).take(8).collect();
+    format!("INV/{}/{}", date, short_id(&data.transaction_id))
+}
+
+fn short_id(transaction_id: &str) -> String {
+    transaction_id.chars().take(8).collect::<String>().to_uppercase()
+}
+
+// Layout invoice A4 (point)
+const MARGIN: f32 = 40.0;
+const RIGHT: f32 = A4_WIDTH - MARGIN;
+const ROW: f32 = 16.0;
+const COL_NO: f32 = MARGIN + 4.0;
+const COL_NAME: f32 = MARGIN + 28.0;
+const COL_QTY_R: f32 = 330.0;
+const COL_PRICE_R: f32 = 410.0;
+const COL_DISC_R: f32 = 480.0;
+const COL_TOTAL_R: f32 = RIGHT - 4.0;
+/// Batas bawah area tabel sebelum pindah halaman
+const TABLE_BOTTOM: f32 = 90.0;
+/// Jumlah karakter nama barang yang muat di kolom nama (Helvetica 9pt)
+const NAME_CHARS: usize = 48;
+
+/// Render invoice pajak A4 (multi-halaman bila item banyak).
+/// Menampilkan NPWP penjual dari `company.tax_number` dan rincian DPP + pajak.
+pub fn tax_invoice_pdf(
+    company: &CompanyProfile,
+    tax: &TaxSettings,
+    data: &ReceiptData,
+    customer: Option<&InvoiceCustomer>,
+    logo: Option<&RasterImage>,
+) -> Vec<u8> {
+    let number = invoice_number(data);
+    let mut pages: Vec<PdfPage> = Vec::new();
+
+    let mut page = PdfPage::new(A4_WIDTH, A4_HEIGHT);
+    let mut y = invoice_header(&mut page, company, data, customer, logo, &number);
+    y = table_header(&mut page, y);
+
+    for (i, item) in data.items.iter().enumerate() {
+        let name_lines = wrap_text(&item.name, NAME_CHARS);
+        let row_height = ROW * name_lines.len() as f32;
+        if y - row_height < TABLE_BOTTOM {
+            pages.push(page);
+            page = PdfPage::new(A4_WIDTH, A4_HEIGHT);
+            y = table_header(&mut page, A4_HEIGHT - MARGIN);
+        }
+
+        let baseline = y - ROW + 4.0;
+        page.text(COL_NO, baseline, 9.0, PdfFont::Helvetica, &(i + 1).to_string());
+        for (n, l) in name_lines.iter().enumerate() {
+            page.text(COL_NAME, baseline - ROW * n as f32, 9.0, PdfFont::Helvetica, l);
+        }
+        page.text_right(COL_QTY_R, baseline, 9.0, PdfFont::Courier, &format_quantity(item.quantity));
+        page.text_right(COL_PRICE_R, baseline, 9.0, PdfFont::Courier, &format_number(item.price.round() as i64));
+        let discount = if item.discount_amount > 0.0 {
+            format!("-{}", format_number(item.discount_amount.round() as i64))
+        } else {
+            "-".into()
+        };
+        page.text_right(COL_DISC_R, baseline, 9.0, PdfFont::Courier, &discount);
+        page.text_right(COL_TOTAL_R, baseline, 9.0, PdfFont::Courier, &format_number(item.subtotal.round() as i64));
+        y -= row_height;
+        page.line(MARGIN, y, RIGHT, y, 0.3);
+    }
+
+    // Blok total butuh ± 9 baris
+    if y - ROW * 9.0 < TABLE_BOTTOM - 40.0 {
+        pages.push(page);
+        page = PdfPage::new(A4_WIDTH, A4_HEIGHT);
+        y = A4_HEIGHT - MARGIN;
+    }
+    invoice_totals(&mut page, y - 8.0, tax, data);
+    pages.push(page);
+
+    // Footer + nomor halaman setelah jumlah halaman diketahui
+    let total_pages = pages.len();
+    let mut doc = PdfDocument::new(&format!("Invoice {}", number));
+    for (i, mut page) in pages.into_iter().enumerate() {
+        page.line(MARGIN, 60.0, RIGHT, 60.0, 0.5);
+        page.text(
+            MARGIN,
+            46.0,
+            8.0,
+            PdfFont::Helvetica,
+            "Dokumen ini dibuat secara elektronik dan sah tanpa tanda tangan.",
+        );
+        page.text_right(
+            RIGHT,
+            46.0,
+            8.0,
+            PdfFont::Courier,
+            &format!("Hal. {}/{}", i + 1, total_pages),
+        );
+        doc.add_page(page);
+    }
+    doc.to_bytes()
+}
+
+/// Kop invoice: identitas toko, judul, info transaksi, dan pembeli.
+/// Mengembalikan posisi y untuk tabel item.
+fn invoice_header(
+    page: &mut PdfPage,
+    company: &CompanyProfile,
+    data: &ReceiptData,
+    customer: Option<&InvoiceCustomer>,
+    logo: Option<&RasterImage>,
+    number: &str,
+) -> f32 {
+    let mut y = A4_HEIGHT - MARGIN;
+    let mut text_x = MARGIN;
+
+    if let Some(logo) = logo.filter(|l| l.width > 0 && l.height > 0) {
+        let h = 56.0;
+        let w = (h * logo.width as f32 / logo.height as f32).min(120.0);
+        page.image(logo, MARGIN, y - h, w, h);
+        text_x = MARGIN + w + 12.0;
+    }
+
+    let store_name = if company.store_name.trim().is_empty() { "TOKO" } else { company.store_name.trim() };
+    page.text(text_x, y - 14.0, 14.0, PdfFont::HelveticaBold, store_name);
+    let mut info_y = y - 28.0;
+    let contact = [
+        company.address.trim().to_string(),
+        [company.phone.trim(), company.email.trim()]
+            .iter()
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string())
+            .collect::<Vec<_>>()
+            .join("  |  "),
+        company.website.trim().to_string(),
+    ];
+    for line in contact.iter().filter(|l| !l.is_empty()) {
+        for l in wrap_text(line, 60) {
+            page.text(text_x, info_y, 8.5, PdfFont::Helvetica, &l);
+            info_y -= 11.0;
+        }
+    }
+    if !company.tax_number.trim().is_empty() {
+        page.text(
+            text_x,
+            info_y,
+            8.5,
+            PdfFont::HelveticaBold,
+            &format!("NPWP: {}", company.tax_number.trim()),
+        );
+        info_y -= 11.0;
+    }
+
+    page.text_right(RIGHT, y - 16.0, 18.0, PdfFont::CourierBold, "INVOICE");
+    if data.is_void {
+        page.text_right(RIGHT, y - 34.0, 12.0, PdfFont::CourierBold, "*** VOID ***");
+    }
+
+    y = info_y.min(y - 60.0) - 8.0;
+    page.line(MARGIN, y, RIGHT, y, 1.0);
+    y -= 18.0;
+
+    // Pembeli (kiri) & info transaksi (kanan)
+    let top = y;
+    page.text(MARGIN, y, 8.5, PdfFont::HelveticaBold, "Kepada:");
+    y -= 12.0;
+    match customer.filter(|c| !c.name.trim().is_empty()) {
+        Some(c) => {
+            page.text(MARGIN, y, 9.0, PdfFont::Helvetica, c.name.trim());
+            y -= 12.0;
+            for l in wrap_text(c.address.trim(), 50).iter().filter(|l| !l.is_empty()) {
+                page.text(MARGIN, y, 8.5, PdfFont::Helvetica, l);
+                y -= 11.0;
+            }
+            if !c.tax_number.trim().is_empty() {
+                page.text(MARGIN, y, 8.5, PdfFont::Helvetica, &format!("NPWP: {}", c.tax_number.trim()));
+                y -= 11.0;
+            }
+        }
+        None => {
+            page.text(MARGIN, y, 9.0, PdfFont::Helvetica, "Pelanggan Umum");
+            y -= 12.0;
+        }
+    }
+
+    let mut meta_y = top;
+    let meta = [
+        ("No. Invoice", number.to_string()),
+        ("Tanggal", data.timestamp.clone()),
+        ("Kasir", data.cashier_name.clone().unwrap_or_default()),
+        ("Pembayaran", payment_label(&data.payment_method).to_string()),
+    ];
+    for (label, value) in meta.iter().filter(|(_, v)| !v.is_empty()) {
+        page.text(340.0, meta_y, 8.5, PdfFont::Helvetica, label);
+        page.text(410.0, meta_y, 8.5, PdfFont::Helvetica, &format!(": {}", value));
+        meta_y -= 12.0;
+    }
+
+    y.min(meta_y) - 14.0
+}
+
+/// Header tabel item (latar abu-abu). Mengembalikan y di bawah header.
+fn table_header(page: &mut PdfPage, y: f32) -> f32 {
+    page.fill_rect(MARGIN, y - ROW - 2.0, RIGHT - MARGIN, ROW + 2.0, 0.88);
+    let baseline = y - ROW + 3.0;
+    page.text(COL_NO, baseline, 8.5, PdfFont::HelveticaBold, "No");
+    page.text(COL_NAME, baseline, 8.5, PdfFont::HelveticaBold, "Nama Barang");
+    page.text_right(COL_QTY_R, baseline, 8.5, PdfFont::CourierBold, "Qty");
+    page.text_right(COL_PRICE_R, baseline, 8.5, PdfFont::CourierBold, "Harga");
+    page.text_right(COL_DISC_R, baseline, 8.5, PdfFont::CourierBold, "Diskon");
+    page.text_right(COL_TOTAL_R, baseline, 8.5, PdfFont::CourierBold, "Jumlah");
+    y - ROW - 2.0
+}
+
+/// Ringkasan total: subtotal, diskon, DPP, pajak, total, dan pembayaran.
+fn invoice_totals(page: &mut PdfPage, mut y: f32, tax: &TaxSettings, data: &ReceiptData) {
+    let items_total: f64 = data.items.iter().map(|i| i.subtotal).sum();
+    // DPP (Dasar Pengenaan Pajak) = total dikurangi pajak, baik pajak
+    // termasuk dalam harga maupun ditambahkan di atas subtotal
+    let dpp = data.total_amount - data.tax_amount;
+
+    let mut rows: Vec<(String, String, bool)> = vec![(
+        "Subtotal".into(),
+        format_number(items_total.round() as i64),
+        false,
+    )];
+    if data.discount_amount > 0.0 {
+        rows.push((
+            "Diskon".into(),
+            format!("-{}", format_number(data.discount_amount.round() as i64)),
+            false,
+        ));
+    }
+    if data.tax_amount > 0.0 {
+        rows.push(("DPP".into(), format_number(dpp.round() as i64), false));
+        let mut label = format!("{} {}%", tax.label, tax.rate);
+        if tax.is_included {
+            label.push_str(" (termasuk)");
+        }
+        rows.push((label, format_number(data.tax_amount.round() as i64), false));
+    }
+    rows.push(("TOTAL".into(), format!("Rp {}", format_number(data.total_amount.round() as i64)), true));
+    rows.push((
+        format!("Dibayar ({})", payment_label(&data.payment_method)),
+        format_number(data.amount_paid.round() as i64),
+        false,
+    ));
+    if data.change_given > 0.0 {
+        rows.push(("Kembali".into(), format_number(data.change_given.round() as i64), false));
+    }
+
+    let label_x = 360.0;
+    for (label, value, bold) in rows {
+        y -= ROW;
+        if bold {
+            page.line(label_x, y + ROW - 3.0, RIGHT, y + ROW - 3.0, 0.8);
+        }
+        let (font, mono) = if bold {
+            (PdfFont::HelveticaBold, PdfFont::CourierBold)
+        } else {
+            (PdfFont::Helvetica, PdfFont::Courier)
+        };
+        page.text(label_x, y, 9.0, font, &label);
+        page.text_right(COL_TOTAL_R, y, 9.0, mono, &value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invoice_number() {
+        let data = ReceiptData {
+            transaction_id: "1a2b3c4d-0000".into(),
+            timestamp: "2024-05-01 10:20:30".into(),
+            cashier_name: None,
+            items: Vec::new(),
+            discount_amount: 0.0,
+            tax_amount: 0.0,
+            total_amount: 0.0,
+            payment_method: "CASH".into(),
+            amount_paid: 0.0,
+            change_given: 0.0,
+            qr_payload: None,
+            reprint_number: None,
+            is_void: false,
+        };
+        assert_eq!(invoice_number(&data), "INV/20240501/1A2B3C4D");
+    }
+}
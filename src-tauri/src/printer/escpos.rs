@@ -0,0 +1,161 @@
+//! Builder byte stream ESC/POS untuk printer thermal.
+
+use super::raster::RasterImage;
+
+// ============================================================================
+// CONSTANTS - ESC/POS Commands
+// ============================================================================
+pub const ESC_POS_INIT: &[u8] = b"\x1B\x40";           // ESC @ - Initialize
+pub const ESC_POS_CENTER: &[u8] = b"\x1B\x61\x01";     // ESC a 1 - Center alignment
+pub const ESC_POS_LEFT: &[u8] = b"\x1B\x61\x00";       // ESC a 0 - Left alignment
+pub const ESC_POS_RIGHT: &[u8] = b"\x1B\x61\x02";      // ESC a 2 - Right alignment
+pub const ESC_POS_BOLD_ON: &[u8] = b"\x1B\x45\x01";    // ESC E 1 - Bold ON
+pub const ESC_POS_BOLD_OFF: &[u8] = b"\x1B\x45\x00";   // ESC E 0 - Bold OFF
+pub const ESC_POS_DOUBLE_WIDTH: &[u8] = b"\x1D\x21\x11"; // GS ! 0x11 - Double width+height
+pub const ESC_POS_NORMAL: &[u8] = b"\x1D\x21\x00";     // GS ! 0x00 - Normal size
+pub const ESC_POS_CUT: &[u8] = b"\x1D\x56\x41";        // GS V A - Full cut
+
+/// Perataan teks (ESC a n).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    Left,
+    Center,
+    Right,
+}
+
+impl Align {
+    fn command(self) -> &'static [u8] {
+        match self {
+            Align::Left => ESC_POS_LEFT,
+            Align::Center => ESC_POS_CENTER,
+            Align::Right => ESC_POS_RIGHT,
+        }
+    }
+}
+
+/// Builder sederhana untuk menyusun perintah ESC/POS.
+pub struct EscPosBuilder {
+    buf: Vec<u8>,
+}
+
+impl EscPosBuilder {
+    /// Buat builder baru, diawali dengan `ESC @` (reset printer).
+    pub fn new() -> Self {
+        let mut buf = Vec::with_capacity(1024);
+        buf.extend_from_slice(ESC_POS_INIT);
+        Self { buf }
+    }
+
+    pub fn align(&mut self, align: Align) -> &mut Self {
+        self.buf.extend_from_slice(align.command());
+        self
+    }
+
+    pub fn bold(&mut self, on: bool) -> &mut Self {
+        self.buf
+            .extend_from_slice(if on { ESC_POS_BOLD_ON } else { ESC_POS_BOLD_OFF });
+        self
+    }
+
+    /// Ukuran huruf dobel (lebar + tinggi) atau normal.
+    pub fn double_size(&mut self, on: bool) -> &mut Self {
+        self.buf
+            .extend_from_slice(if on { ESC_POS_DOUBLE_WIDTH } else { ESC_POS_NORMAL });
+        self
+    }
+
+    /// Tulis teks tanpa newline.
+    pub fn text(&mut self, text: &str) -> &mut Self {
+        self.buf.extend_from_slice(text.as_bytes());
+        self
+    }
+
+    /// Tulis teks diakhiri newline.
+    pub fn line(&mut self, text: &str) -> &mut Self {
+        self.text(text);
+        self.buf.push(b'\n');
+        self
+    }
+
+    pub fn feed(&mut self, lines: usize) -> &mut Self {
+        for _ in 0..lines {
+            self.buf.push(b'\n');
+        }
+        self
+    }
+
+    /// Byte mentah (untuk perintah yang belum punya helper).
+    pub fn raw(&mut self, bytes: &[u8]) -> &mut Self {
+        self.buf.extend_from_slice(bytes);
+        self
+    }
+
+    /// Cetak gambar raster via `GS v 0`. Gambar tinggi dipecah per 255 baris
+    /// karena sebagian printer murah membatasi tinggi per perintah.
+    pub fn raster_image(&mut self, image: &RasterImage) -> &mut Self {
+        let bytes_per_row = image.bytes_per_row();
+        if bytes_per_row == 0 || image.height == 0 {
+            return self;
+        }
+        for band in image.data.chunks(bytes_per_row * 255) {
+            let rows = band.len() / bytes_per_row;
+            self.buf.extend_from_slice(b"\x1D\x76\x30\x00"); // GS v 0, mode normal
+            self.buf.extend_from_slice(&(bytes_per_row as u16).to_le_bytes());
+            self.buf.extend_from_slice(&(rows as u16).to_le_bytes());
+            self.buf.extend_from_slice(band);
+        }
+        self
+    }
+
+    /// Cetak QR code native printer via `GS ( k` (model 2, koreksi error M).
+    /// `module_size` = ukuran titik per modul (1–16).
+    pub fn qr_code(&mut self, data: &str, module_size: u8) -> &mut Self {
+        let bytes = data.as_bytes();
+        // Batas kapasitas QR model 2 (byte mode, level M)
+        if bytes.is_empty() || bytes.len() > 2331 {
+            return self;
+        }
+        self.buf.extend_from_slice(b"\x1D\x28\x6B\x04\x00\x31\x41\x32\x00"); // Model 2
+        self.buf.extend_from_slice(b"\x1D\x28\x6B\x03\x00\x31\x43"); // Ukuran modul
+        self.buf.push(module_size.clamp(1, 16));
+        self.buf.extend_from_slice(b"\x1D\x28\x6B\x03\x00\x31\x45\x31"); // Error correction M
+
+        let len = (bytes.len() + 3) as u16;
+        self.buf.extend_from_slice(b"\x1D\x28\x6B"); // Simpan data
+        self.buf.extend_from_slice(&len.to_le_bytes());
+        self.buf.extend_from_slice(b"\x31\x50\x30");
+        self.buf.extend_from_slice(bytes);
+
+        self.buf.extend_from_slice(b"\x1D\x28\x6B\x03\x00\x31\x51\x30"); // Cetak
+        self
+    }
+
+    /// Pulse pembuka laci kas via `ESC p m t1 t2`.
+    /// `pin` 2 atau 5 (konektor RJ11 printer), durasi dalam milidetik
+    /// (dibulatkan ke kelipatan 2 ms, maksimal 510 ms).
+    pub fn drawer_kick(&mut self, pin: u8, on_ms: u16, off_ms: u16) -> &mut Self {
+        let m = if pin == 5 { 1 } else { 0 };
+        let t1 = (on_ms / 2).clamp(1, 255) as u8;
+        let t2 = (off_ms / 2).clamp(1, 255) as u8;
+        self.buf.extend_from_slice(&[0x1B, 0x70, m, t1, t2]);
+        self
+    }
+
+    /// Feed lalu potong kertas.
+    pub fn cut(&mut self) -> &mut Self {
+        self.feed(3);
+        self.buf.extend_from_slice(ESC_POS_CUT);
+        self.buf.push(0x03); // Partial cut
+        self
+    }
+
+    pub fn build(self) -> Vec<u8> {
+        self.buf
+    }
+}
+
+impl Default for EscPosBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,481 @@
+//! Label barcode produk: perintah TSPL / ZPL / ESC/POS dan PDF lembar
+//! stiker A4. Layout menyesuaikan ukuran label (mis. 33x15mm, 50x30mm).
+
+use super::barcode::{barcode_modules, is_valid_ean13};
+use super::escpos::{Align, EscPosBuilder};
+use super::pdf::{courier_width, PdfDocument, PdfFont, PdfPage, A4_HEIGHT, A4_WIDTH, PT_PER_MM};
+use super::receipt::format_number;
+use serde::{Deserialize, Serialize};
+
+/// Satu produk yang dicetak labelnya sebanyak `qty`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BarcodeLabelItem {
+    pub name: String,
+    pub price: f64,
+    pub barcode: String,
+    pub qty: u32,
+}
+
+/// Bahasa perintah printer label.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LabelLanguage {
+    /// Printer struk biasa — label dicetak berurutan di kertas roll
+    EscPos,
+    /// TSC / Xprinter / kebanyakan printer label murah
+    Tspl,
+    /// Zebra
+    Zpl,
+}
+
+impl LabelLanguage {
+    pub fn parse(value: &str) -> Self {
+        match value.to_ascii_uppercase().as_str() {
+            "TSPL" => LabelLanguage::Tspl,
+            "ZPL" => LabelLanguage::Zpl,
+            _ => LabelLanguage::EscPos,
+        }
+    }
+}
+
+/// Ukuran label dalam milimeter.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LabelSize {
+    pub width_mm: f32,
+    pub height_mm: f32,
+}
+
+impl LabelSize {
+    /// Parse format "LEBARxTINGGI" (mis. "50x30"). Default 50x30mm.
+    pub fn parse(value: &str) -> Self {
+        let parsed = value
+            .to_ascii_lowercase()
+            .trim_end_matches("mm")
+            .split_once('x')
+            .and_then(|(w, h)| Some((w.trim().parse::<f32>().ok()?, h.trim().parse::<f32>().ok()?)))
+            .filter(|(w, h)| *w >= 15.0 && *h >= 10.0 && *w <= 120.0 && *h <= 120.0);
+        match parsed {
+            Some((width_mm, height_mm)) => Self { width_mm, height_mm },
+            None => Self { width_mm: 50.0, height_mm: 30.0 },
+        }
+    }
+
+    /// Label kecil (tinggi < 20mm) memakai font lebih kecil.
+    fn is_small(&self) -> bool {
+        self.height_mm < 20.0
+    }
+}
+
+/// Posisi elemen label dalam dot printer.
+struct DotLayout {
+    width: u32,
+    height: u32,
+    margin: u32,
+    name_height: u32,
+    price_height: u32,
+    barcode_y: u32,
+    barcode_height: u32,
+}
+
+impl DotLayout {
+    fn new(size: LabelSize, dpi: u32) -> Self {
+        let dots = |mm: f32| (mm * dpi as f32 / 25.4).round() as u32;
+        let width = dots(size.width_mm);
+        let height = dots(size.height_mm);
+        let margin = dots(1.0);
+        let (name_mm, price_mm) = if size.is_small() { (1.6, 1.6) } else { (2.6, 3.2) };
+        let name_height = dots(name_mm);
+        let price_height = dots(price_mm);
+        let barcode_y = margin + name_height + price_height + dots(1.0);
+        // Sisakan ± 2mm untuk teks di bawah barcode (human readable)
+        let barcode_height = height
+            .saturating_sub(barcode_y + dots(2.0) + margin)
+            .clamp(dots(4.0), dots(15.0));
+        Self { width, height, margin, name_height, price_height, barcode_y, barcode_height }
+    }
+
+    /// Lebar modul barcode (dot) terbesar yang masih muat.
+    fn module_width(&self, barcode: &str) -> u32 {
+        let modules = estimated_modules(barcode);
+        (self.width.saturating_sub(2 * self.margin) / modules.max(1)).clamp(1, 3)
+    }
+
+    /// Jumlah karakter nama yang muat untuk font setinggi `name_height`.
+    fn name_chars(&self, char_width: u32) -> usize {
+        (self.width.saturating_sub(2 * self.margin) / char_width.max(1)) as usize
+    }
+}
+
+/// Perkiraan jumlah modul barcode (untuk memilih lebar modul & posisi tengah).
+fn estimated_modules(barcode: &str) -> u32 {
+    if is_valid_ean13(barcode) {
+        95
+    } else {
+        // Code 128: (start + data + checksum) x 11 + stop 13
+        (barcode.chars().count() as u32 + 2) * 11 + 13
+    }
+}
+
+fn truncate(text: &str, max: usize) -> String {
+    text.chars().take(max.max(1)).collect()
+}
+
+fn price_text(price: f64) -> String {
+    format!("Rp {}", format_number(price.round() as i64))
+}
+
+// ============================================================================
+// TSPL
+// ============================================================================
+
+/// Font bawaan TSPL (nama, lebar, tinggi dalam dot @203dpi).
+const TSPL_FONTS: [(&str, u32, u32); 5] = [
+    ("1", 8, 12),
+    ("2", 12, 20),
+    ("3", 16, 24),
+    ("4", 24, 32),
+    ("5", 32, 48),
+];
+
+/// Font TSPL terbesar yang tingginya tidak melebihi `max_height`.
+fn tspl_font(max_height: u32) -> (&'static str, u32, u32) {
+    TSPL_FONTS
+        .iter()
+        .rev()
+        .find(|(_, _, h)| *h <= max_height)
+        .copied()
+        .unwrap_or(TSPL_FONTS[0])
+}
+
+/// Escape string TSPL (tanda kutip tidak boleh ada di dalam "...").
+fn tspl_string(text: &str) -> String {
+    text.replace('"', "'")
+}
+
+/// Encode teks ke CP1252 sesuai `CODEPAGE 1252`. Karakter di luar
+/// codepage diganti '?'.
+fn cp1252_bytes(text: &str) -> Vec<u8> {
+    text.chars()
+        .map(|ch| match ch {
+            '\u{20AC}' => 0x80,
+            '\u{2026}' => 0x85,
+            '\u{2018}' => 0x91,
+            '\u{2019}' => 0x92,
+            '\u{201C}' => 0x93,
+            '\u{201D}' => 0x94,
+            '\u{2022}' => 0x95,
+            '\u{2013}' => 0x96,
+            '\u{2014}' => 0x97,
+            // 0x80-0x9F di CP1252 bukan karakter kontrol Latin-1
+            c if (c as u32) < 0x80 || (0xA0..=0xFF).contains(&(c as u32)) => c as u32 as u8,
+            _ => b'?',
+        })
+        .collect()
+}
+
+/// Perintah TSPL untuk semua label.
+pub fn tspl_labels(items: &[BarcodeLabelItem], size: LabelSize, dpi: u32, gap_mm: f32) -> Vec<u8> {
+    let layout = DotLayout::new(size, dpi);
+    let mut out = String::new();
+    out.push_str(&format!("SIZE {} mm, {} mm\r\n", size.width_mm, size.height_mm));
+    out.push_str(&format!("GAP {} mm, 0 mm\r\n", gap_mm.max(0.0)));
+    out.push_str("DIRECTION 1\r\n");
+    out.push_str("CODEPAGE 1252\r\n");
+
+    for item in items.iter().filter(|i| i.qty > 0) {
+        let (name_font, name_w, _) = tspl_font(layout.name_height);
+        let (price_font, price_w, _) = tspl_font(layout.price_height);
+        let name = truncate(&item.name, layout.name_chars(name_w));
+        let price = price_text(item.price);
+        let center = |chars: usize, char_w: u32| {
+            layout.width.saturating_sub(chars as u32 * char_w) / 2
+        };
+
+        out.push_str("CLS\r\n");
+        out.push_str(&format!(
+            "TEXT {},{},\"{}\",0,1,1,\"{}\"\r\n",
+            center(name.chars().count(), name_w),
+            layout.margin,
+            name_font,
+            tspl_string(&name)
+        ));
+        out.push_str(&format!(
+            "TEXT {},{},\"{}\",0,1,1,\"{}\"\r\n",
+            center(price.chars().count(), price_w),
+            layout.margin + layout.name_height,
+            price_font,
+            price
+        ));
+
+        let barcode = item.barcode.trim();
+        if !barcode.is_empty() {
+            let narrow = layout.module_width(barcode);
+            let x = layout.width.saturating_sub(estimated_modules(barcode) * narrow) / 2;
+            let symbology = if is_valid_ean13(barcode) { "EAN13" } else { "128" };
+            out.push_str(&format!(
+                "BARCODE {},{},\"{}\",{},1,0,{},{},\"{}\"\r\n",
+                x,
+                layout.barcode_y,
+                symbology,
+                layout.barcode_height,
+                narrow,
+                narrow,
+                tspl_string(barcode)
+            ));
+        }
+        out.push_str(&format!("PRINT 1,{}\r\n", item.qty));
+    }
+    cp1252_bytes(&out)
+}
+
+// ============================================================================
+// ZPL
+// ============================================================================
+
+/// Field data ZPL tidak boleh mengandung karakter kontrol `^` dan `~`.
+fn zpl_string(text: &str) -> String {
+    text.replace(['^', '~'], " ")
+}
+
+/// Perintah ZPL II untuk semua label.
+pub fn zpl_labels(items: &[BarcodeLabelItem], size: LabelSize, dpi: u32) -> Vec<u8> {
+    let layout = DotLayout::new(size, dpi);
+    let mut out = String::new();
+
+    for item in items.iter().filter(|i| i.qty > 0) {
+        // Font 0 (skalabel): lebar karakter ± 0.6 x tinggi
+        let name_w = (layout.name_height as f32 * 0.6).ceil() as u32;
+        let name = truncate(&item.name, layout.name_chars(name_w));
+
+        out.push_str("^XA\n^CI28\n");
+        out.push_str(&format!("^PW{}\n^LL{}\n", layout.width, layout.height));
+        out.push_str(&format!(
+            "^FO0,{}^FB{},1,0,C^A0N,{},{}^FD{}^FS\n",
+            layout.margin,
+            layout.width,
+            layout.name_height,
+            layout.name_height,
+            zpl_string(&name)
+        ));
+        out.push_str(&format!(
+            "^FO0,{}^FB{},1,0,C^A0N,{},{}^FD{}^FS\n",
+            layout.margin + layout.name_height,
+            layout.width,
+            layout.price_height,
+            layout.price_height,
+            price_text(item.price)
+        ));
+
+        let barcode = item.barcode.trim();
+        if !barcode.is_empty() {
+            let narrow = layout.module_width(barcode);
+            let x = layout.width.saturating_sub(estimated_modules(barcode) * narrow) / 2;
+            if is_valid_ean13(barcode) {
+                // ^BE menghitung digit cek sendiri dari 12 digit pertama
+                out.push_str(&format!(
+                    "^FO{},{}^BY{}^BEN,{},Y,N^FD{}^FS\n",
+                    x,
+                    layout.barcode_y,
+                    narrow,
+                    layout.barcode_height,
+                    &barcode[..12]
+                ));
+            } else {
+                out.push_str(&format!(
+                    "^FO{},{}^BY{}^BCN,{},Y,N,N^FD{}^FS\n",
+                    x,
+                    layout.barcode_y,
+                    narrow,
+                    layout.barcode_height,
+                    zpl_string(barcode)
+                ));
+            }
+        }
+        out.push_str(&format!("^PQ{}\n^XZ\n", item.qty));
+    }
+    out.into_bytes()
+}
+
+// ============================================================================
+// ESC/POS (printer struk)
+// ============================================================================
+
+/// Label di kertas struk: nama, harga, barcode native, dipisah garis putus.
+pub fn escpos_labels(items: &[BarcodeLabelItem]) -> Vec<u8> {
+    let mut esc = EscPosBuilder::new();
+
+    for label in items {
+        for _ in 0..label.qty {
+            esc.align(Align::Center)
+                .bold(true)
+                .line(&truncate(&label.name, 24))
+                .bold(false)
+                .line(&price_text(label.price));
+
+            esc.raw(b"\x1D\x48\x02"); // GS H 2 — HRI below barcode
+            esc.raw(b"\x1D\x68\x50"); // GS h 80 — Barcode height 80 dots
+            esc.raw(b"\x1D\x77\x02"); // GS w 2 — Barcode width multiplier
+
+            let barcode = label.barcode.as_bytes();
+            if barcode.len() == 13 {
+                esc.raw(b"\x1D\x6B\x43"); // GS k C (CODE 67 = EAN13)
+            } else {
+                esc.raw(b"\x1D\x6B\x49"); // GS k I (CODE 73 = CODE128)
+            }
+            esc.raw(&[barcode.len() as u8]).raw(barcode).feed(1);
+
+            // Separator between labels
+            esc.line("--------------------------------");
+        }
+    }
+
+    esc.feed(2).cut();
+    esc.build()
+}
+
+// ============================================================================
+// PDF lembar stiker A4
+// ============================================================================
+
+/// Margin kertas & jarak antar stiker (mm) untuk lembar A4.
+const SHEET_MARGIN_MM: f32 = 8.0;
+const SHEET_GAP_MM: f32 = 2.0;
+
+/// Jumlah kolom & baris stiker per halaman A4.
+pub fn sheet_grid(size: LabelSize) -> (usize, usize) {
+    let usable_w = A4_WIDTH / PT_PER_MM - 2.0 * SHEET_MARGIN_MM + SHEET_GAP_MM;
+    let usable_h = A4_HEIGHT / PT_PER_MM - 2.0 * SHEET_MARGIN_MM + SHEET_GAP_MM;
+    let cols = (usable_w / (size.width_mm + SHEET_GAP_MM)).floor().max(1.0) as usize;
+    let rows = (usable_h / (size.height_mm + SHEET_GAP_MM)).floor().max(1.0) as usize;
+    (cols, rows)
+}
+
+/// PDF lembar stiker A4 untuk printer kantor (barcode digambar sebagai vektor).
+pub fn label_sheet_pdf(items: &[BarcodeLabelItem], size: LabelSize) -> Vec<u8> {
+    let (cols, rows) = sheet_grid(size);
+    let per_page = cols * rows;
+    let w = size.width_mm * PT_PER_MM;
+    let h = size.height_mm * PT_PER_MM;
+    let margin = SHEET_MARGIN_MM * PT_PER_MM;
+    let gap = SHEET_GAP_MM * PT_PER_MM;
+    let small = size.is_small();
+    let (name_size, price_size) = if small { (5.5, 6.0) } else { (7.5, 9.0) };
+
+    let labels: Vec<&BarcodeLabelItem> = items
+        .iter()
+        .flat_map(|i| std::iter::repeat_n(i, i.qty as usize))
+        .collect();
+
+    let mut doc = PdfDocument::new("Label Barcode");
+    for chunk in labels.chunks(per_page.max(1)) {
+        let mut page = PdfPage::new(A4_WIDTH, A4_HEIGHT);
+        for (i, label) in chunk.iter().enumerate() {
+            let x = margin + (i % cols) as f32 * (w + gap);
+            let top = A4_HEIGHT - margin - (i / cols) as f32 * (h + gap);
+            let pad = 1.2 * PT_PER_MM;
+
+            // Garis potong tipis
+            page.line(x, top, x + w, top, 0.1);
+            page.line(x, top - h, x + w, top - h, 0.1);
+            page.line(x, top, x, top - h, 0.1);
+            page.line(x + w, top, x + w, top - h, 0.1);
+
+            let max_chars = ((w - 2.0 * pad) / (name_size * 0.6)) as usize;
+            let name = truncate(&label.name, max_chars);
+            let name_y = top - pad - name_size;
+            page.text(x + (w - courier_width(&name, name_size)) / 2.0, name_y, name_size, PdfFont::Courier, &name);
+
+            let price = price_text(label.price);
+            let price_y = name_y - price_size - 0.5;
+            page.text(
+                x + (w - courier_width(&price, price_size)) / 2.0,
+                price_y,
+                price_size,
+                PdfFont::CourierBold,
+                &price,
+            );
+
+            let barcode = label.barcode.trim();
+            if let Some(modules) = barcode_modules(barcode) {
+                let hri_size = if small { 5.0 } else { 6.5 };
+                let bottom = top - h + pad + hri_size + 1.0;
+                let bar_top = price_y - 2.0;
+                let bar_h = (bar_top - bottom).max(4.0);
+                let module = ((w - 2.0 * pad) / modules.len() as f32).min(0.33 * PT_PER_MM);
+                let bar_x = x + (w - module * modules.len() as f32) / 2.0;
+
+                // Gabungkan modul hitam berurutan menjadi satu kotak
+                let mut run_start: Option<usize> = None;
+                for (m, &black) in modules.iter().chain(std::iter::once(&false)).enumerate() {
+                    match (black, run_start) {
+                        (true, None) => run_start = Some(m),
+                        (false, Some(start)) => {
+                            page.fill_rect(bar_x + start as f32 * module, bottom, (m - start) as f32 * module, bar_h, 0.0);
+                            run_start = None;
+                        }
+                        _ => {}
+                    }
+                }
+
+                page.text(
+                    x + (w - courier_width(barcode, hri_size)) / 2.0,
+                    top - h + pad,
+                    hri_size,
+                    PdfFont::Courier,
+                    barcode,
+                );
+            }
+        }
+        doc.add_page(page);
+    }
+    doc.to_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(barcode: &str, qty: u32) -> BarcodeLabelItem {
+        BarcodeLabelItem { name: "Kopi \"Susu\"".into(), price: 15000.0, barcode: barcode.into(), qty }
+    }
+
+    #[test]
+    fn test_label_size_parse() {
+        assert_eq!(LabelSize::parse("33x15"), LabelSize { width_mm: 33.0, height_mm: 15.0 });
+        assert_eq!(LabelSize::parse("50X30mm"), LabelSize { width_mm: 50.0, height_mm: 30.0 });
+        assert_eq!(LabelSize::parse("abc"), LabelSize { width_mm: 50.0, height_mm: 30.0 });
+    }
+
+    #[test]
+    fn test_tspl_output() {
+        let out = String::from_utf8(tspl_labels(&[item("8991234567891", 3)], LabelSize::parse("33x15"), 203, 2.0)).unwrap();
+        assert!(out.starts_with("SIZE 33 mm, 15 mm\r\n"));
+        assert!(out.contains("\"EAN13\""));
+        assert!(out.contains("Kopi 'Susu'"));
+        assert!(out.contains("PRINT 1,3\r\n"));
+    }
+
+    #[test]
+    fn test_tspl_encodes_cp1252() {
+        let mut label = item("ABC-123", 1);
+        label.name = "Caf\u{e9} \u{2013} \u{4e2d}".into();
+        let out = tspl_labels(&[label], LabelSize::parse("50x30"), 203, 2.0);
+        let expected = b"Caf\xE9 \x96 ?";
+        assert!(out.windows(expected.len()).any(|w| w == expected));
+    }
+
+    #[test]
+    fn test_zpl_output() {
+        let out = String::from_utf8(zpl_labels(&[item("ABC-123", 2)], LabelSize::parse("50x30"), 203)).unwrap();
+        assert!(out.starts_with("^XA"));
+        assert!(out.contains("^PW400\n^LL240"));
+        assert!(out.contains("^BCN"));
+        assert!(out.contains("^PQ2\n^XZ"));
+    }
+
+    #[test]
+    fn test_sheet_grid() {
+        // A4 210x297mm, margin 8mm, jarak 2mm
+        assert_eq!(sheet_grid(LabelSize::parse("50x30")), (3, 8));
+        assert_eq!(sheet_grid(LabelSize::parse("33x15")), (5, 16));
+    }
+}
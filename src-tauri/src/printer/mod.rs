@@ -0,0 +1,21 @@
+//! Modul printer — builder perintah ESC/POS, raster logo, renderer struk,
+//! label barcode (TSPL/ZPL), tiket dapur/bar, pole display pelanggan, parser
+//! timbangan, dokumen PDF (struk, invoice, purchase order), dan aturan antrian
+//! cetak.
+//!
+//! Command Tauri tetap berada di `commands::settings_cmd`; modul ini hanya
+//! berisi logika murni (tanpa akses DB) supaya mudah diuji.
+
+pub mod barcode;
+pub mod display;
+pub mod document;
+pub mod escpos;
+pub mod label;
+pub mod pdf;
+pub mod purchase_order;
+pub mod queue;
+pub mod raster;
+pub mod receipt;
+pub mod scale;
+pub mod status;
+pub mod ticket;
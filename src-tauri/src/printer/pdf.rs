@@ -0,0 +1,327 @@
+//! Penulis PDF minimal (PDF 1.4) tanpa dependensi eksternal.
+//!
+//! Hanya memakai font standar Type1 (Helvetica & Courier, WinAnsiEncoding)
+//! dan gambar 1-bit dari `RasterImage`, cukup untuk struk dan invoice.
+//! Koordinat dalam point (1/72 inci) dengan titik (0,0) di kiri bawah.
+
+use super::raster::RasterImage;
+
+/// Jumlah point per milimeter.
+pub const PT_PER_MM: f32 = 72.0 / 25.4;
+/// Ukuran kertas A4 dalam point.
+pub const A4_WIDTH: f32 = 595.28;
+pub const A4_HEIGHT: f32 = 841.89;
+
+/// Font standar PDF yang tersedia di semua viewer (tanpa embed).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PdfFont {
+    Helvetica,
+    HelveticaBold,
+    Courier,
+    CourierBold,
+}
+
+impl PdfFont {
+    const ALL: [PdfFont; 4] = [
+        PdfFont::Helvetica,
+        PdfFont::HelveticaBold,
+        PdfFont::Courier,
+        PdfFont::CourierBold,
+    ];
+
+    fn resource(self) -> &'static str {
+        match self {
+            PdfFont::Helvetica => "F1",
+            PdfFont::HelveticaBold => "F2",
+            PdfFont::Courier => "F3",
+            PdfFont::CourierBold => "F4",
+        }
+    }
+
+    fn base_font(self) -> &'static str {
+        match self {
+            PdfFont::Helvetica => "Helvetica",
+            PdfFont::HelveticaBold => "Helvetica-Bold",
+            PdfFont::Courier => "Courier",
+            PdfFont::CourierBold => "Courier-Bold",
+        }
+    }
+}
+
+/// Lebar teks Courier (monospace, 600/1000 em per karakter).
+/// Dipakai untuk teks rata kanan / tengah.
+pub fn courier_width(text: &str, size: f32) -> f32 {
+    text.chars().count() as f32 * 0.6 * size
+}
+
+/// Encode teks ke WinAnsi + escape string literal PDF.
+/// Karakter di luar Latin-1 diganti padanan ASCII atau '?'.
+fn encode_text(text: &str) -> Vec<u8> {
+    let mut out = Vec::with_capacity(text.len() + 2);
+    out.push(b'(');
+    for ch in text.chars() {
+        let byte = match ch {
+            '(' | ')' | '\\' => {
+                out.push(b'\\');
+                ch as u8
+            }
+            '\u{2013}' | '\u{2014}' => b'-',
+            '\u{2018}' | '\u{2019}' => b'\'',
+            '\u{201C}' | '\u{201D}' => b'"',
+            c if (c as u32) < 0x20 => b' ',
+            c if (c as u32) <= 0xFF => c as u32 as u8,
+            _ => b'?',
+        };
+        out.push(byte);
+    }
+    out.push(b')');
+    out
+}
+
+/// Satu halaman PDF beserta isi content stream-nya.
+pub struct PdfPage {
+    pub width: f32,
+    pub height: f32,
+    content: Vec<u8>,
+    images: Vec<RasterImage>,
+}
+
+impl PdfPage {
+    pub fn new(width: f32, height: f32) -> Self {
+        Self {
+            width,
+            height,
+            content: Vec::new(),
+            images: Vec::new(),
+        }
+    }
+
+    /// Tulis teks dengan baseline di (x, y).
+    pub fn text(&mut self, x: f32, y: f32, size: f32, font: PdfFont, text: &str) {
+        self.content.extend_from_slice(
+            format!("BT /{} {:.2} Tf {:.2} {:.2} Td ", font.resource(), size, x, y).as_bytes(),
+        );
+        self.content.extend_from_slice(&encode_text(text));
+        self.content.extend_from_slice(b" Tj ET\n");
+    }
+
+    /// Teks Courier rata kanan dengan tepi kanan di `right`.
+    pub fn text_right(&mut self, right: f32, y: f32, size: f32, font: PdfFont, text: &str) {
+        self.text(right - courier_width(text, size), y, size, font, text);
+    }
+
+    /// Garis lurus dengan ketebalan `width` point.
+    pub fn line(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, width: f32) {
+        self.content.extend_from_slice(
+            format!("{:.2} w {:.2} {:.2} m {:.2} {:.2} l S\n", width, x1, y1, x2, y2).as_bytes(),
+        );
+    }
+
+    /// Kotak terisi abu-abu (`gray` 0.0 = hitam, 1.0 = putih).
+    pub fn fill_rect(&mut self, x: f32, y: f32, w: f32, h: f32, gray: f32) {
+        self.content.extend_from_slice(
+            format!("{:.2} g {:.2} {:.2} {:.2} {:.2} re f 0 g\n", gray, x, y, w, h).as_bytes(),
+        );
+    }
+
+    /// Gambar raster 1-bit di kotak (x, y, w, h) — (x, y) = pojok kiri bawah.
+    pub fn image(&mut self, image: &RasterImage, x: f32, y: f32, w: f32, h: f32) {
+        if image.width == 0 || image.height == 0 {
+            return;
+        }
+        self.images.push(image.clone());
+        let name = format!("Im{}", self.images.len());
+        self.content.extend_from_slice(
+            format!("q {:.2} 0 0 {:.2} {:.2} {:.2} cm /{} Do Q\n", w, h, x, y, name).as_bytes(),
+        );
+    }
+}
+
+/// Dokumen PDF multi-halaman.
+pub struct PdfDocument {
+    title: String,
+    pages: Vec<PdfPage>,
+}
+
+impl PdfDocument {
+    pub fn new(title: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            pages: Vec::new(),
+        }
+    }
+
+    pub fn add_page(&mut self, page: PdfPage) {
+        self.pages.push(page);
+    }
+
+    /// Serialisasi ke byte PDF (objek + tabel xref).
+    pub fn to_bytes(&self) -> Vec<u8> {
+        // Nomor objek: 1 catalog, 2 pages, 3.. font, lalu info, lalu per halaman
+        let font_base = 3;
+        let info_id = font_base + PdfFont::ALL.len();
+        let mut next_id = info_id + 1;
+        let mut page_ids = Vec::with_capacity(self.pages.len());
+        for page in &self.pages {
+            // page, content, lalu gambar-gambarnya
+            page_ids.push(next_id);
+            next_id += 2 + page.images.len();
+        }
+        let object_count = next_id - 1;
+
+        let mut out: Vec<u8> = b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n".to_vec();
+        let mut offsets = vec![0usize; object_count + 1];
+
+        fn begin(out: &mut Vec<u8>, offsets: &mut [usize], id: usize) {
+            offsets[id] = out.len();
+            out.extend_from_slice(format!("{} 0 obj\n", id).as_bytes());
+        }
+        fn end(out: &mut Vec<u8>) {
+            out.extend_from_slice(b"\nendobj\n");
+        }
+
+        begin(&mut out, &mut offsets, 1);
+        out.extend_from_slice(b"<< /Type /Catalog /Pages 2 0 R >>");
+        end(&mut out);
+
+        begin(&mut out, &mut offsets, 2);
+        let kids: Vec<String> = page_ids.iter().map(|id| format!("{} 0 R", id)).collect();
+        out.extend_from_slice(
+            format!("<< /Type /Pages /Kids [{}] /Count {} >>", kids.join(" "), page_ids.len())
+                .as_bytes(),
+        );
+        end(&mut out);
+
+        for (i, font) in PdfFont::ALL.iter().enumerate() {
+            begin(&mut out, &mut offsets, font_base + i);
+            out.extend_from_slice(
+                format!(
+                    "<< /Type /Font /Subtype /Type1 /BaseFont /{} /Encoding /WinAnsiEncoding >>",
+                    font.base_font()
+                )
+                .as_bytes(),
+            );
+            end(&mut out);
+        }
+
+        begin(&mut out, &mut offsets, info_id);
+        out.extend_from_slice(b"<< /Producer (POS Kasir) /Title ");
+        out.extend_from_slice(&encode_text(&self.title));
+        out.extend_from_slice(b" >>");
+        end(&mut out);
+
+        let fonts: Vec<String> = PdfFont::ALL
+            .iter()
+            .enumerate()
+            .map(|(i, f)| format!("/{} {} 0 R", f.resource(), font_base + i))
+            .collect();
+
+        for (page, &page_id) in self.pages.iter().zip(&page_ids) {
+            let content_id = page_id + 1;
+            let images: Vec<String> = (0..page.images.len())
+                .map(|i| format!("/Im{} {} 0 R", i + 1, content_id + 1 + i))
+                .collect();
+
+            begin(&mut out, &mut offsets, page_id);
+            out.extend_from_slice(
+                format!(
+                    "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {:.2} {:.2}] \
+                     /Resources << /Font << {} >> /XObject << {} >> >> /Contents {} 0 R >>",
+                    page.width,
+                    page.height,
+                    fonts.join(" "),
+                    images.join(" "),
+                    content_id
+                )
+                .as_bytes(),
+            );
+            end(&mut out);
+
+            begin(&mut out, &mut offsets, content_id);
+            out.extend_from_slice(format!("<< /Length {} >>\nstream\n", page.content.len()).as_bytes());
+            out.extend_from_slice(&page.content);
+            out.extend_from_slice(b"\nendstream");
+            end(&mut out);
+
+            for (i, image) in page.images.iter().enumerate() {
+                begin(&mut out, &mut offsets, content_id + 1 + i);
+                // Bit 1 = titik hitam (sama seperti ESC/POS) → Decode [1 0]
+                out.extend_from_slice(
+                    format!(
+                        "<< /Type /XObject /Subtype /Image /Width {} /Height {} \
+                         /ColorSpace /DeviceGray /BitsPerComponent 1 /Decode [1 0] /Length {} >>\nstream\n",
+                        image.width,
+                        image.height,
+                        image.data.len()
+                    )
+                    .as_bytes(),
+                );
+                out.extend_from_slice(&image.data);
+                out.extend_from_slice(b"\nendstream");
+                end(&mut out);
+            }
+        }
+
+        let xref_offset = out.len();
+        out.extend_from_slice(format!("xref\n0 {}\n0000000000 65535 f \n", object_count + 1).as_bytes());
+        for offset in &offsets[1..] {
+            out.extend_from_slice(format!("{:010} 00000 n \n", offset).as_bytes());
+        }
+        out.extend_from_slice(
+            format!(
+                "trailer\n<< /Size {} /Root 1 0 R /Info {} 0 R >>\nstartxref\n{}\n%%EOF\n",
+                object_count + 1,
+                info_id,
+                xref_offset
+            )
+            .as_bytes(),
+        );
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_text_escapes() {
+        assert_eq!(encode_text("a(b)\\c"), b"(a\\(b\\)\\\\c)".to_vec());
+        assert_eq!(encode_text("Rp—é€"), b"(Rp-\xE9?)".to_vec());
+    }
+
+    #[test]
+    fn test_document_xref_offsets() {
+        let mut doc = PdfDocument::new("Test");
+        let mut page = PdfPage::new(A4_WIDTH, A4_HEIGHT);
+        page.text(40.0, 800.0, 12.0, PdfFont::Helvetica, "Halo");
+        page.image(
+            &RasterImage { width: 8, height: 1, data: vec![0xFF] },
+            40.0,
+            700.0,
+            8.0,
+            1.0,
+        );
+        doc.add_page(page);
+        let bytes = doc.to_bytes();
+        assert!(bytes.starts_with(b"%PDF-1.4"));
+        assert!(bytes.ends_with(b"%%EOF\n"));
+
+        // Setiap entri xref harus menunjuk tepat ke awal "n 0 obj"
+        let tail = String::from_utf8_lossy(&bytes[bytes.len() - 20..]).to_string();
+        let xref_at: usize = tail.lines().rev().nth(1).unwrap().parse().unwrap();
+        let xref = String::from_utf8_lossy(&bytes[xref_at..]).to_string();
+        assert!(xref.starts_with("xref\n"));
+        let entries: Vec<usize> = xref
+            .lines()
+            .skip(3)
+            .take_while(|l| l.ends_with(" n "))
+            .map(|l| l[..10].parse().unwrap())
+            .collect();
+        assert_eq!(entries.len(), 10);
+        for (i, offset) in entries.iter().enumerate() {
+            let expected = format!("{} 0 obj", i + 1);
+            assert_eq!(&bytes[*offset..*offset + expected.len()], expected.as_bytes());
+        }
+    }
+}
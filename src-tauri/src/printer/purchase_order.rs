@@ -0,0 +1,304 @@
+//! Dokumen purchase order (PO) A4 untuk dikirim ke supplier.
+
+use super::pdf::{PdfDocument, PdfFont, PdfPage, A4_HEIGHT, A4_WIDTH};
+use super::raster::RasterImage;
+use super::receipt::{format_number, format_quantity, wrap_text};
+use crate::models::purchase::PurchaseOrderDetail;
+use crate::models::settings::CompanyProfile;
+
+// Layout A4 (point)
+const MARGIN: f32 = 40.0;
+const RIGHT: f32 = A4_WIDTH - MARGIN;
+const ROW: f32 = 16.0;
+const COL_NO: f32 = MARGIN + 4.0;
+const COL_NAME: f32 = MARGIN + 28.0;
+const COL_QTY_R: f32 = 370.0;
+const COL_UNIT: f32 = 378.0;
+const COL_COST_R: f32 = 470.0;
+const COL_TOTAL_R: f32 = RIGHT - 4.0;
+/// Batas bawah area tabel sebelum pindah halaman
+const TABLE_BOTTOM: f32 = 90.0;
+/// Jumlah karakter nama barang yang muat di kolom nama (Helvetica 9pt)
+const NAME_CHARS: usize = 52;
+
+/// Nomor PO: `PO/YYYYMMDD/NNN` dengan urutan per hari.
+pub fn po_number(date: &str, sequence: i64) -> String {
+    let date: String = date.chars().filter(|c| c.is_ascii_digit()).take(8).collect();
+    format!("PO/{}/{:03}", date, sequence)
+}
+
+/// Label status PO untuk dokumen & UI.
+pub fn status_label(status: &str) -> &'static str {
+    match status {
+        "DRAFT" => "Draft",
+        "SENT" => "Terkirim",
+        "PARTIALLY_RECEIVED" => "Diterima Sebagian",
+        "RECEIVED" => "Diterima",
+        "CANCELLED" => "Dibatalkan",
+        _ => "-",
+    }
+}
+
+/// Render PO A4 (multi-halaman bila item banyak): kop toko, supplier,
+/// tabel barang dengan perkiraan harga, total, dan kolom tanda tangan.
+pub fn purchase_order_pdf(
+    company: &CompanyProfile,
+    po: &PurchaseOrderDetail,
+    logo: Option<&RasterImage>,
+) -> Vec<u8> {
+    let mut pages: Vec<PdfPage> = Vec::new();
+
+    let mut page = PdfPage::new(A4_WIDTH, A4_HEIGHT);
+    let mut y = po_header(&mut page, company, po, logo);
+    y = table_header(&mut page, y);
+
+    for (i, item) in po.items.iter().enumerate() {
+        let name = match item.sku.as_deref().filter(|s| !s.is_empty()) {
+            Some(sku) => format!("{} ({})", item.product_name, sku),
+            None => item.product_name.clone(),
+        };
+        let name_lines = wrap_text(&name, NAME_CHARS);
+        let row_height = ROW * name_lines.len() as f32;
+        if y - row_height < TABLE_BOTTOM {
+            pages.push(page);
+            page = PdfPage::new(A4_WIDTH, A4_HEIGHT);
+            y = table_header(&mut page, A4_HEIGHT - MARGIN);
+        }
+
+        let baseline = y - ROW + 4.0;
+        page.text(COL_NO, baseline, 9.0, PdfFont::Helvetica, &(i + 1).to_string());
+        for (n, l) in name_lines.iter().enumerate() {
+            page.text(COL_NAME, baseline - ROW * n as f32, 9.0, PdfFont::Helvetica, l);
+        }
+        page.text_right(COL_QTY_R, baseline, 9.0, PdfFont::Courier, &format_quantity(item.quantity));
+        page.text(COL_UNIT, baseline, 8.5, PdfFont::Helvetica, &item.unit);
+        page.text_right(COL_COST_R, baseline, 9.0, PdfFont::Courier, &format_number(item.unit_cost.round() as i64));
+        page.text_right(COL_TOTAL_R, baseline, 9.0, PdfFont::Courier, &format_number(item.subtotal.round() as i64));
+        y -= row_height;
+        page.line(MARGIN, y, RIGHT, y, 0.3);
+    }
+
+    // Total + catatan + tanda tangan butuh ± 10 baris
+    if y - ROW * 10.0 < TABLE_BOTTOM - 40.0 {
+        pages.push(page);
+        page = PdfPage::new(A4_WIDTH, A4_HEIGHT);
+        y = A4_HEIGHT - MARGIN;
+    }
+    po_footer(&mut page, y - 8.0, po);
+    pages.push(page);
+
+    let total_pages = pages.len();
+    let mut doc = PdfDocument::new(&format!("Purchase Order {}", po.order.po_number));
+    for (i, mut page) in pages.into_iter().enumerate() {
+        page.line(MARGIN, 60.0, RIGHT, 60.0, 0.5);
+        page.text(MARGIN, 46.0, 8.0, PdfFont::Helvetica, &po.order.po_number);
+        page.text_right(
+            RIGHT,
+            46.0,
+            8.0,
+            PdfFont::Courier,
+            &format!("Hal. {}/{}", i + 1, total_pages),
+        );
+        doc.add_page(page);
+    }
+    doc.to_bytes()
+}
+
+/// Kop PO: identitas toko, judul, supplier, dan info PO.
+/// Mengembalikan posisi y untuk tabel item.
+fn po_header(
+    page: &mut PdfPage,
+    company: &CompanyProfile,
+    po: &PurchaseOrderDetail,
+    logo: Option<&RasterImage>,
+) -> f32 {
+    let mut y = A4_HEIGHT - MARGIN;
+    let mut text_x = MARGIN;
+
+    if let Some(logo) = logo.filter(|l| l.width > 0 && l.height > 0) {
+        let h = 56.0;
+        let w = (h * logo.width as f32 / logo.height as f32).min(120.0);
+        page.image(logo, MARGIN, y - h, w, h);
+        text_x = MARGIN + w + 12.0;
+    }
+
+    let store_name = if company.store_name.trim().is_empty() { "TOKO" } else { company.store_name.trim() };
+    page.text(text_x, y - 14.0, 14.0, PdfFont::HelveticaBold, store_name);
+    let mut info_y = y - 28.0;
+    let contact = [
+        company.address.trim().to_string(),
+        [company.phone.trim(), company.email.trim()]
+            .iter()
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string())
+            .collect::<Vec<_>>()
+            .join("  |  "),
+    ];
+    for line in contact.iter().filter(|l| !l.is_empty()) {
+        for l in wrap_text(line, 60) {
+            page.text(text_x, info_y, 8.5, PdfFont::Helvetica, &l);
+            info_y -= 11.0;
+        }
+    }
+
+    page.text_right(RIGHT, y - 16.0, 16.0, PdfFont::CourierBold, "PURCHASE ORDER");
+    if po.order.status == "CANCELLED" {
+        page.text_right(RIGHT, y - 34.0, 12.0, PdfFont::CourierBold, "*** DIBATALKAN ***");
+    }
+
+    y = info_y.min(y - 60.0) - 8.0;
+    page.line(MARGIN, y, RIGHT, y, 1.0);
+    y -= 18.0;
+
+    // Supplier (kiri) & info PO (kanan)
+    let top = y;
+    let supplier = &po.supplier;
+    page.text(MARGIN, y, 8.5, PdfFont::HelveticaBold, "Kepada Supplier:");
+    y -= 12.0;
+    page.text(MARGIN, y, 9.0, PdfFont::Helvetica, supplier.name.trim());
+    y -= 12.0;
+    let details = [
+        supplier.contact_person.as_deref().map(|c| format!("Up. {}", c.trim())),
+        supplier.address.clone(),
+        supplier.phone.as_deref().map(|p| format!("Telp. {}", p.trim())),
+        supplier.email.clone(),
+    ];
+    for detail in details.iter().flatten().filter(|d| !d.trim().is_empty()) {
+        for l in wrap_text(detail.trim(), 50) {
+            page.text(MARGIN, y, 8.5, PdfFont::Helvetica, &l);
+            y -= 11.0;
+        }
+    }
+
+    let mut meta_y = top;
+    let date: String = po.order.created_at.as_deref().unwrap_or("").chars().take(10).collect();
+    let meta = [
+        ("No. PO", po.order.po_number.clone()),
+        ("Tanggal", date),
+        ("Tgl. Kirim", po.order.expected_date.clone().unwrap_or_default()),
+        ("Status", status_label(&po.order.status).to_string()),
+        ("Dibuat oleh", po.order.created_by_name.clone().unwrap_or_default()),
+    ];
+    for (label, value) in meta.iter().filter(|(_, v)| !v.is_empty()) {
+        page.text(340.0, meta_y, 8.5, PdfFont::Helvetica, label);
+        page.text(410.0, meta_y, 8.5, PdfFont::Helvetica, &format!(": {}", value));
+        meta_y -= 12.0;
+    }
+
+    y.min(meta_y) - 14.0
+}
+
+/// Header tabel item (latar abu-abu). Mengembalikan y di bawah header.
+fn table_header(page: &mut PdfPage, y: f32) -> f32 {
+    page.fill_rect(MARGIN, y - ROW - 2.0, RIGHT - MARGIN, ROW + 2.0, 0.88);
+    let baseline = y - ROW + 3.0;
+    page.text(COL_NO, baseline, 8.5, PdfFont::HelveticaBold, "No");
+    page.text(COL_NAME, baseline, 8.5, PdfFont::HelveticaBold, "Nama Barang");
+    page.text_right(COL_QTY_R, baseline, 8.5, PdfFont::CourierBold, "Qty");
+    page.text(COL_UNIT, baseline, 8.5, PdfFont::HelveticaBold, "Sat.");
+    page.text_right(COL_COST_R, baseline, 8.5, PdfFont::CourierBold, "Harga");
+    page.text_right(COL_TOTAL_R, baseline, 8.5, PdfFont::CourierBold, "Jumlah");
+    y - ROW - 2.0
+}
+
+/// Total perkiraan, catatan, dan kolom tanda tangan.
+fn po_footer(page: &mut PdfPage, mut y: f32, po: &PurchaseOrderDetail) {
+    y -= ROW;
+    page.line(360.0, y + ROW - 3.0, RIGHT, y + ROW - 3.0, 0.8);
+    page.text(360.0, y, 9.0, PdfFont::HelveticaBold, "TOTAL PERKIRAAN");
+    page.text_right(
+        COL_TOTAL_R,
+        y,
+        9.0,
+        PdfFont::CourierBold,
+        &format!("Rp {}", format_number(po.order.total_amount.round() as i64)),
+    );
+
+    if let Some(notes) = po.order.notes.as_deref().filter(|n| !n.trim().is_empty()) {
+        y -= ROW * 1.5;
+        page.text(MARGIN, y, 8.5, PdfFont::HelveticaBold, "Catatan:");
+        for l in wrap_text(notes.trim(), 90) {
+            y -= 11.0;
+            page.text(MARGIN, y, 8.5, PdfFont::Helvetica, &l);
+        }
+    }
+
+    y -= ROW * 2.0;
+    page.text(MARGIN, y, 8.5, PdfFont::Helvetica, "Dipesan oleh,");
+    page.text(380.0, y, 8.5, PdfFont::Helvetica, "Disetujui supplier,");
+    y -= ROW * 3.5;
+    page.line(MARGIN, y, MARGIN + 150.0, y, 0.5);
+    page.line(380.0, y, 530.0, y, 0.5);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::purchase::{PurchaseOrder, PurchaseOrderItem, Supplier};
+
+    #[test]
+    fn test_po_number() {
+        assert_eq!(po_number("2024-05-01", 7), "PO/20240501/007");
+        assert_eq!(po_number("20240501", 1234), "PO/20240501/1234");
+    }
+
+    #[test]
+    fn test_purchase_order_pdf_paginates() {
+        let item = |i: i64| PurchaseOrderItem {
+            id: i,
+            purchase_order_id: 1,
+            product_id: i,
+            product_name: format!("Barang {}", i),
+            sku: None,
+            unit: "pcs".into(),
+            quantity: 10.0,
+            received_quantity: 0.0,
+            unit_cost: 1500.0,
+            subtotal: 15000.0,
+        };
+        let po = PurchaseOrderDetail {
+            order: PurchaseOrder {
+                id: 1,
+                po_number: "PO/20240501/001".into(),
+                supplier_id: 1,
+                supplier_name: "CV Maju".into(),
+                status: "DRAFT".into(),
+                expected_date: None,
+                notes: Some("Kirim pagi".into()),
+                total_amount: 15000.0 * 60.0,
+                created_by: None,
+                created_by_name: None,
+                created_at: Some("2024-05-01 08:00:00".into()),
+                sent_at: None,
+                closed_at: None,
+                item_count: 60,
+            },
+            supplier: Supplier {
+                id: 1,
+                name: "CV Maju".into(),
+                contact_person: None,
+                phone: None,
+                email: None,
+                address: None,
+                notes: None,
+                is_active: true,
+                created_at: None,
+            },
+            items: (1..=60).map(item).collect(),
+        };
+        let company = CompanyProfile {
+            store_name: "Toko".into(),
+            address: String::new(),
+            phone: String::new(),
+            email: String::new(),
+            website: String::new(),
+            logo_path: String::new(),
+            tax_number: String::new(),
+        };
+
+        let bytes = purchase_order_pdf(&company, &po, None);
+        let text = String::from_utf8_lossy(&bytes);
+        assert!(bytes.starts_with(b"%PDF-1.4"));
+        assert!(text.contains("/Count 2"));
+    }
+}
@@ -0,0 +1,49 @@
+//! Aturan antrian cetak — status job dan jadwal retry (backoff).
+
+/// Job menunggu dikirim ke printer (termasuk yang menunggu retry)
+pub const JOB_QUEUED: &str = "QUEUED";
+/// Job sedang dikirim oleh worker
+pub const JOB_PRINTING: &str = "PRINTING";
+/// Job berhasil dicetak
+pub const JOB_DONE: &str = "DONE";
+/// Job gagal setelah semua percobaan habis — menunggu kasir memperbaiki printer
+pub const JOB_FAILED: &str = "FAILED";
+
+/// Jumlah percobaan default sebelum job ditandai FAILED
+pub const DEFAULT_MAX_ATTEMPTS: i64 = 5;
+
+/// Nama event Tauri yang dikirim ke frontend saat job gagal permanen
+pub const EVENT_JOB_FAILED: &str = "print-job-failed";
+
+/// Jeda (detik) sebelum percobaan berikutnya setelah `attempts` kali gagal.
+/// Exponential backoff: 2, 4, 8, 16, ... dibatasi maksimal 60 detik.
+pub fn retry_delay_secs(attempts: i64) -> i64 {
+    let exp = attempts.clamp(1, 6) as u32;
+    2_i64.pow(exp).min(60)
+}
+
+/// Apakah job harus berhenti dicoba (ditandai FAILED).
+pub fn is_exhausted(attempts: i64, max_attempts: i64) -> bool {
+    attempts >= max_attempts.max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_delay_backoff() {
+        assert_eq!(retry_delay_secs(1), 2);
+        assert_eq!(retry_delay_secs(2), 4);
+        assert_eq!(retry_delay_secs(4), 16);
+        assert_eq!(retry_delay_secs(10), 60);
+        assert_eq!(retry_delay_secs(0), 2);
+    }
+
+    #[test]
+    fn test_is_exhausted() {
+        assert!(!is_exhausted(4, 5));
+        assert!(is_exhausted(5, 5));
+        assert!(is_exhausted(1, 0));
+    }
+}
@@ -0,0 +1,118 @@
+//! Konversi gambar (logo PNG/JPEG) ke raster monokrom untuk ESC/POS `GS v 0`.
+
+use image::imageops::FilterType;
+use image::GenericImageView;
+
+/// Lebar area cetak (dot) untuk lebar kertas tertentu pada printer 203 dpi.
+pub fn paper_dots(paper_width: &str) -> u32 {
+    match paper_width {
+        "58mm" => 384,
+        _ => 576,
+    }
+}
+
+/// Gambar monokrom 1 bit per pixel, baris dipadatkan per byte (MSB = kiri).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RasterImage {
+    pub width: u32,
+    pub height: u32,
+    /// `height` baris × `bytes_per_row` byte, bit 1 = titik hitam.
+    pub data: Vec<u8>,
+}
+
+impl RasterImage {
+    pub fn bytes_per_row(&self) -> usize {
+        self.width.div_ceil(8) as usize
+    }
+}
+
+/// Muat file gambar dan ubah ke raster ber-dithering (Floyd–Steinberg),
+/// diperkecil agar tidak melebihi `max_width` dot.
+pub fn load_logo(path: &str, max_width: u32) -> Result<RasterImage, String> {
+    let img = image::open(path).map_err(|e| format!("Gagal membaca logo: {}", e))?;
+    Ok(dither_image(&img, max_width))
+}
+
+/// Ubah `DynamicImage` ke raster monokrom. Area transparan dianggap putih.
+pub fn dither_image(img: &image::DynamicImage, max_width: u32) -> RasterImage {
+    let (w, h) = img.dimensions();
+    let img = if w > max_width {
+        let new_h = ((h as u64 * max_width as u64) / w as u64).max(1) as u32;
+        img.resize_exact(max_width, new_h, FilterType::Triangle)
+    } else {
+        img.clone()
+    };
+
+    let rgba = img.to_rgba8();
+    let (width, height) = rgba.dimensions();
+
+    // Grayscale (0.0 = hitam, 255.0 = putih) dengan alpha di-blend ke putih
+    let mut gray: Vec<f32> = rgba
+        .pixels()
+        .map(|p| {
+            let [r, g, b, a] = p.0;
+            let luma = 0.299 * r as f32 + 0.587 * g as f32 + 0.114 * b as f32;
+            let alpha = a as f32 / 255.0;
+            luma * alpha + 255.0 * (1.0 - alpha)
+        })
+        .collect();
+
+    let bytes_per_row = width.div_ceil(8) as usize;
+    let mut data = vec![0u8; bytes_per_row * height as usize];
+    let (w, h) = (width as usize, height as usize);
+
+    for y in 0..h {
+        for x in 0..w {
+            let idx = y * w + x;
+            let old = gray[idx];
+            let new = if old < 128.0 { 0.0 } else { 255.0 };
+            let err = old - new;
+
+            if new == 0.0 {
+                data[y * bytes_per_row + x / 8] |= 0x80 >> (x % 8);
+            }
+
+            // Sebarkan error ke pixel tetangga
+            if x + 1 < w {
+                gray[idx + 1] += err * 7.0 / 16.0;
+            }
+            if y + 1 < h {
+                if x > 0 {
+                    gray[idx + w - 1] += err * 3.0 / 16.0;
+                }
+                gray[idx + w] += err * 5.0 / 16.0;
+                if x + 1 < w {
+                    gray[idx + w + 1] += err / 16.0;
+                }
+            }
+        }
+    }
+
+    RasterImage { width, height, data }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{DynamicImage, Rgba, RgbaImage};
+
+    #[test]
+    fn test_dither_black_white_and_transparent() {
+        let mut img = RgbaImage::new(10, 2);
+        for x in 0..10 {
+            img.put_pixel(x, 0, Rgba([0, 0, 0, 255]));
+            img.put_pixel(x, 1, Rgba([0, 0, 0, 0])); // transparan → putih
+        }
+        let raster = dither_image(&DynamicImage::ImageRgba8(img), 576);
+
+        assert_eq!(raster.bytes_per_row(), 2);
+        assert_eq!(raster.data, vec![0xFF, 0xC0, 0x00, 0x00]);
+    }
+
+    #[test]
+    fn test_dither_scales_to_paper_width() {
+        let img = DynamicImage::ImageRgba8(RgbaImage::new(800, 400));
+        let raster = dither_image(&img, paper_dots("58mm"));
+        assert_eq!((raster.width, raster.height), (384, 192));
+    }
+}
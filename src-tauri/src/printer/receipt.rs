@@ -0,0 +1,548 @@
+//! Renderer struk berbasis template.
+//!
+//! Struk disusun dulu menjadi daftar `ReceiptLine` (template), lalu
+//! di-encode ke ESC/POS. Lebar kolom mengikuti `receipt.paper_width`
+//! (58mm = 32 kolom, 80mm = 48 kolom).
+
+use super::escpos::{Align, EscPosBuilder};
+use super::raster::RasterImage;
+use crate::models::settings::{CompanyProfile, ReceiptSettings, TaxSettings};
+
+/// Jumlah karakter per baris (font A) untuk lebar kertas tertentu.
+pub fn paper_columns(paper_width: &str) -> usize {
+    match paper_width {
+        "58mm" => 32,
+        _ => 48,
+    }
+}
+
+/// Format number with thousand separator (optimized)
+pub fn format_number(n: i64) -> String {
+    let s = n.abs().to_string();
+    let len = s.len();
+    let mut result = String::with_capacity(len + len / 3);
+    for (i, c) in s.chars().enumerate() {
+        if i > 0 && (len - i) % 3 == 0 {
+            result.push('.');
+        }
+        result.push(c);
+    }
+    if n < 0 { result.insert(0, '-'); }
+    result
+}
+
+/// Format qty: bilangan bulat apa adanya, qty timbang maks. 3 desimal
+/// dengan koma (mis. 0,25).
+pub fn format_quantity(q: f64) -> String {
+    // Bulatkan ke 3 desimal dulu supaya 1.9999 tercetak "2", bukan "2,"
+    let text = format!("{:.3}", q);
+    text.trim_end_matches('0').trim_end_matches('.').replace('.', ",")
+}
+
+/// Label metode pembayaran untuk dicetak di struk.
+pub fn payment_label(method: &str) -> &str {
+    match method {
+        "CASH" => "Tunai",
+        "DEBIT" => "Debit",
+        "QRIS" => "QRIS",
+        other => other,
+    }
+}
+
+/// Word-wrap teks ke lebar tertentu. Kata yang lebih panjang dari `width`
+/// dipotong paksa. Baris baru (`\n`) dan indentasi awal tiap paragraf
+/// dipertahankan.
+pub fn wrap_text(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+
+    for paragraph in text.lines() {
+        let indent_len = paragraph.len() - paragraph.trim_start_matches(' ').len();
+        let indent = " ".repeat(indent_len.min(width.saturating_sub(1)));
+        let width = width.saturating_sub(indent.len()).max(1);
+        let start = lines.len();
+        let mut current = String::new();
+        let mut current_len = 0usize;
+
+        for word in paragraph.split_whitespace() {
+            let mut word: Vec<char> = word.chars().collect();
+
+            // Kata terlalu panjang: potong per `width` karakter
+            while word.len() > width {
+                if current_len > 0 {
+                    lines.push(std::mem::take(&mut current));
+                    current_len = 0;
+                }
+                let rest = word.split_off(width);
+                lines.push(word.into_iter().collect());
+                word = rest;
+            }
+
+            let needed = if current_len == 0 { word.len() } else { current_len + 1 + word.len() };
+            if needed > width {
+                lines.push(std::mem::take(&mut current));
+                current_len = 0;
+            }
+            if current_len > 0 {
+                current.push(' ');
+                current_len += 1;
+            }
+            current.extend(word.iter());
+            current_len += word.len();
+        }
+
+        if current_len > 0 {
+            lines.push(current);
+        }
+        for line in &mut lines[start..] {
+            line.insert_str(0, &indent);
+        }
+    }
+
+    if lines.is_empty() {
+        lines.push(String::new());
+    }
+    lines
+}
+
+/// Susun teks kiri dan kanan dalam satu baris selebar `width`.
+/// Jika tidak muat, teks kiri di-wrap dan teks kanan diletakkan rata kanan
+/// di baris terakhir (atau baris sendiri bila tetap tidak muat).
+pub fn columns(left: &str, right: &str, width: usize) -> Vec<String> {
+    let right_len = right.chars().count();
+    let left_width = width.saturating_sub(right_len + 1).max(1);
+    let mut lines = wrap_text(left, left_width);
+
+    let last = lines.pop().unwrap_or_default();
+    let last_len = last.chars().count();
+    if last_len + 1 + right_len <= width {
+        let pad = width - last_len - right_len;
+        lines.push(format!("{}{}{}", last, " ".repeat(pad), right));
+    } else {
+        lines.push(last);
+        lines.push(format!("{:>width$}", right, width = width));
+    }
+    lines
+}
+
+/// Satu baris template struk.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReceiptLine {
+    Text {
+        text: String,
+        align: Align,
+        bold: bool,
+        large: bool,
+    },
+    Columns {
+        left: String,
+        right: String,
+        bold: bool,
+    },
+    Separator(char),
+    /// Gambar raster (logo), dicetak rata tengah.
+    Image(RasterImage),
+    /// QR code native printer, dicetak rata tengah.
+    QrCode(String),
+    Feed,
+}
+
+impl ReceiptLine {
+    fn text(text: impl Into<String>, align: Align) -> Self {
+        ReceiptLine::Text { text: text.into(), align, bold: false, large: false }
+    }
+
+    fn pair(left: impl Into<String>, right: impl Into<String>) -> Self {
+        ReceiptLine::Columns { left: left.into(), right: right.into(), bold: false }
+    }
+}
+
+/// Item yang dicetak di struk.
+#[derive(Debug, Clone)]
+pub struct ReceiptItem {
+    pub name: String,
+    pub quantity: f64,
+    pub price: f64,
+    pub discount_amount: f64,
+    pub subtotal: f64,
+}
+
+/// Data transaksi yang dibutuhkan untuk mencetak struk.
+#[derive(Debug, Clone)]
+pub struct ReceiptData {
+    pub transaction_id: String,
+    pub timestamp: String,
+    pub cashier_name: Option<String>,
+    pub items: Vec<ReceiptItem>,
+    pub discount_amount: f64,
+    pub tax_amount: f64,
+    pub total_amount: f64,
+    pub payment_method: String,
+    pub amount_paid: f64,
+    pub change_given: f64,
+    /// Isi QR code di bawah total (link transaksi / payload QRIS), jika ada.
+    pub qr_payload: Option<String>,
+    /// Nomor cetak ulang — `Some(n)` mencetak banner "SALINAN/COPY #n".
+    pub reprint_number: Option<i64>,
+    /// Transaksi sudah dibatalkan — dicetak banner "VOID".
+    pub is_void: bool,
+}
+
+/// Banner status struk (VOID / SALINAN) di bawah header toko.
+pub fn status_banners(data: &ReceiptData) -> Vec<ReceiptLine> {
+    let banner = |text: String| ReceiptLine::Text {
+        text,
+        align: Align::Center,
+        bold: true,
+        large: true,
+    };
+
+    let mut lines = Vec::new();
+    if data.is_void {
+        lines.push(banner("*** VOID ***".into()));
+    }
+    if let Some(n) = data.reprint_number {
+        lines.push(banner("SALINAN/COPY".into()));
+        lines.push(ReceiptLine::text(format!("Cetak ulang ke-{}", n), Align::Center));
+    }
+    if !lines.is_empty() {
+        lines.push(ReceiptLine::Separator('='));
+    }
+    lines
+}
+
+/// Template struk: gabungan profil toko, pengaturan struk, dan pajak.
+pub struct ReceiptTemplate<'a> {
+    pub company: &'a CompanyProfile,
+    pub receipt: &'a ReceiptSettings,
+    pub tax: &'a TaxSettings,
+    /// Logo yang sudah dikonversi ke raster (None = tidak dicetak).
+    pub logo: Option<&'a RasterImage>,
+}
+
+impl<'a> ReceiptTemplate<'a> {
+    pub fn columns(&self) -> usize {
+        paper_columns(&self.receipt.paper_width)
+    }
+
+    /// Susun struk menjadi daftar baris (tanpa perintah printer).
+    pub fn lines(&self, data: &ReceiptData) -> Vec<ReceiptLine> {
+        let mut lines = Vec::new();
+        self.push_header(&mut lines, data);
+        self.push_items(&mut lines, data);
+        self.push_totals(&mut lines, data);
+        self.push_footer(&mut lines, data);
+        lines
+    }
+
+    fn push_header(&self, lines: &mut Vec<ReceiptLine>, data: &ReceiptData) {
+        let c = self.company;
+
+        if self.receipt.show_logo {
+            if let Some(logo) = self.logo {
+                lines.push(ReceiptLine::Image(logo.clone()));
+            }
+        }
+
+        let store_name = if c.store_name.trim().is_empty() { "TOKO" } else { c.store_name.trim() };
+        lines.push(ReceiptLine::Text {
+            text: store_name.to_string(),
+            align: Align::Center,
+            bold: true,
+            large: true,
+        });
+
+        if !c.address.trim().is_empty() {
+            lines.push(ReceiptLine::text(c.address.trim(), Align::Center));
+        }
+        if !c.phone.trim().is_empty() {
+            lines.push(ReceiptLine::text(format!("Telp: {}", c.phone.trim()), Align::Center));
+        }
+        if !c.tax_number.trim().is_empty() {
+            lines.push(ReceiptLine::text(format!("NPWP: {}", c.tax_number.trim()), Align::Center));
+        }
+
+        if !self.receipt.header_text.trim().is_empty() {
+            lines.push(ReceiptLine::text(self.receipt.header_text.trim(), Align::Center));
+        }
+
+        lines.push(ReceiptLine::Separator('='));
+        lines.extend(status_banners(data));
+
+        let short_id: String = data.transaction_id.chars().take(8).collect();
+        lines.push(ReceiptLine::pair("No", short_id.to_uppercase()));
+        lines.push(ReceiptLine::pair("Tgl", data.timestamp.clone()));
+        if self.receipt.show_cashier_name {
+            if let Some(name) = data.cashier_name.as_deref().filter(|n| !n.is_empty()) {
+                lines.push(ReceiptLine::pair("Kasir", name));
+            }
+        }
+        lines.push(ReceiptLine::Separator('-'));
+    }
+
+    fn push_items(&self, lines: &mut Vec<ReceiptLine>, data: &ReceiptData) {
+        for item in &data.items {
+            // Tanpa rincian diskon, jumlah baris = subtotal bersih supaya
+            // baris item tetap sama dengan Subtotal
+            let amount = if self.receipt.show_discount_detail {
+                item.price * item.quantity
+            } else {
+                item.subtotal
+            };
+            lines.push(ReceiptLine::text(item.name.clone(), Align::Left));
+            lines.push(ReceiptLine::pair(
+                format!("  {} x {}", format_quantity(item.quantity), format_number(item.price.round() as i64)),
+                format_number(amount.round() as i64),
+            ));
+            if self.receipt.show_discount_detail && item.discount_amount > 0.0 {
+                lines.push(ReceiptLine::pair(
+                    "  Diskon",
+                    format!("-{}", format_number(item.discount_amount.round() as i64)),
+                ));
+            }
+        }
+        lines.push(ReceiptLine::Separator('-'));
+    }
+
+    fn push_totals(&self, lines: &mut Vec<ReceiptLine>, data: &ReceiptData) {
+        let items_total: f64 = data.items.iter().map(|i| i.subtotal).sum();
+        lines.push(ReceiptLine::pair("Subtotal", format_number(items_total.round() as i64)));
+
+        if self.receipt.show_discount_detail && data.discount_amount > 0.0 {
+            lines.push(ReceiptLine::pair(
+                "Diskon",
+                format!("-{}", format_number(data.discount_amount.round() as i64)),
+            ));
+        }
+
+        if self.receipt.show_tax_detail && data.tax_amount > 0.0 {
+            let mut label = format!("{} {}%", self.tax.label, self.tax.rate);
+            if self.tax.is_included {
+                label.push_str(" (termasuk)");
+            }
+            lines.push(ReceiptLine::pair(label, format_number(data.tax_amount.round() as i64)));
+        }
+
+        lines.push(ReceiptLine::Columns {
+            left: "TOTAL".into(),
+            right: format_number(data.total_amount.round() as i64),
+            bold: true,
+        });
+        lines.push(ReceiptLine::pair(
+            format!("Bayar ({})", payment_label(&data.payment_method)),
+            format_number(data.amount_paid.round() as i64),
+        ));
+        if data.payment_method == "CASH" || data.change_given > 0.0 {
+            lines.push(ReceiptLine::pair("Kembali", format_number(data.change_given.round() as i64)));
+        }
+        lines.push(ReceiptLine::Separator('='));
+    }
+
+    fn push_footer(&self, lines: &mut Vec<ReceiptLine>, data: &ReceiptData) {
+        if let Some(payload) = data.qr_payload.as_deref().filter(|p| !p.is_empty()) {
+            lines.push(ReceiptLine::QrCode(payload.to_string()));
+        }
+        if data.is_void {
+            lines.push(ReceiptLine::Text {
+                text: "TRANSAKSI DIBATALKAN - TIDAK SAH".into(),
+                align: Align::Center,
+                bold: true,
+                large: false,
+            });
+        } else if !self.receipt.footer_text.trim().is_empty() {
+            lines.push(ReceiptLine::text(self.receipt.footer_text.trim(), Align::Center));
+        }
+        lines.push(ReceiptLine::Feed);
+    }
+
+    /// Render struk ke ESC/POS, diulang sebanyak `receipt.copies`
+    /// (cetak ulang selalu satu lembar).
+    pub fn render_escpos(&self, data: &ReceiptData) -> Vec<u8> {
+        let lines = self.lines(data);
+        let copies = if data.reprint_number.is_some() { 1 } else { self.receipt.copies.max(1) };
+        let mut esc = EscPosBuilder::new();
+        for _ in 0..copies {
+            encode_lines(&mut esc, &lines, self.columns());
+            esc.cut();
+        }
+        esc.build()
+    }
+}
+
+/// Encode baris template ke builder ESC/POS.
+pub fn encode_lines(esc: &mut EscPosBuilder, lines: &[ReceiptLine], width: usize) {
+    for line in lines {
+        match line {
+            ReceiptLine::Text { text, align, bold, large } => {
+                // Huruf dobel memakan 2 kolom per karakter
+                let wrap_width = if *large { width / 2 } else { width };
+                esc.align(*align).bold(*bold).double_size(*large);
+                for l in wrap_text(text, wrap_width) {
+                    esc.line(&l);
+                }
+                esc.double_size(false).bold(false).align(Align::Left);
+            }
+            ReceiptLine::Columns { left, right, bold } => {
+                esc.align(Align::Left).bold(*bold);
+                for l in columns(left, right, width) {
+                    esc.line(&l);
+                }
+                esc.bold(false);
+            }
+            ReceiptLine::Separator(ch) => {
+                esc.align(Align::Left).line(&ch.to_string().repeat(width));
+            }
+            ReceiptLine::Image(image) => {
+                esc.align(Align::Center).raster_image(image).align(Align::Left);
+            }
+            ReceiptLine::QrCode(payload) => {
+                // Modul lebih besar di kertas 80mm supaya mudah di-scan
+                let module_size = if width > 32 { 6 } else { 5 };
+                esc.align(Align::Center).qr_code(payload, module_size).feed(1).align(Align::Left);
+            }
+            ReceiptLine::Feed => {
+                esc.feed(1);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrap_text() {
+        assert_eq!(wrap_text("Kopi Susu Gula Aren", 10), vec!["Kopi Susu", "Gula Aren"]);
+        assert_eq!(wrap_text("ABCDEFGHIJKL", 5), vec!["ABCDE", "FGHIJ", "KL"]);
+        assert_eq!(wrap_text("", 10), vec![""]);
+        assert_eq!(wrap_text("  2 x 15.000", 10), vec!["  2 x", "  15.000"]);
+    }
+
+    #[test]
+    fn test_columns_fit_width() {
+        let lines = columns("TOTAL", "15.000", 32);
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].chars().count(), 32);
+        assert!(lines[0].starts_with("TOTAL") && lines[0].ends_with("15.000"));
+
+        let lines = columns("Nama produk yang sangat panjang sekali", "1.000.000", 20);
+        assert!(lines.iter().all(|l| l.chars().count() <= 20));
+        assert!(lines.last().unwrap().ends_with("1.000.000"));
+    }
+
+    #[test]
+    fn test_status_banners() {
+        let mut data = ReceiptData {
+            transaction_id: "abc".into(),
+            timestamp: String::new(),
+            cashier_name: None,
+            items: Vec::new(),
+            discount_amount: 0.0,
+            tax_amount: 0.0,
+            total_amount: 0.0,
+            payment_method: "CASH".into(),
+            amount_paid: 0.0,
+            change_given: 0.0,
+            qr_payload: None,
+            reprint_number: None,
+            is_void: false,
+        };
+        assert!(status_banners(&data).is_empty());
+
+        data.reprint_number = Some(2);
+        data.is_void = true;
+        let text: Vec<String> = status_banners(&data)
+            .into_iter()
+            .filter_map(|l| match l {
+                ReceiptLine::Text { text, .. } => Some(text),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(text, vec!["*** VOID ***", "SALINAN/COPY", "Cetak ulang ke-2"]);
+    }
+
+    #[test]
+    fn test_item_lines_sum_to_subtotal() {
+        let company = CompanyProfile {
+            store_name: "Toko".into(),
+            address: String::new(),
+            phone: String::new(),
+            email: String::new(),
+            website: String::new(),
+            logo_path: String::new(),
+            tax_number: String::new(),
+        };
+        let mut receipt = ReceiptSettings {
+            show_logo: false,
+            header_text: String::new(),
+            footer_text: String::new(),
+            show_cashier_name: false,
+            show_tax_detail: false,
+            show_discount_detail: false,
+            paper_width: "58mm".into(),
+            copies: 1,
+            qr_mode: String::new(),
+            qr_link_template: String::new(),
+        };
+        let tax = TaxSettings { is_enabled: false, rate: 0.0, label: "PPN".into(), is_included: false };
+        let item = |price: f64, quantity: f64, discount_amount: f64| ReceiptItem {
+            name: "Item".into(),
+            quantity,
+            price,
+            discount_amount,
+            subtotal: price * quantity - discount_amount,
+        };
+        let data = ReceiptData {
+            transaction_id: "abc".into(),
+            timestamp: String::new(),
+            cashier_name: None,
+            items: vec![item(15000.0, 2.0, 5000.0), item(8000.0, 1.0, 0.0)],
+            discount_amount: 0.0,
+            tax_amount: 0.0,
+            total_amount: 33000.0,
+            payment_method: "CASH".into(),
+            amount_paid: 33000.0,
+            change_given: 0.0,
+            qr_payload: None,
+            reprint_number: None,
+            is_void: false,
+        };
+        let amount = |text: &str| {
+            let value = text.trim_start_matches('-').replace('.', "").parse::<i64>().unwrap();
+            if text.starts_with('-') { -value } else { value }
+        };
+
+        for show_discount_detail in [false, true] {
+            receipt.show_discount_detail = show_discount_detail;
+            let template = ReceiptTemplate { company: &company, receipt: &receipt, tax: &tax, logo: None };
+            let (mut items_sum, mut subtotal) = (0, None);
+            for line in template.lines(&data) {
+                if let ReceiptLine::Columns { left, right, .. } = line {
+                    if left.starts_with("  ") {
+                        items_sum += amount(&right);
+                    } else if left == "Subtotal" {
+                        subtotal = Some(amount(&right));
+                    }
+                }
+            }
+            assert_eq!(Some(items_sum), subtotal, "show_discount_detail = {}", show_discount_detail);
+        }
+    }
+
+    #[test]
+    fn test_format_quantity() {
+        assert_eq!(format_quantity(3.0), "3");
+        assert_eq!(format_quantity(0.25), "0,25");
+        assert_eq!(format_quantity(1.5), "1,5");
+        assert_eq!(format_quantity(0.5), "0,5");
+        assert_eq!(format_quantity(2.0), "2");
+        assert_eq!(format_quantity(1.9999), "2");
+        assert_eq!(format_quantity(2.0004), "2");
+    }
+
+    #[test]
+    fn test_paper_columns() {
+        assert_eq!(paper_columns("58mm"), 32);
+        assert_eq!(paper_columns("80mm"), 48);
+    }
+}
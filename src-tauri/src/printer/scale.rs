@@ -0,0 +1,159 @@
+//! Parser data timbangan elektronik serial (mode continuous & polled).
+//!
+//! Format yang dikenali:
+//! - A&D / CAS continuous: `ST,GS,+0001.250kg` (`US` = belum stabil, `OL` = overload)
+//! - Toledo / NCI polled (`W` + CR): `  1.250kg` atau `?  1.250kg` saat bergerak
+
+use serde::Serialize;
+
+/// Toleransi dua pembacaan dianggap sama (0,5 gram).
+pub const STABLE_TOLERANCE_KG: f64 = 0.0005;
+
+/// Mode komunikasi timbangan.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScaleProtocol {
+    /// Timbangan mengirim data terus-menerus
+    Continuous,
+    /// Timbangan hanya menjawab setelah diminta (perintah poll)
+    Polled,
+}
+
+impl ScaleProtocol {
+    pub fn parse(value: &str) -> Self {
+        match value.to_uppercase().as_str() {
+            "POLLED" => ScaleProtocol::Polled,
+            _ => ScaleProtocol::Continuous,
+        }
+    }
+}
+
+/// Satu pembacaan mentah dari timbangan.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScaleReading {
+    pub weight_kg: f64,
+    pub stable: bool,
+}
+
+/// Berat yang dikirim ke UI (gross dikurangi tare).
+#[derive(Debug, Clone, Serialize)]
+pub struct ScaleWeight {
+    pub gross_kg: f64,
+    pub tare_kg: f64,
+    pub net_kg: f64,
+    pub stable: bool,
+}
+
+impl ScaleWeight {
+    pub fn new(reading: ScaleReading, tare_kg: f64) -> Self {
+        Self {
+            gross_kg: reading.weight_kg,
+            tare_kg,
+            net_kg: ((reading.weight_kg - tare_kg) * 1000.0).round() / 1000.0,
+            stable: reading.stable,
+        }
+    }
+}
+
+/// Parse satu baris data timbangan. `Ok(None)` untuk baris yang tidak berisi
+/// angka (mis. baris kosong / header), `Err` untuk overload / error timbangan.
+pub fn parse_scale_line(line: &str) -> Result<Option<ScaleReading>, String> {
+    let line = line.trim_matches(|c: char| c.is_whitespace() || c.is_control());
+    let upper = line.to_uppercase();
+    if upper.starts_with("OL") || upper.contains(",OL") {
+        return Err("Timbangan overload".into());
+    }
+
+    let Some(start) = line.find(|c: char| c.is_ascii_digit()) else {
+        return Ok(None);
+    };
+    let negative = line[..start].trim_end().ends_with('-');
+    let rest = &line[start..];
+    let end = rest
+        .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == ','))
+        .unwrap_or(rest.len());
+    let number: f64 = rest[..end]
+        .replace(',', ".")
+        .parse()
+        .map_err(|_| format!("Data timbangan tidak valid: {}", line))?;
+
+    let unit = rest[end..].trim().to_lowercase();
+    let kg = if unit.starts_with("kg") || unit.is_empty() {
+        number
+    } else if unit.starts_with('g') {
+        number / 1000.0
+    } else if unit.starts_with("lb") {
+        number * 0.453_592_37
+    } else if unit.starts_with("oz") {
+        number * 0.028_349_523
+    } else {
+        number
+    };
+
+    // "US" (unstable) / "?" (motion) = belum stabil; selain itu dianggap stabil
+    let stable = !(upper.starts_with("US") || line.contains('?') || upper.starts_with("M "));
+    Ok(Some(ScaleReading {
+        weight_kg: if negative { -kg } else { kg },
+        stable,
+    }))
+}
+
+/// Pelacak stabilitas: berat dianggap final setelah `required` pembacaan
+/// stabil berturut-turut dengan nilai yang sama.
+#[derive(Debug, Default)]
+pub struct StabilityTracker {
+    last: Option<f64>,
+    count: usize,
+}
+
+impl StabilityTracker {
+    /// Masukkan pembacaan; `true` jika sudah stabil.
+    pub fn push(&mut self, reading: ScaleReading, required: usize) -> bool {
+        if !reading.stable {
+            self.last = None;
+            self.count = 0;
+            return false;
+        }
+        match self.last {
+            Some(prev) if (prev - reading.weight_kg).abs() <= STABLE_TOLERANCE_KG => self.count += 1,
+            _ => {
+                self.last = Some(reading.weight_kg);
+                self.count = 1;
+            }
+        }
+        self.count >= required
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_scale_line() {
+        let r = parse_scale_line("ST,GS,+0001.250kg\r\n").unwrap().unwrap();
+        assert_eq!(r, ScaleReading { weight_kg: 1.25, stable: true });
+
+        let r = parse_scale_line("US,GS,-0000.020kg").unwrap().unwrap();
+        assert!(!r.stable);
+        assert!((r.weight_kg + 0.02).abs() < 1e-9);
+
+        let r = parse_scale_line("\x02?  0750 g").unwrap().unwrap();
+        assert!(!r.stable);
+        assert!((r.weight_kg - 0.75).abs() < 1e-9);
+
+        assert!(parse_scale_line("OL,GS,+9999.999kg").is_err());
+        assert_eq!(parse_scale_line("").unwrap(), None);
+    }
+
+    #[test]
+    fn test_stability_tracker() {
+        let mut tracker = StabilityTracker::default();
+        let stable = |w| ScaleReading { weight_kg: w, stable: true };
+        assert!(!tracker.push(stable(1.0), 3));
+        assert!(!tracker.push(stable(1.0), 3));
+        assert!(!tracker.push(ScaleReading { weight_kg: 1.0, stable: false }, 3));
+        assert!(!tracker.push(stable(1.0), 3));
+        assert!(!tracker.push(stable(1.0002), 3));
+        assert!(tracker.push(stable(1.0), 3));
+    }
+}
@@ -0,0 +1,100 @@
+//! Status printer real-time via `DLE EOT n` (Epson ESC/POS).
+
+use serde::{Deserialize, Serialize};
+
+/// `DLE EOT 1` — status printer (bit 3 = offline)
+pub const DLE_EOT_PRINTER: &[u8] = b"\x10\x04\x01";
+/// `DLE EOT 2` — penyebab offline (bit 2 = cover terbuka, bit 5 = kertas habis, bit 6 = error)
+pub const DLE_EOT_OFFLINE: &[u8] = b"\x10\x04\x02";
+/// `DLE EOT 4` — sensor kertas (bit 2-3 = hampir habis, bit 5-6 = habis)
+pub const DLE_EOT_PAPER: &[u8] = b"\x10\x04\x04";
+
+/// Hasil pembacaan status printer.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PrinterStatus {
+    /// false jika transport tidak mendukung query status atau printer tidak menjawab
+    pub checked: bool,
+    pub online: bool,
+    pub cover_open: bool,
+    pub paper_out: bool,
+    pub paper_near_end: bool,
+    pub error: bool,
+}
+
+impl PrinterStatus {
+    /// Status "tidak diketahui" (transport tanpa jalur baca, mis. CUPS/spooler).
+    pub fn unknown() -> Self {
+        Self::default()
+    }
+
+    /// Susun status dari byte balasan `DLE EOT 1`, `2`, dan `4`.
+    /// Byte yang tidak sesuai pola tetap (bukan balasan status) → `unknown()`.
+    pub fn from_bytes(printer: u8, offline: u8, paper: u8) -> Self {
+        // Bit 1 & 4 selalu 1, bit 0 & 7 selalu 0 pada balasan status
+        let valid = |b: u8| b & 0x93 == 0x12;
+        if !valid(printer) || !valid(offline) || !valid(paper) {
+            return Self::unknown();
+        }
+
+        Self {
+            checked: true,
+            online: printer & 0x08 == 0,
+            cover_open: offline & 0x04 != 0,
+            paper_out: offline & 0x20 != 0 || paper & 0x60 != 0,
+            paper_near_end: paper & 0x0C != 0,
+            error: offline & 0x40 != 0,
+        }
+    }
+
+    /// Pesan error jika printer tidak bisa mencetak, `None` jika siap
+    /// (atau status tidak diketahui).
+    pub fn blocking_problem(&self) -> Option<String> {
+        if !self.checked {
+            return None;
+        }
+        if self.cover_open {
+            return Some("Cover printer terbuka. Tutup cover lalu coba lagi.".into());
+        }
+        if self.paper_out {
+            return Some("Kertas printer habis. Ganti gulungan kertas lalu coba lagi.".into());
+        }
+        if self.error {
+            return Some("Printer mengalami error (cutter/mekanik). Matikan dan nyalakan ulang printer.".into());
+        }
+        if !self.online {
+            return Some("Printer sedang offline.".into());
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_ready() {
+        let status = PrinterStatus::from_bytes(0x12, 0x12, 0x12);
+        assert!(status.checked && status.online);
+        assert_eq!(status.blocking_problem(), None);
+    }
+
+    #[test]
+    fn test_status_problems() {
+        let cover = PrinterStatus::from_bytes(0x1A, 0x16, 0x12);
+        assert!(cover.cover_open && !cover.online);
+        assert!(cover.blocking_problem().unwrap().contains("Cover"));
+
+        let paper = PrinterStatus::from_bytes(0x1A, 0x32, 0x72);
+        assert!(paper.paper_out);
+        assert!(paper.blocking_problem().unwrap().contains("Kertas"));
+
+        let near_end = PrinterStatus::from_bytes(0x12, 0x12, 0x1E);
+        assert!(near_end.paper_near_end && near_end.blocking_problem().is_none());
+    }
+
+    #[test]
+    fn test_status_invalid_reply() {
+        assert_eq!(PrinterStatus::from_bytes(0xFF, 0x00, 0x12), PrinterStatus::unknown());
+    }
+}
@@ -0,0 +1,101 @@
+//! Tiket pesanan dapur/bar — hanya item, jumlah, dan catatan (tanpa harga).
+
+use super::escpos::{Align, EscPosBuilder};
+use super::receipt::{format_quantity, wrap_text};
+
+/// Satu baris item di tiket.
+#[derive(Debug, Clone)]
+pub struct TicketItem {
+    pub name: String,
+    pub quantity: f64,
+    pub notes: Option<String>,
+}
+
+/// Data tiket untuk satu stasiun (printer profile).
+#[derive(Debug, Clone)]
+pub struct KitchenTicket {
+    /// Nama stasiun, mis. "DAPUR" / "BAR"
+    pub station: String,
+    pub transaction_id: String,
+    pub cashier_name: String,
+    pub timestamp: String,
+    /// Catatan level pesanan (mis. "Dine in meja 4")
+    pub order_notes: Option<String>,
+    pub items: Vec<TicketItem>,
+}
+
+/// Nomor pesanan pendek (8 karakter pertama UUID) agar mudah dipanggil.
+pub fn order_number(transaction_id: &str) -> String {
+    transaction_id.chars().take(8).collect::<String>().to_uppercase()
+}
+
+/// Susun baris teks tiket selebar `width` kolom.
+pub fn ticket_lines(ticket: &KitchenTicket, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    lines.push(format!("Pesanan #{}", order_number(&ticket.transaction_id)));
+    lines.push(format!("{} - {}", ticket.timestamp, ticket.cashier_name));
+    if let Some(notes) = ticket.order_notes.as_deref().filter(|n| !n.trim().is_empty()) {
+        lines.extend(wrap_text(&format!("Catatan: {}", notes.trim()), width));
+    }
+    lines.push("-".repeat(width));
+
+    for item in &ticket.items {
+        lines.extend(wrap_text(&format!("{:>3} x {}", format_quantity(item.quantity), item.name), width));
+        if let Some(notes) = item.notes.as_deref().filter(|n| !n.trim().is_empty()) {
+            lines.extend(wrap_text(&format!("      * {}", notes.trim()), width));
+        }
+    }
+    lines.push("-".repeat(width));
+    lines
+}
+
+/// Render tiket ke ESC/POS: nama stasiun besar di atas, item tebal agar
+/// terbaca dari jarak jauh, lalu potong kertas.
+pub fn render_ticket(ticket: &KitchenTicket, width: usize) -> Vec<u8> {
+    let mut esc = EscPosBuilder::new();
+    esc.align(Align::Center)
+        .double_size(true)
+        .line(&ticket.station.to_uppercase())
+        .double_size(false)
+        .align(Align::Left)
+        .feed(1);
+
+    let lines = ticket_lines(ticket, width);
+    let (header, body) = lines.split_at(lines.iter().position(|l| l.starts_with('-')).unwrap_or(0));
+    for line in header {
+        esc.line(line);
+    }
+    esc.bold(true);
+    for line in body {
+        esc.line(line);
+    }
+    esc.bold(false).cut();
+    esc.build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ticket_lines() {
+        let ticket = KitchenTicket {
+            station: "Bar".into(),
+            transaction_id: "abcdef12-3456".into(),
+            cashier_name: "Sari".into(),
+            timestamp: "2026-10-18 10:00".into(),
+            order_notes: Some("Meja 4".into()),
+            items: vec![
+                TicketItem { name: "Es Teh".into(), quantity: 2.0, notes: Some("kurang gula".into()) },
+                TicketItem { name: "Kopi".into(), quantity: 1.0, notes: None },
+            ],
+        };
+        let lines = ticket_lines(&ticket, 32);
+        assert_eq!(lines[0], "Pesanan #ABCDEF12");
+        assert_eq!(lines[2], "Catatan: Meja 4");
+        assert!(lines.contains(&"  2 x Es Teh".to_string()));
+        assert!(lines.contains(&"      * kurang gula".to_string()));
+        assert!(lines.contains(&"  1 x Kopi".to_string()));
+        assert!(lines.iter().all(|l| l.chars().count() <= 32));
+    }
+}
@@ -1,5 +1,5 @@
 import { useState, useEffect } from "react";
-import { AlertTriangle, Package, ArrowRight } from "lucide-react";
+import { AlertTriangle, Package, ArrowRight, CalendarClock } from "lucide-react";
 import {
   Dialog,
   DialogContent,
@@ -10,7 +10,7 @@ import {
 import { Button } from "./ui/button";
 import { Badge } from "./ui/badge";
 import { ScrollArea } from "./ui/scroll-area";
-import { ProductWithCategory } from "../types";
+import { ProductBatch, ProductWithCategory } from "../types";
 import { useAuthStore } from "../store/authStore";
 import { invoke } from "../lib/tauri";
 import { useNavigate } from "@tanstack/react-router";
@@ -20,6 +20,7 @@ export function LowStockAlert() {
   const [lowStockProducts, setLowStockProducts] = useState<
     ProductWithCategory[]
   >([]);
+  const [expiringBatches, setExpiringBatches] = useState<ProductBatch[]>([]);
   const sessionToken = useAuthStore((s) => s.sessionToken);
   const user = useAuthStore((s) => s.user);
   const navigate = useNavigate();
@@ -34,12 +35,13 @@ export function LowStockAlert() {
 
     const checkLowStock = async () => {
       try {
-        const products = await invoke<ProductWithCategory[]>(
-          "get_low_stock_products",
-          { sessionToken },
-        );
-        if (products.length > 0) {
+        const [products, batches] = await Promise.all([
+          invoke<ProductWithCategory[]>("get_low_stock_products", { sessionToken }),
+          invoke<ProductBatch[]>("get_expiring_batches", { sessionToken, days: null }),
+        ]);
+        if (products.length > 0 || batches.length > 0) {
           setLowStockProducts(products);
+          setExpiringBatches(batches);
           setOpen(true);
         }
       } catch (error) {
@@ -64,7 +66,7 @@ export function LowStockAlert() {
     }
   };
 
-  if (lowStockProducts.length === 0) return null;
+  if (lowStockProducts.length === 0 && expiringBatches.length === 0) return null;
 
   return (
     <Dialog open={open} onOpenChange={setOpen}>
@@ -77,10 +79,12 @@ export function LowStockAlert() {
         </DialogHeader>
 
         <div className="space-y-3">
-          <p className="text-sm text-muted-foreground">
-            {lowStockProducts.length} produk memiliki stok rendah dan perlu
-            segera di-restock:
-          </p>
+          {lowStockProducts.length > 0 && (
+            <p className="text-sm text-muted-foreground">
+              {lowStockProducts.length} produk memiliki stok rendah dan perlu
+              segera di-restock:
+            </p>
+          )}
 
           <ScrollArea className="max-h-[300px] pr-3">
             <div className="space-y-2">
@@ -108,6 +112,37 @@ export function LowStockAlert() {
                   </Badge>
                 </div>
               ))}
+
+              {expiringBatches.length > 0 && (
+                <p className="text-sm text-muted-foreground pt-2">
+                  {expiringBatches.length} batch sudah / hampir kedaluwarsa:
+                </p>
+              )}
+              {expiringBatches.map((batch) => (
+                <div
+                  key={`batch-${batch.id}`}
+                  className="flex items-center justify-between p-3 rounded-lg border bg-rose-50/50 dark:bg-rose-950/20 border-rose-200 dark:border-rose-800"
+                >
+                  <div className="flex items-center gap-3">
+                    <CalendarClock className="h-4 w-4 text-rose-600 shrink-0" />
+                    <div>
+                      <p className="font-medium text-sm">{batch.product_name}</p>
+                      <p className="text-xs text-muted-foreground">
+                        {batch.batch_number ?? `Batch #${batch.id}`} · ED {batch.expiry_date} · sisa{" "}
+                        {batch.quantity}
+                      </p>
+                    </div>
+                  </div>
+                  <Badge
+                    variant={(batch.days_to_expiry ?? 0) < 0 ? "destructive" : "secondary"}
+                    className="font-bold"
+                  >
+                    {(batch.days_to_expiry ?? 0) < 0
+                      ? "KEDALUWARSA"
+                      : `${batch.days_to_expiry} hari`}
+                  </Badge>
+                </div>
+              ))}
             </div>
           </ScrollArea>
         </div>
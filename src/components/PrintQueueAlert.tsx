@@ -0,0 +1,54 @@
+import { useEffect } from "react";
+import { listen } from "@tauri-apps/api/event";
+import { ToastAction } from "./ui/toast";
+import { useToast } from "../hooks/use-toast";
+import { PrintJobFailedEvent } from "../types";
+import { useAuthStore } from "../store/authStore";
+import { invoke } from "../lib/tauri";
+
+/**
+ * Menampilkan notifikasi saat job di antrian cetak gagal permanen
+ * (printer offline, kertas habis, dll) dengan tombol untuk mencoba lagi.
+ */
+export function PrintQueueAlert() {
+  const sessionToken = useAuthStore((s) => s.sessionToken);
+  const { toast } = useToast();
+
+  useEffect(() => {
+    if (!sessionToken) return;
+
+    const handleRetry = async (jobId: number) => {
+      try {
+        await invoke("retry_print_job", { sessionToken, jobId });
+        toast({ title: "Job cetak diantrikan ulang" });
+      } catch (error) {
+        toast({
+          title: "Gagal mengantrikan ulang",
+          description: String(error),
+          variant: "destructive",
+        });
+      }
+    };
+
+    const unlisten = listen<PrintJobFailedEvent>("print-job-failed", (event) => {
+      const job = event.payload;
+      const label = job.job_type === "RECEIPT" ? "Struk" : "Label";
+      toast({
+        title: `${label} gagal dicetak`,
+        description: `${job.error} Perbaiki printer lalu tekan Coba Lagi.`,
+        variant: "destructive",
+        action: (
+          <ToastAction altText="Coba Lagi" onClick={() => handleRetry(job.job_id)}>
+            Coba Lagi
+          </ToastAction>
+        ),
+      });
+    });
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [sessionToken, toast]);
+
+  return null;
+}
@@ -7,6 +7,7 @@ import {
   Package,
   LogOut,
   BarChart,
+  ClipboardCheck,
 } from "lucide-react";
 import { invoke } from "../lib/tauri";
 import { ThemeToggle } from "./ThemeToggle";
@@ -32,6 +33,7 @@ export function Sidebar() {
 
   const navItems = [
     { name: "POS", path: "/pos", icon: ShoppingCart },
+    { name: "Stock Opname", path: "/opname", icon: ClipboardCheck },
     ...(isAdmin()
       ? [
           { name: "Inventory", path: "/inventory", icon: Package },
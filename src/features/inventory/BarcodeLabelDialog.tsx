@@ -11,7 +11,7 @@ import {
 import { Button } from "../../components/ui/button";
 import { Input } from "../../components/ui/input";
 import { Label } from "../../components/ui/label";
-import { Printer, Plus, Minus, X, Zap } from "lucide-react";
+import { Printer, Plus, Minus, X, Zap, FileDown } from "lucide-react";
 import { useAuthStore } from "../../store/authStore";
 import { useToast } from "../../hooks/use-toast";
 
@@ -88,6 +88,7 @@ export function BarcodeLabelDialog({
   const [labelItems, setLabelItems] = useState<LabelItem[]>([]);
   const [labelsPerRow, setLabelsPerRow] = useState(3);
   const [thermalPrinting, setThermalPrinting] = useState(false);
+  const [exporting, setExporting] = useState(false);
   const printRef = useRef<HTMLDivElement>(null);
   const sessionToken = useAuthStore((s) => s.sessionToken);
   const { toast } = useToast();
@@ -121,6 +122,14 @@ export function BarcodeLabelDialog({
   const noBarcode = products.filter((p) => !p.barcode);
   const totalLabels = allLabels.length;
 
+  const toLabelPayload = () =>
+    labelItems.map((item) => ({
+      name: item.product.name,
+      price: item.product.price,
+      barcode: item.product.barcode || "",
+      qty: item.qty,
+    }));
+
   const handlePrint = () => {
     if (!printRef.current) return;
 
@@ -400,13 +409,10 @@ export function BarcodeLabelDialog({
             onClick={async () => {
               setThermalPrinting(true);
               try {
-                const labels = labelItems.map((item) => ({
-                  name: item.product.name,
-                  price: item.product.price,
-                  barcode: item.product.barcode || "",
-                  qty: item.qty,
-                }));
-                await invoke("print_barcode_labels", { sessionToken, labels });
+                await invoke("print_barcode_labels", {
+                  sessionToken,
+                  labels: toLabelPayload(),
+                });
                 toast({
                   title: "Berhasil",
                   description: `${totalLabels} label barcode terkirim ke printer thermal`,
@@ -425,6 +431,34 @@ export function BarcodeLabelDialog({
             <Zap className="mr-2 h-4 w-4" />
             {thermalPrinting ? "Mengirim..." : `Thermal (${totalLabels})`}
           </Button>
+          <Button
+            variant="secondary"
+            disabled={allLabels.length === 0 || exporting}
+            onClick={async () => {
+              setExporting(true);
+              try {
+                const path = await invoke<string>("export_label_sheet_pdf", {
+                  sessionToken,
+                  labels: toLabelPayload(),
+                });
+                toast({
+                  title: "PDF tersimpan",
+                  description: path,
+                });
+              } catch (e) {
+                toast({
+                  variant: "destructive",
+                  title: "Gagal",
+                  description: String(e),
+                });
+              } finally {
+                setExporting(false);
+              }
+            }}
+          >
+            <FileDown className="mr-2 h-4 w-4" />
+            {exporting ? "Menyimpan..." : "PDF Stiker A4"}
+          </Button>
           <Button onClick={handlePrint} disabled={allLabels.length === 0}>
             <Printer className="mr-2 h-4 w-4" />
             Cetak A4 ({totalLabels})
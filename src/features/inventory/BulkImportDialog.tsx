@@ -33,6 +33,7 @@ interface ParsedRow {
   price: number;
   cost_price: number;
   stock: number;
+  extra_barcodes: Array<{ barcode: string; pack_quantity: number }>;
   valid: boolean;
   error?: string;
 }
@@ -156,6 +157,20 @@ export function BulkImportDialog({
           ) || 0;
         const stock =
           Number(get(["stock", "stok", "qty", "quantity", "jumlah"])) || 0;
+        // Format: "8991234567890;8991234567899x24" (xN = isi kemasan)
+        const extra_barcodes = String(
+          get(["barcode_tambahan", "extra_barcodes", "barcode tambahan"]),
+        )
+          .split(/[;|]/)
+          .map((entry) => entry.trim())
+          .filter(Boolean)
+          .map((entry) => {
+            const [code, pack] = entry.split(/x/i);
+            return {
+              barcode: code.trim(),
+              pack_quantity: Math.max(1, parseInt(pack) || 1),
+            };
+          });
 
         let valid = true;
         let error: string | undefined;
@@ -176,6 +191,7 @@ export function BulkImportDialog({
           price,
           cost_price,
           stock,
+          extra_barcodes,
           valid,
           error,
         };
@@ -211,6 +227,7 @@ export function BulkImportDialog({
         cost_price: r.cost_price,
         stock: r.stock,
         image_path: null,
+        extra_barcodes: r.extra_barcodes,
       }));
 
       const result = await invoke<BulkImportResult>("bulk_import_products", {
@@ -239,9 +256,9 @@ export function BulkImportDialog({
 
   const downloadTemplate = () => {
     const templateData = [
-      ["Name", "SKU", "Barcode", "Category", "Price", "Cost_Price", "Stock"],
-      ["Contoh Produk 1", "SKU001", "", "Makanan", "15000", "10000", "100"],
-      ["Contoh Produk 2", "SKU002", "", "Minuman", "8000", "5000", "50"],
+      ["Name", "SKU", "Barcode", "Category", "Price", "Cost_Price", "Stock", "Barcode_Tambahan"],
+      ["Contoh Produk 1", "SKU001", "", "Makanan", "15000", "10000", "100", ""],
+      ["Contoh Produk 2", "SKU002", "", "Minuman", "8000", "5000", "50", "8991234567899x24"],
     ];
     const ws = XLSX.utils.aoa_to_sheet(templateData);
     const csvContent = XLSX.utils.sheet_to_csv(ws);
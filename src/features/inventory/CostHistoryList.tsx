@@ -0,0 +1,42 @@
+import { CostHistoryEntry } from "../../types";
+import { useInvokeQuery } from "../../hooks/useInvokeQuery";
+import { useAuthStore } from "../../store/authStore";
+import { Label } from "../../components/ui/label";
+import { formatRupiah } from "../../lib/currency";
+import { format } from "date-fns";
+
+/** Riwayat HPP (rata-rata bergerak) dari penerimaan barang. */
+export function CostHistoryList({ productId }: { productId: number }) {
+  const sessionToken = useAuthStore((s) => s.sessionToken);
+
+  const { data: history } = useInvokeQuery<CostHistoryEntry[]>(
+    ["cost-history", productId],
+    "get_product_cost_history",
+    { sessionToken, productId },
+  );
+
+  if (!history || history.length === 0) return null;
+
+  return (
+    <div className="space-y-2">
+      <Label>Riwayat HPP</Label>
+      <div className="max-h-40 overflow-y-auto space-y-1">
+        {history.map((h) => (
+          <div key={h.id} className="flex justify-between text-xs">
+            <span className="text-muted-foreground">
+              {h.created_at
+                ? format(new Date(h.created_at.replace(" ", "T") + "Z"), "dd/MM/yy HH:mm")
+                : "-"}
+              {" · "}
+              {h.receipt_number ?? "-"}
+            </span>
+            <span>
+              +{h.quantity} @ {formatRupiah(h.unit_cost)} · {formatRupiah(h.cost_before)} →{" "}
+              {formatRupiah(h.cost_after)}
+            </span>
+          </div>
+        ))}
+      </div>
+    </div>
+  );
+}
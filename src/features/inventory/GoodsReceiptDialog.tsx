@@ -0,0 +1,303 @@
+import { useEffect, useState } from "react";
+import {
+  Dialog,
+  DialogContent,
+  DialogHeader,
+  DialogTitle,
+} from "../../components/ui/dialog";
+import { Button } from "../../components/ui/button";
+import { Input } from "../../components/ui/input";
+import { Label } from "../../components/ui/label";
+import { NumericInput } from "../../components/NumericInput";
+import { useInvokeQuery, useInvokeMutation } from "../../hooks/useInvokeQuery";
+import {
+  GoodsReceipt,
+  GoodsReceiptDetail,
+  ProductWithCategory,
+  PurchaseOrderDetail,
+  Supplier,
+} from "../../types";
+import { useAuthStore } from "../../store/authStore";
+import { useQueryClient } from "@tanstack/react-query";
+import { useToast } from "../../hooks/use-toast";
+import { formatRupiah } from "../../lib/currency";
+import { format } from "date-fns";
+import { Plus, Trash2 } from "lucide-react";
+import { LocationSelect } from "./LocationSelect";
+
+type Line = {
+  product_id: number;
+  quantity: number;
+  unit_cost: number;
+  batch_number: string;
+  expiry_date: string;
+};
+
+/**
+ * Penerimaan barang: menambah stok dan menghitung ulang HPP (rata-rata
+ * bergerak). Jika dibuka dari PO, barang & sisa qty diambil dari PO.
+ */
+export function GoodsReceiptDialog({
+  open,
+  onOpenChange,
+  purchaseOrder,
+}: {
+  open: boolean;
+  onOpenChange: (open: boolean) => void;
+  purchaseOrder?: PurchaseOrderDetail | null;
+}) {
+  const [supplierId, setSupplierId] = useState<number | null>(null);
+  const [invoiceNumber, setInvoiceNumber] = useState("");
+  const [notes, setNotes] = useState("");
+  const [locationId, setLocationId] = useState<number | null>(null);
+  const [lines, setLines] = useState<Line[]>([]);
+
+  const sessionToken = useAuthStore((s) => s.sessionToken);
+  const queryClient = useQueryClient();
+  const { toast } = useToast();
+
+  const { data: suppliers } = useInvokeQuery<Supplier[]>(
+    ["suppliers", false],
+    "get_suppliers",
+    { sessionToken, showInactive: false },
+    { enabled: open && !purchaseOrder },
+  );
+  const { data: products } = useInvokeQuery<ProductWithCategory[]>(
+    ["products", "", null],
+    "get_products",
+    { sessionToken, search: "", categoryId: null, showInactive: false },
+    { enabled: open },
+  );
+  const { data: receipts } = useInvokeQuery<GoodsReceipt[]>(
+    ["goods-receipts", purchaseOrder?.id ?? null],
+    "get_goods_receipts",
+    { sessionToken, purchaseOrderId: purchaseOrder?.id ?? null },
+    { enabled: open },
+  );
+
+  useEffect(() => {
+    if (!open) return;
+    setSupplierId(purchaseOrder?.supplier_id ?? null);
+    setInvoiceNumber("");
+    setNotes("");
+    setLocationId(null);
+    setLines(
+      purchaseOrder?.items
+        .filter((i) => i.received_quantity < i.quantity)
+        .map((i) => ({
+          product_id: i.product_id,
+          quantity: Math.round((i.quantity - i.received_quantity) * 1000) / 1000,
+          unit_cost: i.unit_cost,
+          batch_number: "",
+          expiry_date: "",
+        })) ?? [],
+    );
+  }, [open, purchaseOrder]);
+
+  const createMutation = useInvokeMutation<GoodsReceiptDetail>("create_goods_receipt", {
+    onSuccess: (gr) => {
+      queryClient.invalidateQueries({ queryKey: ["goods-receipts"] });
+      queryClient.invalidateQueries({ queryKey: ["purchase-orders"] });
+      queryClient.invalidateQueries({ queryKey: ["purchase-order"] });
+      queryClient.invalidateQueries({ queryKey: ["products"] });
+      queryClient.invalidateQueries({ queryKey: ["product-batches"] });
+      queryClient.invalidateQueries({ queryKey: ["cost-history"] });
+      queryClient.invalidateQueries({ queryKey: ["product-location-stock"] });
+      queryClient.invalidateQueries({ queryKey: ["location-stock"] });
+      toast({ title: "Berhasil", description: `Penerimaan ${gr.receipt_number} dicatat` });
+      onOpenChange(false);
+    },
+    onError: (e) => toast({ variant: "destructive", title: "Error", description: String(e) }),
+  });
+
+  const updateLine = (index: number, patch: Partial<Line>) =>
+    setLines((ls) => ls.map((l, i) => (i === index ? { ...l, ...patch } : l)));
+
+  const productById = (id: number) => products?.find((p) => p.id === id);
+  const receivable = products?.filter((p) => !p.is_kit && !p.is_recipe) ?? [];
+  const total = lines.reduce((sum, l) => sum + Math.round(l.quantity * l.unit_cost), 0);
+
+  const handleSave = () => {
+    createMutation.mutate({
+      sessionToken,
+      payload: {
+        purchase_order_id: purchaseOrder?.id ?? null,
+        supplier_id: supplierId,
+        invoice_number: invoiceNumber || null,
+        notes: notes || null,
+        location_id: locationId,
+        items: lines
+          .filter((l) => l.product_id && l.quantity > 0)
+          .map((l) => ({
+            ...l,
+            batch_number: l.batch_number || null,
+            expiry_date: l.expiry_date || null,
+          })),
+      },
+    });
+  };
+
+  return (
+    <Dialog open={open} onOpenChange={onOpenChange}>
+      <DialogContent className="sm:max-w-4xl">
+        <DialogHeader>
+          <DialogTitle>
+            Penerimaan Barang{purchaseOrder && ` · PO ${purchaseOrder.po_number}`}
+          </DialogTitle>
+        </DialogHeader>
+
+        <div className="grid grid-cols-4 gap-3">
+          <div className="space-y-1">
+            <Label>Supplier</Label>
+            {purchaseOrder ? (
+              <Input value={purchaseOrder.supplier_name} disabled />
+            ) : (
+              <select
+                value={supplierId ?? ""}
+                onChange={(e) => setSupplierId(e.target.value ? Number(e.target.value) : null)}
+                className="w-full h-9 rounded-md border border-input bg-transparent px-2 text-sm"
+              >
+                <option value="">Tanpa supplier</option>
+                {suppliers?.map((s) => (
+                  <option key={s.id} value={s.id}>
+                    {s.name}
+                  </option>
+                ))}
+              </select>
+            )}
+          </div>
+          <div className="space-y-1">
+            <Label>No. Faktur / Surat Jalan</Label>
+            <Input value={invoiceNumber} onChange={(e) => setInvoiceNumber(e.target.value)} />
+          </div>
+          <div className="space-y-1">
+            <Label>Catatan</Label>
+            <Input value={notes} onChange={(e) => setNotes(e.target.value)} />
+          </div>
+          <LocationSelect value={locationId} onChange={setLocationId} label="Diterima di" />
+        </div>
+
+        <div className="max-h-[300px] overflow-y-auto space-y-2">
+          <div className="flex gap-2 text-[10px] font-bold uppercase text-muted-foreground">
+            <span className="flex-1">Produk</span>
+            <span className="w-20">Qty</span>
+            <span className="w-32">Modal / satuan</span>
+            <span className="w-28">No. Batch</span>
+            <span className="w-36">Kedaluwarsa</span>
+            <span className="w-7" />
+          </div>
+          {lines.map((line, index) => {
+            const product = productById(line.product_id);
+            return (
+              <div key={index} className="flex items-center gap-2 text-sm">
+                <select
+                  value={line.product_id || ""}
+                  disabled={!!purchaseOrder}
+                  onChange={(e) => {
+                    const p = productById(Number(e.target.value));
+                    updateLine(index, {
+                      product_id: Number(e.target.value),
+                      unit_cost: p?.cost_price ?? 0,
+                    });
+                  }}
+                  className="flex-1 h-9 rounded-md border border-input bg-transparent px-2 text-sm"
+                >
+                  <option value="">Pilih produk...</option>
+                  {receivable.map((p) => (
+                    <option key={p.id} value={p.id}>
+                      {p.name} (HPP {formatRupiah(p.cost_price)})
+                    </option>
+                  ))}
+                </select>
+                <Input
+                  type="number"
+                  min={0}
+                  step={product?.is_weighed ? "0.001" : "1"}
+                  className="w-20"
+                  value={line.quantity}
+                  onChange={(e) => updateLine(index, { quantity: Number(e.target.value) })}
+                />
+                <NumericInput
+                  className="w-32"
+                  prefix="Rp"
+                  value={line.unit_cost}
+                  onChange={(v) => updateLine(index, { unit_cost: v })}
+                />
+                <Input
+                  className="w-28"
+                  placeholder="Opsional"
+                  value={line.batch_number}
+                  onChange={(e) => updateLine(index, { batch_number: e.target.value })}
+                />
+                <Input
+                  type="date"
+                  className="w-36"
+                  value={line.expiry_date}
+                  onChange={(e) => updateLine(index, { expiry_date: e.target.value })}
+                />
+                <Button
+                  variant="ghost"
+                  size="icon"
+                  className="h-7 w-7 text-destructive"
+                  onClick={() => setLines((ls) => ls.filter((_, i) => i !== index))}
+                >
+                  <Trash2 className="h-3 w-3" />
+                </Button>
+              </div>
+            );
+          })}
+          {!purchaseOrder && (
+            <Button
+              variant="outline"
+              size="sm"
+              onClick={() =>
+                setLines((ls) => [
+                  ...ls,
+                  { product_id: 0, quantity: 1, unit_cost: 0, batch_number: "", expiry_date: "" },
+                ])
+              }
+            >
+              <Plus className="mr-2 h-3 w-3" />
+              Tambah Barang
+            </Button>
+          )}
+        </div>
+
+        <div className="flex items-center gap-2 border-t pt-3">
+          <div className="flex-1 font-bold">Total {formatRupiah(total)}</div>
+          <Button
+            onClick={handleSave}
+            disabled={lines.length === 0 || createMutation.isPending}
+          >
+            Simpan Penerimaan
+          </Button>
+        </div>
+
+        {receipts && receipts.length > 0 && (
+          <div className="space-y-1">
+            <Label>Penerimaan Sebelumnya</Label>
+            <div className="max-h-32 overflow-y-auto space-y-1">
+              {receipts.map((r) => (
+                <div key={r.id} className="flex justify-between text-xs">
+                  <span className="text-muted-foreground">
+                    {r.received_at
+                      ? format(new Date(r.received_at.replace(" ", "T") + "Z"), "dd/MM/yy HH:mm")
+                      : "-"}
+                    {" · "}
+                    {r.receipt_number}
+                    {r.po_number && ` · ${r.po_number}`}
+                    {r.supplier_name && ` · ${r.supplier_name}`}
+                    {r.location_name && ` · ${r.location_name}`}
+                  </span>
+                  <span>
+                    {r.item_count} barang · {formatRupiah(r.total_amount)}
+                  </span>
+                </div>
+              ))}
+            </div>
+          </div>
+        )}
+      </DialogContent>
+    </Dialog>
+  );
+}
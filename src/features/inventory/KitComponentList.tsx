@@ -0,0 +1,125 @@
+import { useState, useEffect } from "react";
+import { useAuthStore } from "../../store/authStore";
+import { invoke } from "../../lib/tauri";
+import { ProductComponent, ProductWithCategory } from "../../types";
+import { useInvokeQuery } from "../../hooks/useInvokeQuery";
+import { useToast } from "../../hooks/use-toast";
+import { useQueryClient } from "@tanstack/react-query";
+import { Button } from "../../components/ui/button";
+import { Input } from "../../components/ui/input";
+import { Label } from "../../components/ui/label";
+import { Plus, Trash2 } from "lucide-react";
+
+type ComponentRow = { component_id: number | null; quantity: number };
+
+/**
+ * Komponen paket (kit). Menjual paket memotong stok komponennya; stok paket
+ * dihitung dari komponen. Daftar kosong = produk biasa.
+ */
+export function KitComponentList({ productId }: { productId: number }) {
+  const [rows, setRows] = useState<ComponentRow[]>([]);
+  const [isSaving, setIsSaving] = useState(false);
+
+  const sessionToken = useAuthStore((s) => s.sessionToken);
+  const queryClient = useQueryClient();
+  const { toast } = useToast();
+
+  const { data: products } = useInvokeQuery<ProductWithCategory[]>(
+    ["products", "", null],
+    "get_products",
+    { sessionToken, search: "", categoryId: null, showInactive: false },
+  );
+  const candidates = (products ?? []).filter(
+    (p) => p.id !== productId && !p.is_kit && !p.variant_count,
+  );
+
+  useEffect(() => {
+    invoke<ProductComponent[]>("get_kit_components", { sessionToken, productId })
+      .then((components) =>
+        setRows(components.map(({ component_id, quantity }) => ({ component_id, quantity }))),
+      )
+      .catch((error) => console.error("Failed to load kit components:", error));
+  }, [productId]);
+
+  const updateRow = (index: number, patch: Partial<ComponentRow>) =>
+    setRows(rows.map((r, i) => (i === index ? { ...r, ...patch } : r)));
+
+  const handleSave = async () => {
+    setIsSaving(true);
+    try {
+      await invoke("save_kit_components", {
+        sessionToken,
+        productId,
+        components: rows.filter((r) => r.component_id !== null),
+      });
+      queryClient.invalidateQueries({ queryKey: ["products"] });
+      toast({ title: "Berhasil", description: "Komponen paket disimpan" });
+    } catch (error) {
+      toast({ variant: "destructive", title: "Gagal", description: String(error) });
+    } finally {
+      setIsSaving(false);
+    }
+  };
+
+  return (
+    <div className="space-y-2">
+      <Label>Isi Paket</Label>
+      {rows.map((row, index) => {
+        const component = candidates.find((p) => p.id === row.component_id);
+        return (
+          <div key={index} className="flex items-center gap-2">
+            <select
+              value={row.component_id ?? ""}
+              onChange={(e) =>
+                updateRow(index, {
+                  component_id: e.target.value ? Number(e.target.value) : null,
+                })
+              }
+              className="flex-1 h-9 rounded-md border border-input bg-transparent px-2 text-sm"
+            >
+              <option value="">Pilih produk...</option>
+              {candidates.map((p) => (
+                <option key={p.id} value={p.id}>
+                  {p.name} (stok {p.stock})
+                </option>
+              ))}
+            </select>
+            <Input
+              type="number"
+              min={0}
+              step="any"
+              value={row.quantity}
+              onChange={(e) => updateRow(index, { quantity: Number(e.target.value) })}
+              className="w-20"
+            />
+            <span className="text-xs text-muted-foreground w-10">
+              {component?.unit ?? "pcs"}
+            </span>
+            <Button
+              type="button"
+              variant="ghost"
+              size="icon"
+              className="h-8 w-8 text-destructive"
+              onClick={() => setRows(rows.filter((_, i) => i !== index))}
+            >
+              <Trash2 className="h-4 w-4" />
+            </Button>
+          </div>
+        );
+      })}
+      <div className="flex gap-2">
+        <Button
+          type="button"
+          variant="outline"
+          size="sm"
+          onClick={() => setRows([...rows, { component_id: null, quantity: 1 }])}
+        >
+          <Plus className="h-4 w-4 mr-1" /> Komponen
+        </Button>
+        <Button type="button" size="sm" onClick={handleSave} disabled={isSaving}>
+          Simpan Paket
+        </Button>
+      </div>
+    </div>
+  );
+}
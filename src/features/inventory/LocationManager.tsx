@@ -0,0 +1,149 @@
+import { useState } from "react";
+import {
+  Dialog,
+  DialogContent,
+  DialogHeader,
+  DialogTitle,
+} from "../../components/ui/dialog";
+import { Button } from "../../components/ui/button";
+import { Input } from "../../components/ui/input";
+import { Badge } from "../../components/ui/badge";
+import { useInvokeQuery, useInvokeMutation } from "../../hooks/useInvokeQuery";
+import { Location, LocationType } from "../../types";
+import { useAuthStore } from "../../store/authStore";
+import { useQueryClient } from "@tanstack/react-query";
+import { useToast } from "../../hooks/use-toast";
+import { Pencil } from "lucide-react";
+
+export const LOCATION_TYPE_LABEL: Record<LocationType, string> = {
+  STORE: "Toko",
+  WAREHOUSE: "Gudang",
+};
+
+/** Kelola lokasi stok (toko / gudang). Lokasi utama tidak bisa dinonaktifkan. */
+export function LocationManager({
+  open,
+  onOpenChange,
+}: {
+  open: boolean;
+  onOpenChange: (open: boolean) => void;
+}) {
+  const [name, setName] = useState("");
+  const [locationType, setLocationType] = useState<LocationType>("WAREHOUSE");
+  const [editingId, setEditingId] = useState<number | null>(null);
+
+  const sessionToken = useAuthStore((s) => s.sessionToken);
+  const queryClient = useQueryClient();
+  const { toast } = useToast();
+
+  const { data: locations } = useInvokeQuery<Location[]>(
+    ["locations", true],
+    "get_locations",
+    { sessionToken, showInactive: true },
+    { enabled: open },
+  );
+
+  const onError = (e: unknown) =>
+    toast({ variant: "destructive", title: "Error", description: String(e) });
+
+  const reset = () => {
+    setName("");
+    setLocationType("WAREHOUSE");
+    setEditingId(null);
+  };
+
+  const onSaved = () => {
+    queryClient.invalidateQueries({ queryKey: ["locations"] });
+    reset();
+    toast({ title: "Berhasil", description: "Lokasi disimpan" });
+  };
+
+  const createMutation = useInvokeMutation<Location>("create_location", { onSuccess: onSaved, onError });
+  const updateMutation = useInvokeMutation<Location>("update_location", { onSuccess: onSaved, onError });
+  const toggleMutation = useInvokeMutation<boolean>("toggle_location", {
+    onSuccess: () => queryClient.invalidateQueries({ queryKey: ["locations"] }),
+    onError,
+  });
+
+  const handleSave = () => {
+    const payload = { name, location_type: locationType };
+    if (editingId) {
+      updateMutation.mutate({ sessionToken, id: editingId, payload });
+    } else {
+      createMutation.mutate({ sessionToken, payload });
+    }
+  };
+
+  const isSaving = createMutation.isPending || updateMutation.isPending;
+
+  return (
+    <Dialog open={open} onOpenChange={onOpenChange}>
+      <DialogContent className="sm:max-w-lg">
+        <DialogHeader>
+          <DialogTitle>Lokasi Stok</DialogTitle>
+        </DialogHeader>
+
+        <div className="flex gap-2">
+          <Input
+            className="flex-1"
+            placeholder="Nama lokasi (mis. Gudang Belakang)"
+            value={name}
+            onChange={(e) => setName(e.target.value)}
+          />
+          <select
+            value={locationType}
+            onChange={(e) => setLocationType(e.target.value as LocationType)}
+            className="h-9 rounded-md border border-input bg-transparent px-2 text-sm"
+          >
+            {Object.entries(LOCATION_TYPE_LABEL).map(([value, label]) => (
+              <option key={value} value={value}>
+                {label}
+              </option>
+            ))}
+          </select>
+          {editingId && (
+            <Button variant="outline" onClick={reset}>
+              Batal
+            </Button>
+          )}
+          <Button onClick={handleSave} disabled={!name.trim() || isSaving}>
+            {editingId ? "Simpan" : "Tambah"}
+          </Button>
+        </div>
+
+        <div className="max-h-[300px] overflow-y-auto space-y-2">
+          {locations?.map((l) => (
+            <div key={l.id} className="flex items-center gap-3 text-sm border-b pb-2">
+              <div className="flex-1">
+                <div className="font-medium">{l.name}</div>
+                <div className="text-xs text-muted-foreground">
+                  {LOCATION_TYPE_LABEL[l.location_type]}
+                  {l.is_default && " · lokasi utama"}
+                </div>
+              </div>
+              <Badge
+                variant={l.is_active ? "default" : "outline"}
+                className={l.is_default ? "" : "cursor-pointer"}
+                onClick={() => !l.is_default && toggleMutation.mutate({ sessionToken, id: l.id })}
+              >
+                {l.is_active ? "Aktif" : "Nonaktif"}
+              </Badge>
+              <Button
+                variant="ghost"
+                size="icon"
+                className="h-7 w-7"
+                onClick={() => {
+                  setEditingId(l.id);
+                  setName(l.name);
+                  setLocationType(l.location_type);
+                }}
+              >
+                <Pencil className="h-3 w-3" />
+              </Button>
+            </div>
+          ))}
+        </div>
+      </DialogContent>
+    </Dialog>
+  );
+}
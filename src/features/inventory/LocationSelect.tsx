@@ -0,0 +1,46 @@
+import { Location } from "../../types";
+import { useInvokeQuery } from "../../hooks/useInvokeQuery";
+import { useAuthStore } from "../../store/authStore";
+import { Label } from "../../components/ui/label";
+
+/**
+ * Pilih lokasi stok (null = lokasi utama). Tidak tampil bila hanya ada
+ * satu lokasi aktif.
+ */
+export function LocationSelect({
+  value,
+  onChange,
+  label = "Lokasi",
+}: {
+  value: number | null;
+  onChange: (locationId: number | null) => void;
+  label?: string;
+}) {
+  const sessionToken = useAuthStore((s) => s.sessionToken);
+
+  const { data: locations } = useInvokeQuery<Location[]>(
+    ["locations", false],
+    "get_locations",
+    { sessionToken, showInactive: false },
+  );
+
+  if (!locations || locations.length < 2) return null;
+
+  return (
+    <div className="space-y-1">
+      <Label className="text-xs">{label}</Label>
+      <select
+        value={value ?? ""}
+        onChange={(e) => onChange(e.target.value ? Number(e.target.value) : null)}
+        className="w-full h-9 rounded-md border border-input bg-transparent px-2 text-sm"
+      >
+        {locations.map((l) => (
+          <option key={l.id} value={l.is_default ? "" : l.id}>
+            {l.name}
+            {l.is_default && " (utama)"}
+          </option>
+        ))}
+      </select>
+    </div>
+  );
+}
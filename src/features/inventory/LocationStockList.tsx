@@ -0,0 +1,43 @@
+import { LocationStock } from "../../types";
+import { useInvokeQuery } from "../../hooks/useInvokeQuery";
+import { useAuthStore } from "../../store/authStore";
+import { Label } from "../../components/ui/label";
+
+/**
+ * Stok produk per lokasi. Penyesuaian stok manual berlaku untuk lokasi utama;
+ * pindahkan stok ke lokasi lain lewat Transfer Stok.
+ */
+export function LocationStockList({ productId, unit }: { productId: number; unit: string }) {
+  const sessionToken = useAuthStore((s) => s.sessionToken);
+
+  const { data: stocks } = useInvokeQuery<LocationStock[]>(
+    ["product-location-stock", productId],
+    "get_product_location_stock",
+    { sessionToken, productId },
+  );
+
+  // Hanya satu lokasi → sama dengan stok total, tidak perlu ditampilkan
+  if (!stocks || stocks.length < 2) return null;
+
+  return (
+    <div className="space-y-2">
+      <Label>Stok per Lokasi</Label>
+      <div className="space-y-1">
+        {stocks.map((s) => (
+          <div key={s.location_id} className="flex justify-between text-xs">
+            <span className={s.is_default ? "font-medium" : "text-muted-foreground"}>
+              {s.location_name}
+              {s.is_default && " (utama)"}
+            </span>
+            <span>
+              {s.quantity} {unit}
+              {s.incoming > 0 && (
+                <span className="text-muted-foreground"> · +{s.incoming} dalam perjalanan</span>
+              )}
+            </span>
+          </div>
+        ))}
+      </div>
+    </div>
+  );
+}
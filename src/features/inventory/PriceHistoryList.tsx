@@ -0,0 +1,44 @@
+import { PriceHistoryEntry } from "../../types";
+import { useInvokeQuery } from "../../hooks/useInvokeQuery";
+import { useAuthStore } from "../../store/authStore";
+import { Label } from "../../components/ui/label";
+import { formatRupiah } from "../../lib/currency";
+import { format } from "date-fns";
+
+/** Riwayat harga jual & modal produk (manual maupun terjadwal). */
+export function PriceHistoryList({ productId }: { productId: number }) {
+  const sessionToken = useAuthStore((s) => s.sessionToken);
+
+  const { data: history } = useInvokeQuery<PriceHistoryEntry[]>(
+    ["price-history", productId],
+    "get_product_price_history",
+    { sessionToken, productId },
+  );
+
+  if (!history || history.length === 0) return null;
+
+  return (
+    <div className="space-y-2">
+      <Label>Riwayat Harga</Label>
+      <div className="max-h-40 overflow-y-auto space-y-1">
+        {history.map((h) => (
+          <div key={h.id} className="flex justify-between text-xs">
+            <span className="text-muted-foreground">
+              {h.changed_at
+                ? format(new Date(h.changed_at.replace(" ", "T") + "Z"), "dd/MM/yy HH:mm")
+                : "-"}
+              {" · "}
+              {h.source === "SCHEDULE" ? "Terjadwal" : h.changed_by_name ?? "Manual"}
+              {h.target && ` · ${h.target}`}
+            </span>
+            <span>
+              {formatRupiah(h.old_price)} → {formatRupiah(h.new_price)}
+              {h.old_cost_price !== h.new_cost_price &&
+                ` (modal ${formatRupiah(h.old_cost_price)} → ${formatRupiah(h.new_cost_price)})`}
+            </span>
+          </div>
+        ))}
+      </div>
+    </div>
+  );
+}
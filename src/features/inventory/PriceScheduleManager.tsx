@@ -0,0 +1,231 @@
+import { useState } from "react";
+import {
+  Dialog,
+  DialogContent,
+  DialogHeader,
+  DialogTitle,
+} from "../../components/ui/dialog";
+import { Button } from "../../components/ui/button";
+import { Input } from "../../components/ui/input";
+import { Label } from "../../components/ui/label";
+import { Badge } from "../../components/ui/badge";
+import { NumericInput } from "../../components/NumericInput";
+import { useInvokeQuery, useInvokeMutation } from "../../hooks/useInvokeQuery";
+import { CategoryWithCount, PriceSchedule, ProductWithCategory } from "../../types";
+import { useAuthStore } from "../../store/authStore";
+import { useQueryClient } from "@tanstack/react-query";
+import { useToast } from "../../hooks/use-toast";
+import { formatRupiah } from "../../lib/currency";
+import { format } from "date-fns";
+
+// effective_at dari backend dalam UTC tanpa zona
+const fromUtc = (value: string) => new Date(value.replace(" ", "T") + "Z");
+
+/**
+ * Jadwal perubahan harga: harga baru / persentase untuk satu produk, atau
+ * persentase untuk satu kategori. Diterapkan otomatis saat waktunya tiba.
+ */
+export function PriceScheduleManager({
+  open,
+  onOpenChange,
+}: {
+  open: boolean;
+  onOpenChange: (open: boolean) => void;
+}) {
+  const [target, setTarget] = useState<"PRODUCT" | "CATEGORY">("PRODUCT");
+  const [targetId, setTargetId] = useState<number | null>(null);
+  const [mode, setMode] = useState<"PRICE" | "PERCENT">("PRICE");
+  const [value, setValue] = useState(0);
+  const [effectiveAt, setEffectiveAt] = useState("");
+  const [notes, setNotes] = useState("");
+
+  const sessionToken = useAuthStore((s) => s.sessionToken);
+  const queryClient = useQueryClient();
+  const { toast } = useToast();
+
+  const { data: schedules } = useInvokeQuery<PriceSchedule[]>(
+    ["price-schedules"],
+    "get_price_schedules",
+    { sessionToken },
+    { enabled: open },
+  );
+  const { data: products } = useInvokeQuery<ProductWithCategory[]>(
+    ["products", "", null],
+    "get_products",
+    { sessionToken, search: "", categoryId: null, showInactive: false },
+    { enabled: open },
+  );
+  const { data: categories } = useInvokeQuery<CategoryWithCount[]>(
+    ["categories"],
+    "get_categories",
+    { sessionToken },
+    { enabled: open },
+  );
+
+  const onError = (e: unknown) =>
+    toast({ variant: "destructive", title: "Error", description: String(e) });
+
+  const createMutation = useInvokeMutation<PriceSchedule>("create_price_schedule", {
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["price-schedules"] });
+      setTargetId(null);
+      setValue(0);
+      setNotes("");
+      toast({ title: "Berhasil", description: "Perubahan harga dijadwalkan" });
+    },
+    onError,
+  });
+
+  const cancelMutation = useInvokeMutation<void>("cancel_price_schedule", {
+    onSuccess: () => queryClient.invalidateQueries({ queryKey: ["price-schedules"] }),
+    onError,
+  });
+
+  const isPercent = target === "CATEGORY" || mode === "PERCENT";
+
+  const handleCreate = () => {
+    if (!targetId || !effectiveAt) return;
+    createMutation.mutate({
+      sessionToken,
+      payload: {
+        product_id: target === "PRODUCT" ? targetId : null,
+        category_id: target === "CATEGORY" ? targetId : null,
+        new_price: isPercent ? null : value,
+        percent_change: isPercent ? value : null,
+        effective_at: new Date(effectiveAt).toISOString(),
+        notes: notes || null,
+      },
+    });
+  };
+
+  const statusVariant = (status: PriceSchedule["status"]) =>
+    status === "PENDING" ? "default" : status === "APPLIED" ? "secondary" : "outline";
+
+  return (
+    <Dialog open={open} onOpenChange={onOpenChange}>
+      <DialogContent className="sm:max-w-2xl">
+        <DialogHeader>
+          <DialogTitle>Jadwal Perubahan Harga</DialogTitle>
+        </DialogHeader>
+
+        <div className="grid grid-cols-2 gap-3 border rounded-md p-3">
+          <div className="space-y-1">
+            <Label>Target</Label>
+            <select
+              value={target}
+              onChange={(e) => {
+                setTarget(e.target.value as "PRODUCT" | "CATEGORY");
+                setTargetId(null);
+              }}
+              className="w-full h-9 rounded-md border border-input bg-transparent px-2 text-sm"
+            >
+              <option value="PRODUCT">Produk</option>
+              <option value="CATEGORY">Kategori (persentase)</option>
+            </select>
+          </div>
+          <div className="space-y-1">
+            <Label>{target === "PRODUCT" ? "Produk" : "Kategori"}</Label>
+            <select
+              value={targetId ?? ""}
+              onChange={(e) => setTargetId(e.target.value ? Number(e.target.value) : null)}
+              className="w-full h-9 rounded-md border border-input bg-transparent px-2 text-sm"
+            >
+              <option value="">Pilih...</option>
+              {target === "PRODUCT"
+                ? products?.map((p) => (
+                    <option key={p.id} value={p.id}>
+                      {p.name} ({formatRupiah(p.price)})
+                    </option>
+                  ))
+                : categories?.map((c) => (
+                    <option key={c.id} value={c.id}>
+                      {c.name}
+                    </option>
+                  ))}
+            </select>
+          </div>
+          <div className="space-y-1">
+            <Label>Perubahan</Label>
+            <div className="flex gap-2">
+              {target === "PRODUCT" && (
+                <select
+                  value={mode}
+                  onChange={(e) => setMode(e.target.value as "PRICE" | "PERCENT")}
+                  className="h-9 rounded-md border border-input bg-transparent px-2 text-sm"
+                >
+                  <option value="PRICE">Harga baru</option>
+                  <option value="PERCENT">Persentase</option>
+                </select>
+              )}
+              <NumericInput
+                value={value}
+                onChange={setValue}
+                allowNegative={isPercent}
+                prefix={isPercent ? undefined : "Rp"}
+                suffix={isPercent ? "%" : undefined}
+              />
+            </div>
+          </div>
+          <div className="space-y-1">
+            <Label>Berlaku Mulai</Label>
+            <Input
+              type="datetime-local"
+              value={effectiveAt}
+              onChange={(e) => setEffectiveAt(e.target.value)}
+            />
+          </div>
+          <Input
+            className="col-span-2"
+            placeholder="Catatan (mis. kenaikan harga supplier)"
+            value={notes}
+            onChange={(e) => setNotes(e.target.value)}
+          />
+          <Button
+            className="col-span-2"
+            onClick={handleCreate}
+            disabled={!targetId || !effectiveAt || createMutation.isPending}
+          >
+            Jadwalkan
+          </Button>
+        </div>
+
+        <div className="max-h-[300px] overflow-y-auto space-y-2">
+          {schedules?.length === 0 && (
+            <p className="text-sm text-muted-foreground text-center py-4">
+              Belum ada jadwal harga.
+            </p>
+          )}
+          {schedules?.map((s) => (
+            <div key={s.id} className="flex items-center gap-3 text-sm border-b pb-2">
+              <div className="flex-1">
+                <div className="font-medium">
+                  {s.product_name ?? `Kategori ${s.category_name ?? "-"}`}
+                </div>
+                <div className="text-xs text-muted-foreground">
+                  {s.new_price !== null
+                    ? `→ ${formatRupiah(s.new_price)}`
+                    : `${(s.percent_change ?? 0) > 0 ? "+" : ""}${s.percent_change}%`}
+                  {" · "}
+                  {format(fromUtc(s.effective_at), "dd MMM yyyy, HH:mm")}
+                  {s.status === "APPLIED" && ` · ${s.applied_count} produk`}
+                  {s.notes && ` · ${s.notes}`}
+                </div>
+              </div>
+              <Badge variant={statusVariant(s.status)}>{s.status}</Badge>
+              {s.status === "PENDING" && (
+                <Button
+                  variant="ghost"
+                  size="sm"
+                  className="text-destructive"
+                  onClick={() => cancelMutation.mutate({ sessionToken, id: s.id })}
+                >
+                  Batalkan
+                </Button>
+              )}
+            </div>
+          ))}
+        </div>
+      </DialogContent>
+    </Dialog>
+  );
+}
@@ -0,0 +1,128 @@
+import { useState, useEffect } from "react";
+import { useAuthStore } from "../../store/authStore";
+import { invoke } from "../../lib/tauri";
+import { ProductBarcode } from "../../types";
+import { useToast } from "../../hooks/use-toast";
+import { Button } from "../../components/ui/button";
+import { Input } from "../../components/ui/input";
+import { Label } from "../../components/ui/label";
+import { Plus, Trash2 } from "lucide-react";
+
+/**
+ * Barcode tambahan produk: barcode lama supplier atau barcode kemasan
+ * (karton/pak) yang saat di-scan menambah N unit sekaligus.
+ */
+export function ProductBarcodeList({ productId }: { productId: number }) {
+  const [barcodes, setBarcodes] = useState<ProductBarcode[]>([]);
+  const [barcode, setBarcode] = useState("");
+  const [packQuantity, setPackQuantity] = useState(1);
+  const [label, setLabel] = useState("");
+  const [isSaving, setIsSaving] = useState(false);
+
+  const sessionToken = useAuthStore((s) => s.sessionToken);
+  const { toast } = useToast();
+
+  const fetchBarcodes = async () => {
+    try {
+      const data = await invoke<ProductBarcode[]>("get_product_barcodes", {
+        sessionToken,
+        productId,
+      });
+      setBarcodes(data);
+    } catch (error) {
+      console.error("Failed to load product barcodes:", error);
+    }
+  };
+
+  useEffect(() => {
+    fetchBarcodes();
+  }, [productId]);
+
+  const handleAdd = async () => {
+    if (!barcode.trim()) return;
+    setIsSaving(true);
+    try {
+      await invoke("add_product_barcode", {
+        sessionToken,
+        productId,
+        payload: {
+          barcode: barcode.trim(),
+          pack_quantity: packQuantity,
+          label: label || null,
+        },
+      });
+      setBarcode("");
+      setPackQuantity(1);
+      setLabel("");
+      fetchBarcodes();
+    } catch (error) {
+      toast({ variant: "destructive", title: "Gagal", description: String(error) });
+    } finally {
+      setIsSaving(false);
+    }
+  };
+
+  const handleDelete = async (id: number) => {
+    try {
+      await invoke("delete_product_barcode", { sessionToken, id });
+      fetchBarcodes();
+    } catch (error) {
+      toast({ variant: "destructive", title: "Gagal", description: String(error) });
+    }
+  };
+
+  return (
+    <div className="space-y-2">
+      <Label>Barcode Tambahan / Kemasan</Label>
+      {barcodes.map((b) => (
+        <div key={b.id} className="flex items-center gap-2 text-sm">
+          <span className="font-mono flex-1">{b.barcode}</span>
+          <span className="text-muted-foreground">
+            {b.pack_quantity > 1 ? `isi ${b.pack_quantity}` : "satuan"}
+            {b.label && ` · ${b.label}`}
+          </span>
+          <Button
+            type="button"
+            variant="ghost"
+            size="icon"
+            className="h-7 w-7 text-destructive"
+            onClick={() => handleDelete(b.id)}
+          >
+            <Trash2 className="h-4 w-4" />
+          </Button>
+        </div>
+      ))}
+      <div className="flex gap-2">
+        <Input
+          placeholder="Barcode"
+          value={barcode}
+          onChange={(e) => setBarcode(e.target.value)}
+          className="flex-1"
+        />
+        <Input
+          type="number"
+          min={1}
+          title="Isi per kemasan"
+          value={packQuantity}
+          onChange={(e) => setPackQuantity(Math.max(1, Number(e.target.value) || 1))}
+          className="w-20"
+        />
+        <Input
+          placeholder="Keterangan"
+          value={label}
+          onChange={(e) => setLabel(e.target.value)}
+          className="w-32"
+        />
+        <Button
+          type="button"
+          variant="outline"
+          size="icon"
+          onClick={handleAdd}
+          disabled={isSaving || !barcode.trim()}
+        >
+          <Plus className="h-4 w-4" />
+        </Button>
+      </div>
+    </div>
+  );
+}
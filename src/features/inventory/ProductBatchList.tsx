@@ -0,0 +1,103 @@
+import { useState } from "react";
+import { ProductBatch } from "../../types";
+import { useInvokeQuery, useInvokeMutation } from "../../hooks/useInvokeQuery";
+import { useAuthStore } from "../../store/authStore";
+import { useQueryClient } from "@tanstack/react-query";
+import { useToast } from "../../hooks/use-toast";
+import { Label } from "../../components/ui/label";
+import { Button } from "../../components/ui/button";
+import { Badge } from "../../components/ui/badge";
+import { ConfirmDialog } from "../../components/ConfirmDialog";
+import { Trash2 } from "lucide-react";
+
+/** Label sisa umur batch, dipakai juga di laporan kedaluwarsa. */
+export function ExpiryBadge({ days }: { days: number | null }) {
+  if (days === null) return null;
+  if (days < 0) return <Badge variant="destructive">Kedaluwarsa</Badge>;
+  return (
+    <Badge variant={days <= 7 ? "destructive" : "secondary"}>
+      {days === 0 ? "Hari ini" : `${days} hari`}
+    </Badge>
+  );
+}
+
+/**
+ * Batch produk yang masih bersisa (urut FEFO) dengan tombol buang. Stok yang
+ * dibuang dipotong dari `locationId` (null = lokasi utama).
+ */
+export function ProductBatchList({
+  productId,
+  unit,
+  locationId = null,
+}: {
+  productId: number;
+  unit: string;
+  locationId?: number | null;
+}) {
+  const [toDispose, setToDispose] = useState<ProductBatch | null>(null);
+  const sessionToken = useAuthStore((s) => s.sessionToken);
+  const queryClient = useQueryClient();
+  const { toast } = useToast();
+
+  const { data: batches } = useInvokeQuery<ProductBatch[]>(
+    ["product-batches", productId],
+    "get_product_batches",
+    { sessionToken, productId },
+  );
+
+  const disposeMutation = useInvokeMutation<void>("dispose_batch", {
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["product-batches"] });
+      queryClient.invalidateQueries({ queryKey: ["products"] });
+      queryClient.invalidateQueries({ queryKey: ["product-location-stock"] });
+      toast({ title: "Berhasil", description: "Sisa batch dibuang" });
+    },
+    onError: (e) => toast({ variant: "destructive", title: "Gagal", description: String(e) }),
+  });
+
+  if (!batches || batches.length === 0) return null;
+
+  return (
+    <div className="space-y-2">
+      <Label>Batch</Label>
+      <div className="max-h-40 overflow-y-auto space-y-1">
+        {batches.map((b) => (
+          <div key={b.id} className="flex items-center gap-2 text-xs">
+            <span className="flex-1">
+              {b.batch_number ?? `#${b.id}`}
+              {b.expiry_date && ` · ED ${b.expiry_date}`}
+            </span>
+            <ExpiryBadge days={b.days_to_expiry} />
+            <span className="w-16 text-right font-bold">
+              {b.quantity} {unit}
+            </span>
+            <Button
+              type="button"
+              variant="ghost"
+              size="icon"
+              className="h-6 w-6 text-destructive"
+              title="Buang sisa batch"
+              disabled={disposeMutation.isPending}
+              onClick={() => setToDispose(b)}
+            >
+              <Trash2 className="h-3 w-3" />
+            </Button>
+          </div>
+        ))}
+      </div>
+
+      <ConfirmDialog
+        open={toDispose !== null}
+        onOpenChange={(open) => !open && setToDispose(null)}
+        title="Buang Sisa Batch"
+        description={`Buang sisa ${toDispose?.quantity} ${unit} dari batch ${toDispose?.batch_number ?? `#${toDispose?.id}`}? Stok produk akan berkurang.`}
+        onConfirm={() =>
+          toDispose && disposeMutation.mutate({ sessionToken, batchId: toDispose.id, notes: null, locationId })
+        }
+        confirmLabel="Buang"
+        cancelLabel="Batal"
+        isDangerous
+      />
+    </div>
+  );
+}
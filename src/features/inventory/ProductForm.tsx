@@ -26,6 +26,14 @@ import { useQueryClient } from "@tanstack/react-query";
 import { useToast } from "../../hooks/use-toast";
 import { Product } from "../../types";
 import { NumericInput } from "../../components/NumericInput";
+import { ProductBarcodeList } from "./ProductBarcodeList";
+import { ProductUnitList } from "./ProductUnitList";
+import { ProductVariantList } from "./ProductVariantList";
+import { ProductPricingEditor } from "./ProductPricingEditor";
+import { PriceHistoryList } from "./PriceHistoryList";
+import { CostHistoryList } from "./CostHistoryList";
+import { KitComponentList } from "./KitComponentList";
+import { RecipeEditor } from "./RecipeEditor";
 
 export function ProductForm({
   open,
@@ -45,6 +53,8 @@ export function ProductForm({
     cost_price: 0,
     stock: 0,
     is_active: true,
+    is_weighed: false,
+    unit: "",
   });
   const [selectedImage, setSelectedImage] = useState<string | null>(null);
 
@@ -69,6 +79,8 @@ export function ProductForm({
         cost_price: product.cost_price || 0,
         stock: product.stock,
         is_active: product.is_active,
+        is_weighed: product.is_weighed,
+        unit: product.unit || "",
       });
     } else {
       setFormData({
@@ -80,6 +92,8 @@ export function ProductForm({
         cost_price: 0,
         stock: 0,
         is_active: true,
+        is_weighed: false,
+        unit: "",
       });
     }
     setSelectedImage(null);
@@ -162,6 +176,7 @@ export function ProductForm({
   };
 
   const handleUpdateSuccess = (updatedProduct: Product) => {
+    queryClient.invalidateQueries({ queryKey: ["price-history", updatedProduct.id] });
     if (selectedImage) {
       saveImageMutation.mutate({
         sessionToken,
@@ -199,6 +214,8 @@ export function ProductForm({
       cost_price: formData.cost_price,
       stock: product ? product.stock : formData.stock,
       is_active: formData.is_active,
+      is_weighed: formData.is_weighed,
+      unit: formData.unit.trim() || null,
     };
 
     if (product) {
@@ -276,6 +293,44 @@ export function ProductForm({
             </div>
           </div>
 
+          {product && <ProductBarcodeList productId={product.id} />}
+
+          <div className="space-y-2">
+            <Label>Satuan Dasar Stok</Label>
+            <Input
+              value={formData.unit}
+              placeholder={formData.is_weighed ? "kg" : "pcs"}
+              onChange={(e) => setFormData({ ...formData, unit: e.target.value })}
+            />
+          </div>
+
+          {product && (
+            <ProductUnitList
+              productId={product.id}
+              baseUnit={formData.unit || product.unit}
+            />
+          )}
+
+          {product && (
+            <ProductPricingEditor
+              productId={product.id}
+              baseUnit={formData.unit || product.unit}
+            />
+          )}
+
+          {product && <PriceHistoryList productId={product.id} />}
+          {product && <CostHistoryList productId={product.id} />}
+
+          {product && !product.parent_id && <ProductVariantList parentId={product.id} />}
+
+          {product && !product.variant_count && !product.is_recipe && (
+            <KitComponentList productId={product.id} />
+          )}
+
+          {product && !product.variant_count && !product.is_kit && (
+            <RecipeEditor productId={product.id} />
+          )}
+
           <div className="space-y-2">
             <Label>Product Image</Label>
             <div className="flex items-center gap-4">
@@ -348,16 +403,45 @@ export function ProductForm({
 
             {!product && (
               <div className="space-y-2">
-                <Label>Stok Awal *</Label>
-                <NumericInput
-                  required
-                  value={formData.stock}
-                  onChange={(val) => setFormData({ ...formData, stock: val })}
-                />
+                <Label>Stok Awal *{formData.is_weighed && " (kg)"}</Label>
+                {formData.is_weighed ? (
+                  <Input
+                    required
+                    type="number"
+                    min="0"
+                    step="0.001"
+                    value={formData.stock}
+                    onChange={(e) =>
+                      setFormData({
+                        ...formData,
+                        stock: parseFloat(e.target.value) || 0,
+                      })
+                    }
+                  />
+                ) : (
+                  <NumericInput
+                    required
+                    value={formData.stock}
+                    onChange={(val) => setFormData({ ...formData, stock: val })}
+                  />
+                )}
               </div>
             )}
           </div>
 
+          <div className="flex items-center space-x-2 pt-2">
+            <Checkbox
+              id="weighed"
+              checked={formData.is_weighed}
+              onCheckedChange={(c) =>
+                setFormData({ ...formData, is_weighed: c as boolean })
+              }
+            />
+            <Label htmlFor="weighed" className="cursor-pointer">
+              Produk timbang (dijual per kg)
+            </Label>
+          </div>
+
           {product && (
             <div className="flex items-center space-x-2 pt-2">
               <Checkbox
@@ -0,0 +1,138 @@
+import { useState, useEffect } from "react";
+import { useAuthStore } from "../../store/authStore";
+import { invoke } from "../../lib/tauri";
+import { PriceLevel, PriceTier, ProductPricing } from "../../types";
+import { useInvokeQuery } from "../../hooks/useInvokeQuery";
+import { useToast } from "../../hooks/use-toast";
+import { Button } from "../../components/ui/button";
+import { Input } from "../../components/ui/input";
+import { Label } from "../../components/ui/label";
+import { NumericInput } from "../../components/NumericInput";
+import { Plus, Trash2 } from "lucide-react";
+
+/**
+ * Harga grosir (beli >= N satuan dasar) dan harga khusus per level
+ * pelanggan. Harga kosong/0 pada level = pakai potongan default level.
+ */
+export function ProductPricingEditor({
+  productId,
+  baseUnit,
+}: {
+  productId: number;
+  baseUnit: string;
+}) {
+  const [tiers, setTiers] = useState<PriceTier[]>([]);
+  const [levelPrices, setLevelPrices] = useState<Record<number, number>>({});
+  const [isSaving, setIsSaving] = useState(false);
+
+  const sessionToken = useAuthStore((s) => s.sessionToken);
+  const { toast } = useToast();
+
+  const { data: levels } = useInvokeQuery<PriceLevel[]>(
+    ["price-levels"],
+    "get_price_levels",
+    { sessionToken },
+  );
+
+  useEffect(() => {
+    invoke<ProductPricing>("get_product_pricing", { sessionToken, productId })
+      .then((pricing) => {
+        setTiers(pricing.tiers);
+        setLevelPrices(
+          Object.fromEntries(pricing.level_prices.map((l) => [l.price_level_id, l.price])),
+        );
+      })
+      .catch((error) => console.error("Failed to load product pricing:", error));
+  }, [productId]);
+
+  const updateTier = (index: number, patch: Partial<PriceTier>) =>
+    setTiers(tiers.map((t, i) => (i === index ? { ...t, ...patch } : t)));
+
+  const handleSave = async () => {
+    setIsSaving(true);
+    try {
+      await invoke("save_product_pricing", {
+        sessionToken,
+        productId,
+        pricing: {
+          tiers,
+          level_prices: Object.entries(levelPrices)
+            .filter(([, price]) => price > 0)
+            .map(([id, price]) => ({ price_level_id: Number(id), price })),
+        },
+      });
+      toast({ title: "Berhasil", description: "Harga grosir & level disimpan" });
+    } catch (error) {
+      toast({ variant: "destructive", title: "Gagal", description: String(error) });
+    } finally {
+      setIsSaving(false);
+    }
+  };
+
+  return (
+    <div className="space-y-2">
+      <Label>Harga Grosir</Label>
+      {tiers.map((tier, index) => (
+        <div key={index} className="flex items-center gap-2">
+          <span className="text-xs text-muted-foreground">min.</span>
+          <Input
+            type="number"
+            min={0}
+            step="any"
+            value={tier.min_quantity}
+            onChange={(e) => updateTier(index, { min_quantity: Number(e.target.value) })}
+            className="w-20"
+          />
+          <span className="text-xs text-muted-foreground w-10">{baseUnit || "pcs"}</span>
+          <NumericInput
+            value={tier.price}
+            onChange={(val) => updateTier(index, { price: val })}
+            prefix="Rp"
+          />
+          <Button
+            type="button"
+            variant="ghost"
+            size="icon"
+            className="h-8 w-8 text-destructive"
+            onClick={() => setTiers(tiers.filter((_, i) => i !== index))}
+          >
+            <Trash2 className="h-4 w-4" />
+          </Button>
+        </div>
+      ))}
+
+      {levels && levels.length > 0 && (
+        <>
+          <Label>Harga per Level Pelanggan</Label>
+          {levels.map((level) => (
+            <div key={level.id} className="flex items-center gap-2">
+              <span className="text-sm w-28 truncate">{level.name}</span>
+              <NumericInput
+                value={levelPrices[level.id] ?? 0}
+                onChange={(val) => setLevelPrices({ ...levelPrices, [level.id]: val })}
+                prefix="Rp"
+                placeholder={level.discount_percent > 0 ? `-${level.discount_percent}%` : undefined}
+              />
+            </div>
+          ))}
+        </>
+      )}
+
+      <div className="flex gap-2">
+        <Button
+          type="button"
+          variant="outline"
+          size="sm"
+          onClick={() =>
+            setTiers([...tiers, { min_quantity: (tiers[tiers.length - 1]?.min_quantity ?? 0) + 12, price: 0 }])
+          }
+        >
+          <Plus className="h-4 w-4 mr-1" /> Tier
+        </Button>
+        <Button type="button" size="sm" onClick={handleSave} disabled={isSaving}>
+          Simpan Harga
+        </Button>
+      </div>
+    </div>
+  );
+}
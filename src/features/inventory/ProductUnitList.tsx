@@ -0,0 +1,109 @@
+import { useState, useEffect } from "react";
+import { useAuthStore } from "../../store/authStore";
+import { invoke } from "../../lib/tauri";
+import { ProductUnit } from "../../types";
+import { useToast } from "../../hooks/use-toast";
+import { useQueryClient } from "@tanstack/react-query";
+import { Button } from "../../components/ui/button";
+import { Input } from "../../components/ui/input";
+import { Label } from "../../components/ui/label";
+import { NumericInput } from "../../components/NumericInput";
+import { Plus, Trash2 } from "lucide-react";
+
+type UnitRow = { name: string; conversion: number; price: number };
+
+/**
+ * Satuan jual tambahan (pack, box, ...) dengan konversi ke satuan dasar dan
+ * harga jual per satuan. Stok tetap disimpan dalam satuan dasar.
+ */
+export function ProductUnitList({
+  productId,
+  baseUnit,
+}: {
+  productId: number;
+  baseUnit: string;
+}) {
+  const [rows, setRows] = useState<UnitRow[]>([]);
+  const [isSaving, setIsSaving] = useState(false);
+
+  const sessionToken = useAuthStore((s) => s.sessionToken);
+  const queryClient = useQueryClient();
+  const { toast } = useToast();
+
+  useEffect(() => {
+    invoke<ProductUnit[]>("get_product_units", { sessionToken, productId })
+      .then((units) =>
+        setRows(units.map(({ name, conversion, price }) => ({ name, conversion, price }))),
+      )
+      .catch((error) => console.error("Failed to load product units:", error));
+  }, [productId]);
+
+  const updateRow = (index: number, patch: Partial<UnitRow>) =>
+    setRows(rows.map((r, i) => (i === index ? { ...r, ...patch } : r)));
+
+  const handleSave = async () => {
+    setIsSaving(true);
+    try {
+      await invoke("save_product_units", { sessionToken, productId, units: rows });
+      queryClient.invalidateQueries({ queryKey: ["product-units", productId] });
+      toast({ title: "Berhasil", description: "Satuan jual disimpan" });
+    } catch (error) {
+      toast({ variant: "destructive", title: "Gagal", description: String(error) });
+    } finally {
+      setIsSaving(false);
+    }
+  };
+
+  return (
+    <div className="space-y-2">
+      <Label>Satuan Jual Lain</Label>
+      {rows.map((row, index) => (
+        <div key={index} className="flex items-center gap-2">
+          <Input
+            placeholder="box"
+            value={row.name}
+            onChange={(e) => updateRow(index, { name: e.target.value })}
+            className="w-24"
+          />
+          <span className="text-xs text-muted-foreground">=</span>
+          <Input
+            type="number"
+            min={0}
+            step="any"
+            value={row.conversion}
+            onChange={(e) => updateRow(index, { conversion: Number(e.target.value) })}
+            className="w-20"
+          />
+          <span className="text-xs text-muted-foreground w-10">{baseUnit || "pcs"}</span>
+          <NumericInput
+            value={row.price}
+            onChange={(val) => updateRow(index, { price: val })}
+            prefix="Rp"
+          />
+          <Button
+            type="button"
+            variant="ghost"
+            size="icon"
+            className="h-8 w-8 text-destructive"
+            onClick={() => setRows(rows.filter((_, i) => i !== index))}
+          >
+            <Trash2 className="h-4 w-4" />
+          </Button>
+        </div>
+      ))}
+      <div className="flex gap-2">
+        <Button
+          type="button"
+          variant="outline"
+          size="sm"
+          onClick={() => setRows([...rows, { name: "", conversion: 1, price: 0 }])}
+        >
+          <Plus className="h-4 w-4 mr-1" /> Satuan
+        </Button>
+        <Button type="button" size="sm" onClick={handleSave} disabled={isSaving}>
+          Simpan Satuan
+        </Button>
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,161 @@
+import { useState, useEffect } from "react";
+import { useAuthStore } from "../../store/authStore";
+import { invoke } from "../../lib/tauri";
+import { ProductWithCategory } from "../../types";
+import { useToast } from "../../hooks/use-toast";
+import { useQueryClient } from "@tanstack/react-query";
+import { Button } from "../../components/ui/button";
+import { Input } from "../../components/ui/input";
+import { Label } from "../../components/ui/label";
+import { NumericInput } from "../../components/NumericInput";
+import { formatRupiah } from "../../lib/currency";
+import { Plus, X } from "lucide-react";
+
+type AttributeRow = { key: string; value: string };
+
+/**
+ * Varian produk (ukuran, warna, ...). Tiap varian adalah produk sendiri
+ * dengan SKU/barcode/stok sendiri; harga kosong = ikut harga induk.
+ */
+export function ProductVariantList({ parentId }: { parentId: number }) {
+  const [variants, setVariants] = useState<ProductWithCategory[]>([]);
+  const [attributes, setAttributes] = useState<AttributeRow[]>([{ key: "", value: "" }]);
+  const [sku, setSku] = useState("");
+  const [barcode, setBarcode] = useState("");
+  const [price, setPrice] = useState(0);
+  const [stock, setStock] = useState(0);
+  const [isSaving, setIsSaving] = useState(false);
+
+  const sessionToken = useAuthStore((s) => s.sessionToken);
+  const queryClient = useQueryClient();
+  const { toast } = useToast();
+
+  const fetchVariants = async () => {
+    try {
+      const data = await invoke<ProductWithCategory[]>("get_product_variants", {
+        sessionToken,
+        parentId,
+      });
+      setVariants(data);
+    } catch (error) {
+      console.error("Failed to load product variants:", error);
+    }
+  };
+
+  useEffect(() => {
+    fetchVariants();
+  }, [parentId]);
+
+  const updateAttribute = (index: number, patch: Partial<AttributeRow>) =>
+    setAttributes(attributes.map((a, i) => (i === index ? { ...a, ...patch } : a)));
+
+  const filledAttributes = attributes.filter((a) => a.key.trim() && a.value.trim());
+
+  const handleAdd = async () => {
+    if (filledAttributes.length === 0) return;
+    setIsSaving(true);
+    try {
+      await invoke("create_product_variant", {
+        sessionToken,
+        parentId,
+        payload: {
+          attributes: Object.fromEntries(
+            filledAttributes.map((a) => [a.key.trim(), a.value.trim()]),
+          ),
+          sku: sku.trim() || null,
+          barcode: barcode.trim() || null,
+          price: price > 0 ? price : null,
+          cost_price: null,
+          stock,
+        },
+      });
+      setAttributes(attributes.map((a) => ({ key: a.key, value: "" })));
+      setSku("");
+      setBarcode("");
+      setPrice(0);
+      setStock(0);
+      queryClient.invalidateQueries({ queryKey: ["products"] });
+      fetchVariants();
+    } catch (error) {
+      toast({ variant: "destructive", title: "Gagal", description: String(error) });
+    } finally {
+      setIsSaving(false);
+    }
+  };
+
+  return (
+    <div className="space-y-2">
+      <Label>Varian</Label>
+      {variants.map((v) => (
+        <div key={v.id} className="flex items-center gap-2 text-sm">
+          <span className="flex-1">{v.name}</span>
+          <span className="font-mono text-muted-foreground">{v.sku || v.barcode || "-"}</span>
+          <span className="w-24 text-right">{formatRupiah(v.price)}</span>
+          <span className="w-16 text-right text-muted-foreground">Stok {v.stock}</span>
+        </div>
+      ))}
+
+      <div className="rounded-md border border-border p-2 space-y-2">
+        {attributes.map((a, index) => (
+          <div key={index} className="flex gap-2">
+            <Input
+              placeholder="Atribut (mis. Ukuran)"
+              value={a.key}
+              onChange={(e) => updateAttribute(index, { key: e.target.value })}
+            />
+            <Input
+              placeholder="Nilai (mis. L)"
+              value={a.value}
+              onChange={(e) => updateAttribute(index, { value: e.target.value })}
+            />
+            <Button
+              type="button"
+              variant="ghost"
+              size="icon"
+              onClick={() => setAttributes(attributes.filter((_, i) => i !== index))}
+              disabled={attributes.length === 1}
+            >
+              <X className="h-4 w-4" />
+            </Button>
+          </div>
+        ))}
+        <Button
+          type="button"
+          variant="ghost"
+          size="sm"
+          onClick={() => setAttributes([...attributes, { key: "", value: "" }])}
+        >
+          <Plus className="h-4 w-4 mr-1" /> Atribut
+        </Button>
+        <div className="grid grid-cols-2 gap-2">
+          <Input placeholder="SKU" value={sku} onChange={(e) => setSku(e.target.value)} />
+          <Input
+            placeholder="Barcode"
+            value={barcode}
+            onChange={(e) => setBarcode(e.target.value)}
+          />
+          <NumericInput
+            placeholder="Harga (kosong = harga induk)"
+            value={price}
+            onChange={(val: number) => setPrice(val)}
+          />
+          <NumericInput
+            placeholder="Stok awal"
+            value={stock}
+            onChange={(val: number) => setStock(val)}
+          />
+        </div>
+        <Button
+          type="button"
+          variant="outline"
+          size="sm"
+          className="w-full"
+          onClick={handleAdd}
+          disabled={isSaving || filledAttributes.length === 0}
+        >
+          Tambah Varian
+        </Button>
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,441 @@
+import { useState } from "react";
+import {
+  Dialog,
+  DialogContent,
+  DialogHeader,
+  DialogTitle,
+} from "../../components/ui/dialog";
+import { Button } from "../../components/ui/button";
+import { Input } from "../../components/ui/input";
+import { Label } from "../../components/ui/label";
+import { Badge } from "../../components/ui/badge";
+import { NumericInput } from "../../components/NumericInput";
+import { ConfirmDialog } from "../../components/ConfirmDialog";
+import { useInvokeQuery, useInvokeMutation } from "../../hooks/useInvokeQuery";
+import {
+  ProductWithCategory,
+  PurchaseOrder,
+  PurchaseOrderDetail,
+  PurchaseOrderStatus,
+  Supplier,
+} from "../../types";
+import { useAuthStore } from "../../store/authStore";
+import { useQueryClient } from "@tanstack/react-query";
+import { useToast } from "../../hooks/use-toast";
+import { invoke } from "../../lib/tauri";
+import { formatRupiah } from "../../lib/currency";
+import { ArrowLeft, FileDown, PackageCheck, Plus, Send, Trash2, XCircle } from "lucide-react";
+import { GoodsReceiptDialog } from "./GoodsReceiptDialog";
+
+export const PO_STATUS_LABEL: Record<PurchaseOrderStatus, string> = {
+  DRAFT: "Draft",
+  SENT: "Terkirim",
+  PARTIALLY_RECEIVED: "Diterima Sebagian",
+  RECEIVED: "Diterima",
+  CANCELLED: "Dibatalkan",
+};
+
+const statusVariant = (status: PurchaseOrderStatus) =>
+  status === "CANCELLED" ? "outline" : status === "RECEIVED" ? "secondary" : "default";
+
+type Line = { product_id: number; quantity: number; unit_cost: number };
+
+/**
+ * Purchase order ke supplier: daftar PO, buat/ubah draft, kirim, batalkan,
+ * dan export dokumen PO (PDF).
+ */
+export function PurchaseOrderManager({
+  open,
+  onOpenChange,
+}: {
+  open: boolean;
+  onOpenChange: (open: boolean) => void;
+}) {
+  const [statusFilter, setStatusFilter] = useState<PurchaseOrderStatus | "">("");
+  // null = daftar, 0 = PO baru, >0 = detail/ubah PO
+  const [selectedId, setSelectedId] = useState<number | null>(null);
+  const [supplierId, setSupplierId] = useState<number | null>(null);
+  const [expectedDate, setExpectedDate] = useState("");
+  const [notes, setNotes] = useState("");
+  const [lines, setLines] = useState<Line[]>([]);
+  const [cancelOpen, setCancelOpen] = useState(false);
+  const [receiveOpen, setReceiveOpen] = useState(false);
+
+  const sessionToken = useAuthStore((s) => s.sessionToken);
+  const queryClient = useQueryClient();
+  const { toast } = useToast();
+
+  const { data: orders } = useInvokeQuery<PurchaseOrder[]>(
+    ["purchase-orders", statusFilter],
+    "get_purchase_orders",
+    { sessionToken, status: statusFilter || null, supplierId: null },
+    { enabled: open && selectedId === null },
+  );
+  const { data: detail } = useInvokeQuery<PurchaseOrderDetail>(
+    ["purchase-order", selectedId],
+    "get_purchase_order",
+    { sessionToken, id: selectedId },
+    { enabled: open && !!selectedId },
+  );
+  const { data: suppliers } = useInvokeQuery<Supplier[]>(
+    ["suppliers", false],
+    "get_suppliers",
+    { sessionToken, showInactive: false },
+    { enabled: open },
+  );
+  const { data: products } = useInvokeQuery<ProductWithCategory[]>(
+    ["products", "", null],
+    "get_products",
+    { sessionToken, search: "", categoryId: null, showInactive: false },
+    { enabled: open },
+  );
+
+  const isEditable = selectedId === 0 || detail?.status === "DRAFT";
+
+  const loadForm = (po: PurchaseOrderDetail | null) => {
+    setSupplierId(po?.supplier_id ?? null);
+    setExpectedDate(po?.expected_date ?? "");
+    setNotes(po?.notes ?? "");
+    setLines(
+      po?.items.map((i) => ({
+        product_id: i.product_id,
+        quantity: i.quantity,
+        unit_cost: i.unit_cost,
+      })) ?? [],
+    );
+  };
+
+  const openOrder = async (id: number) => {
+    setSelectedId(id);
+    try {
+      loadForm(await invoke<PurchaseOrderDetail>("get_purchase_order", { sessionToken, id }));
+    } catch (e) {
+      onError(e);
+    }
+  };
+
+  const onError = (e: unknown) =>
+    toast({ variant: "destructive", title: "Error", description: String(e) });
+
+  const onSaved = (po: PurchaseOrderDetail) => {
+    queryClient.invalidateQueries({ queryKey: ["purchase-orders"] });
+    queryClient.setQueryData(["purchase-order", po.id], po);
+    setSelectedId(po.id);
+    loadForm(po);
+    toast({ title: "Berhasil", description: `PO ${po.po_number} disimpan` });
+  };
+
+  const createMutation = useInvokeMutation<PurchaseOrderDetail>("create_purchase_order", {
+    onSuccess: onSaved,
+    onError,
+  });
+  const updateMutation = useInvokeMutation<PurchaseOrderDetail>("update_purchase_order", {
+    onSuccess: onSaved,
+    onError,
+  });
+  const statusMutation = useInvokeMutation<PurchaseOrderDetail>("set_purchase_order_status", {
+    onSuccess: (po) => {
+      queryClient.invalidateQueries({ queryKey: ["purchase-orders"] });
+      queryClient.setQueryData(["purchase-order", po.id], po);
+      toast({ title: "Berhasil", description: `PO ${po.po_number}: ${PO_STATUS_LABEL[po.status]}` });
+    },
+    onError,
+  });
+
+  const handleSave = () => {
+    if (!supplierId) return;
+    const payload = {
+      supplier_id: supplierId,
+      expected_date: expectedDate || null,
+      notes: notes || null,
+      items: lines.filter((l) => l.product_id),
+    };
+    if (selectedId) {
+      updateMutation.mutate({ sessionToken, id: selectedId, payload });
+    } else {
+      createMutation.mutate({ sessionToken, payload });
+    }
+  };
+
+  const handleExport = async () => {
+    if (!selectedId) return;
+    try {
+      const filePath = await invoke<string>("export_purchase_order_pdf", {
+        sessionToken,
+        id: selectedId,
+      });
+      toast({ title: "PO Diekspor", description: `File disimpan di: ${filePath}` });
+    } catch (e) {
+      onError(e);
+    }
+  };
+
+  const updateLine = (index: number, patch: Partial<Line>) =>
+    setLines((ls) => ls.map((l, i) => (i === index ? { ...l, ...patch } : l)));
+
+  const productById = (id: number) => products?.find((p) => p.id === id);
+  const orderable = products?.filter((p) => !p.is_kit && !p.is_recipe) ?? [];
+  const total = lines.reduce((sum, l) => sum + Math.round(l.quantity * l.unit_cost), 0);
+  const isSaving = createMutation.isPending || updateMutation.isPending;
+
+  return (
+    <Dialog
+      open={open}
+      onOpenChange={(o) => {
+        if (!o) setSelectedId(null);
+        onOpenChange(o);
+      }}
+    >
+      <DialogContent className="sm:max-w-3xl">
+        <DialogHeader>
+          <DialogTitle>
+            {selectedId === null
+              ? "Purchase Order"
+              : selectedId === 0
+                ? "PO Baru"
+                : `PO ${detail?.po_number ?? ""}`}
+          </DialogTitle>
+        </DialogHeader>
+
+        {selectedId === null ? (
+          <>
+            <div className="flex gap-2">
+              <select
+                value={statusFilter}
+                onChange={(e) => setStatusFilter(e.target.value as PurchaseOrderStatus | "")}
+                className="h-9 rounded-md border border-input bg-transparent px-2 text-sm"
+              >
+                <option value="">Semua status</option>
+                {Object.entries(PO_STATUS_LABEL).map(([value, label]) => (
+                  <option key={value} value={value}>
+                    {label}
+                  </option>
+                ))}
+              </select>
+              <div className="flex-1" />
+              <Button
+                onClick={() => {
+                  setSelectedId(0);
+                  loadForm(null);
+                }}
+              >
+                <Plus className="mr-2 h-4 w-4" />
+                Buat PO
+              </Button>
+            </div>
+
+            <div className="max-h-[400px] overflow-y-auto space-y-2">
+              {orders?.length === 0 && (
+                <p className="text-sm text-muted-foreground text-center py-4">Belum ada PO.</p>
+              )}
+              {orders?.map((po) => (
+                <div
+                  key={po.id}
+                  className="flex items-center gap-3 text-sm border-b pb-2 cursor-pointer hover:bg-muted/40"
+                  onClick={() => openOrder(po.id)}
+                >
+                  <div className="flex-1">
+                    <div className="font-medium">
+                      {po.po_number} · {po.supplier_name}
+                    </div>
+                    <div className="text-xs text-muted-foreground">
+                      {po.item_count} barang · {formatRupiah(po.total_amount)}
+                      {po.expected_date && ` · ETA ${po.expected_date}`}
+                    </div>
+                  </div>
+                  <Badge variant={statusVariant(po.status)}>{PO_STATUS_LABEL[po.status]}</Badge>
+                </div>
+              ))}
+            </div>
+          </>
+        ) : (
+          <>
+            <div className="grid grid-cols-3 gap-3">
+              <div className="space-y-1">
+                <Label>Supplier</Label>
+                <select
+                  value={supplierId ?? ""}
+                  disabled={!isEditable}
+                  onChange={(e) => setSupplierId(e.target.value ? Number(e.target.value) : null)}
+                  className="w-full h-9 rounded-md border border-input bg-transparent px-2 text-sm"
+                >
+                  <option value="">Pilih...</option>
+                  {suppliers?.map((s) => (
+                    <option key={s.id} value={s.id}>
+                      {s.name}
+                    </option>
+                  ))}
+                  {detail && !suppliers?.some((s) => s.id === detail.supplier_id) && (
+                    <option value={detail.supplier_id}>{detail.supplier_name}</option>
+                  )}
+                </select>
+              </div>
+              <div className="space-y-1">
+                <Label>Perkiraan Tiba</Label>
+                <Input
+                  type="date"
+                  value={expectedDate}
+                  disabled={!isEditable}
+                  onChange={(e) => setExpectedDate(e.target.value)}
+                />
+              </div>
+              <div className="space-y-1">
+                <Label>Status</Label>
+                <div className="h-9 flex items-center">
+                  <Badge variant={statusVariant(detail?.status ?? "DRAFT")}>
+                    {PO_STATUS_LABEL[detail?.status ?? "DRAFT"]}
+                  </Badge>
+                </div>
+              </div>
+              <Input
+                className="col-span-3"
+                placeholder="Catatan untuk supplier"
+                value={notes}
+                disabled={!isEditable}
+                onChange={(e) => setNotes(e.target.value)}
+              />
+            </div>
+
+            <div className="max-h-[300px] overflow-y-auto space-y-2">
+              {lines.map((line, index) => {
+                const product = productById(line.product_id);
+                const received = detail?.items.find((i) => i.product_id === line.product_id);
+                return (
+                  <div key={index} className="flex items-center gap-2 text-sm">
+                    <select
+                      value={line.product_id || ""}
+                      disabled={!isEditable}
+                      onChange={(e) => {
+                        const p = productById(Number(e.target.value));
+                        updateLine(index, {
+                          product_id: Number(e.target.value),
+                          unit_cost: p?.cost_price ?? 0,
+                        });
+                      }}
+                      className="flex-1 h-9 rounded-md border border-input bg-transparent px-2 text-sm"
+                    >
+                      <option value="">Pilih produk...</option>
+                      {orderable.map((p) => (
+                        <option key={p.id} value={p.id}>
+                          {p.name} (stok {p.stock} {p.unit})
+                        </option>
+                      ))}
+                      {received && !product && (
+                        <option value={received.product_id}>{received.product_name}</option>
+                      )}
+                    </select>
+                    <Input
+                      type="number"
+                      min={0}
+                      step={product?.is_weighed ? "0.001" : "1"}
+                      className="w-24"
+                      value={line.quantity}
+                      disabled={!isEditable}
+                      onChange={(e) => updateLine(index, { quantity: Number(e.target.value) })}
+                    />
+                    <NumericInput
+                      className="w-32"
+                      prefix="Rp"
+                      value={line.unit_cost}
+                      disabled={!isEditable}
+                      onChange={(v) => updateLine(index, { unit_cost: v })}
+                    />
+                    <span className="w-28 text-right text-xs">
+                      {!isEditable && received
+                        ? `diterima ${received.received_quantity}/${received.quantity}`
+                        : formatRupiah(Math.round(line.quantity * line.unit_cost))}
+                    </span>
+                    {isEditable && (
+                      <Button
+                        variant="ghost"
+                        size="icon"
+                        className="h-7 w-7 text-destructive"
+                        onClick={() => setLines((ls) => ls.filter((_, i) => i !== index))}
+                      >
+                        <Trash2 className="h-3 w-3" />
+                      </Button>
+                    )}
+                  </div>
+                );
+              })}
+              {isEditable && (
+                <Button
+                  variant="outline"
+                  size="sm"
+                  onClick={() => setLines((ls) => [...ls, { product_id: 0, quantity: 1, unit_cost: 0 }])}
+                >
+                  <Plus className="mr-2 h-3 w-3" />
+                  Tambah Barang
+                </Button>
+              )}
+            </div>
+
+            <div className="flex items-center gap-2 border-t pt-3">
+              <Button variant="ghost" onClick={() => setSelectedId(null)}>
+                <ArrowLeft className="mr-2 h-4 w-4" />
+                Kembali
+              </Button>
+              <div className="flex-1 text-right font-bold">Total {formatRupiah(total)}</div>
+              {selectedId > 0 && (
+                <Button variant="outline" onClick={handleExport}>
+                  <FileDown className="mr-2 h-4 w-4" />
+                  PDF
+                </Button>
+              )}
+              {detail &&
+                ["DRAFT", "SENT", "PARTIALLY_RECEIVED"].includes(detail.status) && (
+                  <Button variant="outline" className="text-destructive" onClick={() => setCancelOpen(true)}>
+                    <XCircle className="mr-2 h-4 w-4" />
+                    Batalkan
+                  </Button>
+                )}
+              {isEditable && (
+                <Button
+                  variant="outline"
+                  onClick={handleSave}
+                  disabled={!supplierId || lines.length === 0 || isSaving}
+                >
+                  Simpan Draft
+                </Button>
+              )}
+              {(detail?.status === "SENT" || detail?.status === "PARTIALLY_RECEIVED") && (
+                <Button onClick={() => setReceiveOpen(true)}>
+                  <PackageCheck className="mr-2 h-4 w-4" />
+                  Terima Barang
+                </Button>
+              )}
+              {detail?.status === "DRAFT" && (
+                <Button
+                  disabled={statusMutation.isPending}
+                  onClick={() => statusMutation.mutate({ sessionToken, id: detail.id, status: "SENT" })}
+                >
+                  <Send className="mr-2 h-4 w-4" />
+                  Tandai Terkirim
+                </Button>
+              )}
+            </div>
+          </>
+        )}
+
+        <GoodsReceiptDialog
+          open={receiveOpen}
+          onOpenChange={setReceiveOpen}
+          purchaseOrder={detail}
+        />
+
+        <ConfirmDialog
+          open={cancelOpen}
+          onOpenChange={setCancelOpen}
+          title="Batalkan PO"
+          description={`Batalkan PO ${detail?.po_number ?? ""}? Barang yang belum diterima tidak akan ditunggu lagi.`}
+          onConfirm={() =>
+            detail && statusMutation.mutate({ sessionToken, id: detail.id, status: "CANCELLED" })
+          }
+          confirmLabel="Batalkan PO"
+          cancelLabel="Kembali"
+          isDangerous
+        />
+      </DialogContent>
+    </Dialog>
+  );
+}
@@ -0,0 +1,171 @@
+import { useState, useEffect } from "react";
+import { useAuthStore } from "../../store/authStore";
+import { invoke } from "../../lib/tauri";
+import { ProductComponent, ProductUnit, ProductWithCategory } from "../../types";
+import { useInvokeQuery } from "../../hooks/useInvokeQuery";
+import { useToast } from "../../hooks/use-toast";
+import { useQueryClient } from "@tanstack/react-query";
+import { Button } from "../../components/ui/button";
+import { Input } from "../../components/ui/input";
+import { Label } from "../../components/ui/label";
+import { Plus, Trash2 } from "lucide-react";
+
+type IngredientRow = { component_id: number | null; quantity: number; unit_id: number | null };
+
+/** Satu baris bahan: pilih bahan, takaran, dan satuan (dasar / satuan jual bahan). */
+function IngredientRowInput({
+  row,
+  candidates,
+  onChange,
+  onRemove,
+}: {
+  row: IngredientRow;
+  candidates: ProductWithCategory[];
+  onChange: (patch: Partial<IngredientRow>) => void;
+  onRemove: () => void;
+}) {
+  const sessionToken = useAuthStore((s) => s.sessionToken);
+  const ingredient = candidates.find((p) => p.id === row.component_id);
+
+  const { data: units } = useInvokeQuery<ProductUnit[]>(
+    ["product-units", row.component_id],
+    "get_product_units",
+    { sessionToken, productId: row.component_id },
+    { enabled: row.component_id !== null },
+  );
+
+  return (
+    <div className="flex items-center gap-2">
+      <select
+        value={row.component_id ?? ""}
+        onChange={(e) =>
+          onChange({
+            component_id: e.target.value ? Number(e.target.value) : null,
+            unit_id: null,
+          })
+        }
+        className="flex-1 h-9 rounded-md border border-input bg-transparent px-2 text-sm"
+      >
+        <option value="">Pilih bahan...</option>
+        {candidates.map((p) => (
+          <option key={p.id} value={p.id}>
+            {p.name} (stok {p.stock} {p.unit})
+          </option>
+        ))}
+      </select>
+      <Input
+        type="number"
+        min={0}
+        step="any"
+        value={row.quantity}
+        onChange={(e) => onChange({ quantity: Number(e.target.value) })}
+        className="w-20"
+      />
+      <select
+        value={row.unit_id ?? ""}
+        onChange={(e) => onChange({ unit_id: e.target.value ? Number(e.target.value) : null })}
+        className="w-20 h-9 rounded-md border border-input bg-transparent px-1 text-sm"
+      >
+        <option value="">{ingredient?.unit ?? "pcs"}</option>
+        {units?.map((u) => (
+          <option key={u.id} value={u.id}>
+            {u.name}
+          </option>
+        ))}
+      </select>
+      <Button
+        type="button"
+        variant="ghost"
+        size="icon"
+        className="h-8 w-8 text-destructive"
+        onClick={onRemove}
+      >
+        <Trash2 className="h-4 w-4" />
+      </Button>
+    </div>
+  );
+}
+
+/**
+ * Resep menu (mis. kopi susu: 18 g kopi, 150 ml susu, 1 cup). Takaran boleh
+ * desimal; menjual menu memotong stok bahan. Daftar kosong = produk biasa.
+ */
+export function RecipeEditor({ productId }: { productId: number }) {
+  const [rows, setRows] = useState<IngredientRow[]>([]);
+  const [isSaving, setIsSaving] = useState(false);
+
+  const sessionToken = useAuthStore((s) => s.sessionToken);
+  const queryClient = useQueryClient();
+  const { toast } = useToast();
+
+  const { data: products } = useInvokeQuery<ProductWithCategory[]>(
+    ["products", "", null],
+    "get_products",
+    { sessionToken, search: "", categoryId: null, showInactive: false },
+  );
+  const candidates = (products ?? []).filter(
+    (p) => p.id !== productId && !p.is_kit && !p.is_recipe && !p.variant_count,
+  );
+
+  useEffect(() => {
+    invoke<ProductComponent[]>("get_recipe", { sessionToken, productId })
+      .then((ingredients) =>
+        setRows(
+          ingredients.map(({ component_id, quantity, unit_id }) => ({
+            component_id,
+            quantity,
+            unit_id,
+          })),
+        ),
+      )
+      .catch((error) => console.error("Failed to load recipe:", error));
+  }, [productId]);
+
+  const updateRow = (index: number, patch: Partial<IngredientRow>) =>
+    setRows(rows.map((r, i) => (i === index ? { ...r, ...patch } : r)));
+
+  const handleSave = async () => {
+    setIsSaving(true);
+    try {
+      await invoke("save_recipe", {
+        sessionToken,
+        productId,
+        ingredients: rows.filter((r) => r.component_id !== null),
+      });
+      queryClient.invalidateQueries({ queryKey: ["products"] });
+      toast({ title: "Berhasil", description: "Resep disimpan" });
+    } catch (error) {
+      toast({ variant: "destructive", title: "Gagal", description: String(error) });
+    } finally {
+      setIsSaving(false);
+    }
+  };
+
+  return (
+    <div className="space-y-2">
+      <Label>Resep (per porsi)</Label>
+      {rows.map((row, index) => (
+        <IngredientRowInput
+          key={index}
+          row={row}
+          candidates={candidates}
+          onChange={(patch) => updateRow(index, patch)}
+          onRemove={() => setRows(rows.filter((_, i) => i !== index))}
+        />
+      ))}
+      <div className="flex gap-2">
+        <Button
+          type="button"
+          variant="outline"
+          size="sm"
+          onClick={() => setRows([...rows, { component_id: null, quantity: 1, unit_id: null }])}
+        >
+          <Plus className="h-4 w-4 mr-1" /> Bahan
+        </Button>
+        <Button type="button" size="sm" onClick={handleSave} disabled={isSaving}>
+          Simpan Resep
+        </Button>
+      </div>
+    </div>
+  );
+}
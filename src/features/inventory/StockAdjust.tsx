@@ -8,12 +8,16 @@ import {
 } from "../../components/ui/dialog";
 import { Button } from "../../components/ui/button";
 import { Label } from "../../components/ui/label";
-import { ProductWithCategory } from "../../types";
-import { useInvokeMutation } from "../../hooks/useInvokeQuery";
+import { Input } from "../../components/ui/input";
+import { ProductUnit, ProductWithCategory } from "../../types";
+import { useInvokeMutation, useInvokeQuery } from "../../hooks/useInvokeQuery";
 import { useAuthStore } from "../../store/authStore";
 import { useQueryClient } from "@tanstack/react-query";
 import { useToast } from "../../hooks/use-toast";
 import { NumericInput } from "../../components/NumericInput";
+import { ProductBatchList } from "./ProductBatchList";
+import { LocationStockList } from "./LocationStockList";
+import { LocationSelect } from "./LocationSelect";
 
 export function StockAdjust({
   open,
@@ -25,19 +29,39 @@ export function StockAdjust({
   product: ProductWithCategory | null;
 }) {
   const [deltaStr, setDeltaStr] = useState("");
+  const [unitId, setUnitId] = useState<number | null>(null);
+  const [batchNumber, setBatchNumber] = useState("");
+  const [expiryDate, setExpiryDate] = useState("");
+  const [batchCost, setBatchCost] = useState(0);
+  const [locationId, setLocationId] = useState<number | null>(null);
   const sessionToken = useAuthStore((s) => s.sessionToken);
+
+  const { data: units } = useInvokeQuery<ProductUnit[]>(
+    ["product-units", product?.id],
+    "get_product_units",
+    { sessionToken, productId: product?.id },
+    { enabled: !!product },
+  );
+  const conversion = units?.find((u) => u.id === unitId)?.conversion ?? 1;
   const queryClient = useQueryClient();
   const { toast } = useToast();
 
   const adjustMutation = useInvokeMutation<number>("adjust_stock", {
     onSuccess: (newStock) => {
       queryClient.invalidateQueries({ queryKey: ["products"] });
+      queryClient.invalidateQueries({ queryKey: ["product-batches"] });
+      queryClient.invalidateQueries({ queryKey: ["product-location-stock"] });
+      queryClient.invalidateQueries({ queryKey: ["location-stock"] });
       toast({
         title: "Stock Adjusted",
         description: `New stock is ${newStock}`,
       });
       onOpenChange(false);
       setDeltaStr("");
+      setUnitId(null);
+      setBatchNumber("");
+      setExpiryDate("");
+      setBatchCost(0);
     },
     onError: (e) =>
       toast({ variant: "destructive", title: "Error", description: String(e) }),
@@ -46,7 +70,23 @@ export function StockAdjust({
   const handleAdjust = () => {
     const delta = Number(deltaStr);
     if (!product || isNaN(delta) || delta === 0) return;
-    adjustMutation.mutate({ sessionToken, productId: product.id, delta });
+    // Data batch hanya untuk stok masuk
+    const batch =
+      delta > 0 && (batchNumber.trim() || expiryDate)
+        ? {
+            batch_number: batchNumber.trim() || null,
+            expiry_date: expiryDate || null,
+            cost_price: batchCost > 0 ? batchCost : null,
+          }
+        : null;
+    adjustMutation.mutate({
+      sessionToken,
+      productId: product.id,
+      delta,
+      unitId,
+      batch,
+      locationId,
+    });
   };
 
   const getStockColor = (stock: number) => {
@@ -75,6 +115,8 @@ export function StockAdjust({
               </span>
             </div>
 
+            <LocationSelect value={locationId} onChange={setLocationId} />
+
             <div className="space-y-2">
               <Label>Adjustment (+/-)</Label>
               <NumericInput
@@ -83,19 +125,65 @@ export function StockAdjust({
                 onChange={(val: number) => setDeltaStr(val.toString())}
                 allowNegative
               />
+              {units && units.length > 0 && (
+                <select
+                  value={unitId ?? ""}
+                  onChange={(e) => setUnitId(e.target.value ? Number(e.target.value) : null)}
+                  className="w-full h-9 rounded-md border border-input bg-transparent px-2 text-sm"
+                >
+                  <option value="">{product.unit || "pcs"} (satuan dasar)</option>
+                  {units.map((u) => (
+                    <option key={u.id} value={u.id}>
+                      {u.name} (isi {u.conversion} {product.unit || "pcs"})
+                    </option>
+                  ))}
+                </select>
+              )}
               <p className="text-[10px] text-muted-foreground pt-1 leading-tight uppercase font-bold">
                 Angka akan ditambahkan ke stok saat ini. Gunakan angka negatif untuk mengurangi.
               </p>
             </div>
 
+            {Number(deltaStr) > 0 && (
+              <div className="grid grid-cols-2 gap-2">
+                <div className="space-y-1">
+                  <Label className="text-xs">No. Batch</Label>
+                  <Input
+                    value={batchNumber}
+                    placeholder="Opsional"
+                    onChange={(e) => setBatchNumber(e.target.value)}
+                  />
+                </div>
+                <div className="space-y-1">
+                  <Label className="text-xs">Kedaluwarsa</Label>
+                  <Input
+                    type="date"
+                    value={expiryDate}
+                    onChange={(e) => setExpiryDate(e.target.value)}
+                  />
+                </div>
+                {(batchNumber.trim() || expiryDate) && (
+                  <div className="col-span-2 space-y-1">
+                    <Label className="text-xs">
+                      Modal per {product.unit || "pcs"} (kosong = modal produk)
+                    </Label>
+                    <NumericInput value={batchCost} onChange={setBatchCost} prefix="Rp" />
+                  </div>
+                )}
+              </div>
+            )}
+
             {deltaStr !== "" && deltaStr !== "0" && (
               <div className="p-3 bg-primary/5 rounded-lg border border-primary/10 text-center">
                 <span className="text-xs font-bold text-muted-foreground uppercase">Estimasi Stok Baru:</span>
-                <div className={`text-2xl font-black ${getStockColor(Math.max(0, product.stock + Number(deltaStr)))}`}>
-                  {Math.max(0, product.stock + Number(deltaStr))}
+                <div className={`text-2xl font-black ${getStockColor(Math.max(0, product.stock + Number(deltaStr) * conversion))}`}>
+                  {Math.max(0, product.stock + Number(deltaStr) * conversion)}
                 </div>
               </div>
             )}
+
+            <LocationStockList productId={product.id} unit={product.unit} />
+            <ProductBatchList productId={product.id} unit={product.unit} locationId={locationId} />
           </div>
         )}
 
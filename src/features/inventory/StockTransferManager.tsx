@@ -0,0 +1,386 @@
+import { useState } from "react";
+import {
+  Dialog,
+  DialogContent,
+  DialogHeader,
+  DialogTitle,
+} from "../../components/ui/dialog";
+import { Button } from "../../components/ui/button";
+import { Input } from "../../components/ui/input";
+import { Label } from "../../components/ui/label";
+import { Badge } from "../../components/ui/badge";
+import { ConfirmDialog } from "../../components/ConfirmDialog";
+import { useInvokeQuery, useInvokeMutation } from "../../hooks/useInvokeQuery";
+import {
+  Location,
+  LocationProductStock,
+  StockTransfer,
+  StockTransferDetail,
+  StockTransferStatus,
+} from "../../types";
+import { useAuthStore } from "../../store/authStore";
+import { useQueryClient } from "@tanstack/react-query";
+import { useToast } from "../../hooks/use-toast";
+import { format } from "date-fns";
+import { ArrowLeft, ArrowRight, PackageCheck, Plus, Send, Trash2, XCircle } from "lucide-react";
+
+const TRANSFER_STATUS_LABEL: Record<StockTransferStatus, string> = {
+  IN_TRANSIT: "Dalam Perjalanan",
+  RECEIVED: "Diterima",
+  CANCELLED: "Dibatalkan",
+};
+
+type Line = { product_id: number; quantity: number };
+
+const formatDate = (value: string | null) =>
+  value ? format(new Date(value.replace(" ", "T") + "Z"), "dd/MM/yy HH:mm") : "-";
+
+/**
+ * Transfer stok antar lokasi: kirim (stok asal langsung berkurang), lalu
+ * terima di lokasi tujuan atau batalkan selama masih dalam perjalanan.
+ * Batch/kedaluwarsa tidak ikut berpindah lokasi (FEFO tetap lintas lokasi).
+ */
+export function StockTransferManager({
+  open,
+  onOpenChange,
+}: {
+  open: boolean;
+  onOpenChange: (open: boolean) => void;
+}) {
+  const [statusFilter, setStatusFilter] = useState<StockTransferStatus | "">("");
+  // null = daftar, 0 = transfer baru, >0 = detail transfer
+  const [selectedId, setSelectedId] = useState<number | null>(null);
+  const [fromId, setFromId] = useState<number | null>(null);
+  const [toId, setToId] = useState<number | null>(null);
+  const [notes, setNotes] = useState("");
+  const [lines, setLines] = useState<Line[]>([]);
+  const [cancelOpen, setCancelOpen] = useState(false);
+
+  const sessionToken = useAuthStore((s) => s.sessionToken);
+  const queryClient = useQueryClient();
+  const { toast } = useToast();
+
+  const { data: transfers } = useInvokeQuery<StockTransfer[]>(
+    ["stock-transfers", statusFilter],
+    "get_stock_transfers",
+    { sessionToken, status: statusFilter || null },
+    { enabled: open && selectedId === null },
+  );
+  const { data: detail } = useInvokeQuery<StockTransferDetail>(
+    ["stock-transfer", selectedId],
+    "get_stock_transfer",
+    { sessionToken, id: selectedId },
+    { enabled: open && !!selectedId },
+  );
+  const { data: locations } = useInvokeQuery<Location[]>(
+    ["locations", false],
+    "get_locations",
+    { sessionToken, showInactive: false },
+    { enabled: open },
+  );
+  const { data: available } = useInvokeQuery<LocationProductStock[]>(
+    ["location-stock", fromId],
+    "get_location_stock",
+    { sessionToken, locationId: fromId },
+    { enabled: open && selectedId === 0 && !!fromId },
+  );
+
+  const onError = (e: unknown) =>
+    toast({ variant: "destructive", title: "Error", description: String(e) });
+
+  const onChanged = (message: string) => (t: StockTransferDetail) => {
+    queryClient.invalidateQueries({ queryKey: ["stock-transfers"] });
+    queryClient.invalidateQueries({ queryKey: ["location-stock"] });
+    queryClient.invalidateQueries({ queryKey: ["product-location-stock"] });
+    queryClient.setQueryData(["stock-transfer", t.id], t);
+    setSelectedId(t.id);
+    toast({ title: "Berhasil", description: `${t.transfer_number}: ${message}` });
+  };
+
+  const createMutation = useInvokeMutation<StockTransferDetail>("create_stock_transfer", {
+    onSuccess: onChanged("dikirim"),
+    onError,
+  });
+  const receiveMutation = useInvokeMutation<StockTransferDetail>("receive_stock_transfer", {
+    onSuccess: onChanged("diterima"),
+    onError,
+  });
+  const cancelMutation = useInvokeMutation<StockTransferDetail>("cancel_stock_transfer", {
+    onSuccess: onChanged("dibatalkan"),
+    onError,
+  });
+
+  const startNew = () => {
+    const defaultLocation = locations?.find((l) => l.is_default);
+    setFromId(defaultLocation?.id ?? null);
+    setToId(locations?.find((l) => !l.is_default)?.id ?? null);
+    setNotes("");
+    setLines([]);
+    setSelectedId(0);
+  };
+
+  const handleCreate = () => {
+    if (!fromId || !toId) return;
+    createMutation.mutate({
+      sessionToken,
+      payload: {
+        from_location_id: fromId,
+        to_location_id: toId,
+        notes: notes || null,
+        items: lines.filter((l) => l.product_id),
+      },
+    });
+  };
+
+  const updateLine = (index: number, patch: Partial<Line>) =>
+    setLines((ls) => ls.map((l, i) => (i === index ? { ...l, ...patch } : l)));
+
+  const locationSelect = (
+    value: number | null,
+    onChange: (id: number | null) => void,
+  ) => (
+    <select
+      value={value ?? ""}
+      onChange={(e) => onChange(e.target.value ? Number(e.target.value) : null)}
+      className="w-full h-9 rounded-md border border-input bg-transparent px-2 text-sm"
+    >
+      <option value="">Pilih...</option>
+      {locations?.map((l) => (
+        <option key={l.id} value={l.id}>
+          {l.name}
+        </option>
+      ))}
+    </select>
+  );
+
+  return (
+    <Dialog
+      open={open}
+      onOpenChange={(o) => {
+        if (!o) setSelectedId(null);
+        onOpenChange(o);
+      }}
+    >
+      <DialogContent className="sm:max-w-2xl">
+        <DialogHeader>
+          <DialogTitle>
+            {selectedId === null
+              ? "Transfer Stok"
+              : selectedId === 0
+                ? "Transfer Baru"
+                : `Transfer ${detail?.transfer_number ?? ""}`}
+          </DialogTitle>
+        </DialogHeader>
+
+        {selectedId === null ? (
+          <>
+            <div className="flex gap-2">
+              <select
+                value={statusFilter}
+                onChange={(e) => setStatusFilter(e.target.value as StockTransferStatus | "")}
+                className="h-9 rounded-md border border-input bg-transparent px-2 text-sm"
+              >
+                <option value="">Semua status</option>
+                {Object.entries(TRANSFER_STATUS_LABEL).map(([value, label]) => (
+                  <option key={value} value={value}>
+                    {label}
+                  </option>
+                ))}
+              </select>
+              <div className="flex-1" />
+              <Button onClick={startNew} disabled={(locations?.length ?? 0) < 2}>
+                <Plus className="mr-2 h-4 w-4" />
+                Buat Transfer
+              </Button>
+            </div>
+
+            <div className="max-h-[400px] overflow-y-auto space-y-2">
+              {transfers?.length === 0 && (
+                <p className="text-sm text-muted-foreground text-center py-4">Belum ada transfer.</p>
+              )}
+              {transfers?.map((t) => (
+                <div
+                  key={t.id}
+                  className="flex items-center gap-3 text-sm border-b pb-2 cursor-pointer hover:bg-muted/40"
+                  onClick={() => setSelectedId(t.id)}
+                >
+                  <div className="flex-1">
+                    <div className="font-medium">{t.transfer_number}</div>
+                    <div className="text-xs text-muted-foreground">
+                      {t.from_location_name} → {t.to_location_name} · {t.item_count} barang ·{" "}
+                      {formatDate(t.created_at)}
+                    </div>
+                  </div>
+                  <Badge variant={t.status === "IN_TRANSIT" ? "default" : "outline"}>
+                    {TRANSFER_STATUS_LABEL[t.status]}
+                  </Badge>
+                </div>
+              ))}
+            </div>
+          </>
+        ) : selectedId === 0 ? (
+          <>
+            <div className="grid grid-cols-[1fr_auto_1fr] items-end gap-3">
+              <div className="space-y-1">
+                <Label>Dari</Label>
+                {locationSelect(fromId, (id) => {
+                  setFromId(id);
+                  setLines([]);
+                })}
+              </div>
+              <ArrowRight className="h-4 w-4 mb-2.5 text-muted-foreground" />
+              <div className="space-y-1">
+                <Label>Ke</Label>
+                {locationSelect(toId, setToId)}
+              </div>
+              <Input
+                className="col-span-3"
+                placeholder="Catatan (mis. nama pengantar)"
+                value={notes}
+                onChange={(e) => setNotes(e.target.value)}
+              />
+            </div>
+
+            <div className="max-h-[300px] overflow-y-auto space-y-2">
+              {lines.map((line, index) => {
+                const stock = available?.find((p) => p.product_id === line.product_id);
+                return (
+                  <div key={index} className="flex items-center gap-2 text-sm">
+                    <select
+                      value={line.product_id || ""}
+                      onChange={(e) => updateLine(index, { product_id: Number(e.target.value) })}
+                      className="flex-1 h-9 rounded-md border border-input bg-transparent px-2 text-sm"
+                    >
+                      <option value="">Pilih produk...</option>
+                      {available?.map((p) => (
+                        <option key={p.product_id} value={p.product_id}>
+                          {p.name} (tersedia {p.quantity} {p.unit})
+                        </option>
+                      ))}
+                    </select>
+                    <Input
+                      type="number"
+                      min={0}
+                      step={stock?.is_weighed ? "0.001" : "1"}
+                      className="w-28"
+                      value={line.quantity}
+                      onChange={(e) => updateLine(index, { quantity: Number(e.target.value) })}
+                    />
+                    <span className="w-12 text-xs text-muted-foreground">{stock?.unit}</span>
+                    <Button
+                      variant="ghost"
+                      size="icon"
+                      className="h-7 w-7 text-destructive"
+                      onClick={() => setLines((ls) => ls.filter((_, i) => i !== index))}
+                    >
+                      <Trash2 className="h-3 w-3" />
+                    </Button>
+                  </div>
+                );
+              })}
+              <Button
+                variant="outline"
+                size="sm"
+                disabled={!fromId}
+                onClick={() => setLines((ls) => [...ls, { product_id: 0, quantity: 1 }])}
+              >
+                <Plus className="mr-2 h-3 w-3" />
+                Tambah Barang
+              </Button>
+            </div>
+
+            <div className="flex items-center gap-2 border-t pt-3">
+              <Button variant="ghost" onClick={() => setSelectedId(null)}>
+                <ArrowLeft className="mr-2 h-4 w-4" />
+                Kembali
+              </Button>
+              <div className="flex-1" />
+              <Button
+                onClick={handleCreate}
+                disabled={
+                  !fromId ||
+                  !toId ||
+                  fromId === toId ||
+                  !lines.some((l) => l.product_id) ||
+                  createMutation.isPending
+                }
+              >
+                <Send className="mr-2 h-4 w-4" />
+                Kirim
+              </Button>
+            </div>
+          </>
+        ) : (
+          <>
+            <div className="text-sm space-y-1">
+              <div className="flex items-center gap-2 font-medium">
+                {detail?.from_location_name} <ArrowRight className="h-4 w-4" />{" "}
+                {detail?.to_location_name}
+                {detail && (
+                  <Badge variant={detail.status === "IN_TRANSIT" ? "default" : "outline"}>
+                    {TRANSFER_STATUS_LABEL[detail.status]}
+                  </Badge>
+                )}
+              </div>
+              <div className="text-xs text-muted-foreground">
+                Dikirim {formatDate(detail?.created_at ?? null)} oleh {detail?.created_by_name ?? "-"}
+                {detail?.closed_at &&
+                  ` · ${detail.status === "RECEIVED" ? "diterima" : "dibatalkan"} ${formatDate(
+                    detail.closed_at,
+                  )} oleh ${detail.closed_by_name ?? "-"}`}
+              </div>
+              {detail?.notes && <div className="text-xs">{detail.notes}</div>}
+            </div>
+
+            <div className="max-h-[300px] overflow-y-auto space-y-1">
+              {detail?.items.map((i) => (
+                <div key={i.id} className="flex justify-between text-sm border-b pb-1">
+                  <span>{i.product_name}</span>
+                  <span className="font-bold">
+                    {i.quantity} {i.unit}
+                  </span>
+                </div>
+              ))}
+            </div>
+
+            <div className="flex items-center gap-2 border-t pt-3">
+              <Button variant="ghost" onClick={() => setSelectedId(null)}>
+                <ArrowLeft className="mr-2 h-4 w-4" />
+                Kembali
+              </Button>
+              <div className="flex-1" />
+              {detail?.status === "IN_TRANSIT" && (
+                <>
+                  <Button variant="outline" className="text-destructive" onClick={() => setCancelOpen(true)}>
+                    <XCircle className="mr-2 h-4 w-4" />
+                    Batalkan
+                  </Button>
+                  <Button
+                    disabled={receiveMutation.isPending}
+                    onClick={() => receiveMutation.mutate({ sessionToken, id: detail.id })}
+                  >
+                    <PackageCheck className="mr-2 h-4 w-4" />
+                    Terima di {detail.to_location_name}
+                  </Button>
+                </>
+              )}
+            </div>
+          </>
+        )}
+
+        <ConfirmDialog
+          open={cancelOpen}
+          onOpenChange={setCancelOpen}
+          title="Batalkan Transfer"
+          description={`Batalkan transfer ${detail?.transfer_number ?? ""}? Stok kembali ke ${
+            detail?.from_location_name ?? "lokasi asal"
+          }.`}
+          onConfirm={() => detail && cancelMutation.mutate({ sessionToken, id: detail.id })}
+          confirmLabel="Batalkan Transfer"
+          cancelLabel="Kembali"
+          isDangerous
+        />
+      </DialogContent>
+    </Dialog>
+  );
+}
@@ -0,0 +1,182 @@
+import { useState } from "react";
+import {
+  Dialog,
+  DialogContent,
+  DialogHeader,
+  DialogTitle,
+} from "../../components/ui/dialog";
+import { Button } from "../../components/ui/button";
+import { Input } from "../../components/ui/input";
+import { Label } from "../../components/ui/label";
+import { Badge } from "../../components/ui/badge";
+import { useInvokeQuery, useInvokeMutation } from "../../hooks/useInvokeQuery";
+import { Supplier } from "../../types";
+import { useAuthStore } from "../../store/authStore";
+import { useQueryClient } from "@tanstack/react-query";
+import { useToast } from "../../hooks/use-toast";
+import { Pencil } from "lucide-react";
+
+const emptyForm = {
+  name: "",
+  contact_person: "",
+  phone: "",
+  email: "",
+  address: "",
+  notes: "",
+};
+
+/** Kelola data supplier (tambah, ubah, aktif/nonaktif). */
+export function SupplierManager({
+  open,
+  onOpenChange,
+}: {
+  open: boolean;
+  onOpenChange: (open: boolean) => void;
+}) {
+  const [form, setForm] = useState(emptyForm);
+  const [editingId, setEditingId] = useState<number | null>(null);
+
+  const sessionToken = useAuthStore((s) => s.sessionToken);
+  const queryClient = useQueryClient();
+  const { toast } = useToast();
+
+  const { data: suppliers } = useInvokeQuery<Supplier[]>(
+    ["suppliers", true],
+    "get_suppliers",
+    { sessionToken, showInactive: true },
+    { enabled: open },
+  );
+
+  const onError = (e: unknown) =>
+    toast({ variant: "destructive", title: "Error", description: String(e) });
+
+  const onSaved = () => {
+    queryClient.invalidateQueries({ queryKey: ["suppliers"] });
+    setForm(emptyForm);
+    setEditingId(null);
+    toast({ title: "Berhasil", description: "Supplier disimpan" });
+  };
+
+  const createMutation = useInvokeMutation<Supplier>("create_supplier", { onSuccess: onSaved, onError });
+  const updateMutation = useInvokeMutation<Supplier>("update_supplier", { onSuccess: onSaved, onError });
+  const toggleMutation = useInvokeMutation<boolean>("toggle_supplier", {
+    onSuccess: () => queryClient.invalidateQueries({ queryKey: ["suppliers"] }),
+    onError,
+  });
+
+  const set = (key: keyof typeof emptyForm) => (e: React.ChangeEvent<HTMLInputElement>) =>
+    setForm((f) => ({ ...f, [key]: e.target.value }));
+
+  const handleSave = () => {
+    const payload = {
+      name: form.name,
+      contact_person: form.contact_person || null,
+      phone: form.phone || null,
+      email: form.email || null,
+      address: form.address || null,
+      notes: form.notes || null,
+    };
+    if (editingId) {
+      updateMutation.mutate({ sessionToken, id: editingId, payload });
+    } else {
+      createMutation.mutate({ sessionToken, payload });
+    }
+  };
+
+  const handleEdit = (s: Supplier) => {
+    setEditingId(s.id);
+    setForm({
+      name: s.name,
+      contact_person: s.contact_person ?? "",
+      phone: s.phone ?? "",
+      email: s.email ?? "",
+      address: s.address ?? "",
+      notes: s.notes ?? "",
+    });
+  };
+
+  const isSaving = createMutation.isPending || updateMutation.isPending;
+
+  return (
+    <Dialog open={open} onOpenChange={onOpenChange}>
+      <DialogContent className="sm:max-w-2xl">
+        <DialogHeader>
+          <DialogTitle>Supplier</DialogTitle>
+        </DialogHeader>
+
+        <div className="grid grid-cols-2 gap-3 border rounded-md p-3">
+          <div className="space-y-1">
+            <Label>Nama *</Label>
+            <Input value={form.name} onChange={set("name")} />
+          </div>
+          <div className="space-y-1">
+            <Label>Kontak</Label>
+            <Input value={form.contact_person} onChange={set("contact_person")} />
+          </div>
+          <div className="space-y-1">
+            <Label>Telepon</Label>
+            <Input value={form.phone} onChange={set("phone")} />
+          </div>
+          <div className="space-y-1">
+            <Label>Email</Label>
+            <Input type="email" value={form.email} onChange={set("email")} />
+          </div>
+          <Input
+            className="col-span-2"
+            placeholder="Alamat"
+            value={form.address}
+            onChange={set("address")}
+          />
+          <Input
+            className="col-span-2"
+            placeholder="Catatan (mis. termin pembayaran)"
+            value={form.notes}
+            onChange={set("notes")}
+          />
+          <div className="col-span-2 flex gap-2">
+            {editingId && (
+              <Button
+                variant="outline"
+                onClick={() => {
+                  setEditingId(null);
+                  setForm(emptyForm);
+                }}
+              >
+                Batal
+              </Button>
+            )}
+            <Button className="flex-1" onClick={handleSave} disabled={!form.name.trim() || isSaving}>
+              {editingId ? "Simpan Perubahan" : "Tambah Supplier"}
+            </Button>
+          </div>
+        </div>
+
+        <div className="max-h-[300px] overflow-y-auto space-y-2">
+          {suppliers?.length === 0 && (
+            <p className="text-sm text-muted-foreground text-center py-4">Belum ada supplier.</p>
+          )}
+          {suppliers?.map((s) => (
+            <div key={s.id} className="flex items-center gap-3 text-sm border-b pb-2">
+              <div className="flex-1">
+                <div className="font-medium">{s.name}</div>
+                <div className="text-xs text-muted-foreground">
+                  {[s.contact_person, s.phone, s.email].filter(Boolean).join(" · ") || "-"}
+                </div>
+              </div>
+              <Badge
+                variant={s.is_active ? "default" : "outline"}
+                className="cursor-pointer"
+                onClick={() => toggleMutation.mutate({ sessionToken, id: s.id })}
+              >
+                {s.is_active ? "Aktif" : "Nonaktif"}
+              </Badge>
+              <Button variant="ghost" size="icon" className="h-7 w-7" onClick={() => handleEdit(s)}>
+                <Pencil className="h-3 w-3" />
+              </Button>
+            </div>
+          ))}
+        </div>
+      </DialogContent>
+    </Dialog>
+  );
+}
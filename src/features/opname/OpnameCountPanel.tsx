@@ -0,0 +1,146 @@
+import { useRef, useState } from "react";
+import { OpnameCount, OpnameSession } from "../../types";
+import { useInvokeQuery, useInvokeMutation } from "../../hooks/useInvokeQuery";
+import { useAuthStore } from "../../store/authStore";
+import { useQueryClient } from "@tanstack/react-query";
+import { useToast } from "../../hooks/use-toast";
+import { Card, CardHeader, CardTitle, CardContent } from "../../components/ui/card";
+import { Input } from "../../components/ui/input";
+import { Label } from "../../components/ui/label";
+import { Button } from "../../components/ui/button";
+import { format } from "date-fns";
+import { ScanBarcode, Trash2 } from "lucide-react";
+
+/**
+ * Input hitung fisik: scan barcode (qty default 1, barcode kemasan = isi
+ * kemasan). Kasir hanya melihat entri miliknya, tanpa stok sistem.
+ */
+export function OpnameCountPanel({ session }: { session: OpnameSession }) {
+  const [barcode, setBarcode] = useState("");
+  const [quantity, setQuantity] = useState("");
+  const barcodeRef = useRef<HTMLInputElement>(null);
+
+  const sessionToken = useAuthStore((s) => s.sessionToken);
+  const queryClient = useQueryClient();
+  const { toast } = useToast();
+
+  const { data: counts } = useInvokeQuery<OpnameCount[]>(
+    ["opname-counts", session.id],
+    "get_opname_counts",
+    { sessionToken, sessionId: session.id },
+  );
+
+  const invalidate = () => {
+    queryClient.invalidateQueries({ queryKey: ["opname-counts"] });
+    queryClient.invalidateQueries({ queryKey: ["opname-session"] });
+    queryClient.invalidateQueries({ queryKey: ["opname-sessions"] });
+  };
+
+  const submitMutation = useInvokeMutation<OpnameCount>("submit_opname_count", {
+    onSuccess: (c) => {
+      invalidate();
+      setBarcode("");
+      setQuantity("");
+      toast({ title: c.product_name, description: `+${c.quantity} ${c.unit}` });
+      barcodeRef.current?.focus();
+    },
+    onError: (e) => {
+      setBarcode("");
+      toast({ variant: "destructive", title: "Gagal", description: String(e) });
+      barcodeRef.current?.focus();
+    },
+  });
+
+  const deleteMutation = useInvokeMutation<void>("delete_opname_count", {
+    onSuccess: invalidate,
+    onError: (e) => toast({ variant: "destructive", title: "Gagal", description: String(e) }),
+  });
+
+  const handleSubmit = (e: React.FormEvent) => {
+    e.preventDefault();
+    if (!barcode.trim()) return;
+    submitMutation.mutate({
+      sessionToken,
+      payload: {
+        session_id: session.id,
+        barcode: barcode.trim(),
+        product_id: null,
+        quantity: quantity === "" ? null : Number(quantity),
+      },
+    });
+  };
+
+  return (
+    <Card>
+      <CardHeader className="py-4 border-b">
+        <CardTitle className="text-lg flex items-center gap-2">
+          <ScanBarcode className="h-5 w-5" /> Hitung: {session.name}
+          {session.location_name && (
+            <span className="text-sm font-normal text-muted-foreground">· {session.location_name}</span>
+          )}
+        </CardTitle>
+      </CardHeader>
+      <CardContent className="p-4 space-y-4">
+        <form onSubmit={handleSubmit} className="flex items-end gap-2">
+          <div className="flex-1 space-y-1">
+            <Label>Barcode / SKU</Label>
+            <Input
+              ref={barcodeRef}
+              autoFocus
+              value={barcode}
+              onChange={(e) => setBarcode(e.target.value)}
+              placeholder="Scan barcode..."
+            />
+          </div>
+          <div className="w-28 space-y-1">
+            <Label>Qty</Label>
+            <Input
+              type="number"
+              min={0}
+              step="0.001"
+              placeholder="1"
+              value={quantity}
+              onChange={(e) => setQuantity(e.target.value)}
+            />
+          </div>
+          <Button type="submit" disabled={!barcode.trim() || submitMutation.isPending}>
+            Catat
+          </Button>
+        </form>
+
+        <div className="max-h-[400px] overflow-y-auto space-y-1">
+          {counts?.length === 0 && (
+            <p className="text-sm text-muted-foreground text-center py-4">Belum ada entri hitung.</p>
+          )}
+          {counts?.map((c) => (
+            <div key={c.id} className="flex items-center gap-2 text-sm border-b pb-1">
+              <span className="flex-1">
+                {c.product_name}
+                <span className="text-xs text-muted-foreground">
+                  {" · "}
+                  {c.counted_at
+                    ? format(new Date(c.counted_at.replace(" ", "T") + "Z"), "HH:mm:ss")
+                    : "-"}
+                  {c.counted_by_name && ` · ${c.counted_by_name}`}
+                </span>
+              </span>
+              <span className="font-bold">
+                {c.quantity} {c.unit}
+              </span>
+              <Button
+                variant="ghost"
+                size="icon"
+                className="h-7 w-7 text-destructive"
+                title="Hapus entri"
+                disabled={deleteMutation.isPending}
+                onClick={() => deleteMutation.mutate({ sessionToken, countId: c.id })}
+              >
+                <Trash2 className="h-3 w-3" />
+              </Button>
+            </div>
+          ))}
+        </div>
+      </CardContent>
+    </Card>
+  );
+}
@@ -0,0 +1,180 @@
+import { useState } from "react";
+import { OpnameSessionDetail } from "../../types";
+import { useInvokeQuery, useInvokeMutation } from "../../hooks/useInvokeQuery";
+import { useAuthStore } from "../../store/authStore";
+import { useQueryClient } from "@tanstack/react-query";
+import { useToast } from "../../hooks/use-toast";
+import { formatRupiah } from "../../lib/currency";
+import {
+  Table,
+  TableBody,
+  TableCell,
+  TableHead,
+  TableHeader,
+  TableRow,
+} from "../../components/ui/table";
+import { Card, CardHeader, CardTitle, CardContent } from "../../components/ui/card";
+import { Button } from "../../components/ui/button";
+import { Badge } from "../../components/ui/badge";
+import { Checkbox } from "../../components/ui/checkbox";
+import { ConfirmDialog } from "../../components/ConfirmDialog";
+
+/** Selisih per produk (fisik vs snapshot) dan persetujuan sesi (Admin). */
+export function OpnameReview({ sessionId }: { sessionId: number }) {
+  const [showUncounted, setShowUncounted] = useState(false);
+  const [uncountedAsZero, setUncountedAsZero] = useState(false);
+  const [approveOpen, setApproveOpen] = useState(false);
+  const [cancelOpen, setCancelOpen] = useState(false);
+
+  const sessionToken = useAuthStore((s) => s.sessionToken);
+  const queryClient = useQueryClient();
+  const { toast } = useToast();
+
+  const { data: detail, isLoading } = useInvokeQuery<OpnameSessionDetail>(
+    ["opname-session", sessionId],
+    "get_opname_session",
+    { sessionToken, id: sessionId },
+  );
+
+  const onDone = (message: string) => () => {
+    queryClient.invalidateQueries({ queryKey: ["opname-sessions"] });
+    queryClient.invalidateQueries({ queryKey: ["opname-session"] });
+    queryClient.invalidateQueries({ queryKey: ["products"] });
+    toast({ title: "Berhasil", description: message });
+  };
+  const onError = (e: unknown) =>
+    toast({ variant: "destructive", title: "Gagal", description: String(e) });
+
+  const approveMutation = useInvokeMutation<OpnameSessionDetail>("approve_opname_session", {
+    onSuccess: onDone("Selisih stok diposting"),
+    onError,
+  });
+  const cancelMutation = useInvokeMutation<void>("cancel_opname_session", {
+    onSuccess: onDone("Sesi dibatalkan"),
+    onError,
+  });
+
+  const items = (detail?.items ?? []).filter((i) => showUncounted || i.counted !== null);
+  const isOpen = detail?.status === "OPEN";
+
+  return (
+    <Card className="flex-1 overflow-hidden flex flex-col">
+      <CardHeader className="py-4 border-b flex flex-row items-center justify-between space-y-0">
+        <div>
+          <CardTitle className="text-lg">Selisih: {detail?.name}</CardTitle>
+          <p className="text-xs text-muted-foreground">
+            {detail?.counted_count ?? 0}/{detail?.item_count ?? 0} produk dihitung · selisih nilai{" "}
+            <span
+              className={
+                (detail?.total_variance_value ?? 0) < 0 ? "text-destructive font-bold" : "font-bold"
+              }
+            >
+              {formatRupiah(detail?.total_variance_value ?? 0)}
+            </span>
+          </p>
+        </div>
+        <div className="flex items-center gap-3">
+          <label className="flex items-center gap-2 text-xs">
+            <Checkbox checked={showUncounted} onCheckedChange={(v) => setShowUncounted(v === true)} />
+            Tampilkan yang belum dihitung
+          </label>
+          {isOpen && (
+            <>
+              <label className="flex items-center gap-2 text-xs">
+                <Checkbox
+                  checked={uncountedAsZero}
+                  onCheckedChange={(v) => setUncountedAsZero(v === true)}
+                />
+                Belum dihitung = 0
+              </label>
+              <Button variant="outline" className="text-destructive" onClick={() => setCancelOpen(true)}>
+                Batalkan
+              </Button>
+              <Button onClick={() => setApproveOpen(true)} disabled={approveMutation.isPending}>
+                Setujui & Posting
+              </Button>
+            </>
+          )}
+          {detail && !isOpen && <Badge variant="secondary">{detail.status}</Badge>}
+        </div>
+      </CardHeader>
+      <CardContent className="flex-1 p-0 overflow-auto">
+        <Table>
+          <TableHeader className="bg-slate-50 dark:bg-slate-900/50 sticky top-0 z-10">
+            <TableRow>
+              <TableHead className="font-bold text-[10px] uppercase">Produk</TableHead>
+              <TableHead className="font-bold text-[10px] uppercase text-right">Sistem</TableHead>
+              <TableHead className="font-bold text-[10px] uppercase text-right">Fisik</TableHead>
+              <TableHead className="font-bold text-[10px] uppercase text-right">Selisih</TableHead>
+              <TableHead className="font-bold text-[10px] uppercase text-right">Nilai</TableHead>
+              <TableHead className="font-bold text-[10px] uppercase">Penghitung</TableHead>
+            </TableRow>
+          </TableHeader>
+          <TableBody>
+            {isLoading ? (
+              <TableRow>
+                <TableCell colSpan={6} className="h-12 animate-pulse bg-muted/20" />
+              </TableRow>
+            ) : items.length === 0 ? (
+              <TableRow>
+                <TableCell colSpan={6} className="h-24 text-center text-muted-foreground italic">
+                  Belum ada produk yang dihitung.
+                </TableCell>
+              </TableRow>
+            ) : (
+              items.map((i) => (
+                <TableRow key={i.product_id}>
+                  <TableCell className="text-xs font-bold">
+                    {i.product_name}
+                    {i.sku && <span className="text-muted-foreground font-normal"> · {i.sku}</span>}
+                  </TableCell>
+                  <TableCell className="text-right text-xs">
+                    {i.system_stock} {i.unit}
+                  </TableCell>
+                  <TableCell className="text-right text-xs">{i.counted ?? "-"}</TableCell>
+                  <TableCell
+                    className={`text-right text-xs font-bold ${
+                      (i.variance ?? 0) < 0 ? "text-destructive" : (i.variance ?? 0) > 0 ? "text-green-600" : ""
+                    }`}
+                  >
+                    {i.variance === null ? "-" : `${i.variance > 0 ? "+" : ""}${i.variance}`}
+                  </TableCell>
+                  <TableCell className="text-right text-xs">
+                    {i.variance_value === null ? "-" : formatRupiah(i.variance_value)}
+                  </TableCell>
+                  <TableCell className="text-xs text-muted-foreground">{i.counted_by ?? "-"}</TableCell>
+                </TableRow>
+              ))
+            )}
+          </TableBody>
+        </Table>
+      </CardContent>
+
+      <ConfirmDialog
+        open={approveOpen}
+        onOpenChange={setApproveOpen}
+        title="Setujui Stock Opname"
+        description={`Selisih fisik vs sistem akan diposting sebagai penyesuaian stok (OPNAME). ${
+          uncountedAsZero
+            ? "Produk yang belum dihitung dianggap stok 0."
+            : "Produk yang belum dihitung tidak diubah."
+        } Sesi tidak bisa diubah lagi.`}
+        onConfirm={() =>
+          approveMutation.mutate({ sessionToken, sessionId, uncountedAsZero })
+        }
+        confirmLabel="Setujui"
+        cancelLabel="Batal"
+      />
+      <ConfirmDialog
+        open={cancelOpen}
+        onOpenChange={setCancelOpen}
+        title="Batalkan Stock Opname"
+        description={`Batalkan sesi '${detail?.name ?? ""}'? Hasil hitung tidak akan diposting.`}
+        onConfirm={() => cancelMutation.mutate({ sessionToken, sessionId })}
+        confirmLabel="Batalkan Sesi"
+        cancelLabel="Kembali"
+        isDangerous
+      />
+    </Card>
+  );
+}
@@ -1,10 +1,12 @@
 import { useCartStore } from "../../store/cartStore";
-import { formatRupiah } from "../../lib/currency";
+import { formatRupiah, formatQuantity } from "../../lib/currency";
 import { Button } from "../../components/ui/button";
 import { ScrollArea } from "../../components/ui/scroll-area";
 import { Trash2, Plus, Minus, Tag, Banknote, ShoppingCart, Info, Percent } from "lucide-react";
 import { Separator } from "../../components/ui/separator";
 import { useState } from "react";
+import { CartUnitSelect } from "./CartUnitSelect";
+import { CustomerSelect } from "./CustomerSelect";
 
 export function CartPanel({
   onCheckout,
@@ -17,6 +19,8 @@ export function CartPanel({
     items,
     updateQuantity,
     setQuantity,
+    setItemNotes,
+    setItemPriceOverride,
     removeItem,
     clearCart,
     getSubtotal,
@@ -32,6 +36,7 @@ export function CartPanel({
   } = useCartStore();
 
   const [editingQty, setEditingQty] = useState<number | null>(null);
+  const [editingPrice, setEditingPrice] = useState<number | null>(null);
 
   const subtotal = getSubtotal();
   const discountTotal = getDiscountAmount();
@@ -72,6 +77,10 @@ export function CartPanel({
         </Button>
       </div>
 
+      <div className="px-5 pt-3">
+        <CustomerSelect />
+      </div>
+
       <ScrollArea className="flex-1 px-5 pt-4">
         {items.length === 0 ? (
           <div className="flex flex-col items-center justify-center h-full text-slate-400 py-16 text-center space-y-4">
@@ -96,8 +105,53 @@ export function CartPanel({
                       {item.product_name}
                     </span>
                     <span className="text-[11px] font-bold text-slate-500 dark:text-slate-400 uppercase tracking-wider">
-                      {formatRupiah(item.price)} x {item.quantity}
+                      {editingPrice === item.product_id ? (
+                        <input
+                          autoFocus
+                          type="number"
+                          min="0"
+                          defaultValue={item.price}
+                          className="w-24 text-xs font-bold bg-transparent border-0 border-b border-primary focus:outline-none"
+                          onBlur={(e) => {
+                            const val = parseFloat(e.target.value);
+                            if (val >= 0 && val !== item.price) setItemPriceOverride(item.product_id, val);
+                            setEditingPrice(null);
+                          }}
+                          onKeyDown={(e) => {
+                            if (e.key === "Enter") e.currentTarget.blur();
+                            if (e.key === "Escape") setEditingPrice(null);
+                          }}
+                        />
+                      ) : (
+                        <button
+                          onClick={() => setEditingPrice(item.product_id)}
+                          className="hover:text-primary"
+                          title="Klik untuk ubah harga (butuh persetujuan admin)"
+                        >
+                          {formatRupiah(item.price)}
+                        </button>
+                      )}{" "}
+                      x {formatQuantity(item.quantity)}
+                      {item.unit_name ? ` ${item.unit_name}` : item.is_weighed && " kg"}
                     </span>
+                    {item.price_override != null && (
+                      <span className="flex items-center gap-2 text-[10px] font-bold text-amber-600">
+                        Harga manual
+                        <button
+                          onClick={() => setItemPriceOverride(item.product_id, null)}
+                          className="underline text-slate-500 hover:text-primary"
+                        >
+                          Reset
+                        </button>
+                      </span>
+                    )}
+                    <CartUnitSelect item={item} />
+                    <input
+                      value={item.notes || ""}
+                      onChange={(e) => setItemNotes(item.product_id, e.target.value)}
+                      placeholder="Catatan (mis. tanpa es)"
+                      className="block w-full text-xs bg-transparent border-0 border-b border-dashed border-slate-200 dark:border-slate-700 focus:outline-none focus:border-primary placeholder:text-slate-400"
+                    />
                   </div>
                   <div className="flex flex-col items-end">
                     <span className="font-black text-slate-900 dark:text-white">
@@ -112,7 +166,7 @@ export function CartPanel({
                       variant="ghost"
                       size="icon"
                       className="h-7 w-7 rounded-md hover:bg-white dark:hover:bg-slate-700 hover:shadow-sm"
-                      onClick={() => updateQuantity(item.product_id, -1)}
+                      onClick={() => updateQuantity(item.product_id, item.is_weighed ? -0.1 : -1)}
                     >
                       <Minus className="h-3 w-3" />
                     </Button>
@@ -121,12 +175,13 @@ export function CartPanel({
                       <input
                         autoFocus
                         type="number"
-                        min="1"
+                        min={item.is_weighed ? "0.001" : "1"}
+                        step={item.is_weighed ? "0.001" : "1"}
                         defaultValue={item.quantity}
                         className="w-20 h-7 text-center text-xs font-black bg-transparent border-0 focus:outline-none focus:ring-1 focus:ring-primary [appearance:textfield] [&::-webkit-outer-spin-button]:appearance-none [&::-webkit-inner-spin-button]:appearance-none"
                         onBlur={(e) => {
-                          const val = parseInt(e.target.value) || 1;
-                          setQuantity(item.product_id, Math.max(1, val));
+                          const val = parseFloat(e.target.value);
+                          if (val > 0) setQuantity(item.product_id, val);
                           setEditingQty(null);
                         }}
                         onKeyDown={(e) => {
@@ -144,7 +199,7 @@ export function CartPanel({
                         className="w-12 text-center text-xs font-black text-slate-900 dark:text-white hover:bg-white dark:hover:bg-slate-700 rounded-md transition-colors"
                         title="Klik untuk edit quantity"
                       >
-                        {item.quantity}
+                        {formatQuantity(item.quantity)}
                       </button>
                     )}
                     
@@ -152,7 +207,7 @@ export function CartPanel({
                       variant="ghost"
                       size="icon"
                       className="h-7 w-7 rounded-md hover:bg-white dark:hover:bg-slate-700 hover:shadow-sm"
-                      onClick={() => updateQuantity(item.product_id, 1)}
+                      onClick={() => updateQuantity(item.product_id, item.is_weighed ? 0.1 : 1)}
                     >
                       <Plus className="h-3 w-3" />
                     </Button>
@@ -0,0 +1,38 @@
+import { useInvokeQuery } from "../../hooks/useInvokeQuery";
+import { useAuthStore } from "../../store/authStore";
+import { CartItem, useCartStore } from "../../store/cartStore";
+import { ProductUnit } from "../../types";
+
+const BASE_UNIT = "BASE";
+
+/** Pilihan satuan jual (pcs/pack/box) untuk satu baris keranjang. */
+export function CartUnitSelect({ item }: { item: CartItem }) {
+  const sessionToken = useAuthStore((s) => s.sessionToken);
+  const setItemUnit = useCartStore((s) => s.setItemUnit);
+
+  const { data: units } = useInvokeQuery<ProductUnit[]>(
+    ["product-units", item.product_id],
+    "get_product_units",
+    { sessionToken, productId: item.product_id },
+  );
+
+  if (!units || units.length === 0) return null;
+
+  return (
+    <select
+      value={item.unit_id ? String(item.unit_id) : BASE_UNIT}
+      onChange={(e) => {
+        const unit = units.find((u) => String(u.id) === e.target.value);
+        setItemUnit(item.product_id, unit ?? null);
+      }}
+      className="text-xs bg-transparent border border-slate-200 dark:border-slate-700 rounded px-1 py-0.5 focus:outline-none focus:border-primary"
+    >
+      <option value={BASE_UNIT}>Satuan dasar</option>
+      {units.map((u) => (
+        <option key={u.id} value={String(u.id)}>
+          {u.name} (isi {u.conversion})
+        </option>
+      ))}
+    </select>
+  );
+}
@@ -0,0 +1,105 @@
+import { useState } from "react";
+import { useQueryClient } from "@tanstack/react-query";
+import { Customer } from "../../types";
+import { useInvokeQuery } from "../../hooks/useInvokeQuery";
+import { useAuthStore } from "../../store/authStore";
+import { useCartStore } from "../../store/cartStore";
+import { invoke } from "../../lib/tauri";
+import { useToast } from "../../hooks/use-toast";
+import { Button } from "../../components/ui/button";
+import { Input } from "../../components/ui/input";
+import { UserPlus, Check, X } from "lucide-react";
+
+/**
+ * Pilih pelanggan transaksi. Level harga pelanggan menentukan harga item
+ * yang dihitung server.
+ */
+export function CustomerSelect() {
+  const sessionToken = useAuthStore((s) => s.sessionToken);
+  const { customer_id, setCustomer } = useCartStore();
+  const queryClient = useQueryClient();
+  const { toast } = useToast();
+  const [adding, setAdding] = useState(false);
+  const [name, setName] = useState("");
+  const [phone, setPhone] = useState("");
+
+  const { data: customers } = useInvokeQuery<Customer[]>(
+    ["customers"],
+    "get_customers",
+    { sessionToken },
+  );
+
+  const handleAdd = async () => {
+    if (!name.trim()) return;
+    try {
+      const customer = await invoke<Customer>("create_customer", {
+        sessionToken,
+        payload: { name, phone: phone || null, address: null, price_level_id: null },
+      });
+      queryClient.invalidateQueries({ queryKey: ["customers"] });
+      setCustomer(customer);
+      setAdding(false);
+      setName("");
+      setPhone("");
+    } catch (error) {
+      toast({ variant: "destructive", title: "Gagal", description: String(error) });
+    }
+  };
+
+  if (adding) {
+    return (
+      <div className="flex gap-2">
+        <Input
+          autoFocus
+          placeholder="Nama pelanggan"
+          value={name}
+          onChange={(e) => setName(e.target.value)}
+          className="h-8 text-xs"
+        />
+        <Input
+          placeholder="No. HP"
+          value={phone}
+          onChange={(e) => setPhone(e.target.value)}
+          className="h-8 text-xs w-28"
+        />
+        <Button size="icon" variant="ghost" className="h-8 w-8" onClick={handleAdd}>
+          <Check className="h-4 w-4" />
+        </Button>
+        <Button size="icon" variant="ghost" className="h-8 w-8" onClick={() => setAdding(false)}>
+          <X className="h-4 w-4" />
+        </Button>
+      </div>
+    );
+  }
+
+  return (
+    <div className="flex gap-2">
+      <select
+        value={customer_id ?? ""}
+        onChange={(e) => {
+          const c = customers?.find((c) => c.id === Number(e.target.value));
+          setCustomer(c ?? null);
+        }}
+        className="flex-1 h-8 rounded-md border border-input bg-transparent px-2 text-xs"
+      >
+        <option value="">Pelanggan umum (harga eceran)</option>
+        {customers?.map((c) => (
+          <option key={c.id} value={c.id}>
+            {c.name}
+            {c.phone ? ` · ${c.phone}` : ""}
+            {c.price_level_name ? ` — ${c.price_level_name}` : ""}
+          </option>
+        ))}
+      </select>
+      <Button
+        size="icon"
+        variant="ghost"
+        className="h-8 w-8"
+        title="Pelanggan baru"
+        onClick={() => setAdding(true)}
+      >
+        <UserPlus className="h-4 w-4" />
+      </Button>
+    </div>
+  );
+}
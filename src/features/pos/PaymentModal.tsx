@@ -20,6 +20,7 @@ import { useAuthStore } from "../../store/authStore";
 import { useToast } from "../../hooks/use-toast";
 import { Banknote, CreditCard, QrCode } from "lucide-react";
 import { NumericInput } from "../../components/NumericInput";
+import { Input } from "../../components/ui/input";
 import { QRISModal } from "./QRISModal";
 
 export function PaymentModal({
@@ -38,9 +39,30 @@ export function PaymentModal({
 
   const { toast } = useToast();
   const sessionToken = useAuthStore((s) => s.sessionToken);
-  const { items, getTotal, discount_id, getDiscountAmount, clearCart } =
+  const isAdmin = useAuthStore((s) => s.isAdmin)();
+  const { items, getTotal, discount_id, getDiscountAmount, clearCart, customer_id, hasPriceOverride } =
     useCartStore();
 
+  // Harga manual oleh kasir non-admin butuh kredensial admin penyetuju
+  const [approverUsername, setApproverUsername] = useState("");
+  const [approverPassword, setApproverPassword] = useState("");
+  const needsApproval = hasPriceOverride() && !isAdmin;
+  const overrideApproval = needsApproval
+    ? { username: approverUsername, password: approverPassword }
+    : null;
+
+  const payloadItems = () =>
+    items.map((i) => ({
+      product_id: i.product_id,
+      quantity: i.quantity,
+      price_at_time: i.price_override == null ? i.price : null,
+      price_override: i.price_override ?? null,
+      discount_amount: i.discount_amount || 0,
+      notes: i.notes,
+      unit_id: i.unit_id ?? null,
+      scanned_barcode: i.scanned_barcode ?? null,
+    }));
+
   const total = getTotal();
   const change = Math.max(0, amountPaid - total);
 
@@ -52,22 +74,26 @@ export function PaymentModal({
     }
   }, [open, total]);
 
+  useEffect(() => {
+    if (!open) {
+      setApproverUsername("");
+      setApproverPassword("");
+    }
+  }, [open]);
+
   const handleQrisSuccess = async (orderId: string) => {
     setShowQrisModal(false);
     setLoading(true);
     try {
       const payload: CreateTransactionPayload = {
-        items: items.map((i) => ({
-          product_id: i.product_id,
-          quantity: i.quantity,
-          price_at_time: i.price,
-          discount_amount: i.discount_amount || 0,
-        })),
+        items: payloadItems(),
         discount_id,
         discount_amount: getDiscountAmount(),
         payment_method: "QRIS",
         amount_paid: Math.round(total),
         notes: `QRIS Order: ${orderId}`,
+        customer_id,
+        override_approval: overrideApproval,
       };
 
       const transaction = await invoke<Transaction>("create_transaction", {
@@ -94,6 +120,15 @@ export function PaymentModal({
     const roundedTotal = Math.round(total);
     const roundedAmountPaid = Math.round(amountPaid);
 
+    if (needsApproval && (!approverUsername.trim() || !approverPassword)) {
+      toast({
+        variant: "destructive",
+        title: "Butuh Persetujuan",
+        description: "Ada harga manual. Masukkan username & password admin.",
+      });
+      return;
+    }
+
     // Intercept QRIS payment → buka modal khusus
     if (method === "QRIS") {
       setShowQrisModal(true);
@@ -114,17 +149,14 @@ export function PaymentModal({
     setLoading(true);
     try {
       const payload: CreateTransactionPayload = {
-        items: items.map((i) => ({
-          product_id: i.product_id,
-          quantity: i.quantity,
-          price_at_time: i.price,
-          discount_amount: i.discount_amount || 0,
-        })),
+        items: payloadItems(),
         discount_id,
         discount_amount: getDiscountAmount(), // Use calculated amount (handles percentage)
         payment_method: method,
         amount_paid: method === "CASH" ? roundedAmountPaid : roundedTotal,
         notes: "",
+        customer_id,
+        override_approval: overrideApproval,
       };
 
       const transaction = await invoke<Transaction>("create_transaction", {
@@ -202,6 +234,27 @@ export function PaymentModal({
                 </div>
               </div>
 
+              {needsApproval && (
+                <div className="space-y-2 rounded-lg border border-amber-300 bg-amber-50 dark:bg-amber-900/20 p-3">
+                  <div className="text-sm font-medium">
+                    Persetujuan admin untuk harga manual
+                  </div>
+                  <div className="grid grid-cols-2 gap-2">
+                    <Input
+                      placeholder="Username admin"
+                      value={approverUsername}
+                      onChange={(e) => setApproverUsername(e.target.value)}
+                    />
+                    <Input
+                      type="password"
+                      placeholder="Password"
+                      value={approverPassword}
+                      onChange={(e) => setApproverPassword(e.target.value)}
+                    />
+                  </div>
+                </div>
+              )}
+
               {method === "CASH" && (
                 <div className="bg-muted/50 p-4 rounded-lg flex justify-between items-center text-lg">
                   <span className="font-medium">Kembalian:</span>
@@ -8,6 +8,8 @@ import { Button } from "../../components/ui/button";
 import { Search, PackageX } from "lucide-react";
 import { ScrollArea } from "../../components/ui/scroll-area";
 import { formatRupiah } from "../../lib/currency";
+import { WeighDialog } from "./WeighDialog";
+import { VariantPicker } from "./VariantPicker";
 
 // === Product Image Component ===
 function ProductImage({
@@ -81,6 +83,8 @@ export function ProductGrid() {
   const addItem = useCartStore((s) => s.addItem);
   const [search, setSearch] = useState("");
   const [categoryId, setCategoryId] = useState<number | null>(null);
+  const [weighProduct, setWeighProduct] = useState<ProductWithCategory | null>(null);
+  const [variantParent, setVariantParent] = useState<ProductWithCategory | null>(null);
 
   const { data: categories } = useInvokeQuery<CategoryWithCount[]>(
     ["categories"],
@@ -91,20 +95,32 @@ export function ProductGrid() {
   const { data: products, isLoading } = useInvokeQuery<ProductWithCategory[]>(
     ["products", search, categoryId],
     "get_products",
-    { sessionToken, search, categoryId, showInactive: false },
+    { sessionToken, search, categoryId, showInactive: false, parentsOnly: true },
   );
 
   const { items } = useCartStore();
 
   const handleAddToCart = (p: ProductWithCategory) => {
+    if (p.variant_count > 0) {
+      setVariantParent(p);
+      return;
+    }
+
     const cartItem = items.find((item) => item.product_id === p.id);
-    const currentQuantity = cartItem ? cartItem.quantity : 0;
+    const currentQuantity = cartItem
+      ? cartItem.quantity * (cartItem.unit_conversion ?? 1)
+      : 0;
 
     if (currentQuantity >= p.stock) {
       alert(`Stok tidak mencukupi. Stok tersedia: ${p.stock}`);
       return;
     }
 
+    if (p.is_weighed) {
+      setWeighProduct(p);
+      return;
+    }
+
     addItem({
       product_id: p.id,
       product_name: p.name,
@@ -213,8 +229,11 @@ export function ProductGrid() {
         ) : (
           <div className="grid grid-cols-3 gap-2.5 pb-6">
             {products?.map((p) => {
-              const isOutOfStock = p.stock <= 0;
-              const stock = getStockLabel(p.stock);
+              const hasVariants = p.variant_count > 0;
+              const isOutOfStock = !hasVariants && p.stock <= 0;
+              const stock = hasVariants
+                ? { text: `${p.variant_count} varian`, className: "text-muted-foreground" }
+                : getStockLabel(p.stock);
 
               return (
                 <div
@@ -292,6 +311,26 @@ export function ProductGrid() {
           </div>
         )}
       </ScrollArea>
+
+      <WeighDialog
+        product={weighProduct}
+        onOpenChange={(open) => !open && setWeighProduct(null)}
+        onConfirm={(p, weightKg) =>
+          addItem({
+            product_id: p.id,
+            product_name: p.name,
+            price: p.price,
+            quantity: weightKg,
+            discount_amount: 0,
+            is_weighed: true,
+          })
+        }
+      />
+      <VariantPicker
+        parent={variantParent}
+        onOpenChange={(open) => !open && setVariantParent(null)}
+        onSelect={handleAddToCart}
+      />
     </div>
   );
 }
@@ -4,6 +4,7 @@ import {
   DialogContent,
 } from "../../components/ui/dialog";
 import { Button } from "../../components/ui/button";
+import { Input } from "../../components/ui/input";
 import { formatRupiah } from "../../lib/currency";
 import { Transaction, TransactionDetail, AppSettings } from "../../types";
 import { invoke } from "../../lib/tauri";
@@ -13,6 +14,7 @@ import { format } from "date-fns";
 import { useToast } from "../../hooks/use-toast";
 
 type PrintMode = "thermal" | "windows" | "pdf";
+type PdfLayout = "RECEIPT" | "INVOICE";
 
 export function ReceiptDialog({
   open,
@@ -27,6 +29,8 @@ export function ReceiptDialog({
   const [detail, setDetail] = useState<TransactionDetail | null>(null);
   const [settings, setSettings] = useState<AppSettings | null>(null);
   const [printMode, setPrintMode] = useState<PrintMode>("thermal");
+  const [pdfLayout, setPdfLayout] = useState<PdfLayout>("RECEIPT");
+  const [customerName, setCustomerName] = useState("");
   const [isPrinting, setIsPrinting] = useState(false);
   const receiptRef = useRef<HTMLDivElement>(null);
   const { toast } = useToast();
@@ -113,13 +117,13 @@ export function ReceiptDialog({
     setIsPrinting(true);
     
     try {
-      // Log Activity for printing
-      invoke("print_receipt", {
-        sessionToken,
-        transactionId: transaction.id,
-      }).catch(console.error);
-
       if (printMode === "thermal") {
+        // Struk ESC/POS lewat antrian; cetakan kedua dst. otomatis SALINAN
+        invoke("print_receipt", {
+          sessionToken,
+          transactionId: transaction.id,
+        }).catch(console.error);
+
         // Thermal Printer Optimized Printing (existing)
         if (receiptRef.current) {
           const printWindow = window.open("", "_blank", "width=300,height=600");
@@ -177,6 +181,7 @@ export function ReceiptDialog({
         const htmlContent = getReceiptHtml();
         await invoke("print_receipt_windows", {
           sessionToken,
+          transactionId: transaction.id,
           htmlContent,
           printerName: null,
         });
@@ -186,15 +191,18 @@ export function ReceiptDialog({
           variant: "default",
         });
       } else if (printMode === "pdf") {
-        // Export to PDF/HTML
-        const htmlContent = getReceiptHtml();
+        // Export ke PDF (dibuat di backend)
         const filePath = await invoke<string>("export_receipt_pdf", {
           sessionToken,
-          htmlContent,
           transactionId: transaction.id,
+          layout: pdfLayout,
+          customer:
+            pdfLayout === "INVOICE" && customerName.trim()
+              ? { name: customerName.trim() }
+              : null,
         });
         toast({
-          title: "Struk Diekspor",
+          title: pdfLayout === "INVOICE" ? "Invoice Diekspor" : "Struk Diekspor",
           description: `File disimpan di: ${filePath}`,
           variant: "default",
         });
@@ -418,9 +426,37 @@ export function ReceiptDialog({
             </p>
           )}
           {printMode === "pdf" && (
-            <p className="text-xs text-slate-500 mb-3">
-              Export struk ke file yang bisa dibuka di browser
-            </p>
+            <div className="space-y-2 mb-3">
+              <div className="flex gap-2">
+                {(["RECEIPT", "INVOICE"] as PdfLayout[]).map((layout) => (
+                  <button
+                    key={layout}
+                    type="button"
+                    onClick={() => setPdfLayout(layout)}
+                    className={`flex-1 py-1.5 px-3 rounded-md text-xs font-medium transition-all ${
+                      pdfLayout === layout
+                        ? "bg-slate-200 text-slate-900 dark:bg-slate-700 dark:text-white"
+                        : "bg-slate-50 text-slate-500 dark:bg-slate-800/50 dark:text-slate-400"
+                    }`}
+                  >
+                    {layout === "RECEIPT" ? "Struk (thermal)" : "Invoice A4"}
+                  </button>
+                ))}
+              </div>
+              {pdfLayout === "INVOICE" && (
+                <Input
+                  placeholder="Nama pelanggan (opsional)"
+                  className="h-9 text-xs"
+                  value={customerName}
+                  onChange={(e) => setCustomerName(e.target.value)}
+                />
+              )}
+              <p className="text-xs text-slate-500">
+                {pdfLayout === "INVOICE"
+                  ? "Invoice A4 dengan NPWP toko, disimpan sebagai file PDF"
+                  : "Struk selebar kertas thermal, disimpan sebagai file PDF"}
+              </p>
+            </div>
           )}
           
           <div className="flex gap-3">
@@ -0,0 +1,84 @@
+import {
+  Dialog,
+  DialogContent,
+  DialogHeader,
+  DialogTitle,
+} from "../../components/ui/dialog";
+import { ProductWithCategory } from "../../types";
+import { useInvokeQuery } from "../../hooks/useInvokeQuery";
+import { useAuthStore } from "../../store/authStore";
+import { formatRupiah } from "../../lib/currency";
+
+/**
+ * Dialog pilih varian (ukuran/warna/dll.) saat produk induk diklik di POS.
+ */
+export function VariantPicker({
+  parent,
+  onOpenChange,
+  onSelect,
+}: {
+  parent: ProductWithCategory | null;
+  onOpenChange: (open: boolean) => void;
+  onSelect: (variant: ProductWithCategory) => void;
+}) {
+  const sessionToken = useAuthStore((s) => s.sessionToken);
+
+  const { data: variants, isLoading } = useInvokeQuery<ProductWithCategory[]>(
+    ["product-variants", parent?.id],
+    "get_product_variants",
+    { sessionToken, parentId: parent?.id },
+    { enabled: !!parent },
+  );
+
+  return (
+    <Dialog open={!!parent} onOpenChange={onOpenChange}>
+      <DialogContent className="sm:max-w-md">
+        <DialogHeader>
+          <DialogTitle>{parent?.name}</DialogTitle>
+        </DialogHeader>
+
+        <div className="grid grid-cols-2 gap-2 max-h-[60vh] overflow-y-auto">
+          {isLoading && (
+            <p className="col-span-2 text-sm text-muted-foreground">Memuat varian...</p>
+          )}
+          {variants
+            ?.filter((v) => v.is_active)
+            .map((v) => {
+              const isOutOfStock = v.stock <= 0;
+              return (
+                <button
+                  key={v.id}
+                  type="button"
+                  disabled={isOutOfStock}
+                  onClick={() => {
+                    onSelect(v);
+                    onOpenChange(false);
+                  }}
+                  className="rounded-lg border border-border/50 p-3 text-left hover:bg-muted/50 disabled:opacity-50 disabled:cursor-not-allowed"
+                >
+                  <div className="text-sm font-medium">
+                    {Object.values(parseAttributes(v.variant_attributes)).join(" / ") || v.name}
+                  </div>
+                  <div className="flex justify-between text-xs mt-1">
+                    <span className="font-bold text-primary">{formatRupiah(v.price)}</span>
+                    <span className={isOutOfStock ? "text-red-500" : "text-muted-foreground"}>
+                      {isOutOfStock ? "Habis" : `Stok ${v.stock}`}
+                    </span>
+                  </div>
+                </button>
+              );
+            })}
+        </div>
+      </DialogContent>
+    </Dialog>
+  );
+}
+
+function parseAttributes(raw: string | null | undefined): Record<string, string> {
+  if (!raw) return {};
+  try {
+    return JSON.parse(raw);
+  } catch {
+    return {};
+  }
+}
@@ -0,0 +1,178 @@
+import { useEffect, useState } from "react";
+import {
+  Dialog,
+  DialogContent,
+  DialogHeader,
+  DialogTitle,
+  DialogFooter,
+} from "../../components/ui/dialog";
+import { Button } from "../../components/ui/button";
+import { Input } from "../../components/ui/input";
+import { Label } from "../../components/ui/label";
+import { Scale, RefreshCw } from "lucide-react";
+import { ProductWithCategory, ScaleWeight } from "../../types";
+import { useAuthStore } from "../../store/authStore";
+import { invoke } from "../../lib/tauri";
+import { formatRupiah } from "../../lib/currency";
+import { useToast } from "../../hooks/use-toast";
+
+/**
+ * Dialog input berat untuk produk timbang: baca dari timbangan serial atau
+ * ketik manual (kg).
+ */
+export function WeighDialog({
+  product,
+  onOpenChange,
+  onConfirm,
+}: {
+  product: ProductWithCategory | null;
+  onOpenChange: (open: boolean) => void;
+  onConfirm: (product: ProductWithCategory, weightKg: number) => void;
+}) {
+  const sessionToken = useAuthStore((s) => s.sessionToken);
+  const { toast } = useToast();
+  const [weight, setWeight] = useState("");
+  const [reading, setReading] = useState<ScaleWeight | null>(null);
+  const [busy, setBusy] = useState(false);
+
+  const readScale = async () => {
+    setBusy(true);
+    try {
+      const result = await invoke<ScaleWeight>("read_scale_weight", {
+        sessionToken,
+      });
+      setReading(result);
+      setWeight(result.net_kg > 0 ? String(result.net_kg) : "");
+    } catch (error) {
+      toast({
+        variant: "destructive",
+        title: "Gagal Membaca Timbangan",
+        description: String(error),
+      });
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const setTare = async (tareKg: number | null) => {
+    setBusy(true);
+    try {
+      const result = await invoke<ScaleWeight>("set_scale_tare", {
+        sessionToken,
+        tareKg,
+      });
+      setReading(result);
+      setWeight(result.net_kg > 0 ? String(result.net_kg) : "");
+    } catch (error) {
+      toast({
+        variant: "destructive",
+        title: "Gagal Mengatur Tare",
+        description: String(error),
+      });
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  useEffect(() => {
+    setWeight("");
+    setReading(null);
+  }, [product]);
+
+  const weightKg = Math.round((parseFloat(weight.replace(",", ".")) || 0) * 1000) / 1000;
+
+  const handleConfirm = () => {
+    if (!product || weightKg <= 0) return;
+    if (weightKg > product.stock) {
+      toast({
+        variant: "destructive",
+        title: "Stok Tidak Mencukupi",
+        description: `Stok tersedia: ${product.stock} kg`,
+      });
+      return;
+    }
+    onConfirm(product, weightKg);
+    onOpenChange(false);
+  };
+
+  return (
+    <Dialog open={!!product} onOpenChange={onOpenChange}>
+      <DialogContent className="sm:max-w-md">
+        <DialogHeader>
+          <DialogTitle className="flex items-center gap-2">
+            <Scale className="h-5 w-5" /> {product?.name}
+          </DialogTitle>
+        </DialogHeader>
+
+        <div className="space-y-4">
+          <div className="flex gap-2">
+            <Button
+              type="button"
+              className="flex-1"
+              onClick={readScale}
+              disabled={busy}
+            >
+              <RefreshCw className={`h-4 w-4 mr-2 ${busy ? "animate-spin" : ""}`} />
+              Baca Timbangan
+            </Button>
+            <Button
+              type="button"
+              variant="outline"
+              onClick={() => setTare(null)}
+              disabled={busy}
+            >
+              Tare
+            </Button>
+            <Button
+              type="button"
+              variant="ghost"
+              onClick={() => setTare(0)}
+              disabled={busy}
+            >
+              Reset Tare
+            </Button>
+          </div>
+
+          {reading && (
+            <p className="text-xs text-muted-foreground">
+              Gross {reading.gross_kg} kg · Tare {reading.tare_kg} kg
+              {!reading.stable && " · belum stabil"}
+            </p>
+          )}
+
+          <div className="space-y-2">
+            <Label>Berat (kg)</Label>
+            <Input
+              autoFocus
+              inputMode="decimal"
+              placeholder="0,000"
+              value={weight}
+              onChange={(e) => setWeight(e.target.value)}
+              onKeyDown={(e) => e.key === "Enter" && handleConfirm()}
+            />
+          </div>
+
+          {product && (
+            <div className="flex justify-between text-sm">
+              <span className="text-muted-foreground">
+                {formatRupiah(product.price)} / kg
+              </span>
+              <span className="font-bold">
+                {formatRupiah(product.price * weightKg)}
+              </span>
+            </div>
+          )}
+        </div>
+
+        <DialogFooter>
+          <Button variant="ghost" onClick={() => onOpenChange(false)}>
+            Batal
+          </Button>
+          <Button onClick={handleConfirm} disabled={weightKg <= 0}>
+            Tambahkan
+          </Button>
+        </DialogFooter>
+      </DialogContent>
+    </Dialog>
+  );
+}
@@ -0,0 +1,68 @@
+import {
+  Card,
+  CardHeader,
+  CardTitle,
+  CardContent,
+} from "../../components/ui/card";
+import { formatRupiah, formatQuantity } from "../../lib/currency";
+import { useInvokeQuery } from "../../hooks/useInvokeQuery";
+import { useAuthStore } from "../../store/authStore";
+import { CategoryStat } from "../../types";
+import { LayoutGrid } from "lucide-react";
+
+export function CategorySales({ startDate, endDate }: { startDate: string, endDate: string }) {
+  const sessionToken = useAuthStore((s) => s.sessionToken);
+
+  const { data: categories, isLoading } = useInvokeQuery<CategoryStat[]>(
+    ["category_sales", startDate, endDate],
+    "get_category_sales",
+    { sessionToken, startDate, endDate },
+  );
+
+  const maxRevenue = Math.max(1, ...(categories ?? []).map((c) => c.total_revenue));
+
+  return (
+    <Card className="h-full shadow-sm border-slate-200 dark:border-slate-800 bg-white dark:bg-slate-900 overflow-hidden">
+      <CardHeader className="p-5 pb-2">
+        <CardTitle className="text-sm font-black uppercase tracking-widest text-muted-foreground flex items-center gap-2">
+          <LayoutGrid className="h-4 w-4" /> Penjualan per Kategori
+        </CardTitle>
+      </CardHeader>
+      <CardContent className="p-5 pt-0">
+        {isLoading ? (
+          <div className="space-y-4">
+            {[...Array(5)].map((_, i) => (
+              <div key={i} className="h-10 bg-muted animate-pulse rounded-xl" />
+            ))}
+          </div>
+        ) : (
+          <div className="space-y-3 pt-2">
+            {categories?.map((c) => (
+              <div key={c.category_id ?? "none"} className="space-y-1">
+                <div className="flex justify-between text-sm">
+                  <span className="font-bold text-slate-900 dark:text-white truncate">{c.name}</span>
+                  <span className="font-black">{formatRupiah(c.total_revenue)}</span>
+                </div>
+                <div className="h-2 rounded-full bg-slate-100 dark:bg-slate-800 overflow-hidden">
+                  <div
+                    className="h-full bg-primary rounded-full"
+                    style={{ width: `${(c.total_revenue / maxRevenue) * 100}%` }}
+                  />
+                </div>
+                <div className="text-[10px] font-black uppercase text-muted-foreground tracking-widest">
+                  {formatQuantity(c.total_sold)} UNIT TERJUAL
+                </div>
+              </div>
+            ))}
+            {categories?.length === 0 && (
+              <div className="text-center text-muted-foreground py-20 flex flex-col items-center">
+                <LayoutGrid className="h-10 w-10 mb-4 opacity-10" />
+                <p className="text-sm font-medium">Data penjualan belum tersedia</p>
+              </div>
+            )}
+          </div>
+        )}
+      </CardContent>
+    </Card>
+  );
+}
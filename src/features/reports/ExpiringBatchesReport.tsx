@@ -0,0 +1,101 @@
+import { useState } from "react";
+import { useInvokeQuery } from "../../hooks/useInvokeQuery";
+import { useAuthStore } from "../../store/authStore";
+import { ProductBatch } from "../../types";
+import { formatRupiah } from "../../lib/currency";
+import { ExpiryBadge } from "../inventory/ProductBatchList";
+import {
+  Table,
+  TableBody,
+  TableCell,
+  TableHead,
+  TableHeader,
+  TableRow,
+} from "../../components/ui/table";
+import { Card, CardHeader, CardTitle, CardContent } from "../../components/ui/card";
+import { Input } from "../../components/ui/input";
+import { CalendarClock } from "lucide-react";
+
+/**
+ * Batch yang sudah / hampir kedaluwarsa. Jendela hari default mengikuti
+ * pengaturan, bisa diubah untuk laporan ini saja.
+ */
+export function ExpiringBatchesReport() {
+  const [days, setDays] = useState("");
+  const sessionToken = useAuthStore((s) => s.sessionToken);
+
+  const { data: batches, isLoading } = useInvokeQuery<ProductBatch[]>(
+    ["expiring-batches", days],
+    "get_expiring_batches",
+    { sessionToken, days: days === "" ? null : Number(days) },
+  );
+
+  const totalValue = (batches ?? []).reduce((sum, b) => sum + b.quantity * b.cost_price, 0);
+
+  return (
+    <Card className="shadow-sm border-slate-200 dark:border-slate-800">
+      <CardHeader className="p-5 flex flex-row items-center justify-between space-y-0">
+        <CardTitle className="text-sm font-black uppercase tracking-widest text-muted-foreground flex items-center gap-2">
+          <CalendarClock className="h-4 w-4" /> Mendekati Kedaluwarsa
+        </CardTitle>
+        <div className="flex items-center gap-2 text-xs text-muted-foreground">
+          <span>Nilai modal {formatRupiah(totalValue)} · dalam</span>
+          <Input
+            type="number"
+            min={0}
+            className="h-7 w-16 text-xs"
+            placeholder="default"
+            value={days}
+            onChange={(e) => setDays(e.target.value)}
+          />
+          <span>hari</span>
+        </div>
+      </CardHeader>
+      <CardContent className="p-0">
+        <div className="border-t max-h-[400px] overflow-auto">
+          <Table>
+            <TableHeader className="bg-slate-50 dark:bg-slate-900/50 sticky top-0 z-10">
+              <TableRow>
+                <TableHead className="font-bold text-[10px] uppercase">Produk</TableHead>
+                <TableHead className="font-bold text-[10px] uppercase">Batch</TableHead>
+                <TableHead className="font-bold text-[10px] uppercase">Kedaluwarsa</TableHead>
+                <TableHead className="font-bold text-[10px] uppercase text-right">Sisa</TableHead>
+                <TableHead className="font-bold text-[10px] uppercase text-right">Nilai Modal</TableHead>
+              </TableRow>
+            </TableHeader>
+            <TableBody>
+              {isLoading ? (
+                <TableRow>
+                  <TableCell colSpan={5} className="h-12 animate-pulse bg-muted/20" />
+                </TableRow>
+              ) : batches?.length === 0 ? (
+                <TableRow>
+                  <TableCell colSpan={5} className="h-24 text-center text-muted-foreground italic">
+                    Tidak ada batch yang mendekati kedaluwarsa.
+                  </TableCell>
+                </TableRow>
+              ) : (
+                batches?.map((b) => (
+                  <TableRow key={b.id}>
+                    <TableCell className="text-xs font-bold">{b.product_name}</TableCell>
+                    <TableCell className="text-xs">{b.batch_number ?? `#${b.id}`}</TableCell>
+                    <TableCell className="text-xs">
+                      <div className="flex items-center gap-2">
+                        {b.expiry_date}
+                        <ExpiryBadge days={b.days_to_expiry} />
+                      </div>
+                    </TableCell>
+                    <TableCell className="text-right text-xs font-bold">{b.quantity}</TableCell>
+                    <TableCell className="text-right text-xs">
+                      {formatRupiah(b.quantity * b.cost_price)}
+                    </TableCell>
+                  </TableRow>
+                ))
+              )}
+            </TableBody>
+          </Table>
+        </div>
+      </CardContent>
+    </Card>
+  );
+}
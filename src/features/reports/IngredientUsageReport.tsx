@@ -0,0 +1,154 @@
+import { useState } from "react";
+import { useInvokeQuery, useInvokeMutation } from "../../hooks/useInvokeQuery";
+import { useAuthStore } from "../../store/authStore";
+import { useQueryClient } from "@tanstack/react-query";
+import { useToast } from "../../hooks/use-toast";
+import { IngredientUsage } from "../../types";
+import { formatRupiah } from "../../lib/currency";
+import { format } from "date-fns";
+import {
+  Table,
+  TableBody,
+  TableCell,
+  TableHead,
+  TableHeader,
+  TableRow,
+} from "../../components/ui/table";
+import { Card, CardHeader, CardTitle, CardContent } from "../../components/ui/card";
+import { Button } from "../../components/ui/button";
+import { Input } from "../../components/ui/input";
+import { Coffee } from "lucide-react";
+
+interface IngredientUsageReportProps {
+  startDate: string;
+  endDate: string;
+}
+
+/**
+ * Pemakaian bahan resep teoretis (dari penjualan) vs aktual (setelah hitung
+ * stok). Kolom "Hitung Fisik" dipakai untuk mencatat hasil hitung stok bahan.
+ */
+export function IngredientUsageReport({ startDate, endDate }: IngredientUsageReportProps) {
+  const [counts, setCounts] = useState<Record<number, string>>({});
+  const sessionToken = useAuthStore((s) => s.sessionToken);
+  const queryClient = useQueryClient();
+  const { toast } = useToast();
+
+  const { data: rows, isLoading } = useInvokeQuery<IngredientUsage[]>(
+    ["ingredient-usage", startDate, endDate],
+    "get_ingredient_usage_report",
+    { sessionToken, startDate, endDate },
+  );
+
+  const countMutation = useInvokeMutation<void>("record_stock_counts", {
+    onSuccess: () => {
+      setCounts({});
+      queryClient.invalidateQueries({ queryKey: ["ingredient-usage"] });
+      queryClient.invalidateQueries({ queryKey: ["products"] });
+      toast({ title: "Berhasil", description: "Hasil hitung stok disimpan" });
+    },
+    onError: (e) => toast({ variant: "destructive", title: "Gagal", description: String(e) }),
+  });
+
+  const filled = Object.entries(counts).filter(([, v]) => v.trim() !== "");
+
+  const handleSaveCounts = () =>
+    countMutation.mutate({
+      sessionToken,
+      counts: filled.map(([id, v]) => ({ product_id: Number(id), counted: Number(v) })),
+    });
+
+  return (
+    <Card className="shadow-sm border-slate-200 dark:border-slate-800">
+      <CardHeader className="p-5 flex flex-row items-center justify-between space-y-0">
+        <CardTitle className="text-sm font-black uppercase tracking-widest text-muted-foreground flex items-center gap-2">
+          <Coffee className="h-4 w-4" /> Pemakaian Bahan: Teoretis vs Aktual
+        </CardTitle>
+        <Button
+          size="sm"
+          onClick={handleSaveCounts}
+          disabled={filled.length === 0 || countMutation.isPending}
+        >
+          Simpan Hitung Stok
+        </Button>
+      </CardHeader>
+      <CardContent className="p-0">
+        <div className="border-t max-h-[500px] overflow-auto">
+          <Table>
+            <TableHeader className="bg-slate-50 dark:bg-slate-900/50 sticky top-0 z-10">
+              <TableRow>
+                <TableHead className="font-bold text-[10px] uppercase">Bahan</TableHead>
+                <TableHead className="font-bold text-[10px] uppercase text-right">Teoretis</TableHead>
+                <TableHead className="font-bold text-[10px] uppercase text-right">Aktual</TableHead>
+                <TableHead className="font-bold text-[10px] uppercase text-right">Selisih</TableHead>
+                <TableHead className="font-bold text-[10px] uppercase text-right">Nilai Selisih</TableHead>
+                <TableHead className="font-bold text-[10px] uppercase text-right">Stok Sistem</TableHead>
+                <TableHead className="w-[130px] font-bold text-[10px] uppercase">Hitung Fisik</TableHead>
+              </TableRow>
+            </TableHeader>
+            <TableBody>
+              {isLoading ? (
+                <TableRow>
+                  <TableCell colSpan={7} className="h-12 animate-pulse bg-muted/20" />
+                </TableRow>
+              ) : rows?.length === 0 ? (
+                <TableRow>
+                  <TableCell colSpan={7} className="h-24 text-center text-muted-foreground italic">
+                    Belum ada menu dengan resep.
+                  </TableCell>
+                </TableRow>
+              ) : (
+                rows?.map((r) => (
+                  <TableRow key={r.product_id}>
+                    <TableCell className="text-xs">
+                      <div className="font-bold">{r.name}</div>
+                      <div className="text-[10px] text-muted-foreground">
+                        {r.last_counted_at
+                          ? `Hitung terakhir ${format(
+                              new Date(r.last_counted_at.replace(" ", "T") + "Z"),
+                              "dd/MM/yy HH:mm",
+                            )}`
+                          : "Belum pernah dihitung"}
+                      </div>
+                    </TableCell>
+                    <TableCell className="text-right text-xs">
+                      {r.theoretical} {r.unit}
+                    </TableCell>
+                    <TableCell className="text-right text-xs">
+                      {r.actual} {r.unit}
+                    </TableCell>
+                    <TableCell
+                      className={`text-right text-xs font-bold ${r.count_variance > 0 ? "text-rose-600" : ""}`}
+                    >
+                      {r.count_variance > 0 ? "+" : ""}
+                      {r.count_variance} {r.unit}
+                    </TableCell>
+                    <TableCell className="text-right text-xs">
+                      {formatRupiah(r.variance_value)}
+                    </TableCell>
+                    <TableCell className="text-right text-xs">
+                      {r.stock} {r.unit}
+                    </TableCell>
+                    <TableCell>
+                      <Input
+                        type="number"
+                        min={0}
+                        step="any"
+                        className="h-7 text-xs"
+                        placeholder={String(r.stock)}
+                        value={counts[r.product_id] ?? ""}
+                        onChange={(e) =>
+                          setCounts({ ...counts, [r.product_id]: e.target.value })
+                        }
+                      />
+                    </TableCell>
+                  </TableRow>
+                ))
+              )}
+            </TableBody>
+          </Table>
+        </div>
+      </CardContent>
+    </Card>
+  );
+}
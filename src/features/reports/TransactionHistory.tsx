@@ -25,6 +25,7 @@ import {
   ChevronRight,
   Eye,
   Ban,
+  Printer,
 } from "lucide-react";
 import { useState, useEffect } from "react";
 import { Input } from "../../components/ui/input";
@@ -115,6 +116,28 @@ export function TransactionHistory({
     }
   };
 
+  const handleReprint = async (tx: TransactionWithCashier) => {
+    if (!sessionToken) return;
+
+    try {
+      await invoke("reprint_receipt", {
+        sessionToken,
+        transactionId: tx.id,
+      });
+      toast({
+        title: "Salinan Struk Dicetak",
+        description: `Struk ${(tx.id.split("-")[0] ?? "").toUpperCase()} dikirim ke printer sebagai SALINAN (cetak ulang ke-${tx.reprint_count + 1}).`,
+      });
+      queryClient.invalidateQueries({ queryKey: ["transactions_report"] });
+    } catch (error) {
+      toast({
+        variant: "destructive",
+        title: "Gagal Cetak Ulang",
+        description: String(error),
+      });
+    }
+  };
+
   const isAdmin = userRole === "ADMIN";
 
   return (
@@ -234,6 +257,18 @@ export function TransactionHistory({
                               Detail
                             </span>
                           </Button>
+                          <Button
+                            variant="ghost"
+                            size="sm"
+                            className="h-7 px-2 text-muted-foreground hover:bg-muted"
+                            onClick={() => handleReprint(t)}
+                          >
+                            <Printer className="h-3.5 w-3.5 mr-1" />
+                            <span className="text-[10px] font-medium">
+                              Salinan
+                              {t.reprint_count > 0 && ` (${t.reprint_count})`}
+                            </span>
+                          </Button>
                           {isAdmin && t.status === "COMPLETED" && (
                             <Button
                               variant="ghost"
@@ -0,0 +1,318 @@
+import { useState, useEffect } from "react";
+import { useAuthStore } from "../../store/authStore";
+import { invoke } from "../../lib/tauri";
+import { Customer, PriceLevel } from "../../types";
+import { useToast } from "../../hooks/use-toast";
+import { Button } from "../../components/ui/button";
+import { Input } from "../../components/ui/input";
+import { Label } from "../../components/ui/label";
+import { Switch } from "../../components/ui/switch";
+import {
+  Table,
+  TableBody,
+  TableCell,
+  TableHead,
+  TableHeader,
+  TableRow,
+} from "../../components/ui/table";
+import {
+  Dialog,
+  DialogContent,
+  DialogHeader,
+  DialogTitle,
+  DialogFooter,
+} from "../../components/ui/dialog";
+import { Plus, Pencil, Trash2, Loader2 } from "lucide-react";
+import { Badge } from "../../components/ui/badge";
+import { NumericInput } from "../../components/NumericInput";
+
+export function CustomerSettings() {
+  const [customers, setCustomers] = useState<Customer[]>([]);
+  const [levels, setLevels] = useState<PriceLevel[]>([]);
+  const [isLoading, setIsLoading] = useState(true);
+  const [isSaving, setIsSaving] = useState(false);
+  const [editingCustomer, setEditingCustomer] = useState<Partial<Customer> | null>(null);
+  const [newLevelName, setNewLevelName] = useState("");
+  const [newLevelPercent, setNewLevelPercent] = useState(0);
+
+  const sessionToken = useAuthStore((s) => s.sessionToken);
+  const { toast } = useToast();
+
+  useEffect(() => {
+    fetchData();
+  }, []);
+
+  const fetchData = async () => {
+    try {
+      const [customerData, levelData] = await Promise.all([
+        invoke<Customer[]>("get_customers", { sessionToken, showInactive: true }),
+        invoke<PriceLevel[]>("get_price_levels", { sessionToken }),
+      ]);
+      setCustomers(customerData);
+      setLevels(levelData);
+    } catch (error) {
+      toast({
+        variant: "destructive",
+        title: "Error",
+        description: "Gagal mengambil data pelanggan",
+      });
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  const showError = (error: unknown) =>
+    toast({ variant: "destructive", title: "Error", description: String(error) });
+
+  const handleAddLevel = async () => {
+    if (!newLevelName.trim()) return;
+    try {
+      await invoke("create_price_level", {
+        sessionToken,
+        payload: { name: newLevelName, discount_percent: newLevelPercent },
+      });
+      setNewLevelName("");
+      setNewLevelPercent(0);
+      fetchData();
+    } catch (error) {
+      showError(error);
+    }
+  };
+
+  const handleUpdateLevel = async (level: PriceLevel, discount_percent: number) => {
+    try {
+      await invoke("update_price_level", {
+        sessionToken,
+        id: level.id,
+        payload: { name: level.name, discount_percent },
+      });
+      fetchData();
+    } catch (error) {
+      showError(error);
+    }
+  };
+
+  const handleDeleteLevel = async (id: number) => {
+    try {
+      await invoke("delete_price_level", { sessionToken, id });
+      fetchData();
+    } catch (error) {
+      showError(error);
+    }
+  };
+
+  const handleSaveCustomer = async () => {
+    if (!editingCustomer) return;
+    setIsSaving(true);
+    try {
+      const payload = {
+        name: editingCustomer.name ?? "",
+        phone: editingCustomer.phone || null,
+        address: editingCustomer.address || null,
+        price_level_id: editingCustomer.price_level_id ?? null,
+      };
+      if (editingCustomer.id) {
+        await invoke("update_customer", { sessionToken, id: editingCustomer.id, payload });
+      } else {
+        await invoke("create_customer", { sessionToken, payload });
+      }
+      setEditingCustomer(null);
+      fetchData();
+      toast({ title: "Berhasil", description: "Pelanggan berhasil disimpan" });
+    } catch (error) {
+      showError(error);
+    } finally {
+      setIsSaving(false);
+    }
+  };
+
+  const handleToggle = async (id: number) => {
+    try {
+      await invoke("toggle_customer", { sessionToken, id });
+      fetchData();
+    } catch (error) {
+      showError(error);
+    }
+  };
+
+  if (isLoading) {
+    return (
+      <div className="flex items-center justify-center p-8">
+        <Loader2 className="h-8 w-8 animate-spin text-primary" />
+      </div>
+    );
+  }
+
+  return (
+    <div className="space-y-6">
+      <div className="space-y-3">
+        <h2 className="text-2xl font-bold tracking-tight">Level Harga</h2>
+        <p className="text-sm text-muted-foreground">
+          Potongan default dipakai jika produk tidak punya harga khusus untuk level tersebut.
+          Pelanggan tanpa level membayar harga eceran.
+        </p>
+        {levels.map((level) => (
+          <div key={level.id} className="flex items-center gap-2">
+            <span className="w-40 font-medium">{level.name}</span>
+            <NumericInput
+              value={level.discount_percent}
+              onChange={(val) =>
+                setLevels(levels.map((l) => (l.id === level.id ? { ...l, discount_percent: val } : l)))
+              }
+              suffix="%"
+              className="w-28"
+            />
+            <Button
+              variant="outline"
+              size="sm"
+              onClick={() => handleUpdateLevel(level, level.discount_percent)}
+            >
+              Simpan
+            </Button>
+            <Button
+              variant="ghost"
+              size="icon"
+              className="text-destructive"
+              onClick={() => handleDeleteLevel(level.id)}
+            >
+              <Trash2 className="h-4 w-4" />
+            </Button>
+          </div>
+        ))}
+        <div className="flex items-center gap-2">
+          <Input
+            placeholder="Nama level baru"
+            value={newLevelName}
+            onChange={(e) => setNewLevelName(e.target.value)}
+            className="w-40"
+          />
+          <NumericInput
+            value={newLevelPercent}
+            onChange={setNewLevelPercent}
+            suffix="%"
+            className="w-28"
+          />
+          <Button variant="outline" onClick={handleAddLevel} disabled={!newLevelName.trim()}>
+            <Plus className="mr-2 h-4 w-4" /> Tambah Level
+          </Button>
+        </div>
+      </div>
+
+      <div className="space-y-3">
+        <div className="flex justify-between items-center">
+          <h2 className="text-2xl font-bold tracking-tight">Pelanggan</h2>
+          <Button onClick={() => setEditingCustomer({ name: "", price_level_id: null })}>
+            <Plus className="mr-2 h-4 w-4" /> Tambah Pelanggan
+          </Button>
+        </div>
+
+        <div className="border rounded-md">
+          <Table>
+            <TableHeader>
+              <TableRow>
+                <TableHead>Nama</TableHead>
+                <TableHead>No. HP</TableHead>
+                <TableHead>Level Harga</TableHead>
+                <TableHead>Status</TableHead>
+                <TableHead className="text-right">Aksi</TableHead>
+              </TableRow>
+            </TableHeader>
+            <TableBody>
+              {customers.length === 0 ? (
+                <TableRow>
+                  <TableCell colSpan={5} className="text-center py-10 text-muted-foreground">
+                    Belum ada data pelanggan.
+                  </TableCell>
+                </TableRow>
+              ) : (
+                customers.map((c) => (
+                  <TableRow key={c.id}>
+                    <TableCell className="font-medium">{c.name}</TableCell>
+                    <TableCell>{c.phone || "-"}</TableCell>
+                    <TableCell>
+                      <Badge variant={c.price_level_name ? "default" : "secondary"}>
+                        {c.price_level_name ?? "Eceran"}
+                      </Badge>
+                    </TableCell>
+                    <TableCell>
+                      <Switch checked={c.is_active} onCheckedChange={() => handleToggle(c.id)} />
+                    </TableCell>
+                    <TableCell className="text-right">
+                      <Button variant="ghost" size="icon" onClick={() => setEditingCustomer(c)}>
+                        <Pencil className="h-4 w-4" />
+                      </Button>
+                    </TableCell>
+                  </TableRow>
+                ))
+              )}
+            </TableBody>
+          </Table>
+        </div>
+      </div>
+
+      <Dialog open={!!editingCustomer} onOpenChange={(open) => !open && setEditingCustomer(null)}>
+        <DialogContent className="sm:max-w-[425px]">
+          <DialogHeader>
+            <DialogTitle>
+              {editingCustomer?.id ? "Edit Pelanggan" : "Tambah Pelanggan Baru"}
+            </DialogTitle>
+          </DialogHeader>
+          {editingCustomer && (
+            <div className="grid gap-4 py-4">
+              <div className="space-y-2">
+                <Label>Nama</Label>
+                <Input
+                  value={editingCustomer.name ?? ""}
+                  onChange={(e) => setEditingCustomer({ ...editingCustomer, name: e.target.value })}
+                />
+              </div>
+              <div className="space-y-2">
+                <Label>No. HP</Label>
+                <Input
+                  value={editingCustomer.phone ?? ""}
+                  onChange={(e) => setEditingCustomer({ ...editingCustomer, phone: e.target.value })}
+                />
+              </div>
+              <div className="space-y-2">
+                <Label>Alamat</Label>
+                <Input
+                  value={editingCustomer.address ?? ""}
+                  onChange={(e) => setEditingCustomer({ ...editingCustomer, address: e.target.value })}
+                />
+              </div>
+              <div className="space-y-2">
+                <Label>Level Harga</Label>
+                <select
+                  value={editingCustomer.price_level_id ?? ""}
+                  onChange={(e) =>
+                    setEditingCustomer({
+                      ...editingCustomer,
+                      price_level_id: e.target.value ? Number(e.target.value) : null,
+                    })
+                  }
+                  className="w-full h-9 rounded-md border border-input bg-transparent px-2 text-sm"
+                >
+                  <option value="">Eceran</option>
+                  {levels.map((l) => (
+                    <option key={l.id} value={l.id}>
+                      {l.name}
+                    </option>
+                  ))}
+                </select>
+              </div>
+            </div>
+          )}
+          <DialogFooter>
+            <Button variant="ghost" onClick={() => setEditingCustomer(null)}>
+              Batal
+            </Button>
+            <Button onClick={handleSaveCustomer} disabled={isSaving || !editingCustomer?.name?.trim()}>
+              {isSaving && <Loader2 className="mr-2 h-4 w-4 animate-spin" />}
+              Simpan
+            </Button>
+          </DialogFooter>
+        </DialogContent>
+      </Dialog>
+    </div>
+  );
+}
@@ -0,0 +1,319 @@
+import { useState, useEffect } from "react";
+import { useAuthStore } from "../../store/authStore";
+import { invoke } from "../../lib/tauri";
+import { CategoryWithCount, PrinterProfile } from "../../types";
+import { useToast } from "../../hooks/use-toast";
+import { Button } from "../../components/ui/button";
+import { Input } from "../../components/ui/input";
+import { Label } from "../../components/ui/label";
+import { Switch } from "../../components/ui/switch";
+import {
+  Table,
+  TableBody,
+  TableCell,
+  TableHead,
+  TableHeader,
+  TableRow,
+} from "../../components/ui/table";
+import {
+  Dialog,
+  DialogContent,
+  DialogHeader,
+  DialogTitle,
+  DialogFooter,
+} from "../../components/ui/dialog";
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from "../../components/ui/select";
+import { Plus, Pencil, Trash2, Loader2 } from "lucide-react";
+
+const NO_PRINTER = "NONE";
+
+export function KitchenPrinterSettings() {
+  const [profiles, setProfiles] = useState<PrinterProfile[]>([]);
+  const [categories, setCategories] = useState<CategoryWithCount[]>([]);
+  const [isLoading, setIsLoading] = useState(true);
+  const [isDialogOpen, setIsDialogOpen] = useState(false);
+  const [isSaving, setIsSaving] = useState(false);
+  const [editingProfile, setEditingProfile] = useState<Partial<PrinterProfile> | null>(null);
+
+  const sessionToken = useAuthStore((s) => s.sessionToken);
+  const { toast } = useToast();
+
+  useEffect(() => {
+    fetchData();
+  }, []);
+
+  const fetchData = async () => {
+    try {
+      const [profileData, categoryData] = await Promise.all([
+        invoke<PrinterProfile[]>("get_printer_profiles", { sessionToken }),
+        invoke<CategoryWithCount[]>("get_categories", { sessionToken }),
+      ]);
+      setProfiles(profileData);
+      setCategories(categoryData);
+    } catch (error) {
+      toast({
+        variant: "destructive",
+        title: "Error",
+        description: "Gagal mengambil data printer dapur/bar",
+      });
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  const handleOpenAdd = () => {
+    setEditingProfile({
+      name: "",
+      printer_port: "",
+      paper_width: "80mm",
+      is_active: true,
+    });
+    setIsDialogOpen(true);
+  };
+
+  const handleSave = async () => {
+    if (!editingProfile) return;
+    setIsSaving(true);
+    const payload = {
+      name: editingProfile.name,
+      printer_port: editingProfile.printer_port,
+      paper_width: editingProfile.paper_width,
+      is_active: editingProfile.is_active,
+    };
+    try {
+      if (editingProfile.id) {
+        await invoke("update_printer_profile", {
+          sessionToken,
+          id: editingProfile.id,
+          payload,
+        });
+      } else {
+        await invoke("create_printer_profile", { sessionToken, payload });
+      }
+      setIsDialogOpen(false);
+      fetchData();
+      toast({ title: "Berhasil", description: "Printer berhasil disimpan" });
+    } catch (error) {
+      toast({
+        variant: "destructive",
+        title: "Gagal",
+        description: String(error),
+      });
+    } finally {
+      setIsSaving(false);
+    }
+  };
+
+  const handleDelete = async (id: number) => {
+    try {
+      await invoke("delete_printer_profile", { sessionToken, id });
+      fetchData();
+    } catch (error) {
+      toast({
+        variant: "destructive",
+        title: "Gagal",
+        description: String(error),
+      });
+    }
+  };
+
+  const handleMapCategory = async (categoryId: number, value: string) => {
+    try {
+      await invoke("set_category_printer", {
+        sessionToken,
+        categoryId,
+        printerProfileId: value === NO_PRINTER ? null : Number(value),
+      });
+      setCategories((prev) =>
+        prev.map((c) =>
+          c.id === categoryId
+            ? { ...c, printer_profile_id: value === NO_PRINTER ? null : Number(value) }
+            : c
+        )
+      );
+    } catch (error) {
+      toast({
+        variant: "destructive",
+        title: "Gagal",
+        description: String(error),
+      });
+    }
+  };
+
+  if (isLoading) {
+    return (
+      <div className="flex items-center justify-center p-8">
+        <Loader2 className="h-8 w-8 animate-spin text-primary" />
+      </div>
+    );
+  }
+
+  return (
+    <div className="space-y-6">
+      <div className="space-y-2">
+        <div className="flex justify-between items-center">
+          <h3 className="font-medium">Daftar Printer</h3>
+          <Button size="sm" onClick={handleOpenAdd}>
+            <Plus className="mr-2 h-4 w-4" /> Tambah Printer
+          </Button>
+        </div>
+        <div className="border rounded-md">
+          <Table>
+            <TableHeader>
+              <TableRow>
+                <TableHead>Nama</TableHead>
+                <TableHead>Port</TableHead>
+                <TableHead>Kertas</TableHead>
+                <TableHead>Aktif</TableHead>
+                <TableHead className="text-right">Aksi</TableHead>
+              </TableRow>
+            </TableHeader>
+            <TableBody>
+              {profiles.length === 0 ? (
+                <TableRow>
+                  <TableCell colSpan={5} className="text-center py-6 text-muted-foreground">
+                    Belum ada printer dapur/bar.
+                  </TableCell>
+                </TableRow>
+              ) : (
+                profiles.map((p) => (
+                  <TableRow key={p.id}>
+                    <TableCell className="font-medium">{p.name}</TableCell>
+                    <TableCell className="font-mono text-xs">{p.printer_port}</TableCell>
+                    <TableCell>{p.paper_width}</TableCell>
+                    <TableCell>{p.is_active ? "Ya" : "Tidak"}</TableCell>
+                    <TableCell className="text-right">
+                      <Button
+                        variant="ghost"
+                        size="icon"
+                        onClick={() => {
+                          setEditingProfile(p);
+                          setIsDialogOpen(true);
+                        }}
+                      >
+                        <Pencil className="h-4 w-4" />
+                      </Button>
+                      <Button variant="ghost" size="icon" onClick={() => handleDelete(p.id)}>
+                        <Trash2 className="h-4 w-4 text-destructive" />
+                      </Button>
+                    </TableCell>
+                  </TableRow>
+                ))
+              )}
+            </TableBody>
+          </Table>
+        </div>
+      </div>
+
+      <div className="space-y-2">
+        <h3 className="font-medium">Kategori → Printer</h3>
+        <p className="text-xs text-muted-foreground">
+          Item dari kategori tanpa printer tidak dicetak di tiket.
+        </p>
+        <div className="grid grid-cols-2 gap-3 max-w-2xl">
+          {categories.map((c) => (
+            <div key={c.id} className="flex items-center gap-2">
+              <span className="w-1/2 text-sm truncate">{c.name}</span>
+              <Select
+                value={c.printer_profile_id ? String(c.printer_profile_id) : NO_PRINTER}
+                onValueChange={(val) => handleMapCategory(c.id, val)}
+              >
+                <SelectTrigger className="w-1/2">
+                  <SelectValue />
+                </SelectTrigger>
+                <SelectContent>
+                  <SelectItem value={NO_PRINTER}>Tidak dicetak</SelectItem>
+                  {profiles.map((p) => (
+                    <SelectItem key={p.id} value={String(p.id)}>
+                      {p.name}
+                    </SelectItem>
+                  ))}
+                </SelectContent>
+              </Select>
+            </div>
+          ))}
+        </div>
+      </div>
+
+      <Dialog open={isDialogOpen} onOpenChange={setIsDialogOpen}>
+        <DialogContent className="sm:max-w-[425px]">
+          <DialogHeader>
+            <DialogTitle>
+              {editingProfile?.id ? "Edit Printer" : "Tambah Printer Dapur/Bar"}
+            </DialogTitle>
+          </DialogHeader>
+          {editingProfile && (
+            <div className="grid gap-4 py-4">
+              <div className="space-y-2">
+                <Label htmlFor="profileName">Nama</Label>
+                <Input
+                  id="profileName"
+                  placeholder="Contoh: DAPUR"
+                  value={editingProfile.name}
+                  onChange={(e) =>
+                    setEditingProfile({ ...editingProfile, name: e.target.value })
+                  }
+                />
+              </div>
+              <div className="space-y-2">
+                <Label htmlFor="profilePort">Port</Label>
+                <Input
+                  id="profilePort"
+                  placeholder="COM5 / 192.168.1.60:9100"
+                  value={editingProfile.printer_port}
+                  onChange={(e) =>
+                    setEditingProfile({ ...editingProfile, printer_port: e.target.value })
+                  }
+                />
+              </div>
+              <div className="grid grid-cols-2 gap-4">
+                <div className="space-y-2">
+                  <Label>Lebar Kertas</Label>
+                  <Select
+                    value={editingProfile.paper_width}
+                    onValueChange={(val: any) =>
+                      setEditingProfile({ ...editingProfile, paper_width: val })
+                    }
+                  >
+                    <SelectTrigger>
+                      <SelectValue />
+                    </SelectTrigger>
+                    <SelectContent>
+                      <SelectItem value="58mm">58mm</SelectItem>
+                      <SelectItem value="80mm">80mm</SelectItem>
+                    </SelectContent>
+                  </Select>
+                </div>
+                <div className="flex items-center space-x-2 pt-6">
+                  <Switch
+                    id="profileActive"
+                    checked={editingProfile.is_active}
+                    onCheckedChange={(c) =>
+                      setEditingProfile({ ...editingProfile, is_active: c })
+                    }
+                  />
+                  <Label htmlFor="profileActive">Aktif</Label>
+                </div>
+              </div>
+            </div>
+          )}
+          <DialogFooter>
+            <Button variant="outline" onClick={() => setIsDialogOpen(false)}>
+              Batal
+            </Button>
+            <Button onClick={handleSave} disabled={isSaving}>
+              {isSaving && <Loader2 className="mr-2 h-4 w-4 animate-spin" />}
+              Simpan
+            </Button>
+          </DialogFooter>
+        </DialogContent>
+      </Dialog>
+    </div>
+  );
+}
@@ -21,3 +21,14 @@ export function parseCurrency(value: string): number {
     const parsed = Number(value.replace(/[^0-9.-]+/g, ""));
     return isNaN(parsed) ? 0 : parsed;
 }
+
+/**
+ * Formats a quantity: whole numbers as-is, weights with up to 3 decimals.
+ * @param quantity - The quantity (pcs or kg).
+ * @returns The formatted quantity string (e.g. "2", "0,25").
+ */
+export function formatQuantity(quantity: number): string {
+    return new Intl.NumberFormat("id-ID", {
+        maximumFractionDigits: 3,
+    }).format(quantity);
+}
@@ -22,12 +22,24 @@ import {
   Trash2,
   Upload,
   Barcode,
+  CalendarClock,
+  Truck,
+  ClipboardList,
+  PackageCheck,
+  MapPin,
+  ArrowLeftRight,
 } from "lucide-react";
 import { useToast } from "../hooks/use-toast";
 import { ProductForm } from "../features/inventory/ProductForm";
 import { CategoryManager } from "../features/inventory/CategoryManager";
 import { StockAdjust } from "../features/inventory/StockAdjust";
 import { BulkImportDialog } from "../features/inventory/BulkImportDialog";
+import { PriceScheduleManager } from "../features/inventory/PriceScheduleManager";
+import { SupplierManager } from "../features/inventory/SupplierManager";
+import { PurchaseOrderManager } from "../features/inventory/PurchaseOrderManager";
+import { GoodsReceiptDialog } from "../features/inventory/GoodsReceiptDialog";
+import { LocationManager } from "../features/inventory/LocationManager";
+import { StockTransferManager } from "../features/inventory/StockTransferManager";
 import { BarcodeLabelDialog } from "../features/inventory/BarcodeLabelDialog";
 import { ConfirmDialog } from "../components/ConfirmDialog";
 
@@ -41,6 +53,12 @@ export default function InventoryPage() {
   const [catManagerOpen, setCatManagerOpen] = useState(false);
   const [stockAdjustOpen, setStockAdjustOpen] = useState(false);
   const [importOpen, setImportOpen] = useState(false);
+  const [scheduleOpen, setScheduleOpen] = useState(false);
+  const [supplierOpen, setSupplierOpen] = useState(false);
+  const [purchaseOpen, setPurchaseOpen] = useState(false);
+  const [receiptOpen, setReceiptOpen] = useState(false);
+  const [locationOpen, setLocationOpen] = useState(false);
+  const [transferOpen, setTransferOpen] = useState(false);
   const [editingProduct, setEditingProduct] =
     useState<ProductWithCategory | null>(null);
   const [deleteConfirmOpen, setDeleteConfirmOpen] = useState(false);
@@ -196,14 +214,18 @@ export default function InventoryPage() {
             >
               {p.stock}
             </span>
-            <Button
-              variant="outline"
-              size="icon"
-              className="h-6 w-6 ml-2"
-              onClick={() => handleAdjustStock(p)}
-            >
-              <PackagePlus className="h-3 w-3" />
-            </Button>
+            {p.is_kit || p.is_recipe ? (
+              <Badge variant="outline">{p.is_kit ? "Paket" : "Resep"}</Badge>
+            ) : (
+              <Button
+                variant="outline"
+                size="icon"
+                className="h-6 w-6 ml-2"
+                onClick={() => handleAdjustStock(p)}
+              >
+                <PackagePlus className="h-3 w-3" />
+              </Button>
+            )}
           </div>
         ),
       },
@@ -275,6 +297,30 @@ export default function InventoryPage() {
             <Barcode className="mr-2 h-4 w-4" />
             Cetak Barcode
           </Button>
+          <Button variant="outline" onClick={() => setScheduleOpen(true)}>
+            <CalendarClock className="mr-2 h-4 w-4" />
+            Jadwal Harga
+          </Button>
+          <Button variant="outline" onClick={() => setSupplierOpen(true)}>
+            <Truck className="mr-2 h-4 w-4" />
+            Supplier
+          </Button>
+          <Button variant="outline" onClick={() => setPurchaseOpen(true)}>
+            <ClipboardList className="mr-2 h-4 w-4" />
+            Purchase Order
+          </Button>
+          <Button variant="outline" onClick={() => setReceiptOpen(true)}>
+            <PackageCheck className="mr-2 h-4 w-4" />
+            Terima Barang
+          </Button>
+          <Button variant="outline" onClick={() => setLocationOpen(true)}>
+            <MapPin className="mr-2 h-4 w-4" />
+            Lokasi
+          </Button>
+          <Button variant="outline" onClick={() => setTransferOpen(true)}>
+            <ArrowLeftRight className="mr-2 h-4 w-4" />
+            Transfer Stok
+          </Button>
           <Button variant="outline" onClick={() => setCatManagerOpen(true)}>
             <Settings2 className="mr-2 h-4 w-4" />
             Categories
@@ -341,6 +387,13 @@ export default function InventoryPage() {
 
       <BulkImportDialog open={importOpen} onOpenChange={setImportOpen} />
 
+      <PriceScheduleManager open={scheduleOpen} onOpenChange={setScheduleOpen} />
+      <SupplierManager open={supplierOpen} onOpenChange={setSupplierOpen} />
+      <PurchaseOrderManager open={purchaseOpen} onOpenChange={setPurchaseOpen} />
+      <GoodsReceiptDialog open={receiptOpen} onOpenChange={setReceiptOpen} />
+      <LocationManager open={locationOpen} onOpenChange={setLocationOpen} />
+      <StockTransferManager open={transferOpen} onOpenChange={setTransferOpen} />
+
       <BarcodeLabelDialog
         open={barcodePrintOpen}
         onOpenChange={setBarcodePrintOpen}
@@ -4,16 +4,19 @@ import { CartPanel } from "../features/pos/CartPanel";
 import { DiscountModal } from "../features/pos/DiscountModal";
 import { PaymentModal } from "../features/pos/PaymentModal";
 import { ReceiptDialog } from "../features/pos/ReceiptDialog";
+import { WeighDialog } from "../features/pos/WeighDialog";
 import { useBarcodeScanner } from "../hooks/useBarcodeScanner";
 import {
   ProductWithCategory,
   AppSettings,
   Discount,
   Transaction,
+  ScannedProduct,
 } from "../types";
 import { useAuthStore } from "../store/authStore";
 import { useCartStore } from "../store/cartStore";
 import { invoke } from "../lib/tauri";
+import { formatQuantity } from "../lib/currency";
 import { useToast } from "../hooks/use-toast";
 import { Clock, User as UserIcon, Calendar } from "lucide-react";
 
@@ -24,6 +27,9 @@ export default function POSPage() {
   const [lastTransaction, setLastTransaction] = useState<Transaction | null>(
     null,
   );
+  const [weighProduct, setWeighProduct] = useState<ProductWithCategory | null>(
+    null,
+  );
   const [currentTime, setCurrentTime] = useState(new Date());
 
   const sessionToken = useAuthStore((s) => s.sessionToken);
@@ -35,6 +41,9 @@ export default function POSPage() {
     getSubtotal,
     setDiscount,
     manual_discount_applied,
+    getTotal,
+    customer_id,
+    applyPrices,
   } = useCartStore();
   const { toast } = useToast();
 
@@ -64,6 +73,30 @@ export default function POSPage() {
     loadSettings();
   }, [sessionToken, setTaxConfig]);
 
+  // Harga server (level pelanggan + grosir) setiap isi keranjang berubah
+  const priceKey = items
+    .map((i) => `${i.product_id}:${i.quantity}:${i.unit_id ?? ""}:${i.price_override ?? ""}`)
+    .join("|");
+  useEffect(() => {
+    if (items.length === 0) return;
+    let cancelled = false;
+    invoke<number[]>("quote_prices", {
+      sessionToken,
+      customerId: customer_id,
+      items: items.map((i) => ({
+        product_id: i.product_id,
+        quantity: i.quantity,
+        unit_id: i.unit_id ?? null,
+        scanned_barcode: i.scanned_barcode ?? null,
+      })),
+    })
+      .then((prices) => !cancelled && applyPrices(prices))
+      .catch((error) => console.error("Failed to quote prices:", error));
+    return () => {
+      cancelled = true;
+    };
+  }, [priceKey, customer_id, sessionToken, applyPrices]);
+
   // Automatic Discount Logic
   useEffect(() => {
     if (manual_discount_applied || items.length === 0) return;
@@ -111,10 +144,27 @@ export default function POSPage() {
     applyAutoDiscount();
   }, [items, getSubtotal, sessionToken, manual_discount_applied, setDiscount]);
 
+  // Customer pole display: item terakhir + total, total saat bayar, idle saat
+  // keranjang kosong. Kembalian ditampilkan backend setelah transaksi, jadi
+  // layar idle ditahan selama dialog struk terbuka.
+  useEffect(() => {
+    if (receiptOpen) return;
+    const total = Math.round(getTotal());
+    const last = items[items.length - 1];
+    const screen = paymentModalOpen
+      ? { type: "TOTAL", total }
+      : last
+        ? { type: "ITEM", name: last.product_name, price: last.price, total }
+        : { type: "IDLE" };
+    invoke("update_customer_display", { sessionToken, screen }).catch((error) =>
+      console.error("Failed to update customer display:", error),
+    );
+  }, [items, paymentModalOpen, receiptOpen, getTotal, sessionToken]);
+
   useBarcodeScanner({
     onScan: async (barcode) => {
       try {
-        const product = await invoke<ProductWithCategory>(
+        const product = await invoke<ScannedProduct>(
           "get_product_by_barcode",
           {
             sessionToken,
@@ -124,9 +174,39 @@ export default function POSPage() {
 
         // Check stock before adding
         const cartItem = items.find((item) => item.product_id === product.id);
-        const currentQuantity = cartItem ? cartItem.quantity : 0;
+        const currentQuantity = cartItem
+          ? cartItem.quantity * (cartItem.unit_conversion ?? 1)
+          : 0;
 
-        if (product.stock > 0 && currentQuantity < product.stock) {
+        if (product.scanned_quantity !== null) {
+          // Label timbangan: jumlah & harga sudah tertanam di barcode
+          const quantity = product.scanned_quantity;
+          if (currentQuantity + quantity > product.stock) {
+            toast({
+              variant: "destructive",
+              title: "Stok Tidak Mencukupi",
+              description: `${product.name} hanya tersisa ${product.stock}.`,
+            });
+            return;
+          }
+          // Harga label bukan harga manual: barcode ikut dikirim dan harganya
+          // dihitung ulang di server
+          addItem({
+            product_id: product.id,
+            product_name: product.name,
+            price: product.scanned_price ?? product.price,
+            quantity,
+            discount_amount: 0,
+            is_weighed: product.is_weighed,
+            scanned_barcode: product.scanned_price !== null ? barcode : null,
+          });
+          toast({
+            title: "Berhasil Menambahkan",
+            description: `${product.name} (${formatQuantity(quantity)}${product.is_weighed ? " kg" : ""}) ditambahkan.`,
+          });
+        } else if (product.is_weighed && product.stock > 0) {
+          setWeighProduct(product);
+        } else if (product.stock > 0 && currentQuantity < product.stock) {
           addItem({
             product_id: product.id,
             product_name: product.name,
@@ -237,6 +317,21 @@ export default function POSPage() {
         onOpenChange={setReceiptOpen}
         transaction={lastTransaction}
       />
+
+      <WeighDialog
+        product={weighProduct}
+        onOpenChange={(open) => !open && setWeighProduct(null)}
+        onConfirm={(p, weightKg) =>
+          addItem({
+            product_id: p.id,
+            product_name: p.name,
+            price: p.price,
+            quantity: weightKg,
+            discount_amount: 0,
+            is_weighed: true,
+          })
+        }
+      />
     </div>
   );
 }
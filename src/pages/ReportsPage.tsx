@@ -1,12 +1,15 @@
 import { useState } from "react";
 import { SalesChart } from "../features/reports/SalesChart";
 import { TopProducts } from "../features/reports/TopProducts";
+import { CategorySales } from "../features/reports/CategorySales";
 import { ShiftSummary } from "../features/reports/ShiftSummary";
 import { FinancialSummaryCards } from "../features/reports/FinancialSummaryCards";
 import { PaymentMethodsChart } from "../features/reports/PaymentMethodsChart";
 import { TransactionHistory } from "../features/reports/TransactionHistory";
 import { AuditTrail } from "../features/reports/AuditTrail";
 import { StockRestockHistory } from "../features/reports/StockRestockHistory";
+import { IngredientUsageReport } from "../features/reports/IngredientUsageReport";
+import { ExpiringBatchesReport } from "../features/reports/ExpiringBatchesReport";
 import { Button } from "../components/ui/button";
 import {
   BarChart3,
@@ -321,6 +324,10 @@ export default function ReportsPage() {
                 startDate={dateRange.start}
                 endDate={dateRange.end}
               />
+              <CategorySales
+                startDate={dateRange.start}
+                endDate={dateRange.end}
+              />
             </div>
           </TabsContent>
 
@@ -331,7 +338,12 @@ export default function ReportsPage() {
             />
           </TabsContent>
 
-          <TabsContent value="inventory" className="mt-0">
+          <TabsContent value="inventory" className="space-y-5 mt-0">
+            <IngredientUsageReport
+              startDate={dateRange.start}
+              endDate={dateRange.end}
+            />
+            <ExpiringBatchesReport />
             <StockRestockHistory />
           </TabsContent>
 
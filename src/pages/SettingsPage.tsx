@@ -1,6 +1,6 @@
 import { useState, useEffect } from "react";
 import { useInvokeQuery, useInvokeMutation } from "../hooks/useInvokeQuery";
-import { AppSettings } from "../types";
+import { AppSettings, Location, ScaleBarcodeRule } from "../types";
 import { useAuthStore } from "../store/authStore";
 import {
   Card,
@@ -35,10 +35,13 @@ import {
   Tag,
   User as UserIcon,
   CreditCard,
+  Users,
 } from "lucide-react";
 import { useToast } from "../hooks/use-toast";
 import { DiscountSettings } from "../features/settings/DiscountSettings";
+import { CustomerSettings } from "../features/settings/CustomerSettings";
 import { PaymentSettings } from "../features/settings/PaymentSettings";
+import { KitchenPrinterSettings } from "../features/settings/KitchenPrinterSettings";
 import { NumericInput } from "../components/NumericInput";
 import { invoke } from "../lib/tauri";
 
@@ -51,6 +54,11 @@ export default function SettingsPage() {
     "get_settings",
     { sessionToken },
   );
+  const { data: locations } = useInvokeQuery<Location[]>(
+    ["locations", false],
+    "get_locations",
+    { sessionToken, showInactive: false },
+  );
 
   const [settings, setSettings] = useState<AppSettings | null>(null);
   const [profileData, setProfileData] = useState({
@@ -135,6 +143,39 @@ export default function SettingsPage() {
     setSettings({ ...settings, tax: { ...settings.tax, [key]: val } });
   };
 
+  const updateDrawer = (key: keyof AppSettings["drawer"], val: any) => {
+    if (!settings) return;
+    setSettings({ ...settings, drawer: { ...settings.drawer, [key]: val } });
+  };
+
+  const updateLabel = (key: keyof AppSettings["label"], val: any) => {
+    if (!settings) return;
+    setSettings({ ...settings, label: { ...settings.label, [key]: val } });
+  };
+
+  const updateDisplay = (key: keyof AppSettings["display"], val: any) => {
+    if (!settings) return;
+    setSettings({ ...settings, display: { ...settings.display, [key]: val } });
+  };
+
+  const updateScale = (key: keyof AppSettings["scale"], val: any) => {
+    if (!settings) return;
+    setSettings({ ...settings, scale: { ...settings.scale, [key]: val } });
+  };
+
+  const updateScaleBarcode = (key: keyof AppSettings["scale_barcode"], val: any) => {
+    if (!settings) return;
+    setSettings({ ...settings, scale_barcode: { ...settings.scale_barcode, [key]: val } });
+  };
+
+  const updateScaleBarcodeRule = (index: number, patch: Partial<ScaleBarcodeRule>) => {
+    if (!settings) return;
+    updateScaleBarcode(
+      "rules",
+      settings.scale_barcode.rules.map((rule, i) => (i === index ? { ...rule, ...patch } : rule)),
+    );
+  };
+
   const updateRoot = (key: keyof AppSettings, val: any) => {
     if (!settings) return;
     setSettings({ ...settings, [key]: val });
@@ -164,7 +205,7 @@ export default function SettingsPage() {
       </div>
 
       <Tabs defaultValue="company" className="w-full">
-        <TabsList className="grid w-full grid-cols-7 mb-4">
+        <TabsList className="grid w-full grid-cols-8 mb-4">
           <TabsTrigger value="company">
             <Store className="h-4 w-4 mr-2" /> Store
           </TabsTrigger>
@@ -177,6 +218,9 @@ export default function SettingsPage() {
           <TabsTrigger value="discounts">
             <Tag className="h-4 w-4 mr-2" /> Discounts
           </TabsTrigger>
+          <TabsTrigger value="customers">
+            <Users className="h-4 w-4 mr-2" /> Customers
+          </TabsTrigger>
           <TabsTrigger value="account">
             <UserIcon className="h-4 w-4 mr-2" /> Account
           </TabsTrigger>
@@ -323,6 +367,36 @@ export default function SettingsPage() {
                   />
                 </div>
               </div>
+
+              <div className="grid grid-cols-2 gap-4">
+                <div className="space-y-2">
+                  <Label>Receipt QR Code</Label>
+                  <Select
+                    value={settings.receipt.qr_mode || "NONE"}
+                    onValueChange={(val) => updateReceipt("qr_mode", val)}
+                  >
+                    <SelectTrigger>
+                      <SelectValue />
+                    </SelectTrigger>
+                    <SelectContent>
+                      <SelectItem value="NONE">None</SelectItem>
+                      <SelectItem value="TRANSACTION">Transaction Link</SelectItem>
+                      <SelectItem value="QRIS">QRIS Payload</SelectItem>
+                    </SelectContent>
+                  </Select>
+                </div>
+                <div className="space-y-2">
+                  <Label>QR Link Template</Label>
+                  <Input
+                    value={settings.receipt.qr_link_template}
+                    placeholder="https://tokosaya.id/struk/{id}"
+                    disabled={settings.receipt.qr_mode !== "TRANSACTION"}
+                    onChange={(e) =>
+                      updateReceipt("qr_link_template", e.target.value)
+                    }
+                  />
+                </div>
+              </div>
             </CardContent>
           </Card>
         </TabsContent>
@@ -399,6 +473,14 @@ export default function SettingsPage() {
           </Card>
         </TabsContent>
 
+        <TabsContent value="customers">
+          <Card>
+            <CardContent className="pt-6">
+              <CustomerSettings />
+            </CardContent>
+          </Card>
+        </TabsContent>
+
         <TabsContent value="account">
           <Card>
             <CardHeader>
@@ -456,6 +538,409 @@ export default function SettingsPage() {
                 onPortChange={(val) => updateRoot("printer_port", val)}
               />
 
+              <div className="border-t pt-6 space-y-4">
+                <div className="flex items-center space-x-2">
+                  <Checkbox
+                    id="drawerEnabled"
+                    checked={settings.drawer.enabled}
+                    onCheckedChange={(c) => updateDrawer("enabled", !!c)}
+                  />
+                  <Label htmlFor="drawerEnabled" className="cursor-pointer">
+                    Laci kas terhubung ke printer
+                  </Label>
+                </div>
+                <div className="flex items-center space-x-2">
+                  <Checkbox
+                    id="drawerOnCash"
+                    checked={settings.drawer.open_on_cash}
+                    disabled={!settings.drawer.enabled}
+                    onCheckedChange={(c) => updateDrawer("open_on_cash", !!c)}
+                  />
+                  <Label htmlFor="drawerOnCash" className="cursor-pointer">
+                    Buka laci otomatis setelah transaksi tunai
+                  </Label>
+                </div>
+                <div className="grid grid-cols-3 gap-4 max-w-xl">
+                  <div className="space-y-2">
+                    <Label>Pin Laci</Label>
+                    <Select
+                      value={String(settings.drawer.pin)}
+                      onValueChange={(val) => updateDrawer("pin", Number(val))}
+                    >
+                      <SelectTrigger>
+                        <SelectValue />
+                      </SelectTrigger>
+                      <SelectContent>
+                        <SelectItem value="2">Pin 2</SelectItem>
+                        <SelectItem value="5">Pin 5</SelectItem>
+                      </SelectContent>
+                    </Select>
+                  </div>
+                  <div className="space-y-2">
+                    <Label>Pulse ON (ms)</Label>
+                    <NumericInput
+                      value={settings.drawer.pulse_on_ms}
+                      onChange={(val) => updateDrawer("pulse_on_ms", val)}
+                    />
+                  </div>
+                  <div className="space-y-2">
+                    <Label>Pulse OFF (ms)</Label>
+                    <NumericInput
+                      value={settings.drawer.pulse_off_ms}
+                      onChange={(val) => updateDrawer("pulse_off_ms", val)}
+                    />
+                  </div>
+                </div>
+              </div>
+
+              <div className="border-t pt-6 space-y-4">
+                <div>
+                  <h3 className="font-medium">Printer Label</h3>
+                  <p className="text-xs text-muted-foreground">
+                    Kosongkan port untuk mencetak label lewat printer struk
+                    (ESC/POS).
+                  </p>
+                </div>
+                <div className="grid grid-cols-2 gap-4 max-w-xl">
+                  <div className="space-y-2">
+                    <Label>Port Printer Label</Label>
+                    <Input
+                      value={settings.label.printer_port}
+                      onChange={(e) => updateLabel("printer_port", e.target.value)}
+                      placeholder="COM4 / /dev/usb/lp1 / 192.168.1.50:9100"
+                    />
+                  </div>
+                  <div className="space-y-2">
+                    <Label>Bahasa Printer</Label>
+                    <Select
+                      value={settings.label.language}
+                      onValueChange={(val) => updateLabel("language", val)}
+                    >
+                      <SelectTrigger>
+                        <SelectValue />
+                      </SelectTrigger>
+                      <SelectContent>
+                        <SelectItem value="ESCPOS">ESC/POS</SelectItem>
+                        <SelectItem value="TSPL">TSPL (TSC, Xprinter)</SelectItem>
+                        <SelectItem value="ZPL">ZPL (Zebra)</SelectItem>
+                      </SelectContent>
+                    </Select>
+                  </div>
+                </div>
+                <div className="grid grid-cols-3 gap-4 max-w-xl">
+                  <div className="space-y-2">
+                    <Label>Ukuran Label</Label>
+                    <Select
+                      value={settings.label.size}
+                      onValueChange={(val) => updateLabel("size", val)}
+                    >
+                      <SelectTrigger>
+                        <SelectValue />
+                      </SelectTrigger>
+                      <SelectContent>
+                        <SelectItem value="33x15">33 x 15 mm</SelectItem>
+                        <SelectItem value="50x30">50 x 30 mm</SelectItem>
+                      </SelectContent>
+                    </Select>
+                  </div>
+                  <div className="space-y-2">
+                    <Label>Resolusi</Label>
+                    <Select
+                      value={String(settings.label.dpi)}
+                      onValueChange={(val) => updateLabel("dpi", Number(val))}
+                    >
+                      <SelectTrigger>
+                        <SelectValue />
+                      </SelectTrigger>
+                      <SelectContent>
+                        <SelectItem value="203">203 dpi</SelectItem>
+                        <SelectItem value="300">300 dpi</SelectItem>
+                      </SelectContent>
+                    </Select>
+                  </div>
+                  <div className="space-y-2">
+                    <Label>Jarak Antar Label (mm)</Label>
+                    <NumericInput
+                      value={settings.label.gap_mm}
+                      onChange={(val) => updateLabel("gap_mm", val)}
+                    />
+                  </div>
+                </div>
+              </div>
+
+              <div className="border-t pt-6 space-y-4">
+                <div className="flex items-center space-x-2">
+                  <Checkbox
+                    id="displayEnabled"
+                    checked={settings.display.enabled}
+                    onCheckedChange={(c) => updateDisplay("enabled", !!c)}
+                  />
+                  <Label htmlFor="displayEnabled" className="cursor-pointer">
+                    Pole display pelanggan (VFD 2x20)
+                  </Label>
+                </div>
+                <div className="grid grid-cols-3 gap-4 max-w-xl">
+                  <div className="space-y-2">
+                    <Label>Port Serial</Label>
+                    <Input
+                      value={settings.display.port}
+                      onChange={(e) => updateDisplay("port", e.target.value)}
+                      placeholder="COM3 / /dev/ttyUSB1"
+                    />
+                  </div>
+                  <div className="space-y-2">
+                    <Label>Baud Rate</Label>
+                    <Select
+                      value={String(settings.display.baud_rate)}
+                      onValueChange={(val) => updateDisplay("baud_rate", Number(val))}
+                    >
+                      <SelectTrigger>
+                        <SelectValue />
+                      </SelectTrigger>
+                      <SelectContent>
+                        <SelectItem value="2400">2400</SelectItem>
+                        <SelectItem value="9600">9600</SelectItem>
+                        <SelectItem value="19200">19200</SelectItem>
+                      </SelectContent>
+                    </Select>
+                  </div>
+                  <div className="space-y-2">
+                    <Label>Mode</Label>
+                    <Select
+                      value={settings.display.protocol}
+                      onValueChange={(val) => updateDisplay("protocol", val)}
+                    >
+                      <SelectTrigger>
+                        <SelectValue />
+                      </SelectTrigger>
+                      <SelectContent>
+                        <SelectItem value="ESCPOS">Epson ESC/POS</SelectItem>
+                        <SelectItem value="CD5220">CD5220</SelectItem>
+                      </SelectContent>
+                    </Select>
+                  </div>
+                </div>
+                <div className="flex items-end gap-2 max-w-xl">
+                  <div className="space-y-2 flex-1">
+                    <Label>Pesan Idle</Label>
+                    <Input
+                      value={settings.display.idle_message}
+                      maxLength={40}
+                      onChange={(e) => updateDisplay("idle_message", e.target.value)}
+                    />
+                  </div>
+                  <Button
+                    variant="outline"
+                    onClick={async () => {
+                      try {
+                        await invoke("test_customer_display", {
+                          sessionToken,
+                          display: settings.display,
+                        });
+                        toast({ title: "Berhasil", description: "Pesan terkirim ke display" });
+                      } catch (e) {
+                        toast({
+                          variant: "destructive",
+                          title: "Gagal",
+                          description: String(e),
+                        });
+                      }
+                    }}
+                  >
+                    Tes Display
+                  </Button>
+                </div>
+              </div>
+
+              <div className="border-t pt-6 space-y-4">
+                <div className="flex items-center space-x-2">
+                  <Checkbox
+                    id="scaleEnabled"
+                    checked={settings.scale.enabled}
+                    onCheckedChange={(c) => updateScale("enabled", !!c)}
+                  />
+                  <Label htmlFor="scaleEnabled" className="cursor-pointer">
+                    Timbangan elektronik (produk timbang)
+                  </Label>
+                </div>
+                <div className="grid grid-cols-4 gap-4 max-w-2xl">
+                  <div className="space-y-2">
+                    <Label>Port Serial</Label>
+                    <Input
+                      value={settings.scale.port}
+                      onChange={(e) => updateScale("port", e.target.value)}
+                      placeholder="COM4 / /dev/ttyUSB2"
+                    />
+                  </div>
+                  <div className="space-y-2">
+                    <Label>Baud Rate</Label>
+                    <Select
+                      value={String(settings.scale.baud_rate)}
+                      onValueChange={(val) => updateScale("baud_rate", Number(val))}
+                    >
+                      <SelectTrigger>
+                        <SelectValue />
+                      </SelectTrigger>
+                      <SelectContent>
+                        <SelectItem value="2400">2400</SelectItem>
+                        <SelectItem value="4800">4800</SelectItem>
+                        <SelectItem value="9600">9600</SelectItem>
+                        <SelectItem value="19200">19200</SelectItem>
+                      </SelectContent>
+                    </Select>
+                  </div>
+                  <div className="space-y-2">
+                    <Label>Mode</Label>
+                    <Select
+                      value={settings.scale.protocol}
+                      onValueChange={(val) => updateScale("protocol", val)}
+                    >
+                      <SelectTrigger>
+                        <SelectValue />
+                      </SelectTrigger>
+                      <SelectContent>
+                        <SelectItem value="CONTINUOUS">Continuous</SelectItem>
+                        <SelectItem value="POLLED">Polled</SelectItem>
+                      </SelectContent>
+                    </Select>
+                  </div>
+                  <div className="space-y-2">
+                    <Label>Perintah Poll</Label>
+                    <Input
+                      value={settings.scale.poll_command}
+                      disabled={settings.scale.protocol !== "POLLED"}
+                      onChange={(e) => updateScale("poll_command", e.target.value)}
+                    />
+                  </div>
+                </div>
+
+                <div className="flex items-center space-x-2 pt-2">
+                  <Checkbox
+                    id="scaleBarcodeEnabled"
+                    checked={settings.scale_barcode.enabled}
+                    onCheckedChange={(c) => updateScaleBarcode("enabled", !!c)}
+                  />
+                  <Label htmlFor="scaleBarcodeEnabled" className="cursor-pointer">
+                    Baca barcode label timbangan (EAN-13 prefix 20–29)
+                  </Label>
+                </div>
+                {settings.scale_barcode.enabled && (
+                  <div className="space-y-2 max-w-3xl">
+                    <div className="grid grid-cols-[80px_80px_110px_80px_110px_110px_40px] gap-2 text-xs text-muted-foreground">
+                      <span>Prefix</span>
+                      <span>Digit PLU</span>
+                      <span>Nilai</span>
+                      <span>Desimal</span>
+                      <span>Cek harga</span>
+                      <span>Validasi cek</span>
+                      <span />
+                    </div>
+                    {settings.scale_barcode.rules.map((rule, index) => (
+                      <div
+                        key={index}
+                        className="grid grid-cols-[80px_80px_110px_80px_110px_110px_40px] gap-2 items-center"
+                      >
+                        <Input
+                          value={rule.prefix}
+                          maxLength={3}
+                          onChange={(e) =>
+                            updateScaleBarcodeRule(index, {
+                              prefix: e.target.value.replace(/\D/g, ""),
+                            })
+                          }
+                        />
+                        <Input
+                          type="number"
+                          min={1}
+                          max={7}
+                          value={rule.plu_length}
+                          onChange={(e) =>
+                            updateScaleBarcodeRule(index, {
+                              plu_length: Number(e.target.value),
+                            })
+                          }
+                        />
+                        <Select
+                          value={rule.value_type}
+                          onValueChange={(val) =>
+                            updateScaleBarcodeRule(index, {
+                              value_type: val as "WEIGHT" | "PRICE",
+                            })
+                          }
+                        >
+                          <SelectTrigger>
+                            <SelectValue />
+                          </SelectTrigger>
+                          <SelectContent>
+                            <SelectItem value="WEIGHT">Berat</SelectItem>
+                            <SelectItem value="PRICE">Harga</SelectItem>
+                          </SelectContent>
+                        </Select>
+                        <Input
+                          type="number"
+                          min={0}
+                          max={3}
+                          value={rule.value_decimals}
+                          onChange={(e) =>
+                            updateScaleBarcodeRule(index, {
+                              value_decimals: Number(e.target.value),
+                            })
+                          }
+                        />
+                        <Checkbox
+                          checked={rule.value_check_digit}
+                          onCheckedChange={(c) =>
+                            updateScaleBarcodeRule(index, { value_check_digit: !!c })
+                          }
+                        />
+                        <Checkbox
+                          checked={rule.verify_check_digit}
+                          onCheckedChange={(c) =>
+                            updateScaleBarcodeRule(index, { verify_check_digit: !!c })
+                          }
+                        />
+                        <Button
+                          variant="ghost"
+                          size="sm"
+                          onClick={() =>
+                            updateScaleBarcode(
+                              "rules",
+                              settings.scale_barcode.rules.filter((_, i) => i !== index),
+                            )
+                          }
+                        >
+                          ✕
+                        </Button>
+                      </div>
+                    ))}
+                    <Button
+                      variant="outline"
+                      size="sm"
+                      onClick={() =>
+                        updateScaleBarcode("rules", [
+                          ...settings.scale_barcode.rules,
+                          {
+                            prefix: "2",
+                            plu_length: 5,
+                            value_type: "WEIGHT",
+                            value_decimals: 3,
+                            value_check_digit: false,
+                            verify_check_digit: true,
+                          },
+                        ])
+                      }
+                    >
+                      Tambah Aturan
+                    </Button>
+                    <p className="text-xs text-muted-foreground">
+                      PLU dicocokkan ke SKU atau barcode produk. Sisa digit
+                      setelah prefix, PLU, dan digit cek harga adalah nilai
+                      berat (kg) atau total harga.
+                    </p>
+                  </div>
+                )}
+              </div>
+
               <div className="border-t pt-6">
                 <div className="space-y-2 max-w-md">
                   <Label>Low Stock Alert Threshold</Label>
@@ -468,7 +953,68 @@ export default function SettingsPage() {
                     kritis.
                   </p>
                 </div>
+                <div className="space-y-2 max-w-md mt-4">
+                  <Label>Peringatan Kedaluwarsa (hari)</Label>
+                  <NumericInput
+                    value={settings.expiry_warning_days}
+                    onChange={(val) => updateRoot("expiry_warning_days", val)}
+                  />
+                  <p className="text-xs text-muted-foreground">
+                    Batch yang kedaluwarsa dalam jumlah hari ini ikut tampil di
+                    notifikasi stok dan laporan kedaluwarsa.
+                  </p>
+                </div>
+                <div className="space-y-2 max-w-md mt-4">
+                  <Label>Lokasi Stok Kasir Ini</Label>
+                  <Select
+                    value={String(settings.location_id ?? "default")}
+                    onValueChange={(val) =>
+                      updateRoot("location_id", val === "default" ? null : Number(val))
+                    }
+                  >
+                    <SelectTrigger>
+                      <SelectValue />
+                    </SelectTrigger>
+                    <SelectContent>
+                      <SelectItem value="default">Lokasi utama</SelectItem>
+                      {locations
+                        ?.filter((l) => !l.is_default)
+                        .map((l) => (
+                          <SelectItem key={l.id} value={String(l.id)}>
+                            {l.name}
+                          </SelectItem>
+                        ))}
+                    </SelectContent>
+                  </Select>
+                  <p className="text-xs text-muted-foreground">
+                    Penjualan di kasir ini memotong stok dari lokasi tersebut.
+                  </p>
+                </div>
+              </div>
+            </CardContent>
+          </Card>
+
+          <Card className="mt-4">
+            <CardHeader>
+              <CardTitle>Printer Dapur / Bar</CardTitle>
+              <CardDescription>
+                Tiket pesanan dikirim ke printer sesuai kategori produk.
+              </CardDescription>
+            </CardHeader>
+            <CardContent className="space-y-6">
+              <div className="flex items-center space-x-2">
+                <Checkbox
+                  id="kitchenAutoPrint"
+                  checked={settings.kitchen.auto_print}
+                  onCheckedChange={(c) =>
+                    updateRoot("kitchen", { ...settings.kitchen, auto_print: !!c })
+                  }
+                />
+                <Label htmlFor="kitchenAutoPrint" className="cursor-pointer">
+                  Cetak tiket otomatis setelah transaksi
+                </Label>
               </div>
+              <KitchenPrinterSettings />
             </CardContent>
           </Card>
         </TabsContent>
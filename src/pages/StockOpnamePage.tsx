@@ -0,0 +1,132 @@
+import { useState } from "react";
+import { useInvokeQuery, useInvokeMutation } from "../hooks/useInvokeQuery";
+import { OpnameSession } from "../types";
+import { useAuthStore } from "../store/authStore";
+import { useQueryClient } from "@tanstack/react-query";
+import { useToast } from "../hooks/use-toast";
+import { Button } from "../components/ui/button";
+import { Input } from "../components/ui/input";
+import { Badge } from "../components/ui/badge";
+import { Card, CardHeader, CardTitle, CardContent } from "../components/ui/card";
+import { OpnameCountPanel } from "../features/opname/OpnameCountPanel";
+import { OpnameReview } from "../features/opname/OpnameReview";
+import { format } from "date-fns";
+import { ClipboardCheck } from "lucide-react";
+import { LocationSelect } from "../features/inventory/LocationSelect";
+
+export default function StockOpnamePage() {
+  const sessionToken = useAuthStore((s) => s.sessionToken);
+  const isAdmin = useAuthStore((s) => s.isAdmin());
+  const [name, setName] = useState("");
+  const [locationId, setLocationId] = useState<number | null>(null);
+  const [selectedId, setSelectedId] = useState<number | null>(null);
+
+  const queryClient = useQueryClient();
+  const { toast } = useToast();
+
+  const { data: sessions } = useInvokeQuery<OpnameSession[]>(
+    ["opname-sessions"],
+    "get_opname_sessions",
+    { sessionToken, status: null },
+  );
+  const openSession = sessions?.find((s) => s.status === "OPEN") ?? null;
+  const reviewId = selectedId ?? openSession?.id ?? null;
+
+  const createMutation = useInvokeMutation<OpnameSession>("create_opname_session", {
+    onSuccess: (s) => {
+      queryClient.invalidateQueries({ queryKey: ["opname-sessions"] });
+      setName("");
+      setSelectedId(null);
+      toast({ title: "Sesi Dibuka", description: `${s.item_count} produk di-snapshot` });
+    },
+    onError: (e) => toast({ variant: "destructive", title: "Gagal", description: String(e) }),
+  });
+
+  return (
+    <div className="p-6 space-y-6 h-full flex flex-col">
+      <div className="flex items-center justify-between">
+        <div>
+          <h1 className="text-3xl font-bold tracking-tight">Stock Opname</h1>
+          <p className="text-muted-foreground">
+            Hitung fisik stok, bandingkan dengan sistem, lalu posting selisihnya.
+          </p>
+        </div>
+        {isAdmin && !openSession && (
+          <div className="flex items-end gap-2">
+            <div className="w-48">
+              <LocationSelect value={locationId} onChange={setLocationId} />
+            </div>
+            <Input
+              className="w-64"
+              placeholder={`Opname ${format(new Date(), "MMMM yyyy")}`}
+              value={name}
+              onChange={(e) => setName(e.target.value)}
+            />
+            <Button
+              disabled={createMutation.isPending}
+              onClick={() =>
+                createMutation.mutate({
+                  sessionToken,
+                  name: name.trim() || `Opname ${format(new Date(), "MMMM yyyy")}`,
+                  notes: null,
+                  locationId,
+                })
+              }
+            >
+              <ClipboardCheck className="mr-2 h-4 w-4" />
+              Buka Sesi
+            </Button>
+          </div>
+        )}
+      </div>
+
+      {openSession ? (
+        <OpnameCountPanel session={openSession} />
+      ) : (
+        !isAdmin && (
+          <p className="text-sm text-muted-foreground text-center py-8">
+            Tidak ada sesi stock opname yang sedang berjalan.
+          </p>
+        )
+      )}
+
+      {isAdmin && (
+        <div className="flex-1 flex gap-6 min-h-0">
+          <Card className="w-72 overflow-hidden flex flex-col">
+            <CardHeader className="py-4 border-b">
+              <CardTitle className="text-lg">Riwayat Sesi</CardTitle>
+            </CardHeader>
+            <CardContent className="flex-1 p-2 overflow-auto space-y-1">
+              {sessions?.length === 0 && (
+                <p className="text-sm text-muted-foreground text-center py-4">Belum ada sesi.</p>
+              )}
+              {sessions?.map((s) => (
+                <button
+                  key={s.id}
+                  onClick={() => setSelectedId(s.id)}
+                  className={`w-full text-left rounded-md px-3 py-2 text-sm hover:bg-muted ${
+                    s.id === reviewId ? "bg-muted" : ""
+                  }`}
+                >
+                  <div className="flex items-center justify-between gap-2">
+                    <span className="font-medium truncate">{s.name}</span>
+                    <Badge variant={s.status === "OPEN" ? "default" : "outline"}>{s.status}</Badge>
+                  </div>
+                  <div className="text-xs text-muted-foreground">
+                    {s.created_at
+                      ? format(new Date(s.created_at.replace(" ", "T") + "Z"), "dd MMM yyyy")
+                      : "-"}
+                    {" · "}
+                    {s.counted_count}/{s.item_count} dihitung
+                    {s.location_name && ` · ${s.location_name}`}
+                  </div>
+                </button>
+              ))}
+            </CardContent>
+          </Card>
+          {reviewId && <OpnameReview key={reviewId} sessionId={reviewId} />}
+        </div>
+      )}
+    </div>
+  );
+}
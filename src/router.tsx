@@ -8,10 +8,12 @@ import {
 import { useAuthStore } from "./store/authStore";
 import { Sidebar } from "./components/Sidebar";
 import { LowStockAlert } from "./components/LowStockAlert";
+import { PrintQueueAlert } from "./components/PrintQueueAlert";
 
 import LoginPage from "./pages/LoginPage";
 import FirstSetupPage from "./pages/FirstSetupPage";
 import POSPage from "./pages/POSPage";
+import StockOpnamePage from "./pages/StockOpnamePage";
 import InventoryPage from "./pages/InventoryPage";
 import ManageUsersPage from "./pages/ManageUsersPage";
 import ReportsPage from "./pages/ReportsPage";
@@ -51,6 +53,7 @@ const protectedRoute = createRoute({
         <Outlet />
       </main>
       <LowStockAlert />
+      <PrintQueueAlert />
     </div>
   ),
 });
@@ -72,6 +75,12 @@ const posRoute = createRoute({
   component: POSPage,
 });
 
+const opnameRoute = createRoute({
+  getParentRoute: () => protectedRoute,
+  path: "/opname",
+  component: StockOpnamePage,
+});
+
 const adminProtectedRoute = createRoute({
   getParentRoute: () => protectedRoute,
   id: "_admin",
@@ -116,6 +125,7 @@ const routeTree = rootRoute.addChildren([
   protectedRoute.addChildren([
     indexRoute,
     posRoute,
+    opnameRoute,
     adminProtectedRoute.addChildren([
       inventoryRoute,
       usersRoute,
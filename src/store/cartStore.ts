@@ -7,8 +7,22 @@ export interface CartItem {
     price: number;
     quantity: number;
     discount_amount: number;
+    notes?: string; // catatan dapur/bar, mis. "tanpa es"
+    is_weighed?: boolean; // quantity dalam kg (desimal)
+    unit_id?: number | null; // satuan jual (null = satuan dasar)
+    unit_name?: string;
+    unit_conversion?: number; // satuan dasar per 1 satuan jual
+    base_price?: number; // harga satuan dasar, untuk kembali dari satuan lain
+    price_override?: number | null; // harga manual; butuh persetujuan admin saat bayar
+    scanned_barcode?: string | null; // label timbangan; harga label dihitung ulang server
 }
 
+// Produk timbang boleh desimal (3 angka, gram); produk biasa minimal 1 pcs
+const normalizeQuantity = (item: CartItem, quantity: number) =>
+    item.is_weighed
+        ? Math.max(0.001, Math.round(quantity * 1000) / 1000)
+        : Math.max(1, Math.round(quantity));
+
 interface CartState {
     items: CartItem[];
     // Discount state
@@ -18,6 +32,10 @@ interface CartState {
     discount_percent: number | null;
     manual_discount_applied: boolean;
 
+    // Pelanggan (level harga); harga item diperbarui dari server
+    customer_id: number | null;
+    customer_name: string | null;
+
     // Tax state
     tax_rate: number;
     tax_included: boolean;
@@ -29,6 +47,16 @@ interface CartState {
     setQuantity: (product_id: number, quantity: number) => void;
     removeItem: (product_id: number) => void;
     setItemDiscount: (product_id: number, discount_amount: number) => void;
+    setItemNotes: (product_id: number, notes: string) => void;
+    setItemUnit: (
+        product_id: number,
+        unit: { id: number; name: string; conversion: number; price: number } | null,
+    ) => void;
+
+    setItemPriceOverride: (product_id: number, price: number | null) => void;
+    hasPriceOverride: () => boolean;
+    setCustomer: (customer: { id: number; name: string } | null) => void;
+    applyPrices: (prices: number[]) => void;
 
     setDiscount: (id: number | null, name: string | null, amount: number, percent?: number | null, isManual?: boolean) => void;
     setTaxConfig: (rate: number, included: boolean, label: string, enabled: boolean) => void;
@@ -49,6 +77,9 @@ export const useCartStore = create<CartState>()((set, get) => ({
     discount_percent: null,
     manual_discount_applied: false,
 
+    customer_id: null,
+    customer_name: null,
+
     tax_rate: 0,
     tax_included: false,
     tax_label: "PPN",
@@ -61,7 +92,7 @@ export const useCartStore = create<CartState>()((set, get) => ({
                 return {
                     items: state.items.map((i) =>
                         i.product_id === newItem.product_id
-                            ? { ...i, quantity: i.quantity + newItem.quantity }
+                            ? { ...i, quantity: normalizeQuantity(i, i.quantity + newItem.quantity) }
                             : i
                     ),
                 };
@@ -73,7 +104,7 @@ export const useCartStore = create<CartState>()((set, get) => ({
         set((state) => ({
             items: state.items.map((i) =>
                 i.product_id === product_id
-                    ? { ...i, quantity: Math.max(1, i.quantity + delta) }
+                    ? { ...i, quantity: normalizeQuantity(i, i.quantity + delta) }
                     : i
             ),
         })),
@@ -82,7 +113,7 @@ export const useCartStore = create<CartState>()((set, get) => ({
         set((state) => ({
             items: state.items.map((i) =>
                 i.product_id === product_id
-                    ? { ...i, quantity: Math.max(1, quantity) }
+                    ? { ...i, quantity: normalizeQuantity(i, quantity) }
                     : i
             ),
         })),
@@ -96,11 +127,81 @@ export const useCartStore = create<CartState>()((set, get) => ({
             ),
         })),
 
+    setItemNotes: (product_id, notes) =>
+        set((state) => ({
+            items: state.items.map((i) =>
+                i.product_id === product_id ? { ...i, notes } : i
+            ),
+        })),
+
+    setItemUnit: (product_id, unit) =>
+        set((state) => ({
+            items: state.items.map((i) => {
+                if (i.product_id !== product_id) return i;
+                const base_price = i.base_price ?? i.price;
+                return unit
+                    ? {
+                          ...i,
+                          base_price,
+                          unit_id: unit.id,
+                          unit_name: unit.name,
+                          unit_conversion: unit.conversion,
+                          price: unit.price,
+                          price_override: null,
+                          scanned_barcode: null,
+                      }
+                    : {
+                          ...i,
+                          unit_id: null,
+                          unit_name: undefined,
+                          unit_conversion: 1,
+                          price: base_price,
+                          price_override: null,
+                          scanned_barcode: null,
+                      };
+            }),
+        })),
+
     removeItem: (product_id) =>
         set((state) => ({
             items: state.items.filter((i) => i.product_id !== product_id),
         })),
 
+    setCustomer: (customer) =>
+        set({
+            customer_id: customer?.id ?? null,
+            customer_name: customer?.name ?? null,
+        }),
+
+    // prices sejajar dengan urutan items (hasil quote_prices); item dengan
+    // harga manual tetap memakai harga manualnya
+    applyPrices: (prices) =>
+        set((state) => {
+            const next = (i: CartItem, idx: number) => i.price_override ?? prices[idx];
+            if (
+                prices.length !== state.items.length ||
+                state.items.every((i, idx) => i.price === next(i, idx))
+            ) {
+                return {};
+            }
+            return {
+                items: state.items.map((i, idx) => ({ ...i, price: next(i, idx) })),
+            };
+        }),
+
+    setItemPriceOverride: (product_id, price) =>
+        set((state) => ({
+            items: state.items.map((i) =>
+                i.product_id === product_id
+                    ? price === null
+                        ? { ...i, price_override: null }
+                        : { ...i, price_override: Math.max(0, price), price: Math.max(0, price) }
+                    : i
+            ),
+        })),
+
+    hasPriceOverride: () => get().items.some((i) => i.price_override != null),
+
     setDiscount: (id, name, amount, percent = null, isManual = false) =>
         set({
             discount_id: id,
@@ -126,6 +227,8 @@ export const useCartStore = create<CartState>()((set, get) => ({
             discount_amount: 0,
             discount_percent: null,
             manual_discount_applied: false,
+            customer_id: null,
+            customer_name: null,
         }),
 
     getSubtotal: () => {
@@ -43,6 +43,302 @@ export interface ProductWithCategory {
     barcode: string | null;
     image_path: string | null;
     is_active: boolean;
+    is_weighed: boolean;
+    unit: string;
+    parent_id: number | null;
+    variant_attributes: string | null; // JSON {"Ukuran":"L"}
+    variant_count: number;
+    is_kit: boolean; // stok = ketersediaan dari komponen
+    is_recipe: boolean; // menu dengan resep bahan
+}
+
+/** Hasil get_product_by_barcode; field scanned_* terisi untuk label timbangan. */
+export interface ScannedProduct extends ProductWithCategory {
+    scanned_quantity: number | null;
+    scanned_price: number | null;
+}
+
+export interface ScaleBarcodeRule {
+    prefix: string;
+    plu_length: number;
+    value_type: "WEIGHT" | "PRICE";
+    value_decimals: number;
+    value_check_digit: boolean;
+    verify_check_digit: boolean;
+}
+
+export interface ProductBarcode {
+    id: number;
+    product_id: number;
+    barcode: string;
+    pack_quantity: number;
+    label: string | null;
+    created_at: string | null;
+}
+
+export interface ProductUnit {
+    id: number;
+    product_id: number;
+    name: string;
+    conversion: number;
+    price: number;
+}
+
+export interface ProductComponent {
+    component_id: number;
+    name: string;
+    unit: string; // satuan takaran
+    quantity: number;
+    unit_id: number | null;
+    unit_conversion: number; // satuan dasar per 1 unit
+    base_unit: string;
+    stock: number;
+}
+
+/** Batch stok; penjualan memotong batch kedaluwarsa terdekat (FEFO). */
+export interface ProductBatch {
+    id: number;
+    product_id: number;
+    product_name: string | null;
+    batch_number: string | null;
+    expiry_date: string | null; // YYYY-MM-DD
+    quantity: number;
+    initial_quantity: number;
+    cost_price: number;
+    received_at: string | null;
+    days_to_expiry: number | null; // negatif = sudah kedaluwarsa
+}
+
+/** Pemakaian bahan resep: teoretis (penjualan) vs aktual (+ selisih hitung stok). */
+export interface IngredientUsage {
+    product_id: number;
+    name: string;
+    unit: string;
+    theoretical: number;
+    count_variance: number;
+    actual: number;
+    variance_value: number;
+    stock: number;
+    last_counted_at: string | null;
+}
+
+// === Procurement Types ===
+
+export interface Supplier {
+    id: number;
+    name: string;
+    contact_person: string | null;
+    phone: string | null;
+    email: string | null;
+    address: string | null;
+    notes: string | null;
+    is_active: boolean;
+    created_at: string | null;
+}
+
+export type PurchaseOrderStatus =
+    | "DRAFT"
+    | "SENT"
+    | "PARTIALLY_RECEIVED"
+    | "RECEIVED"
+    | "CANCELLED";
+
+export interface PurchaseOrder {
+    id: number;
+    po_number: string;
+    supplier_id: number;
+    supplier_name: string;
+    status: PurchaseOrderStatus;
+    expected_date: string | null; // YYYY-MM-DD
+    notes: string | null;
+    total_amount: number;
+    created_by: number | null;
+    created_by_name: string | null;
+    created_at: string | null;
+    sent_at: string | null;
+    closed_at: string | null;
+    item_count: number;
+}
+
+export interface PurchaseOrderItem {
+    id: number;
+    purchase_order_id: number;
+    product_id: number;
+    product_name: string;
+    sku: string | null;
+    unit: string;
+    quantity: number;
+    received_quantity: number;
+    unit_cost: number;
+    subtotal: number;
+}
+
+export interface PurchaseOrderDetail extends PurchaseOrder {
+    supplier: Supplier;
+    items: PurchaseOrderItem[];
+}
+
+export interface GoodsReceipt {
+    id: number;
+    receipt_number: string;
+    purchase_order_id: number | null;
+    po_number: string | null;
+    supplier_id: number | null;
+    supplier_name: string | null;
+    invoice_number: string | null;
+    notes: string | null;
+    total_amount: number;
+    received_by: number | null;
+    received_by_name: string | null;
+    received_at: string | null;
+    location_id: number | null; // null = lokasi utama
+    location_name: string | null;
+    item_count: number;
+}
+
+export interface GoodsReceiptItem {
+    id: number;
+    goods_receipt_id: number;
+    product_id: number;
+    product_name: string;
+    unit: string;
+    quantity: number;
+    unit_cost: number;
+    subtotal: number;
+    batch_id: number | null;
+    batch_number: string | null;
+    expiry_date: string | null;
+}
+
+export interface GoodsReceiptDetail extends GoodsReceipt {
+    items: GoodsReceiptItem[];
+}
+
+/** Pergerakan HPP rata-rata bergerak per penerimaan barang. */
+export interface CostHistoryEntry {
+    id: number;
+    product_id: number;
+    goods_receipt_id: number | null;
+    receipt_number: string | null;
+    stock_before: number;
+    cost_before: number;
+    quantity: number;
+    unit_cost: number;
+    cost_after: number;
+    created_by_name: string | null;
+    created_at: string | null;
+}
+
+// === Stock Opname Types ===
+
+export type OpnameStatus = "OPEN" | "APPROVED" | "CANCELLED";
+
+export interface OpnameSession {
+    id: number;
+    name: string;
+    status: OpnameStatus;
+    notes: string | null;
+    created_by: number | null;
+    created_by_name: string | null;
+    created_at: string | null;
+    closed_by_name: string | null;
+    closed_at: string | null;
+    location_id: number | null; // null = lokasi utama
+    location_name: string | null;
+    item_count: number;
+    counted_count: number;
+}
+
+export interface OpnameItem {
+    product_id: number;
+    product_name: string;
+    sku: string | null;
+    barcode: string | null;
+    unit: string;
+    system_stock: number; // snapshot saat sesi dibuka
+    counted: number | null; // null = belum dihitung
+    variance: number | null;
+    cost_price: number;
+    variance_value: number | null;
+    counted_by: string | null;
+}
+
+export interface OpnameSessionDetail extends OpnameSession {
+    items: OpnameItem[];
+    total_variance_value: number;
+}
+
+export interface OpnameCount {
+    id: number;
+    session_id: number;
+    product_id: number;
+    product_name: string;
+    unit: string;
+    quantity: number;
+    counted_by: number | null;
+    counted_by_name: string | null;
+    counted_at: string | null;
+}
+
+// === Location & Stock Transfer Types ===
+
+export type LocationType = "STORE" | "WAREHOUSE";
+
+export interface Location {
+    id: number;
+    name: string;
+    location_type: LocationType;
+    is_default: boolean; // stok lokasi utama = stok total - lokasi lain - dalam perjalanan
+    is_active: boolean;
+    created_at: string | null;
+}
+
+export interface LocationStock {
+    location_id: number;
+    location_name: string;
+    is_default: boolean;
+    quantity: number;
+    incoming: number; // qty dalam perjalanan menuju lokasi ini
+}
+
+export interface LocationProductStock {
+    product_id: number;
+    name: string;
+    sku: string | null;
+    unit: string;
+    is_weighed: boolean;
+    quantity: number;
+}
+
+export type StockTransferStatus = "IN_TRANSIT" | "RECEIVED" | "CANCELLED";
+
+export interface StockTransfer {
+    id: number;
+    transfer_number: string;
+    from_location_id: number;
+    from_location_name: string;
+    to_location_id: number;
+    to_location_name: string;
+    status: StockTransferStatus;
+    notes: string | null;
+    created_by_name: string | null;
+    created_at: string | null;
+    closed_by_name: string | null;
+    closed_at: string | null;
+    item_count: number;
+}
+
+export interface StockTransferItem {
+    id: number;
+    transfer_id: number;
+    product_id: number;
+    product_name: string;
+    unit: string;
+    quantity: number;
+}
+
+export interface StockTransferDetail extends StockTransfer {
+    items: StockTransferItem[];
 }
 
 export interface Product {
@@ -55,6 +351,12 @@ export interface Product {
     stock: number;
     barcode: string | null;
     is_active: boolean;
+    is_weighed: boolean;
+    unit: string;
+    parent_id: number | null;
+    variant_attributes: string | null;
+    is_kit: boolean;
+    is_recipe: boolean;
     created_at: string | null;
     updated_at: string | null;
 }
@@ -68,6 +370,16 @@ export interface CategoryWithCount {
     id: number;
     name: string;
     product_count: number;
+    printer_profile_id: number | null;
+}
+
+export interface PrinterProfile {
+    id: number;
+    name: string;
+    printer_port: string;
+    paper_width: "58mm" | "80mm";
+    is_active: boolean;
+    created_at: string | null;
 }
 
 export interface Discount {
@@ -81,6 +393,64 @@ export interface Discount {
     created_at: string | null;
 }
 
+export interface PriceLevel {
+    id: number;
+    name: string;
+    discount_percent: number;
+    created_at: string | null;
+}
+
+export interface Customer {
+    id: number;
+    name: string;
+    phone: string | null;
+    address: string | null;
+    price_level_id: number | null; // null = harga eceran
+    price_level_name: string | null;
+    is_active: boolean;
+    created_at: string | null;
+}
+
+export interface PriceTier {
+    min_quantity: number; // satuan dasar
+    price: number;
+}
+
+export interface ProductPricing {
+    tiers: PriceTier[];
+    level_prices: Array<{ price_level_id: number; price: number }>;
+}
+
+export interface PriceSchedule {
+    id: number;
+    product_id: number | null;
+    product_name: string | null;
+    category_id: number | null;
+    category_name: string | null;
+    new_price: number | null;
+    percent_change: number | null;
+    effective_at: string; // UTC "YYYY-MM-DD HH:MM:SS"
+    status: "PENDING" | "APPLIED" | "CANCELLED";
+    notes: string | null;
+    applied_at: string | null;
+    applied_count: number;
+    created_at: string | null;
+}
+
+export interface PriceHistoryEntry {
+    id: number;
+    product_id: number;
+    old_price: number;
+    new_price: number;
+    old_cost_price: number;
+    new_cost_price: number;
+    source: "MANUAL" | "SCHEDULE";
+    target: string | null; // satuan jual / tier grosir; null = harga dasar
+    schedule_id: number | null;
+    changed_by_name: string | null;
+    changed_at: string | null;
+}
+
 export interface Transaction {
     id: string;
     cashier_id: number;
@@ -96,6 +466,8 @@ export interface Transaction {
     voided_by: number | null;
     voided_at: string | null;
     notes: string | null;
+    reprint_count: number;
+    customer_id?: number | null;
 }
 
 export interface TransactionWithCashier extends Transaction {
@@ -110,6 +482,8 @@ export interface TransactionItemWithProduct {
     quantity: number;
     price_at_time: number;
     subtotal: number;
+    unit: string | null;
+    unit_conversion: number;
 }
 
 export interface TransactionDetail {
@@ -164,6 +538,13 @@ export interface ProductStat {
     total_revenue: number;
 }
 
+export interface CategoryStat {
+    category_id: number | null;
+    name: string;
+    total_sold: number;
+    total_revenue: number;
+}
+
 export interface ShiftSummary {
     cashier_name: string;
     login_at: string;
@@ -213,6 +594,8 @@ export interface AppSettings {
         show_discount_detail: boolean;
         paper_width: "58mm" | "80mm";
         copies: number;
+        qr_mode: "NONE" | "TRANSACTION" | "QRIS";
+        qr_link_template: string;
     };
     tax: {
         is_enabled: boolean;
@@ -220,23 +603,82 @@ export interface AppSettings {
         label: string;
         is_included: boolean;
     };
+    drawer: {
+        enabled: boolean;
+        open_on_cash: boolean;
+        pin: 2 | 5;
+        pulse_on_ms: number;
+        pulse_off_ms: number;
+    };
+    label: {
+        printer_port: string;
+        language: "ESCPOS" | "TSPL" | "ZPL";
+        size: string;
+        dpi: number;
+        gap_mm: number;
+    };
+    kitchen: {
+        auto_print: boolean;
+    };
+    display: {
+        enabled: boolean;
+        port: string;
+        baud_rate: number;
+        protocol: "ESCPOS" | "CD5220";
+        idle_message: string;
+    };
+    scale: {
+        enabled: boolean;
+        port: string;
+        baud_rate: number;
+        protocol: "CONTINUOUS" | "POLLED";
+        poll_command: string;
+    };
+    scale_barcode: {
+        enabled: boolean;
+        rules: ScaleBarcodeRule[];
+    };
     low_stock_threshold: number;
+    expiry_warning_days: number;
+    location_id: number | null; // lokasi stok kasir ini (null = lokasi utama)
     printer_port: string;
     timezone: string;
 }
 
+export interface ScaleWeight {
+    gross_kg: number;
+    tare_kg: number;
+    net_kg: number;
+    stable: boolean;
+}
+
+export interface PrinterStatus {
+    checked: boolean;
+    online: boolean;
+    cover_open: boolean;
+    paper_out: boolean;
+    paper_near_end: boolean;
+    error: boolean;
+}
+
 export interface CreateTransactionPayload {
     items: Array<{
         product_id: number;
         quantity: number;
-        price_at_time: number;
+        price_at_time?: number | null; // harga tampil; ditolak jika beda dengan harga server
+        price_override?: number | null; // harga manual, butuh persetujuan admin
         discount_amount: number;
+        notes?: string;
+        unit_id?: number | null;
+        scanned_barcode?: string | null; // label timbangan
     }>;
     discount_id: number | null;
     discount_amount: number;
     payment_method: PaymentMethod;
     amount_paid: number;
     notes?: string;
+    customer_id?: number | null; // harga item dihitung server dari level pelanggan
+    override_approval?: { username: string; password: string } | null;
 }
 
 export interface ProfitReport {
@@ -271,3 +713,38 @@ export interface QrisStatusResponse {
     transaction_status: string;
     order_id: string;
 }
+
+// === Print Queue Types ===
+
+export type PrintJobStatus = "QUEUED" | "PRINTING" | "DONE" | "FAILED";
+
+export interface PrintJob {
+    id: number;
+    job_type: "RECEIPT" | "LABEL";
+    reference_id: string | null;
+    printer_port: string;
+    status: PrintJobStatus;
+    attempts: number;
+    max_attempts: number;
+    last_error: string | null;
+    next_attempt_at: string | null;
+    created_by: number | null;
+    created_at: string | null;
+    updated_at: string | null;
+    printed_at: string | null;
+}
+
+export interface PaginatedPrintJobs {
+    data: PrintJob[];
+    total: number;
+    page: number;
+    per_page: number;
+}
+
+export interface PrintJobFailedEvent {
+    job_id: number;
+    job_type: string;
+    reference_id: string | null;
+    attempts: number;
+    error: string;
+}